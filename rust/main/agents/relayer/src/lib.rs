@@ -1,5 +1,6 @@
 mod merkle_tree;
 mod msg;
+mod policies;
 mod processor;
 mod prover;
 mod relayer;
@@ -16,6 +16,9 @@ use crate::prover::{Prover, ProverError};
 pub struct MerkleTreeBuilder {
     prover: Prover,
     incremental: IncrementalMerkle,
+    /// The ingested message ids, so the tree can be rebuilt without the
+    /// leaves of reorged insertions
+    leaves: Vec<H256>,
 }
 
 impl Display for MerkleTreeBuilder {
@@ -67,6 +70,7 @@ impl MerkleTreeBuilder {
         Self {
             prover,
             incremental,
+            leaves: vec![],
         }
     }
 
@@ -90,6 +94,7 @@ impl MerkleTreeBuilder {
         debug!(?message_id, "Ingesting leaf");
         self.prover.ingest(message_id).expect("tree full");
         self.incremental.ingest(message_id);
+        self.leaves.push(message_id);
         match self.prover.root().eq(&self.incremental.root()) {
             true => Ok(()),
             false => Err(MerkleTreeBuilderError::MismatchedRoots {
@@ -99,4 +104,57 @@ impl MerkleTreeBuilder {
         }
         .context(CTX)
     }
+
+    /// The message id ingested at `leaf_index`, if any
+    pub fn leaf(&self, leaf_index: u32) -> Option<H256> {
+        self.leaves.get(leaf_index as usize).copied()
+    }
+
+    /// Drop every leaf from `leaf_index` onwards, e.g. because the insertions
+    /// were reorged, and rebuild the trees from the remaining leaves.
+    pub fn truncate(&mut self, leaf_index: u32) {
+        if leaf_index >= self.count() {
+            return;
+        }
+        debug!(leaf_index, count = self.count(), "Truncating merkle tree");
+        self.leaves.truncate(leaf_index as usize);
+        self.prover = Prover::from(&self.leaves);
+        self.incremental = IncrementalMerkle::default();
+        for leaf in &self.leaves {
+            self.incremental.ingest(*leaf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_truncate_rebuilds_tree() {
+        let leaves = (0..5).map(|_| H256::random()).collect::<Vec<_>>();
+
+        let mut expected = MerkleTreeBuilder::new();
+        for leaf in &leaves[..3] {
+            expected.ingest_message_id(*leaf).await.unwrap();
+        }
+        let mut builder = MerkleTreeBuilder::new();
+        for leaf in &leaves {
+            builder.ingest_message_id(*leaf).await.unwrap();
+        }
+
+        builder.truncate(3);
+
+        assert_eq!(builder.count(), 3);
+        assert_eq!(builder.leaf(2), Some(leaves[2]));
+        assert_eq!(builder.leaf(3), None);
+        assert_eq!(builder.prover.root(), expected.prover.root());
+        assert_eq!(builder.incremental.root(), expected.incremental.root());
+
+        // The truncated tree keeps growing like an untouched one
+        let replacement = H256::random();
+        builder.ingest_message_id(replacement).await.unwrap();
+        expected.ingest_message_id(replacement).await.unwrap();
+        assert_eq!(builder.prover.root(), expected.prover.root());
+    }
 }
@@ -11,7 +11,7 @@ use hyperlane_base::db::HyperlaneRocksDB;
 use hyperlane_core::{HyperlaneDomain, MerkleTreeInsertion};
 use prometheus::IntGauge;
 use tokio::sync::RwLock;
-use tracing::trace;
+use tracing::{trace, warn};
 
 use crate::processor::ProcessorExt;
 
@@ -47,6 +47,8 @@ impl ProcessorExt for MerkleTreeProcessor {
     /// One round of processing, extracted from infinite work loop for
     /// testing purposes.
     async fn tick(&mut self) -> Result<()> {
+        self.roll_back_reorged_leaves().await?;
+
         if let Some(insertion) = self.next_unprocessed_leaf()? {
             // Feed the message to the prover sync
             self.prover_sync
@@ -65,6 +67,37 @@ impl ProcessorExt for MerkleTreeProcessor {
 }
 
 impl MerkleTreeProcessor {
+    /// Drops the ingested leaves whose insertions were deleted from or
+    /// replaced in the DB, e.g. because they were reorged, so they can be
+    /// ingested again once they are re-indexed.
+    async fn roll_back_reorged_leaves(&mut self) -> Result<()> {
+        let mut valid_leaves = self.leaf_index;
+        {
+            let prover_sync = self.prover_sync.read().await;
+            while valid_leaves > 0 {
+                let leaf_index = valid_leaves - 1;
+                let stored = self
+                    .db
+                    .retrieve_merkle_tree_insertion_by_leaf_index(&leaf_index)?
+                    .map(|insertion| insertion.message_id());
+                if stored.is_some() && stored == prover_sync.leaf(leaf_index) {
+                    break;
+                }
+                valid_leaves = leaf_index;
+            }
+        }
+        if valid_leaves < self.leaf_index {
+            warn!(
+                from_leaf_index = valid_leaves,
+                to_leaf_index = self.leaf_index,
+                "Merkle tree insertions were rolled back, rebuilding the merkle tree"
+            );
+            self.prover_sync.write().await.truncate(valid_leaves);
+            self.leaf_index = valid_leaves;
+        }
+        Ok(())
+    }
+
     fn next_unprocessed_leaf(&mut self) -> Result<Option<MerkleTreeInsertion>> {
         let leaf = if let Some(insertion) = self
             .db
@@ -99,3 +132,66 @@ impl MerkleTreeProcessorMetrics {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::H256;
+
+    use super::*;
+
+    fn insertions(leaf_indices: std::ops::Range<u32>) -> Vec<MerkleTreeInsertion> {
+        leaf_indices
+            .map(|leaf_index| MerkleTreeInsertion::new(leaf_index, H256::random()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_rebuilds_tree_after_rolled_back_insertions() {
+        test_utils::run_test_db(|db| async move {
+            let domain = HyperlaneDomain::new_test_domain("test_merkle_tree_processor");
+            let db = HyperlaneRocksDB::new(&domain, db);
+            let prover_sync = Arc::new(RwLock::new(MerkleTreeBuilder::new()));
+            let mut processor = MerkleTreeProcessor::new(
+                db.clone(),
+                MerkleTreeProcessorMetrics::new(),
+                prover_sync.clone(),
+            );
+
+            let original = insertions(0..4);
+            for insertion in &original {
+                db.process_tree_insertion(insertion, 1).unwrap();
+                processor.tick().await.unwrap();
+            }
+            assert_eq!(prover_sync.read().await.count(), 4);
+
+            // The last two insertions are reorged and replaced by others
+            for insertion in &original[2..] {
+                db.delete_tree_insertion(insertion).unwrap();
+            }
+            let replacements = insertions(2..5);
+            for insertion in &replacements {
+                db.process_tree_insertion(insertion, 2).unwrap();
+            }
+            for _ in &replacements {
+                processor.tick().await.unwrap();
+            }
+
+            let mut expected = MerkleTreeBuilder::new();
+            for insertion in original[..2].iter().chain(&replacements) {
+                expected
+                    .ingest_message_id(insertion.message_id())
+                    .await
+                    .unwrap();
+            }
+            let prover_sync = prover_sync.read().await;
+            assert_eq!(prover_sync.count(), 5);
+            assert_eq!(
+                prover_sync.get_proof(4, 4).unwrap(),
+                expected.get_proof(4, 4).unwrap()
+            );
+            assert_eq!(prover_sync.leaf(2), Some(replacements[0].message_id()));
+        })
+        .await;
+    }
+}
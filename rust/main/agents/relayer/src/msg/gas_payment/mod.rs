@@ -1,4 +1,4 @@
-use std::fmt::Debug;
+use std::{fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
 use eyre::Result;
@@ -7,9 +7,10 @@ use hyperlane_core::{
     FixedPointNumber, GasPaymentKey, HyperlaneMessage, InterchainGasExpenditure,
     InterchainGasPayment, TxCostEstimate, TxOutcome, U256,
 };
+use tokio::sync::RwLock;
 use tracing::{debug, error, trace};
 
-use self::policies::{GasPaymentPolicyMinimum, GasPaymentPolicyNone};
+use self::policies::{GasPaymentPolicyMinimum, GasPaymentPolicyNone, GasPaymentPolicyUsdCost};
 use crate::{
     msg::gas_payment::policies::GasPaymentPolicyOnChainFeeQuoting,
     settings::{
@@ -18,6 +19,7 @@ use crate::{
 };
 
 mod policies;
+pub(crate) mod price_oracle;
 
 pub const GAS_EXPENDITURE_LOG_MESSAGE: &str = "Recording gas expenditure for message";
 
@@ -41,6 +43,8 @@ pub enum GasPolicyStatus {
     PolicyMet(U256),
 }
 
+type GasPaymentPolicies = Vec<(Box<dyn GasPaymentPolicy>, MatchingList)>;
+
 #[derive(Debug)]
 pub struct GasPaymentEnforcer {
     /// List of policies and a whitelist to decide if it should be used for a
@@ -48,7 +52,8 @@ pub struct GasPaymentEnforcer {
     /// use a wild-card white list to ensure all messages fall into one
     /// policy or another. If a message matches multiple policies'
     /// whitelists, then whichever is first in the list will be used.
-    policies: Vec<(Box<dyn GasPaymentPolicy>, MatchingList)>,
+    /// The whole list is swapped at once when the policies are reloaded.
+    policies: RwLock<Arc<GasPaymentPolicies>>,
     db: HyperlaneRocksDB,
 }
 
@@ -59,7 +64,24 @@ impl GasPaymentEnforcer {
         policy_configs: impl IntoIterator<Item = GasPaymentEnforcementConf>,
         db: HyperlaneRocksDB,
     ) -> Self {
-        let policies = policy_configs
+        Self {
+            policies: RwLock::new(Arc::new(Self::build_policies(policy_configs))),
+            db,
+        }
+    }
+
+    /// Replace the policies used for messages that are evaluated from now on.
+    pub async fn set_policies(
+        &self,
+        policy_configs: impl IntoIterator<Item = GasPaymentEnforcementConf>,
+    ) {
+        *self.policies.write().await = Arc::new(Self::build_policies(policy_configs));
+    }
+
+    fn build_policies(
+        policy_configs: impl IntoIterator<Item = GasPaymentEnforcementConf>,
+    ) -> GasPaymentPolicies {
+        policy_configs
             .into_iter()
             .map(|cfg| {
                 let p: Box<dyn GasPaymentPolicy> = match cfg.policy {
@@ -71,12 +93,19 @@ impl GasPaymentEnforcer {
                         gas_fraction_numerator: n,
                         gas_fraction_denominator: d,
                     } => Box::new(GasPaymentPolicyOnChainFeeQuoting::new(n, d)),
+                    GasPaymentEnforcementPolicy::UsdCost {
+                        margin,
+                        native_token_decimals,
+                        price_source,
+                    } => Box::new(GasPaymentPolicyUsdCost::new(
+                        margin,
+                        native_token_decimals,
+                        price_source.into(),
+                    )),
                 };
                 (p, cfg.matching_list)
             })
-            .collect();
-
-        Self { policies, db }
+            .collect()
     }
 }
 
@@ -102,7 +131,9 @@ impl GasPaymentEnforcer {
         };
         let current_expenditure = self.db.retrieve_gas_expenditure_by_message_id(msg_id)?;
 
-        for (policy, whitelist) in &self.policies {
+        // Evaluate against a snapshot, so that a reload can't change the policies mid-evaluation
+        let policies = self.policies.read().await.clone();
+        for (policy, whitelist) in policies.iter() {
             if !whitelist.msg_matches(message, true) {
                 trace!(
                     msg=%message,
@@ -150,7 +181,7 @@ impl GasPaymentEnforcer {
 
         error!(
             msg=%message,
-            policies=?self.policies,
+            ?policies,
             "No gas payment policy matched for message; consider adding a default policy to the end of the policies array which uses a wildcard whitelist."
         );
         Ok(GasPolicyStatus::PolicyNotMet)
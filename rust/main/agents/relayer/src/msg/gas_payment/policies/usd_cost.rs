@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use eyre::Result;
+
+use hyperlane_core::{
+    FixedPointNumber, HyperlaneMessage, InterchainGasExpenditure, InterchainGasPayment,
+    TxCostEstimate, U256,
+};
+use tracing::trace;
+
+use crate::msg::gas_payment::{
+    price_oracle::{TokenPriceOracle, UsdValuer},
+    GasPaymentPolicy,
+};
+
+/// Approves messages whose payment on the origin is worth at least the
+/// estimated cost of delivering them on the destination, multiplied by
+/// `margin`, plus what was already spent on previous delivery attempts. All
+/// amounts are valued in USD, which makes the policy usable between chains
+/// whose native tokens have very different prices.
+#[derive(Debug)]
+pub struct GasPaymentPolicyUsdCost {
+    margin: FixedPointNumber,
+    valuer: UsdValuer,
+}
+
+impl GasPaymentPolicyUsdCost {
+    pub fn new(
+        margin: FixedPointNumber,
+        native_token_decimals: HashMap<u32, u32>,
+        price_oracle: Box<dyn TokenPriceOracle>,
+    ) -> Self {
+        Self {
+            margin,
+            valuer: UsdValuer::new(native_token_decimals, price_oracle),
+        }
+    }
+}
+
+#[async_trait]
+impl GasPaymentPolicy for GasPaymentPolicyUsdCost {
+    async fn message_meets_gas_payment_requirement(
+        &self,
+        message: &HyperlaneMessage,
+        current_payment: &InterchainGasPayment,
+        current_expenditure: &InterchainGasExpenditure,
+        tx_cost_estimate: &TxCostEstimate,
+    ) -> Result<Option<U256>> {
+        let payment = FixedPointNumber::try_from(current_payment.payment)?;
+        let cost = tx_cost_estimate.gas_price.clone()
+            * FixedPointNumber::try_from(tx_cost_estimate.gas_limit)?;
+
+        let payment_usd = self.valuer.usd_value(message.origin, payment).await?;
+        let required_usd =
+            self.valuer.usd_value(message.destination, cost).await? * self.margin.clone();
+        // Only the part of the payment that hasn't been spent yet can cover
+        // this attempt
+        let spent = FixedPointNumber::try_from(current_expenditure.tokens_used)?;
+        let spent_usd = self.valuer.usd_value(message.destination, spent).await?;
+        trace!(
+            ?payment_usd,
+            ?spent_usd,
+            ?required_usd,
+            "Compared USD value of gas payment to the estimated cost"
+        );
+
+        if payment_usd >= required_usd + spent_usd {
+            Ok(Some(tx_cost_estimate.gas_limit))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_gas_payment_policy_usd_cost() {
+    use std::str::FromStr;
+
+    use hyperlane_core::H256;
+
+    use crate::msg::gas_payment::price_oracle::StaticTokenPriceOracle;
+
+    // An 18 decimals token worth $2000 on the origin, and a 6 decimals token
+    // worth $0.5 on the destination
+    let policy = GasPaymentPolicyUsdCost::new(
+        FixedPointNumber::from_str("1.5").unwrap(),
+        HashMap::from([(2, 6)]),
+        Box::new(StaticTokenPriceOracle::new(HashMap::from([
+            (1, FixedPointNumber::from(2000)),
+            (2, FixedPointNumber::from_str("0.5").unwrap()),
+        ]))),
+    );
+    let message = HyperlaneMessage {
+        origin: 1,
+        destination: 2,
+        ..Default::default()
+    };
+    // 200000 gas at 0.1 units each costs 20000 units ($0.01), so $0.015 must be
+    // paid including the margin
+    let tx_cost_estimate = TxCostEstimate {
+        gas_limit: U256::from(200000u32),
+        gas_price: FixedPointNumber::from_str("0.1").unwrap(),
+        l2_gas_limit: None,
+    };
+    let expenditure = |tokens_used: u64| InterchainGasExpenditure {
+        message_id: H256::zero(),
+        gas_used: U256::zero(),
+        tokens_used: U256::from(tokens_used),
+    };
+    let current_expenditure = expenditure(0);
+    let destination = message.destination;
+    let payment = |payment: U256| InterchainGasPayment {
+        message_id: H256::zero(),
+        destination,
+        payment,
+        gas_amount: U256::zero(),
+    };
+
+    // $0.015 is 7.5e12 units of the origin token
+    assert_eq!(
+        policy
+            .message_meets_gas_payment_requirement(
+                &message,
+                &payment(U256::from(7_499_999_999_999u64)),
+                &current_expenditure,
+                &tx_cost_estimate,
+            )
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        policy
+            .message_meets_gas_payment_requirement(
+                &message,
+                &payment(U256::from(7_500_000_000_000u64)),
+                &current_expenditure,
+                &tx_cost_estimate,
+            )
+            .await
+            .unwrap(),
+        Some(U256::from(200000u32))
+    );
+
+    // Previous attempts spent 10000 units ($0.005) of the destination token,
+    // which the payment must cover as well
+    assert_eq!(
+        policy
+            .message_meets_gas_payment_requirement(
+                &message,
+                &payment(U256::from(7_500_000_000_000u64)),
+                &expenditure(10000),
+                &tx_cost_estimate,
+            )
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        policy
+            .message_meets_gas_payment_requirement(
+                &message,
+                &payment(U256::from(10_000_000_000_000u64)),
+                &expenditure(10000),
+                &tx_cost_estimate,
+            )
+            .await
+            .unwrap(),
+        Some(U256::from(200000u32))
+    );
+
+    // Messages can't be evaluated without a price for both native tokens
+    let message = HyperlaneMessage {
+        destination: 3,
+        ..message
+    };
+    assert!(policy
+        .message_meets_gas_payment_requirement(
+            &message,
+            &payment(U256::from(7_500_000_000_000u64)),
+            &current_expenditure,
+            &tx_cost_estimate,
+        )
+        .await
+        .is_err());
+}
@@ -0,0 +1,238 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use eyre::{eyre, Context, Result};
+use hyperlane_core::{FixedPointNumber, U256};
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use crate::settings::{TokenPriceSourceConf, TokenPricesConf};
+
+const DEFAULT_NATIVE_TOKEN_DECIMALS: u32 = 18;
+/// How long to wait for the price endpoint before giving up on a fetch
+const HTTP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+type TokenPrices = HashMap<u32, FixedPointNumber>;
+
+/// Values amounts of native tokens in USD.
+#[derive(Debug)]
+pub struct UsdValuer {
+    native_token_decimals: HashMap<u32, u32>,
+    price_oracle: Box<dyn TokenPriceOracle>,
+}
+
+impl UsdValuer {
+    pub fn new(
+        native_token_decimals: HashMap<u32, u32>,
+        price_oracle: Box<dyn TokenPriceOracle>,
+    ) -> Self {
+        Self {
+            native_token_decimals,
+            price_oracle,
+        }
+    }
+
+    /// The USD value of `amount` of the smallest unit of `domain`'s native token
+    pub async fn usd_value(
+        &self,
+        domain: u32,
+        amount: FixedPointNumber,
+    ) -> Result<FixedPointNumber> {
+        let price = self
+            .price_oracle
+            .usd_price(domain)
+            .await?
+            .ok_or_else(|| eyre!("No USD price for the native token of domain {domain}"))?;
+        let decimals = self
+            .native_token_decimals
+            .get(&domain)
+            .copied()
+            .unwrap_or(DEFAULT_NATIVE_TOKEN_DECIMALS);
+        let unit = FixedPointNumber::try_from(U256::exp10(decimals as usize))?;
+        Ok(amount * price / unit)
+    }
+}
+
+impl From<TokenPricesConf> for UsdValuer {
+    fn from(conf: TokenPricesConf) -> Self {
+        Self::new(conf.native_token_decimals, conf.price_source.into())
+    }
+}
+
+/// A source of the USD price of the native token of each domain.
+#[async_trait]
+pub trait TokenPriceOracle: Debug + Send + Sync {
+    /// The USD price of one whole native token of `domain`, or None if the
+    /// price is unknown.
+    async fn usd_price(&self, domain: u32) -> Result<Option<FixedPointNumber>>;
+}
+
+impl From<TokenPriceSourceConf> for Box<dyn TokenPriceOracle> {
+    fn from(conf: TokenPriceSourceConf) -> Self {
+        match conf {
+            TokenPriceSourceConf::Static { prices } => {
+                Box::new(StaticTokenPriceOracle::new(prices))
+            }
+            TokenPriceSourceConf::Http {
+                url,
+                cache_duration,
+            } => Box::new(HttpTokenPriceOracle::new(url, cache_duration)),
+        }
+    }
+}
+
+/// Prices fixed in the config.
+#[derive(Debug)]
+pub struct StaticTokenPriceOracle {
+    prices: TokenPrices,
+}
+
+impl StaticTokenPriceOracle {
+    pub fn new(prices: TokenPrices) -> Self {
+        Self { prices }
+    }
+}
+
+#[async_trait]
+impl TokenPriceOracle for StaticTokenPriceOracle {
+    async fn usd_price(&self, domain: u32) -> Result<Option<FixedPointNumber>> {
+        Ok(self.prices.get(&domain).cloned())
+    }
+}
+
+/// Prices fetched from an HTTP endpoint serving a JSON object that maps domain
+/// ids to prices, e.g. `{"1": 3000.5, "1399811149": "150.2"}`. Fetched prices
+/// are reused for `cache_duration`.
+#[derive(Debug)]
+pub struct HttpTokenPriceOracle {
+    url: String,
+    cache_duration: Duration,
+    client: reqwest::Client,
+    cache: RwLock<Option<(Instant, Arc<TokenPrices>)>>,
+}
+
+impl HttpTokenPriceOracle {
+    pub fn new(url: String, cache_duration: Duration) -> Self {
+        Self {
+            url,
+            cache_duration,
+            // Building only fails if the TLS backend can't be initialized,
+            // which is also what makes `reqwest::Client::new` panic
+            client: reqwest::Client::builder()
+                .timeout(HTTP_REQUEST_TIMEOUT)
+                .build()
+                .expect("Failed to build the token price HTTP client"),
+            cache: RwLock::new(None),
+        }
+    }
+
+    async fn prices(&self) -> Result<Arc<TokenPrices>> {
+        if let Some(prices) = self.cached_prices(&*self.cache.read().await) {
+            return Ok(prices);
+        }
+
+        // Fetch without holding the lock, so a slow endpoint doesn't block
+        // readers. Concurrent misses may fetch more than once, and the most
+        // recent fetch wins.
+        let prices = Arc::new(self.fetch_prices().await?);
+        *self.cache.write().await = Some((Instant::now(), prices.clone()));
+        Ok(prices)
+    }
+
+    fn cached_prices(
+        &self,
+        cache: &Option<(Instant, Arc<TokenPrices>)>,
+    ) -> Option<Arc<TokenPrices>> {
+        cache
+            .as_ref()
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < self.cache_duration)
+            .map(|(_, prices)| prices.clone())
+    }
+
+    async fn fetch_prices(&self) -> Result<TokenPrices> {
+        let raw: HashMap<String, Value> = self
+            .client
+            .get(&self.url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await
+            .context("Expected a JSON object of token prices")?;
+
+        raw.into_iter()
+            .map(|(domain, price)| {
+                let domain = domain
+                    .parse()
+                    .with_context(|| format!("Invalid domain id `{domain}` in token prices"))?;
+                let price = match &price {
+                    Value::Number(n) => FixedPointNumber::from_str(&n.to_string()),
+                    Value::String(s) => FixedPointNumber::from_str(s),
+                    _ => return Err(eyre!("Invalid price `{price}` for domain {domain}")),
+                }
+                .with_context(|| format!("Invalid price `{price}` for domain {domain}"))?;
+                Ok((domain, price))
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl TokenPriceOracle for HttpTokenPriceOracle {
+    async fn usd_price(&self, domain: u32) -> Result<Option<FixedPointNumber>> {
+        Ok(self.prices().await?.get(&domain).cloned())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{routing::get, Json, Router};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_http_token_price_oracle() {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let app = Router::new().route(
+            "/prices",
+            get({
+                let requests = requests.clone();
+                move || async move {
+                    requests.fetch_add(1, Ordering::SeqCst);
+                    Json(serde_json::json!({ "1": 3000.5, "2": "0.25" }))
+                }
+            }),
+        );
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        let oracle =
+            HttpTokenPriceOracle::new(format!("http://{addr}/prices"), Duration::from_secs(60));
+        assert_eq!(
+            oracle.usd_price(1).await.unwrap(),
+            Some(FixedPointNumber::from_str("3000.5").unwrap())
+        );
+        assert_eq!(
+            oracle.usd_price(2).await.unwrap(),
+            Some(FixedPointNumber::from_str("0.25").unwrap())
+        );
+        assert_eq!(oracle.usd_price(3).await.unwrap(), None);
+        // Prices are only fetched once while they're cached
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        let oracle = HttpTokenPriceOracle::new(format!("http://{addr}/prices"), Duration::ZERO);
+        oracle.usd_price(1).await.unwrap();
+        oracle.usd_price(1).await.unwrap();
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+}
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use hyperlane_core::HyperlaneMessage;
+use tokio::sync::RwLock;
+
+use super::blacklist::AddressBlacklist;
+use crate::settings::{matching_list::MatchingList, RelayerPolicies};
+
+/// Message filter shared by the message processors of all origins. The whole
+/// filter is swapped at once when the relayer policies are reloaded.
+pub type SharedMessageFilter = Arc<RwLock<Arc<MessageFilter>>>;
+
+/// Decides which messages are relayed at all.
+#[derive(Debug, Default)]
+pub struct MessageFilter {
+    /// A matching list of messages that should be whitelisted.
+    pub whitelist: MatchingList,
+    /// A matching list of messages that should be blacklisted.
+    pub blacklist: MatchingList,
+    /// Addresses that messages may not interact with.
+    pub address_blacklist: AddressBlacklist,
+}
+
+impl MessageFilter {
+    /// Whether `message` is whitelisted, and neither blacklisted nor involves a
+    /// blacklisted address
+    pub fn allows(&self, message: &HyperlaneMessage) -> bool {
+        self.whitelist.msg_matches(message, true)
+            && !self.blacklist.msg_matches(message, false)
+            && self
+                .address_blacklist
+                .find_blacklisted_address(message)
+                .is_none()
+    }
+}
+
+impl From<&RelayerPolicies> for MessageFilter {
+    fn from(policies: &RelayerPolicies) -> Self {
+        Self {
+            whitelist: policies.whitelist.clone(),
+            blacklist: policies.blacklist.clone(),
+            address_blacklist: AddressBlacklist::new(policies.address_blacklist.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_allows() {
+        let policies = RelayerPolicies::default()
+            .with_overrides(&serde_json::json!({
+                "whitelist": [{ "destinationDomain": [1, 2] }],
+                "blacklist": [{ "originDomain": 3 }],
+            }))
+            .unwrap();
+        let filter = MessageFilter::from(&policies);
+
+        let message = |origin, destination| HyperlaneMessage {
+            origin,
+            destination,
+            ..Default::default()
+        };
+        assert!(filter.allows(&message(0, 1)));
+        // Not whitelisted
+        assert!(!filter.allows(&message(0, 4)));
+        // Whitelisted but blacklisted
+        assert!(!filter.allows(&message(3, 2)));
+    }
+}
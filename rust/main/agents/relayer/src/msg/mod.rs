@@ -29,10 +29,12 @@
 
 pub(crate) mod blacklist;
 pub(crate) mod gas_payment;
+pub(crate) mod message_filter;
 pub(crate) mod metadata;
 pub(crate) mod op_queue;
 pub(crate) mod op_submitter;
 pub(crate) mod pending_message;
 pub(crate) mod processor;
+pub(crate) mod profitability;
 
 pub use gas_payment::GAS_EXPENDITURE_LOG_MESSAGE;
@@ -1,22 +1,37 @@
 use std::{cmp::Reverse, collections::BinaryHeap, sync::Arc};
 
 use derive_new::new;
-use hyperlane_core::{PendingOperation, PendingOperationStatus, QueueOperation};
+use hyperlane_core::{PendingOperation, PendingOperationStatus, QueueOperation, H256};
 use prometheus::{IntGauge, IntGaugeVec};
-use tokio::sync::{broadcast::Receiver, Mutex};
-use tracing::{debug, info, instrument};
+use tokio::sync::{
+    broadcast::{error::TryRecvError, Receiver},
+    Mutex,
+};
+use tracing::{debug, info, instrument, warn};
 
 use crate::server::MessageRetryRequest;
 
 pub type OperationPriorityQueue = Arc<Mutex<BinaryHeap<Reverse<QueueOperation>>>>;
 
+/// Command sent by the relayer server to every `OpQueue`, to be applied to
+/// the operations it holds
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OpQueueCommand {
+    /// Retry the matching operations immediately
+    Retry(MessageRetryRequest),
+    /// Move the operation with this id ahead of all others in its queue
+    Prioritize(H256),
+    /// Remove the operation with this id from its queue
+    Drop(H256),
+}
+
 /// Queue of generic operations that can be submitted to a destination chain.
 /// Includes logic for maintaining queue metrics by the destination and `app_context` of an operation
 #[derive(Debug, Clone, new)]
 pub struct OpQueue {
     metrics: IntGaugeVec,
     queue_metrics_label: String,
-    retry_rx: Arc<Mutex<Receiver<MessageRetryRequest>>>,
+    command_rx: Arc<Mutex<Receiver<OpQueueCommand>>>,
     #[new(default)]
     pub queue: OperationPriorityQueue,
 }
@@ -48,7 +63,7 @@ impl OpQueue {
     /// Pop multiple elements at once from the queue and update metrics
     #[instrument(skip(self), fields(queue_label=%self.queue_metrics_label), level = "debug")]
     pub async fn pop_many(&mut self, limit: usize) -> Vec<QueueOperation> {
-        self.process_retry_requests().await;
+        self.process_commands().await;
         let mut queue = self.queue.lock().await;
         let mut popped = vec![];
         while let Some(Reverse(op)) = queue.pop() {
@@ -72,25 +87,58 @@ impl OpQueue {
         popped
     }
 
-    pub async fn process_retry_requests(&mut self) {
+    pub async fn process_commands(&mut self) {
         // TODO: could rate-limit ourselves here, but we expect the volume of messages over this channel to
         // be very low.
         // The other consideration is whether to put the channel receiver in the OpQueue or in a dedicated task
         // that also holds an Arc to the Mutex. For simplicity, we'll put it in the OpQueue for now.
-        let mut message_retry_requests = vec![];
-        while let Ok(message_id) = self.retry_rx.lock().await.try_recv() {
-            message_retry_requests.push(message_id);
+        let mut commands = vec![];
+        {
+            let mut command_rx = self.command_rx.lock().await;
+            loop {
+                match command_rx.try_recv() {
+                    Ok(command) => commands.push(command),
+                    Err(TryRecvError::Lagged(skipped)) => {
+                        // Keep going with the oldest commands that are still buffered
+                        warn!(
+                            skipped,
+                            queue_label = %self.queue_metrics_label,
+                            "OpQueue fell behind on commands, the oldest ones were lost"
+                        );
+                    }
+                    Err(TryRecvError::Empty | TryRecvError::Closed) => break,
+                }
+            }
         }
-        if message_retry_requests.is_empty() {
+        if commands.is_empty() {
             return;
         }
         let mut queue = self.queue.lock().await;
         let mut reprioritized_queue: BinaryHeap<_> = queue
             .drain()
-            .map(|Reverse(mut op)| {
-                // Can check for equality here because of the PartialEq implementation for MessageRetryRequest,
-                // but can't use `contains` because the types are different
-                if message_retry_requests.iter().any(|r| r == op) {
+            .filter_map(|Reverse(mut op)| {
+                let op_id = op.id();
+                if commands.contains(&OpQueueCommand::Drop(op_id)) {
+                    info!(
+                        operation = %op,
+                        queue_label = %self.queue_metrics_label,
+                        "Dropping OpQueue operation"
+                    );
+                    self.get_operation_metric(op.as_ref()).dec();
+                    return None;
+                }
+                if commands.contains(&OpQueueCommand::Prioritize(op_id)) {
+                    info!(
+                        operation = %op,
+                        queue_label = %self.queue_metrics_label,
+                        "Prioritizing OpQueue operation"
+                    );
+                    op.prioritize();
+                } else if commands.iter().any(|command| {
+                    // Can check for equality here because of the PartialEq implementation for MessageRetryRequest,
+                    // but can't use `contains` because the types are different
+                    matches!(command, OpQueueCommand::Retry(request) if request == op)
+                }) {
                     info!(
                         operation = %op,
                         queue_label = %self.queue_metrics_label,
@@ -98,7 +146,7 @@ impl OpQueue {
                     );
                     op.reset_attempts()
                 }
-                Reverse(op)
+                Some(Reverse(op))
             })
             .collect();
         queue.append(&mut reprioritized_queue);
@@ -119,6 +167,7 @@ impl OpQueue {
 #[cfg(test)]
 pub mod test {
     use super::*;
+    use crate::settings::matching_list::MatchingList;
     use hyperlane_core::{
         HyperlaneDomain, HyperlaneMessage, KnownHyperlaneDomain, PendingOperationResult,
         TryBatchAs, TxOutcome, H256, U256,
@@ -126,15 +175,30 @@ pub mod test {
     use serde::Serialize;
     use std::{
         collections::VecDeque,
+        sync::atomic::{AtomicUsize, Ordering},
         time::{Duration, Instant},
     };
     use tokio::sync;
 
+    /// Counts the submissions of mock operations, including how many were in
+    /// flight at the same time
+    #[derive(Debug, Default)]
+    pub struct MockSubmissions {
+        in_flight: AtomicUsize,
+        pub max_in_flight: AtomicUsize,
+        pub submitted: AtomicUsize,
+    }
+
     #[derive(Debug, Clone, Serialize)]
     pub struct MockPendingOperation {
         id: H256,
         seconds_to_next_attempt: u64,
         destination_domain: HyperlaneDomain,
+        sender_address: H256,
+        recipient_address: H256,
+        prioritized: bool,
+        #[serde(skip)]
+        submissions: Option<Arc<MockSubmissions>>,
     }
 
     impl MockPendingOperation {
@@ -143,8 +207,24 @@ pub mod test {
                 id: H256::random(),
                 seconds_to_next_attempt,
                 destination_domain,
+                sender_address: H256::random(),
+                recipient_address: H256::random(),
+                prioritized: false,
+                submissions: None,
             }
         }
+
+        pub fn with_sender_address(mut self, sender_address: H256) -> Self {
+            self.sender_address = sender_address;
+            self
+        }
+
+        /// Makes submitting the operation take a while and get counted in
+        /// `submissions`, after which the operation is dropped
+        pub fn with_submissions(mut self, submissions: Arc<MockSubmissions>) -> Self {
+            self.submissions = Some(submissions);
+            self
+        }
     }
 
     impl TryBatchAs<HyperlaneMessage> for MockPendingOperation {}
@@ -166,6 +246,15 @@ pub mod test {
             self.seconds_to_next_attempt = 0;
         }
 
+        fn is_prioritized(&self) -> bool {
+            self.prioritized
+        }
+
+        fn prioritize(&mut self) {
+            self.prioritized = true;
+            self.reset_attempts();
+        }
+
         fn priority(&self) -> u32 {
             todo!()
         }
@@ -179,7 +268,27 @@ pub mod test {
         }
 
         fn origin_domain_id(&self) -> u32 {
-            todo!()
+            KnownHyperlaneDomain::Arbitrum as u32
+        }
+
+        fn sender_address(&self) -> &H256 {
+            &self.sender_address
+        }
+
+        fn recipient_address(&self) -> &H256 {
+            &self.recipient_address
+        }
+
+        fn nonce(&self) -> u32 {
+            0
+        }
+
+        fn version(&self) -> u8 {
+            3
+        }
+
+        fn body(&self) -> &[u8] {
+            &[]
         }
 
         fn destination_domain(&self) -> &HyperlaneDomain {
@@ -197,7 +306,17 @@ pub mod test {
         /// Submit this operation to the blockchain and report if it was successful
         /// or not.
         async fn submit(&mut self) -> PendingOperationResult {
-            todo!()
+            let Some(submissions) = self.submissions.clone() else {
+                todo!()
+            };
+            let in_flight = submissions.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            submissions
+                .max_in_flight
+                .fetch_max(in_flight, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            submissions.in_flight.fetch_sub(1, Ordering::SeqCst);
+            submissions.submitted.fetch_add(1, Ordering::SeqCst);
+            PendingOperationResult::Drop
         }
 
         fn set_submission_outcome(&mut self, _outcome: TxOutcome) {
@@ -306,10 +425,14 @@ pub mod test {
 
         // Retry by message ids
         broadcaster
-            .send(MessageRetryRequest::MessageId(op_ids[1]))
+            .send(OpQueueCommand::Retry(MessageRetryRequest::MessageId(
+                op_ids[1],
+            )))
             .unwrap();
         broadcaster
-            .send(MessageRetryRequest::MessageId(op_ids[2]))
+            .send(OpQueueCommand::Retry(MessageRetryRequest::MessageId(
+                op_ids[2],
+            )))
             .unwrap();
 
         // Pop elements from queue 1
@@ -367,8 +490,8 @@ pub mod test {
 
         // Retry by domain
         broadcaster
-            .send(MessageRetryRequest::DestinationDomain(
-                destination_domain_2.id(),
+            .send(OpQueueCommand::Retry(
+                MessageRetryRequest::DestinationDomain(destination_domain_2.id()),
             ))
             .unwrap();
 
@@ -387,4 +510,147 @@ pub mod test {
         assert_eq!(popped[3], op_ids[0]);
         assert_eq!(popped[4], op_ids[1]);
     }
+
+    #[tokio::test]
+    async fn test_matching_list_retry() {
+        let (metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let broadcaster = sync::broadcast::Sender::new(100);
+        let mut op_queue = OpQueue::new(
+            metrics.clone(),
+            queue_metrics_label.clone(),
+            Arc::new(Mutex::new(broadcaster.subscribe())),
+        );
+
+        let destination_domain: HyperlaneDomain = KnownHyperlaneDomain::Injective.into();
+        let sender_address = H256::random();
+        let ops = vec![
+            Box::new(MockPendingOperation::new(1, destination_domain.clone())) as QueueOperation,
+            Box::new(MockPendingOperation::new(2, destination_domain.clone())) as QueueOperation,
+            Box::new(
+                MockPendingOperation::new(3, destination_domain.clone())
+                    .with_sender_address(sender_address),
+            ) as QueueOperation,
+        ];
+        let op_ids: Vec<_> = ops.iter().map(|op| op.id()).collect();
+        for op in ops {
+            op_queue
+                .push(op, Some(PendingOperationStatus::FirstPrepareAttempt))
+                .await;
+        }
+
+        // Retry everything sent by `sender_address`
+        let matching_list: MatchingList =
+            serde_json::from_str(&format!(r#"[{{"senderaddress": "{:?}"}}]"#, sender_address))
+                .unwrap();
+        broadcaster
+            .send(OpQueueCommand::Retry(MessageRetryRequest::MatchingList(
+                matching_list,
+            )))
+            .unwrap();
+
+        let mut popped = vec![];
+        while let Some(op) = op_queue.pop().await {
+            popped.push(op.id());
+        }
+        assert_eq!(popped, vec![op_ids[2], op_ids[0], op_ids[1]]);
+    }
+
+    #[tokio::test]
+    async fn test_prioritize_and_drop() {
+        let (metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let broadcaster = sync::broadcast::Sender::new(100);
+        let mut op_queue = OpQueue::new(
+            metrics.clone(),
+            queue_metrics_label.clone(),
+            Arc::new(Mutex::new(broadcaster.subscribe())),
+        );
+
+        let destination_domain: HyperlaneDomain = KnownHyperlaneDomain::Injective.into();
+        let ops: Vec<_> = (1..=4)
+            .map(|seconds_to_next_attempt| {
+                Box::new(MockPendingOperation::new(
+                    seconds_to_next_attempt,
+                    destination_domain.clone(),
+                )) as QueueOperation
+            })
+            .collect();
+        let op_ids: Vec<_> = ops.iter().map(|op| op.id()).collect();
+        for op in ops {
+            op_queue
+                .push(op, Some(PendingOperationStatus::FirstPrepareAttempt))
+                .await;
+        }
+
+        // Retrying an operation must not move it ahead of a prioritized one
+        broadcaster
+            .send(OpQueueCommand::Retry(MessageRetryRequest::MessageId(
+                op_ids[2],
+            )))
+            .unwrap();
+        broadcaster
+            .send(OpQueueCommand::Prioritize(op_ids[3]))
+            .unwrap();
+        broadcaster.send(OpQueueCommand::Drop(op_ids[1])).unwrap();
+
+        let mut popped = vec![];
+        while let Some(op) = op_queue.pop().await {
+            popped.push(op.id());
+        }
+        assert_eq!(popped, vec![op_ids[3], op_ids[2], op_ids[0]]);
+    }
+
+    #[tokio::test]
+    async fn test_commands_after_lagging() {
+        let (metrics, queue_metrics_label) = dummy_metrics_and_label();
+        // Only room for two buffered commands
+        let broadcaster = sync::broadcast::Sender::new(2);
+        let mut op_queue = OpQueue::new(
+            metrics,
+            queue_metrics_label,
+            Arc::new(Mutex::new(broadcaster.subscribe())),
+        );
+
+        let destination_domain: HyperlaneDomain = KnownHyperlaneDomain::Injective.into();
+        let ops: Vec<_> = (1..=4)
+            .map(|seconds_to_next_attempt| {
+                Box::new(MockPendingOperation::new(
+                    seconds_to_next_attempt,
+                    destination_domain.clone(),
+                )) as QueueOperation
+            })
+            .collect();
+        let op_ids: Vec<_> = ops.iter().map(|op| op.id()).collect();
+        for op in ops {
+            op_queue
+                .push(op, Some(PendingOperationStatus::FirstPrepareAttempt))
+                .await;
+        }
+
+        // The first command is overwritten before the queue reads any of them
+        broadcaster.send(OpQueueCommand::Drop(op_ids[0])).unwrap();
+        broadcaster.send(OpQueueCommand::Drop(op_ids[1])).unwrap();
+        broadcaster
+            .send(OpQueueCommand::Prioritize(op_ids[3]))
+            .unwrap();
+
+        // Commands are applied without popping, as while a submitter is paused
+        op_queue.process_commands().await;
+        let mut queued: Vec<_> = op_queue
+            .queue
+            .lock()
+            .await
+            .iter()
+            .map(|Reverse(op)| op.id())
+            .collect();
+        queued.sort();
+        let mut expected = vec![op_ids[0], op_ids[2], op_ids[3]];
+        expected.sort();
+        assert_eq!(queued, expected);
+
+        let mut popped = vec![];
+        while let Some(op) = op_queue.pop().await {
+            popped.push(op.id());
+        }
+        assert_eq!(popped, vec![op_ids[3], op_ids[0], op_ids[2]]);
+    }
 }
@@ -1,11 +1,13 @@
 #![allow(clippy::doc_markdown)] // TODO: `rustc` 1.80.1 clippy issue
 #![allow(clippy::doc_lazy_continuation)] // TODO: `rustc` 1.80.1 clippy issue
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use derive_new::new;
 use futures::future::join_all;
+use futures::FutureExt;
 use futures_util::future::try_join_all;
 use hyperlane_core::total_estimated_cost;
 use hyperlane_core::BatchResult;
@@ -19,10 +21,11 @@ use prometheus::{IntCounter, IntGaugeVec};
 use tokio::sync::broadcast::Sender;
 use tokio::sync::mpsc;
 use tokio::sync::Mutex;
+use tokio::sync::Semaphore;
 use tokio::task::JoinHandle;
 use tokio::time::sleep;
 use tokio_metrics::TaskMonitor;
-use tracing::{debug, info_span, instrument, instrument::Instrumented, trace, Instrument};
+use tracing::{debug, info_span, instrument, instrument::Instrumented, trace, Instrument, Span};
 use tracing::{info, warn};
 
 use hyperlane_base::CoreMetrics;
@@ -32,23 +35,25 @@ use hyperlane_core::{
 };
 
 use crate::msg::pending_message::CONFIRM_DELAY;
-use crate::server::MessageRetryRequest;
 
 use super::op_queue::OpQueue;
+use super::op_queue::OpQueueCommand;
 use super::op_queue::OperationPriorityQueue;
 
 /// SerialSubmitter accepts operations over a channel. It is responsible for
 /// executing the right strategy to deliver those messages to the destination
-/// chain. It is designed to be used in a scenario allowing only one
-/// simultaneously in-flight submission, a consequence imposed by strictly
-/// ordered nonces at the target chain combined with a hesitancy to
-/// speculatively batch > 1 messages with a sequence of nonces, which entails
-/// harder to manage error recovery, could lead to head of line blocking, etc.
+/// chain. By default it allows only one simultaneously in-flight submission,
+/// a consequence imposed by strictly ordered nonces at the target chain
+/// combined with a hesitancy to speculatively batch > 1 messages with a
+/// sequence of nonces, which entails harder to manage error recovery, could
+/// lead to head of line blocking, etc. Destinations that can manage several
+/// in-flight transactions (e.g. EVM chains with multiple signers or pipelined
+/// nonces) are given more execution slots.
 ///
-/// The single transaction execution slot is (likely) a bottlenecked resource
+/// The transaction execution slots are (likely) a bottlenecked resource
 /// under steady state traffic, so the SerialSubmitter implemented in this file
 /// carefully schedules work items onto the constrained
-/// resource (transaction execution slot) according to a policy that
+/// resource (transaction execution slots) according to a policy that
 /// incorporates both user-visible metrics and message operation readiness
 /// checks.
 ///
@@ -94,8 +99,14 @@ pub struct SerialSubmitter {
     metrics: SerialSubmitterMetrics,
     /// Max batch size for submitting messages
     max_batch_size: u32,
+    /// Max number of submissions (single operations or batches) in flight at
+    /// the same time
+    max_concurrent_submissions: u32,
     /// tokio task monitor
     task_monitor: TaskMonitor,
+    /// Set by operators to stop preparing and submitting new operations.
+    /// Operations that were already submitted keep being confirmed.
+    paused: Arc<AtomicBool>,
     prepare_queue: OpQueue,
     submit_queue: OpQueue,
     confirm_queue: OpQueue,
@@ -105,9 +116,10 @@ impl SerialSubmitter {
     pub fn new(
         domain: HyperlaneDomain,
         rx: mpsc::UnboundedReceiver<QueueOperation>,
-        retry_op_transmitter: Sender<MessageRetryRequest>,
+        retry_op_transmitter: Sender<OpQueueCommand>,
         metrics: SerialSubmitterMetrics,
         max_batch_size: u32,
+        max_concurrent_submissions: u32,
         task_monitor: TaskMonitor,
     ) -> Self {
         let prepare_queue = OpQueue::new(
@@ -131,7 +143,9 @@ impl SerialSubmitter {
             rx,
             metrics,
             max_batch_size,
+            max_concurrent_submissions,
             task_monitor,
+            paused: Default::default(),
             prepare_queue,
             submit_queue,
             confirm_queue,
@@ -142,6 +156,10 @@ impl SerialSubmitter {
         self.prepare_queue.queue.clone()
     }
 
+    pub fn paused(&self) -> Arc<AtomicBool> {
+        self.paused.clone()
+    }
+
     pub fn spawn(self) -> Instrumented<JoinHandle<()>> {
         let span = info_span!("SerialSubmitter", destination=%self.domain);
         let task_monitor = self.task_monitor.clone();
@@ -157,7 +175,9 @@ impl SerialSubmitter {
             metrics,
             rx: rx_prepare,
             max_batch_size,
+            max_concurrent_submissions,
             task_monitor,
+            paused,
             prepare_queue,
             submit_queue,
             confirm_queue,
@@ -176,6 +196,7 @@ impl SerialSubmitter {
                     submit_queue.clone(),
                     confirm_queue.clone(),
                     max_batch_size,
+                    paused.clone(),
                     metrics.clone(),
                 ),
             )),
@@ -187,6 +208,8 @@ impl SerialSubmitter {
                     submit_queue,
                     confirm_queue.clone(),
                     max_batch_size,
+                    max_concurrent_submissions,
+                    paused,
                     metrics.clone(),
                 ),
             )),
@@ -242,11 +265,19 @@ async fn prepare_task(
     submit_queue: OpQueue,
     confirm_queue: OpQueue,
     max_batch_size: u32,
+    paused: Arc<AtomicBool>,
     metrics: SerialSubmitterMetrics,
 ) {
     // Prepare at most `max_batch_size` ops at a time to avoid getting rate-limited
     let ops_to_prepare = max_batch_size as usize;
     loop {
+        if paused.load(Ordering::Relaxed) {
+            // the destination was paused by an operator, so wait until it's resumed.
+            // Keep applying commands meanwhile, so that they aren't lost or applied late.
+            prepare_queue.process_commands().await;
+            sleep(Duration::from_millis(500)).await;
+            continue;
+        }
         // Pop messages here according to the configured batch.
         let mut batch = prepare_queue.pop_many(ops_to_prepare).await;
         if batch.is_empty() {
@@ -309,34 +340,70 @@ async fn prepare_task(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 #[instrument(skip_all, fields(%domain))]
 async fn submit_task(
     domain: HyperlaneDomain,
-    mut prepare_queue: OpQueue,
+    prepare_queue: OpQueue,
     mut submit_queue: OpQueue,
-    mut confirm_queue: OpQueue,
+    confirm_queue: OpQueue,
     max_batch_size: u32,
+    max_concurrent_submissions: u32,
+    paused: Arc<AtomicBool>,
     metrics: SerialSubmitterMetrics,
 ) {
     let recv_limit = max_batch_size as usize;
+    // Each in-flight submission holds a slot until it's done
+    let slots = Arc::new(Semaphore::new(max_concurrent_submissions.max(1) as usize));
     loop {
+        if paused.load(Ordering::Relaxed) {
+            // the destination was paused by an operator, so wait until it's resumed.
+            // Keep applying commands meanwhile, so that they aren't lost or applied late.
+            submit_queue.process_commands().await;
+            sleep(Duration::from_millis(500)).await;
+            continue;
+        }
+        let slot = slots
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("submission slots are never closed");
         let mut batch = submit_queue.pop_many(recv_limit).await;
 
-        match batch.len().cmp(&1) {
+        let mut prepare_queue = prepare_queue.clone();
+        let mut confirm_queue = confirm_queue.clone();
+        let metrics = metrics.clone();
+        let submission = match batch.len().cmp(&1) {
             std::cmp::Ordering::Less => {
                 // The queue is empty, so give some time before checking again to prevent burning CPU
+                drop(slot);
                 sleep(Duration::from_millis(100)).await;
                 continue;
             }
             std::cmp::Ordering::Equal => {
                 let op = batch.pop().unwrap();
-                submit_single_operation(op, &mut prepare_queue, &mut confirm_queue, &metrics).await;
+                async move {
+                    submit_single_operation(op, &mut prepare_queue, &mut confirm_queue, &metrics)
+                        .await;
+                    drop(slot);
+                }
+                .boxed()
             }
             std::cmp::Ordering::Greater => {
-                OperationBatch::new(batch, domain.clone())
-                    .submit(&mut prepare_queue, &mut confirm_queue, &metrics)
-                    .await;
+                let batch = OperationBatch::new(batch, domain.clone());
+                async move {
+                    batch
+                        .submit(&mut prepare_queue, &mut confirm_queue, &metrics)
+                        .await;
+                    drop(slot);
+                }
+                .boxed()
             }
+        };
+        if max_concurrent_submissions <= 1 {
+            submission.await;
+        } else {
+            tokio::spawn(submission.instrument(Span::current()));
         }
     }
 }
@@ -388,7 +455,10 @@ async fn confirm_op(
         .push(op, Some(PendingOperationStatus::Confirm(SubmittedBySelf)))
         .await;
     metrics.ops_submitted.inc();
+    wait_for_cosmos_finality(&destination).await;
+}
 
+async fn wait_for_cosmos_finality(destination: &HyperlaneDomain) {
     if matches!(
         destination.domain_protocol(),
         HyperlaneDomainProtocol::Cosmos
@@ -517,7 +587,6 @@ impl SerialSubmitterMetrics {
 #[derive(new, Debug)]
 struct OperationBatch {
     operations: Vec<QueueOperation>,
-    #[allow(dead_code)]
     domain: HyperlaneDomain,
 }
 
@@ -530,7 +599,13 @@ impl OperationBatch {
     ) {
         let excluded_ops = match self.try_submit_as_batch(metrics).await {
             Ok(batch_result) => {
-                Self::handle_batch_result(self.operations, batch_result, confirm_queue).await
+                let batch_sent = batch_result.outcome.is_some();
+                let excluded_ops =
+                    Self::handle_batch_result(self.operations, batch_result, confirm_queue).await;
+                if batch_sent {
+                    wait_for_cosmos_finality(&self.domain).await;
+                }
+                excluded_ops
             }
             Err(e) => {
                 warn!(error=?e, batch=?self.operations, "Error when submitting batch");
@@ -617,3 +692,69 @@ impl OperationBatch {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::KnownHyperlaneDomain;
+    use prometheus::Registry;
+
+    use super::*;
+    use crate::msg::op_queue::test::{
+        dummy_metrics_and_label, MockPendingOperation, MockSubmissions,
+    };
+
+    /// Runs the submit task until it submitted a few slow operations, and
+    /// returns how many of them were in flight at the same time
+    async fn max_in_flight_submissions(max_concurrent_submissions: u32) -> usize {
+        const OPERATIONS: usize = 6;
+        let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+        let (metrics, queue_metrics_label) = dummy_metrics_and_label();
+        let broadcaster = Sender::new(100);
+        let queue = || {
+            OpQueue::new(
+                metrics.clone(),
+                queue_metrics_label.clone(),
+                Arc::new(Mutex::new(broadcaster.subscribe())),
+            )
+        };
+        let submit_queue = queue();
+        let submissions = Arc::new(MockSubmissions::default());
+        for _ in 0..OPERATIONS {
+            let op =
+                MockPendingOperation::new(0, domain.clone()).with_submissions(submissions.clone());
+            submit_queue
+                .push(Box::new(op) as QueueOperation, None)
+                .await;
+        }
+
+        let core_metrics = CoreMetrics::new("test", 9090, Registry::new()).unwrap();
+        let submitter = tokio::spawn(submit_task(
+            domain.clone(),
+            queue(),
+            submit_queue,
+            queue(),
+            1,
+            max_concurrent_submissions,
+            Default::default(),
+            SerialSubmitterMetrics::new(&core_metrics, &domain),
+        ));
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while submissions.submitted.load(Ordering::SeqCst) < OPERATIONS {
+                sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("operations weren't submitted in time");
+        submitter.abort();
+
+        submissions.max_in_flight.load(Ordering::SeqCst)
+    }
+
+    #[tokio::test]
+    async fn test_submissions_are_limited_to_the_available_slots() {
+        // Operations are submitted one after the other by default
+        assert_eq!(max_in_flight_submissions(1).await, 1);
+        // and concurrently up to the number of slots otherwise
+        assert_eq!(max_in_flight_submissions(3).await, 3);
+    }
+}
@@ -12,9 +12,9 @@ use eyre::Result;
 use hyperlane_base::{db::HyperlaneRocksDB, CoreMetrics};
 use hyperlane_core::{
     gas_used_by_operation, BatchItem, ChainCommunicationError, ChainResult, ConfirmReason,
-    HyperlaneChain, HyperlaneDomain, HyperlaneMessage, Mailbox, MessageSubmissionData,
-    PendingOperation, PendingOperationResult, PendingOperationStatus, ReprepareReason, TryBatchAs,
-    TxOutcome, H256, U256,
+    GasPaymentKey, HyperlaneChain, HyperlaneDomain, HyperlaneMessage, Mailbox,
+    MessageSubmissionData, ModuleType, PendingOperation, PendingOperationResult,
+    PendingOperationStatus, ReprepareReason, TryBatchAs, TxOutcome, H256, U256,
 };
 use prometheus::{IntCounter, IntGauge};
 use serde::Serialize;
@@ -22,7 +22,9 @@ use tracing::{debug, error, info, info_span, instrument, trace, warn, Instrument
 
 use super::{
     gas_payment::{GasPaymentEnforcer, GasPolicyStatus},
-    metadata::{BaseMetadataBuilder, MessageMetadataBuilder, MetadataBuilder},
+    message_filter::SharedMessageFilter,
+    metadata::{BaseMetadataBuilder, MessageMetadataBuilder},
+    profitability::ProfitabilityTracker,
 };
 
 pub const CONFIRM_DELAY: Duration = if cfg!(any(test, feature = "test-utils")) {
@@ -50,6 +52,12 @@ pub struct MessageContext {
     /// destination.
     pub transaction_gas_limit: Option<U256>,
     pub metrics: MessageSubmissionMetrics,
+    /// Aggregates the gas paid and spent for delivered messages, shared by
+    /// all contexts.
+    pub profitability: Arc<ProfitabilityTracker>,
+    /// The message filter currently in effect, which may have changed since
+    /// the message was enqueued.
+    pub message_filter: SharedMessageFilter,
 }
 
 /// A message that the submitter can and should try to submit.
@@ -67,6 +75,8 @@ pub struct PendingMessage {
     submission_data: Option<Box<MessageSubmissionData>>,
     #[new(default)]
     num_retries: u32,
+    #[new(default)]
+    prioritized: bool,
     #[new(value = "Instant::now()")]
     #[serde(skip_serializing)]
     last_attempted_at: Instant,
@@ -142,9 +152,9 @@ impl PendingOperation for PendingMessage {
         if let Err(e) = self
             .ctx
             .origin_db
-            .store_status_by_message_id(&self.message.id(), &self.status)
+            .store_status_transition(&self.message.id(), &status)
         {
-            warn!(message_id = ?self.message.id(), err = %e, status = %self.status, "Persisting `status` failed for message");
+            warn!(message_id = ?self.message.id(), err = %e, %status, "Persisting `status` failed for message");
         }
         self.status = status;
     }
@@ -157,6 +167,26 @@ impl PendingOperation for PendingMessage {
         self.message.origin
     }
 
+    fn sender_address(&self) -> &H256 {
+        &self.message.sender
+    }
+
+    fn recipient_address(&self) -> &H256 {
+        &self.message.recipient
+    }
+
+    fn nonce(&self) -> u32 {
+        self.message.nonce
+    }
+
+    fn version(&self) -> u8 {
+        self.message.version
+    }
+
+    fn body(&self) -> &[u8] {
+        &self.message.body
+    }
+
     fn destination_domain(&self) -> &HyperlaneDomain {
         self.ctx.destination_mailbox.domain()
     }
@@ -182,6 +212,20 @@ impl PendingOperation for PendingMessage {
             return PendingOperationResult::NotReady;
         }
 
+        // An operator may have dropped the message through the admin API while it
+        // was waiting in a queue.
+        if self.is_dropped_by_admin() {
+            info!("Dropping message because it was dropped by an operator");
+            return PendingOperationResult::Drop;
+        }
+
+        // The relayer policies may have been updated while the message was waiting
+        // in a queue.
+        if !self.ctx.message_filter.read().await.allows(&self.message) {
+            info!("Dropping message because the relayer policies no longer allow it");
+            return PendingOperationResult::Drop;
+        }
+
         // If the message has already been processed, e.g. due to another relayer having
         // already processed, then mark it as already-processed, and move on to
         // the next tick.
@@ -248,16 +292,18 @@ impl PendingOperation for PendingMessage {
             }
         };
 
-        let metadata = match message_metadata_builder
-            .build(ism_address, &self.message)
+        let ism_with_metadata = match message_metadata_builder
+            .build_ism_and_metadata(ism_address, &self.message)
             .await
         {
-            Ok(metadata) => metadata,
+            Ok(ism_with_metadata) => ism_with_metadata,
             Err(err) => {
                 return self.on_reprepare(Some(err), ReprepareReason::ErrorBuildingMetadata);
             }
         };
+        let metadata = ism_with_metadata.metadata;
         self.metadata = metadata.clone();
+        self.persist_ism_and_metadata(ism_with_metadata.module_type, metadata.as_deref());
 
         let Some(metadata) = metadata else {
             return self.on_reprepare::<String>(None, ReprepareReason::CouldNotFetchMetadata);
@@ -402,6 +448,7 @@ impl PendingOperation for PendingMessage {
                 submission=?self.submission_outcome,
                 "Message successfully processed"
             );
+            self.record_profitability().await;
             PendingOperationResult::Success
         } else {
             let span = info_span!(
@@ -420,6 +467,9 @@ impl PendingOperation for PendingMessage {
         submission_outcome: TxOutcome,
         submission_estimated_cost: U256,
     ) {
+        // An operator's prioritization only applies until the message is submitted,
+        // so that it doesn't jump the queue again if it has to be re-prepared.
+        self.prioritized = false;
         let Some(operation_estimate) = self.get_tx_cost_estimate() else {
             warn!("Cannot set operation outcome without a cost estimate set previously");
             return;
@@ -448,6 +498,12 @@ impl PendingOperation for PendingMessage {
         {
             error!(error=?e, "Error when recording tx outcome");
         }
+        if let Err(e) = self.ctx.origin_db.store_destination_tx_hash_by_message_id(
+            &self.message.id(),
+            &operation_outcome.transaction_id,
+        ) {
+            warn!(message_id = ?self.message.id(), err = %e, "Persisting the destination tx hash failed for message");
+        }
         // set the outcome in `Self` as well, for later logging
         self.set_submission_outcome(operation_outcome);
         debug!(
@@ -471,6 +527,15 @@ impl PendingOperation for PendingMessage {
         self.reset_attempts();
     }
 
+    fn is_prioritized(&self) -> bool {
+        self.prioritized
+    }
+
+    fn prioritize(&mut self) {
+        self.prioritized = true;
+        self.reset_attempts();
+    }
+
     fn set_retries(&mut self, retries: u32) {
         self.set_retries(retries);
     }
@@ -538,6 +603,20 @@ impl PendingMessage {
         PendingOperationResult::NotReady
     }
 
+    fn is_dropped_by_admin(&self) -> bool {
+        match self
+            .ctx
+            .origin_db
+            .retrieve_dropped_by_admin_by_message_id(&self.message.id())
+        {
+            Ok(dropped) => dropped.unwrap_or(false),
+            Err(e) => {
+                warn!(error=?e, "Failed to retrieve admin drop status for message");
+                false
+            }
+        }
+    }
+
     fn is_ready(&self) -> bool {
         self.next_attempt_after
             .map(|a| Instant::now() >= a)
@@ -560,6 +639,42 @@ impl PendingMessage {
         Ok(())
     }
 
+    /// Add the gas paid and spent for this message to the totals of its route.
+    async fn record_profitability(&self) {
+        let id = self.message.id();
+        let payment = self
+            .ctx
+            .origin_db
+            .retrieve_gas_payment_by_gas_payment_key(GasPaymentKey {
+                message_id: id,
+                destination: self.message.destination,
+            });
+        let expenditure = self
+            .ctx
+            .origin_db
+            .retrieve_gas_expenditure_by_message_id(id);
+        let (payment, expenditure) = match (payment, expenditure) {
+            (Ok(payment), Ok(expenditure)) => {
+                (payment.map(|p| p.payment).unwrap_or_default(), expenditure)
+            }
+            (Err(err), _) | (_, Err(err)) => {
+                warn!(message_id = ?id, err = %err, "Reading the gas payment and expenditure failed for message, it won't be included in profitability metrics");
+                return;
+            }
+        };
+        self.ctx
+            .profitability
+            .record_delivery(
+                self.ctx.metadata_builder.origin_domain(),
+                self.destination_domain(),
+                self.app_context.as_deref(),
+                payment,
+                expenditure.gas_used,
+                expenditure.tokens_used,
+            )
+            .await;
+    }
+
     fn reset_attempts(&mut self) {
         self.next_attempt_after = None;
         self.last_attempted_at = Instant::now();
@@ -587,6 +702,26 @@ impl PendingMessage {
         }
     }
 
+    /// Persist the ISM type and metadata size used for the latest prepare attempt, so that
+    /// they can be looked up when debugging a message.
+    fn persist_ism_and_metadata(&self, module_type: ModuleType, metadata: Option<&[u8]>) {
+        let id = self.message.id();
+        let result = self
+            .ctx
+            .origin_db
+            .store_ism_type_by_message_id(&id, &module_type)
+            .and_then(|_| match metadata {
+                Some(metadata) => self
+                    .ctx
+                    .origin_db
+                    .store_metadata_size_by_message_id(&id, &(metadata.len() as u64)),
+                None => Ok(()),
+            });
+        if let Err(e) = result {
+            warn!(message_id = ?id, err = %e, "Persisting the ISM type and metadata size failed for message");
+        }
+    }
+
     /// Get duration we should wait before re-attempting to deliver a message
     /// given the number of retries.
     /// `pub(crate)` for testing purposes
@@ -1,6 +1,6 @@
 use std::{
     cmp::max,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fmt::{Debug, Formatter},
     sync::Arc,
     time::Duration,
@@ -14,24 +14,23 @@ use hyperlane_base::{
     db::{HyperlaneRocksDB, ProcessMessage},
     CoreMetrics,
 };
-use hyperlane_core::{HyperlaneDomain, HyperlaneMessage, QueueOperation};
+use hyperlane_core::{HyperlaneDomain, HyperlaneMessage, QueueOperation, H256};
 use prometheus::IntGauge;
-use tokio::sync::mpsc::UnboundedSender;
-use tracing::{debug, instrument, trace};
+use tokio::sync::{broadcast::Sender as BroadcastSender, mpsc::UnboundedSender};
+use tracing::{debug, instrument, trace, warn};
 
-use super::{blacklist::AddressBlacklist, metadata::AppContextClassifier, pending_message::*};
+use super::{
+    message_filter::SharedMessageFilter, metadata::AppContextClassifier, op_queue::OpQueueCommand,
+    pending_message::*,
+};
 use crate::{processor::ProcessorExt, settings::matching_list::MatchingList};
 
 /// Finds unprocessed messages from an origin and submits then through a channel
 /// for to the appropriate destination.
 #[allow(clippy::too_many_arguments)]
 pub struct MessageProcessor {
-    /// A matching list of messages that should be whitelisted.
-    message_whitelist: Arc<MatchingList>,
-    /// A matching list of messages that should be blacklisted.
-    message_blacklist: Arc<MatchingList>,
-    /// Addresses that messages may not interact with.
-    address_blacklist: Arc<AddressBlacklist>,
+    /// Decides which messages should be relayed.
+    message_filter: SharedMessageFilter,
     metrics: MessageProcessorMetrics,
     /// channel for each destination chain to send operations (i.e. message
     /// submissions) to
@@ -40,12 +39,21 @@ pub struct MessageProcessor {
     destination_ctxs: HashMap<u32, Arc<MessageContext>>,
     metric_app_contexts: Vec<(MatchingList, String)>,
     nonce_iterator: ForwardBackwardIterator,
+    /// Used to drop the operations of reorged messages from the destination queues
+    op_queue_commands: BroadcastSender<OpQueueCommand>,
 }
 
+/// The number of the most recently iterated past messages that are checked for
+/// having been deleted from or replaced in the DB, e.g. because they were reorged.
+const MAX_VISITED_MESSAGES: usize = 10_000;
+
 #[derive(Debug)]
 struct ForwardBackwardIterator {
     low_nonce_iter: DirectionalNonceIterator,
     high_nonce_iter: DirectionalNonceIterator,
+    /// The ids of the messages iterated past, by nonce. Only the
+    /// `MAX_VISITED_MESSAGES` highest nonces are kept.
+    visited_messages: BTreeMap<u32, H256>,
     // here for debugging purposes
     _domain: String,
 }
@@ -75,10 +83,49 @@ impl ForwardBackwardIterator {
         Self {
             low_nonce_iter,
             high_nonce_iter,
+            visited_messages: BTreeMap::new(),
             _domain: domain,
         }
     }
 
+    fn visit(&mut self, message: &HyperlaneMessage) {
+        self.visited_messages.insert(message.nonce, message.id());
+        if self.visited_messages.len() > MAX_VISITED_MESSAGES {
+            self.visited_messages.pop_first();
+        }
+    }
+
+    /// Rewinds the high nonce iterator to the lowest nonce whose message was
+    /// deleted from or replaced in the DB since it was iterated past, e.g. because
+    /// it was reorged, so the message at that nonce is processed again once it's
+    /// re-indexed. Returns the ids of the messages that were rolled back.
+    fn roll_back_reorged_messages(&mut self) -> Result<Vec<H256>> {
+        let mut reorged_message_ids = vec![];
+        while let Some((&nonce, &message_id)) = self.visited_messages.last_key_value() {
+            let stored_message_id = self
+                .high_nonce_iter
+                .db
+                .retrieve_message_by_nonce(nonce)?
+                .map(|message| message.id());
+            if stored_message_id == Some(message_id) {
+                break;
+            }
+            self.visited_messages.remove(&nonce);
+            reorged_message_ids.push(message_id);
+            if self.high_nonce_iter.nonce.map_or(true, |high| high > nonce) {
+                self.high_nonce_iter.nonce = Some(nonce);
+            }
+        }
+        if !reorged_message_ids.is_empty() {
+            warn!(
+                ?reorged_message_ids,
+                high_nonce_iter = ?self.high_nonce_iter,
+                "Messages were rolled back, rewinding the high nonce iterator"
+            );
+        }
+        Ok(reorged_message_ids)
+    }
+
     async fn try_get_next_message(
         &mut self,
         metrics: &MessageProcessorMetrics,
@@ -89,18 +136,22 @@ impl ForwardBackwardIterator {
             // Always prioritize the high nonce message
             match (high_nonce_message_status, low_nonce_message_status) {
                 // Keep iterating if only processed messages are found
-                (MessageStatus::Processed, _) => {
+                (MessageStatus::Processed(high_nonce_message), _) => {
+                    self.visit(&high_nonce_message);
                     self.high_nonce_iter.iterate();
                 }
-                (_, MessageStatus::Processed) => {
+                (_, MessageStatus::Processed(low_nonce_message)) => {
+                    self.visit(&low_nonce_message);
                     self.low_nonce_iter.iterate();
                 }
                 // Otherwise return - either a processable message or nothing to process
                 (MessageStatus::Processable(high_nonce_message), _) => {
+                    self.visit(&high_nonce_message);
                     self.high_nonce_iter.iterate();
                     return Ok(Some(high_nonce_message));
                 }
                 (_, MessageStatus::Processable(low_nonce_message)) => {
+                    self.visit(&low_nonce_message);
                     self.low_nonce_iter.iterate();
                     return Ok(Some(low_nonce_message));
                 }
@@ -166,7 +217,7 @@ impl DirectionalNonceIterator {
                 debug!(?message, iterator=?self, "Found processable message");
                 return Ok(MessageStatus::Processable(message));
             } else {
-                return Ok(MessageStatus::Processed);
+                return Ok(MessageStatus::Processed(message));
             }
         }
         Ok(MessageStatus::Unindexed)
@@ -215,15 +266,15 @@ enum MessageStatus<T> {
     // The message was indexed and is ready to be processed.
     Processable(T),
     // The message was indexed and already processed.
-    Processed,
+    Processed(T),
 }
 
 impl Debug for MessageProcessor {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "MessageProcessor {{ message_whitelist: {:?}, message_blacklist: {:?}, address_blacklist: {:?}, nonce_iterator: {:?}}}",
-            self.message_whitelist, self.message_blacklist, self.address_blacklist, self.nonce_iterator
+            "MessageProcessor {{ message_filter: {:?}, nonce_iterator: {:?}}}",
+            self.message_filter, self.nonce_iterator
         )
     }
 }
@@ -238,6 +289,17 @@ impl ProcessorExt for MessageProcessor {
     /// One round of processing, extracted from infinite work loop for
     /// testing purposes.
     async fn tick(&mut self) -> Result<()> {
+        // Messages that were iterated past may have been reorged since. Their
+        // operations may still be in a destination's queues, so drop them there.
+        for message_id in self.nonce_iterator.roll_back_reorged_messages()? {
+            if let Err(err) = self
+                .op_queue_commands
+                .send(OpQueueCommand::Drop(message_id))
+            {
+                warn!(?message_id, error=?err, "Failed to drop the operation of a reorged message");
+            }
+        }
+
         // Forever, scan HyperlaneRocksDB looking for new messages to send. When criteria are
         // satisfied or the message is disqualified, push the message onto
         // self.tx_msg and then continue the scan at the next highest
@@ -250,21 +312,24 @@ impl ProcessorExt for MessageProcessor {
                 "Processor working on message"
             );
             let destination = msg.destination;
+            let message_filter = self.message_filter.read().await.clone();
 
             // Skip if not whitelisted.
-            if !self.message_whitelist.msg_matches(&msg, true) {
-                debug!(?msg, whitelist=?self.message_whitelist, "Message not whitelisted, skipping");
+            if !message_filter.whitelist.msg_matches(&msg, true) {
+                debug!(?msg, whitelist=?message_filter.whitelist, "Message not whitelisted, skipping");
                 return Ok(());
             }
 
             // Skip if the message is blacklisted
-            if self.message_blacklist.msg_matches(&msg, false) {
-                debug!(?msg, blacklist=?self.message_blacklist, "Message blacklisted, skipping");
+            if message_filter.blacklist.msg_matches(&msg, false) {
+                debug!(?msg, blacklist=?message_filter.blacklist, "Message blacklisted, skipping");
                 return Ok(());
             }
 
             // Skip if the message involves a blacklisted address
-            if let Some(blacklisted_address) = self.address_blacklist.find_blacklisted_address(&msg)
+            if let Some(blacklisted_address) = message_filter
+                .address_blacklist
+                .find_blacklisted_address(&msg)
             {
                 debug!(
                     ?msg,
@@ -286,6 +351,16 @@ impl ProcessorExt for MessageProcessor {
                 return Ok(());
             }
 
+            // Skip if an operator dropped the message through the admin API
+            if self.destination_ctxs[&destination]
+                .origin_db
+                .retrieve_dropped_by_admin_by_message_id(&msg.id())?
+                .unwrap_or(false)
+            {
+                debug!(?msg, "Message dropped by an operator, skipping");
+                return Ok(());
+            }
+
             debug!(%msg, "Sending message to submitter");
 
             let app_context_classifier =
@@ -310,23 +385,21 @@ impl MessageProcessor {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         db: HyperlaneRocksDB,
-        message_whitelist: Arc<MatchingList>,
-        message_blacklist: Arc<MatchingList>,
-        address_blacklist: Arc<AddressBlacklist>,
+        message_filter: SharedMessageFilter,
         metrics: MessageProcessorMetrics,
         send_channels: HashMap<u32, UnboundedSender<QueueOperation>>,
         destination_ctxs: HashMap<u32, Arc<MessageContext>>,
         metric_app_contexts: Vec<(MatchingList, String)>,
+        op_queue_commands: BroadcastSender<OpQueueCommand>,
     ) -> Self {
         Self {
-            message_whitelist,
-            message_blacklist,
-            address_blacklist,
+            message_filter,
             metrics,
             send_channels,
             destination_ctxs,
             metric_app_contexts,
             nonce_iterator: ForwardBackwardIterator::new(Arc::new(db) as Arc<dyn ProcessMessage>),
+            op_queue_commands,
         }
     }
 
@@ -388,8 +461,10 @@ mod test {
         msg::{
             gas_payment::GasPaymentEnforcer,
             metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
+            profitability::ProfitabilityTracker,
         },
         processor::Processor,
+        server::ENDPOINT_MESSAGES_QUEUE_SIZE,
     };
 
     use super::*;
@@ -433,6 +508,7 @@ mod test {
         ChainConf {
             domain: domain.clone(),
             signer: Default::default(),
+            additional_signers: Default::default(),
             reorg_period: Default::default(),
             addresses: Default::default(),
             connection: ChainConnectionConf::Ethereum(hyperlane_ethereum::ConnectionConf {
@@ -441,6 +517,10 @@ mod test {
                 },
                 transaction_overrides: Default::default(),
                 operation_batch: Default::default(),
+                max_in_flight_transactions: 1,
+                fee_escalation: None,
+                log_fetcher: None,
+                subscription_urls: vec![],
             }),
             metrics_conf: Default::default(),
             index: Default::default(),
@@ -488,6 +568,12 @@ mod test {
             origin_gas_payment_enforcer: Arc::new(GasPaymentEnforcer::new([], db.clone())),
             transaction_gas_limit: Default::default(),
             metrics: dummy_submission_metrics(),
+            profitability: Arc::new(ProfitabilityTracker::new(
+                &CoreMetrics::new("dummy_relayer", 37582, Registry::new()).unwrap(),
+                None,
+                [db.clone()],
+            )),
+            message_filter: Default::default(),
         });
 
         let (send_channel, receive_channel) = mpsc::unbounded_channel::<QueueOperation>();
@@ -495,12 +581,11 @@ mod test {
             MessageProcessor::new(
                 db.clone(),
                 Default::default(),
-                Default::default(),
-                Default::default(),
                 dummy_processor_metrics(origin_domain.id()),
                 HashMap::from([(destination_domain.id(), send_channel)]),
                 HashMap::from([(destination_domain.id(), message_context)]),
                 vec![],
+                BroadcastSender::new(ENDPOINT_MESSAGES_QUEUE_SIZE),
             ),
             receive_channel,
         )
@@ -726,4 +811,72 @@ mod test {
             Some(MAX_ONCHAIN_NONCE + 1)
         );
     }
+
+    #[tokio::test]
+    async fn test_forward_backward_iterator_rolls_back_reorged_messages() {
+        test_utils::run_test_db(|db| async move {
+            let origin_domain = dummy_domain(0, "dummy_origin_domain");
+            let destination_domain = dummy_domain(1, "dummy_destination_domain");
+            let db = HyperlaneRocksDB::new(&origin_domain, db);
+            let messages: Vec<_> = (0..4)
+                .map(|nonce| dummy_hyperlane_message(&destination_domain, nonce))
+                .collect();
+            for message in &messages {
+                add_db_entry(&db, message, 0);
+            }
+
+            let dummy_metrics = dummy_processor_metrics(origin_domain.id());
+            let mut forward_backward_iterator =
+                ForwardBackwardIterator::new(Arc::new(db.clone()) as Arc<dyn ProcessMessage>);
+            let mut nonces = vec![];
+            while let Some(msg) = forward_backward_iterator
+                .try_get_next_message(&dummy_metrics)
+                .await
+                .unwrap()
+            {
+                nonces.push(msg.nonce);
+            }
+            assert_eq!(nonces, vec![3, 2, 1, 0]);
+
+            // Nothing was reorged yet
+            assert!(forward_backward_iterator
+                .roll_back_reorged_messages()
+                .unwrap()
+                .is_empty());
+
+            // The messages with nonces 2 and 3 are reorged, and a different message
+            // with nonce 2 is indexed instead
+            db.delete_message(&messages[3]).unwrap();
+            db.delete_message(&messages[2]).unwrap();
+            let replacement_message = HyperlaneMessage {
+                body: vec![1],
+                ..messages[2].clone()
+            };
+            add_db_entry(&db, &replacement_message, 0);
+
+            assert_eq!(
+                forward_backward_iterator
+                    .roll_back_reorged_messages()
+                    .unwrap(),
+                vec![messages[3].id(), messages[2].id()]
+            );
+            assert_eq!(forward_backward_iterator.high_nonce_iter.nonce, Some(2));
+
+            // The replacement message is processed, and the iterator waits for nonce 3
+            // to be indexed again
+            let next_message = forward_backward_iterator
+                .try_get_next_message(&dummy_metrics)
+                .await
+                .unwrap();
+            assert_eq!(next_message, Some(replacement_message));
+            assert_eq!(
+                forward_backward_iterator
+                    .try_get_next_message(&dummy_metrics)
+                    .await
+                    .unwrap(),
+                None
+            );
+        })
+        .await;
+    }
 }
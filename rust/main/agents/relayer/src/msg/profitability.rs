@@ -0,0 +1,542 @@
+use std::{collections::HashMap, io::Write};
+
+use eyre::Result;
+use hyperlane_base::{db::HyperlaneRocksDB, CoreMetrics};
+use hyperlane_core::{
+    Decode, Encode, FixedPointNumber, HyperlaneDomain, HyperlaneProtocolError, U256,
+};
+use prometheus::{CounterVec, IntGaugeVec};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use tracing::warn;
+
+use crate::msg::gas_payment::price_oracle::UsdValuer;
+
+/// The app context label used for messages that don't match any app context
+const UNKNOWN_APP_CONTEXT: &str = "Unknown";
+/// Key under which the totals of every route from an origin are stored in
+/// that origin's database
+const ROUTE_PROFITABILITY: &str = "route_profitability";
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct RouteKey {
+    origin: String,
+    destination: String,
+    app_context: String,
+}
+
+/// Running totals of what the relayer was paid and spent to deliver the
+/// messages of a route. Persisted in the origin's database, so they survive
+/// restarts.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RouteProfitability {
+    pub origin: String,
+    pub destination: String,
+    pub app_context: String,
+    pub messages_delivered: u64,
+    /// Gas payments, in the smallest unit of the origin's native token
+    pub payments: U256,
+    /// Gas used on the destination, including failed attempts
+    pub gas_used: U256,
+    /// Spent on gas, in the smallest unit of the destination's native token
+    pub tokens_spent: U256,
+    /// USD value of the payments, valued at the time of delivery. Only
+    /// messages that could be valued are included.
+    pub payments_usd: f64,
+    /// USD value of the tokens spent, valued at the time of delivery. Only
+    /// messages that could be valued are included.
+    pub spent_usd: f64,
+    /// Messages that couldn't be valued in USD, e.g. due to a missing price
+    pub messages_not_valued: u64,
+    /// Whether the USD value of the payments is lower than what was spent.
+    /// Unknown if no message could be valued.
+    pub at_loss: Option<bool>,
+}
+
+impl RouteProfitability {
+    fn key(&self) -> RouteKey {
+        RouteKey {
+            origin: self.origin.clone(),
+            destination: self.destination.clone(),
+            app_context: self.app_context.clone(),
+        }
+    }
+}
+
+/// The totals of every route from an origin, as stored in its database.
+/// WARNING: This is serialized to JSON and stored in the database, so fields
+/// of `RouteProfitability` shouldn't be removed or renamed.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StoredRoutes(Vec<RouteProfitability>);
+
+impl Encode for StoredRoutes {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        let serialized = serde_json::to_vec(self)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Failed to serialize"))?;
+        writer.write(&serialized)
+    }
+}
+
+impl Decode for StoredRoutes {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        serde_json::from_reader(reader).map_err(|err| {
+            HyperlaneProtocolError::IoError(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to deserialize. Error: {}", err),
+            ))
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+struct ProfitabilityMetrics {
+    payments: CounterVec,
+    tokens_spent: CounterVec,
+    payments_usd: CounterVec,
+    spent_usd: CounterVec,
+    at_loss: IntGaugeVec,
+}
+
+impl ProfitabilityMetrics {
+    const LABELS: &'static [&'static str] = &["origin", "remote", "app_context"];
+
+    fn new(metrics: &CoreMetrics) -> Self {
+        Self {
+            payments: metrics
+                .new_counter(
+                    "route_gas_payments",
+                    "Gas payments for delivered messages, in the smallest unit of the origin's native token",
+                    Self::LABELS,
+                )
+                .expect("failed to register route_gas_payments metric"),
+            tokens_spent: metrics
+                .new_counter(
+                    "route_gas_spent",
+                    "Spent on gas to deliver messages, in the smallest unit of the destination's native token",
+                    Self::LABELS,
+                )
+                .expect("failed to register route_gas_spent metric"),
+            payments_usd: metrics
+                .new_counter(
+                    "route_gas_payments_usd",
+                    "USD value of gas payments for delivered messages",
+                    Self::LABELS,
+                )
+                .expect("failed to register route_gas_payments_usd metric"),
+            spent_usd: metrics
+                .new_counter(
+                    "route_gas_spent_usd",
+                    "USD value of gas spent to deliver messages",
+                    Self::LABELS,
+                )
+                .expect("failed to register route_gas_spent_usd metric"),
+            at_loss: metrics
+                .new_int_gauge(
+                    "route_at_loss",
+                    "Whether the USD value of gas payments is lower than the gas spent, 1 if so",
+                    Self::LABELS,
+                )
+                .expect("failed to register route_at_loss metric"),
+        }
+    }
+}
+
+/// Aggregates the gas payments and expenditures of delivered messages per
+/// (origin, destination, app context).
+#[derive(Debug)]
+pub struct ProfitabilityTracker {
+    routes: RwLock<HashMap<RouteKey, RouteProfitability>>,
+    /// Where the totals of the routes from each origin are persisted, by
+    /// origin name
+    origin_dbs: HashMap<String, HyperlaneRocksDB>,
+    /// Values payments and spending in USD, if token prices are configured
+    valuer: Option<UsdValuer>,
+    metrics: ProfitabilityMetrics,
+}
+
+impl ProfitabilityTracker {
+    /// Creates a tracker that starts from the totals persisted in
+    /// `origin_dbs`. The counter metrics only count deliveries since the
+    /// relayer started, while `route_at_loss` reflects the persisted totals.
+    pub fn new(
+        metrics: &CoreMetrics,
+        valuer: Option<UsdValuer>,
+        origin_dbs: impl IntoIterator<Item = HyperlaneRocksDB>,
+    ) -> Self {
+        let metrics = ProfitabilityMetrics::new(metrics);
+        let origin_dbs: HashMap<_, _> = origin_dbs
+            .into_iter()
+            .map(|db| (db.domain().name().to_owned(), db))
+            .collect();
+
+        let mut routes = HashMap::new();
+        for db in origin_dbs.values() {
+            let stored = match db.retrieve_decodable::<StoredRoutes>("", ROUTE_PROFITABILITY) {
+                Ok(stored) => stored.unwrap_or_default(),
+                Err(err) => {
+                    warn!(origin = %db.domain(), error = ?err, "Failed to load the persisted route profitability, starting from zero");
+                    continue;
+                }
+            };
+            for route in stored.0 {
+                if let Some(at_loss) = route.at_loss {
+                    metrics
+                        .at_loss
+                        .with_label_values(&[&route.origin, &route.destination, &route.app_context])
+                        .set(at_loss as i64);
+                }
+                routes.insert(route.key(), route);
+            }
+        }
+
+        Self {
+            routes: RwLock::new(routes),
+            origin_dbs,
+            valuer,
+            metrics,
+        }
+    }
+
+    /// Records the total gas payment and expenditure of a delivered message.
+    pub async fn record_delivery(
+        &self,
+        origin: &HyperlaneDomain,
+        destination: &HyperlaneDomain,
+        app_context: Option<&str>,
+        payment: U256,
+        gas_used: U256,
+        tokens_spent: U256,
+    ) {
+        let key = RouteKey {
+            origin: origin.name().to_owned(),
+            destination: destination.name().to_owned(),
+            app_context: app_context.unwrap_or(UNKNOWN_APP_CONTEXT).to_owned(),
+        };
+        let labels = [
+            key.origin.as_str(),
+            key.destination.as_str(),
+            key.app_context.as_str(),
+        ];
+        let usd = self
+            .usd_values(origin.id(), payment, destination.id(), tokens_spent)
+            .await;
+
+        let mut routes = self.routes.write().await;
+        let route = routes
+            .entry(key.clone())
+            .or_insert_with(|| RouteProfitability {
+                origin: key.origin.clone(),
+                destination: key.destination.clone(),
+                app_context: key.app_context.clone(),
+                ..Default::default()
+            });
+        route.messages_delivered += 1;
+        route.payments = route.payments.saturating_add(payment);
+        route.gas_used = route.gas_used.saturating_add(gas_used);
+        route.tokens_spent = route.tokens_spent.saturating_add(tokens_spent);
+        self.metrics
+            .payments
+            .with_label_values(&labels)
+            .inc_by(payment.to_f64_lossy());
+        self.metrics
+            .tokens_spent
+            .with_label_values(&labels)
+            .inc_by(tokens_spent.to_f64_lossy());
+
+        match usd {
+            Some((payment_usd, spent_usd)) => {
+                self.record_usd_values(route, &labels, payment_usd, spent_usd)
+            }
+            None => route.messages_not_valued += 1,
+        }
+
+        self.persist(&routes, &key.origin);
+    }
+
+    fn record_usd_values(
+        &self,
+        route: &mut RouteProfitability,
+        labels: &[&str],
+        payment_usd: f64,
+        spent_usd: f64,
+    ) {
+        route.payments_usd += payment_usd;
+        route.spent_usd += spent_usd;
+        self.metrics
+            .payments_usd
+            .with_label_values(labels)
+            .inc_by(payment_usd);
+        self.metrics
+            .spent_usd
+            .with_label_values(labels)
+            .inc_by(spent_usd);
+
+        let at_loss = route.payments_usd < route.spent_usd;
+        if at_loss && route.at_loss != Some(true) {
+            warn!(
+                origin = %route.origin,
+                destination = %route.destination,
+                app_context = %route.app_context,
+                payments_usd = route.payments_usd,
+                spent_usd = route.spent_usd,
+                "Route is running at a loss"
+            );
+        }
+        route.at_loss = Some(at_loss);
+        self.metrics
+            .at_loss
+            .with_label_values(labels)
+            .set(at_loss as i64);
+    }
+
+    /// Persists the totals of every route from `origin`. Expects the routes to
+    /// be locked, so concurrent deliveries are persisted in order.
+    fn persist(&self, routes: &HashMap<RouteKey, RouteProfitability>, origin: &str) {
+        let Some(db) = self.origin_dbs.get(origin) else {
+            warn!(origin, "No database to persist the route profitability in");
+            return;
+        };
+        let stored = StoredRoutes(
+            routes
+                .values()
+                .filter(|route| route.origin == origin)
+                .cloned()
+                .collect(),
+        );
+        if let Err(err) = db.store_encodable("", ROUTE_PROFITABILITY, &stored) {
+            warn!(origin, error = ?err, "Failed to persist the route profitability");
+        }
+    }
+
+    async fn usd_values(
+        &self,
+        origin: u32,
+        payment: U256,
+        destination: u32,
+        tokens_spent: U256,
+    ) -> Option<(f64, f64)> {
+        let valuer = self.valuer.as_ref()?;
+        match Self::value_in_usd(valuer, origin, payment, destination, tokens_spent).await {
+            Ok(values) => Some(values),
+            Err(err) => {
+                warn!(error = ?err, origin, destination, "Failed to value a delivered message in USD");
+                None
+            }
+        }
+    }
+
+    async fn value_in_usd(
+        valuer: &UsdValuer,
+        origin: u32,
+        payment: U256,
+        destination: u32,
+        tokens_spent: U256,
+    ) -> Result<(f64, f64)> {
+        let payment = valuer
+            .usd_value(origin, FixedPointNumber::try_from(payment)?)
+            .await?;
+        let spent = valuer
+            .usd_value(destination, FixedPointNumber::try_from(tokens_spent)?)
+            .await?;
+        Ok((payment.to_f64_lossy(), spent.to_f64_lossy()))
+    }
+
+    /// The totals of every route a message was delivered on, ordered by route
+    pub async fn routes(&self) -> Vec<RouteProfitability> {
+        let routes = self.routes.read().await;
+        let mut keys = routes.keys().collect::<Vec<_>>();
+        keys.sort();
+        keys.into_iter().map(|k| routes[k].clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, str::FromStr};
+
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::KnownHyperlaneDomain;
+
+    use super::*;
+    use crate::msg::gas_payment::price_oracle::StaticTokenPriceOracle;
+
+    fn dummy_metrics() -> CoreMetrics {
+        CoreMetrics::new("test", 9090, prometheus::Registry::new()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_record_delivery() {
+        let origin = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+        let destination = HyperlaneDomain::Known(KnownHyperlaneDomain::Ethereum);
+        let eth = U256::exp10(18);
+        let valuer = UsdValuer::new(
+            HashMap::new(),
+            Box::new(StaticTokenPriceOracle::new(HashMap::from([
+                (origin.id(), FixedPointNumber::from(2000)),
+                (destination.id(), FixedPointNumber::from(2000)),
+            ]))),
+        );
+        let tracker = ProfitabilityTracker::new(&dummy_metrics(), Some(valuer), []);
+
+        // paid 0.01 ETH and spent 0.005 ETH
+        tracker
+            .record_delivery(
+                &origin,
+                &destination,
+                Some("app"),
+                eth / 100,
+                U256::from(100_000u32),
+                eth / 200,
+            )
+            .await;
+        let routes = tracker.routes().await;
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].messages_delivered, 1);
+        assert_eq!(routes[0].payments, eth / 100);
+        assert_eq!(routes[0].payments_usd, 20.0);
+        assert_eq!(routes[0].spent_usd, 10.0);
+        assert_eq!(routes[0].at_loss, Some(false));
+
+        // paid nothing and spent 0.01 ETH, bringing the route to a loss
+        tracker
+            .record_delivery(
+                &origin,
+                &destination,
+                Some("app"),
+                U256::zero(),
+                U256::from(200_000u32),
+                eth / 100,
+            )
+            .await;
+        let routes = tracker.routes().await;
+        assert_eq!(routes[0].messages_delivered, 2);
+        assert_eq!(routes[0].gas_used, U256::from(300_000u32));
+        assert_eq!(routes[0].spent_usd, 30.0);
+        assert_eq!(routes[0].at_loss, Some(true));
+        assert_eq!(
+            tracker
+                .metrics
+                .at_loss
+                .with_label_values(&["arbitrum", "ethereum", "app"])
+                .get(),
+            1
+        );
+
+        // Other app contexts are tracked separately
+        tracker
+            .record_delivery(
+                &origin,
+                &destination,
+                None,
+                U256::one(),
+                U256::one(),
+                U256::one(),
+            )
+            .await;
+        let routes = tracker.routes().await;
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].app_context, UNKNOWN_APP_CONTEXT);
+        assert_eq!(routes[1].app_context, "app");
+    }
+
+    #[tokio::test]
+    async fn test_record_delivery_without_prices() {
+        let origin = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+        let destination = HyperlaneDomain::Known(KnownHyperlaneDomain::Ethereum);
+        let valuer = UsdValuer::new(
+            HashMap::new(),
+            Box::new(StaticTokenPriceOracle::new(HashMap::from([(
+                origin.id(),
+                FixedPointNumber::from_str("0.5").unwrap(),
+            )]))),
+        );
+
+        for tracker in [
+            ProfitabilityTracker::new(&dummy_metrics(), None, []),
+            // No price for the destination
+            ProfitabilityTracker::new(&dummy_metrics(), Some(valuer), []),
+        ] {
+            tracker
+                .record_delivery(
+                    &origin,
+                    &destination,
+                    None,
+                    U256::one(),
+                    U256::one(),
+                    U256::one(),
+                )
+                .await;
+            let routes = tracker.routes().await;
+            assert_eq!(routes[0].messages_delivered, 1);
+            assert_eq!(routes[0].messages_not_valued, 1);
+            assert_eq!(routes[0].at_loss, None);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_totals_are_persisted() {
+        test_utils::run_test_db(|db| async move {
+            let origin = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+            let destination = HyperlaneDomain::Known(KnownHyperlaneDomain::Ethereum);
+            let db = HyperlaneRocksDB::new(&origin, db);
+            let valuer = || {
+                UsdValuer::new(
+                    HashMap::new(),
+                    Box::new(StaticTokenPriceOracle::new(HashMap::from([
+                        (origin.id(), FixedPointNumber::from(1)),
+                        (destination.id(), FixedPointNumber::from(1)),
+                    ]))),
+                )
+            };
+
+            let tracker = ProfitabilityTracker::new(&dummy_metrics(), Some(valuer()), [db.clone()]);
+            tracker
+                .record_delivery(
+                    &origin,
+                    &destination,
+                    Some("app"),
+                    U256::from(5u32),
+                    U256::one(),
+                    U256::from(10u32),
+                )
+                .await;
+            let routes = tracker.routes().await;
+
+            // A restarted relayer picks up where the previous one left off
+            let metrics = dummy_metrics();
+            let tracker = ProfitabilityTracker::new(&metrics, Some(valuer()), [db.clone()]);
+            assert_eq!(tracker.routes().await, routes);
+            assert_eq!(
+                tracker
+                    .metrics
+                    .at_loss
+                    .with_label_values(&["arbitrum", "ethereum", "app"])
+                    .get(),
+                1
+            );
+
+            tracker
+                .record_delivery(
+                    &origin,
+                    &destination,
+                    Some("app"),
+                    U256::from(20u32),
+                    U256::one(),
+                    U256::from(10u32),
+                )
+                .await;
+            let routes = tracker.routes().await;
+            assert_eq!(routes[0].messages_delivered, 2);
+            assert_eq!(routes[0].payments, U256::from(25u32));
+            assert_eq!(routes[0].at_loss, Some(false));
+        })
+        .await;
+    }
+}
@@ -0,0 +1,188 @@
+use std::{sync::Arc, time::Duration};
+
+use hyperlane_base::LoadableFromSettings;
+use tokio::{sync::Mutex, task::JoinHandle, time::sleep};
+use tracing::{debug, info, info_span, instrument::Instrumented, warn, Instrument};
+
+use crate::{
+    msg::{
+        gas_payment::GasPaymentEnforcer,
+        message_filter::{MessageFilter, SharedMessageFilter},
+    },
+    settings::{RelayerPolicies, RelayerSettings},
+};
+
+/// Replaces the relayer's message filter and gas payment enforcement policies
+/// while it is running. Operations that are already in the submitter queues are
+/// kept, and are evaluated against the new policies the next time they are
+/// prepared.
+///
+/// Policies come from two sources: the config files, which are reloaded
+/// periodically, and operators through the admin API. Once an operator sets
+/// policies, they take precedence over the config files until the override is
+/// cleared.
+#[derive(Debug)]
+pub struct PolicyUpdater {
+    /// The policies currently in effect. Also serializes concurrent updates.
+    current: Mutex<CurrentPolicies>,
+    message_filter: SharedMessageFilter,
+    gas_payment_enforcers: Vec<Arc<GasPaymentEnforcer>>,
+}
+
+impl PolicyUpdater {
+    pub fn new(
+        policies: RelayerPolicies,
+        message_filter: SharedMessageFilter,
+        gas_payment_enforcers: Vec<Arc<GasPaymentEnforcer>>,
+    ) -> Self {
+        Self {
+            current: Mutex::new(CurrentPolicies {
+                policies,
+                overridden: false,
+            }),
+            message_filter,
+            gas_payment_enforcers,
+        }
+    }
+
+    /// The policies currently in effect
+    pub async fn current(&self) -> RelayerPolicies {
+        self.current.lock().await.policies.clone()
+    }
+
+    /// Whether policies set by an operator are in effect
+    pub async fn is_overridden(&self) -> bool {
+        self.current.lock().await.overridden
+    }
+
+    /// Apply `policies` set by an operator. They take precedence over the
+    /// config files until `clear_override` is called. Returns whether they
+    /// differed from the ones currently in effect.
+    pub async fn override_policies(&self, policies: RelayerPolicies) -> bool {
+        let mut current = self.current.lock().await;
+        current.overridden = true;
+        self.apply(&mut current, policies).await
+    }
+
+    /// Stop the override set by an operator, and apply `policies` from the
+    /// config files instead. Returns whether they differed from the ones
+    /// currently in effect.
+    pub async fn clear_override(&self, policies: RelayerPolicies) -> bool {
+        let mut current = self.current.lock().await;
+        current.overridden = false;
+        self.apply(&mut current, policies).await
+    }
+
+    /// Apply `policies` reloaded from the config files, unless an operator has
+    /// overridden them. Returns whether they were applied and differed from the
+    /// ones currently in effect.
+    pub async fn reload(&self, policies: RelayerPolicies) -> bool {
+        let mut current = self.current.lock().await;
+        if current.overridden {
+            if current.policies != policies {
+                debug!("Relayer config policies changed, but are overridden through the admin API");
+            }
+            return false;
+        }
+        self.apply(&mut current, policies).await
+    }
+
+    async fn apply(&self, current: &mut CurrentPolicies, policies: RelayerPolicies) -> bool {
+        if current.policies == policies {
+            return false;
+        }
+
+        *self.message_filter.write().await = Arc::new(MessageFilter::from(&policies));
+        for enforcer in &self.gas_payment_enforcers {
+            enforcer
+                .set_policies(policies.gas_payment_enforcement.clone())
+                .await;
+        }
+        info!(
+            whitelist = %policies.whitelist,
+            blacklist = %policies.blacklist,
+            address_blacklist_len = policies.address_blacklist.len(),
+            gas_enforcement_policies = ?policies.gas_payment_enforcement,
+            overridden = current.overridden,
+            "Applied new relayer policies"
+        );
+        current.policies = policies;
+        true
+    }
+
+    /// Periodically reload the relayer config files and apply any changes to
+    /// the policies.
+    pub fn spawn_reloader(self: Arc<Self>, interval: Duration) -> Instrumented<JoinHandle<()>> {
+        tokio::spawn(async move {
+            loop {
+                sleep(interval).await;
+                match RelayerSettings::load() {
+                    Ok(settings) => {
+                        self.reload(settings.policies.clone()).await;
+                    }
+                    Err(err) => {
+                        warn!(error = ?err, "Failed to reload the relayer config, keeping the current policies");
+                    }
+                }
+            }
+        })
+        .instrument(info_span!("PolicyReloader"))
+    }
+}
+
+#[derive(Debug)]
+struct CurrentPolicies {
+    policies: RelayerPolicies,
+    /// Whether `policies` were set by an operator through the admin API
+    overridden: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use hyperlane_core::HyperlaneMessage;
+
+    use super::*;
+
+    fn blacklisting_origin(origin: u32) -> RelayerPolicies {
+        RelayerPolicies::default()
+            .with_overrides(&serde_json::json!({ "blacklist": [{ "originDomain": origin }] }))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_override_takes_precedence_over_reloads() {
+        let message_filter = SharedMessageFilter::default();
+        let updater =
+            PolicyUpdater::new(RelayerPolicies::default(), message_filter.clone(), vec![]);
+        let is_blacklisted = |origin| {
+            let message_filter = message_filter.clone();
+            async move {
+                message_filter.read().await.blacklist.msg_matches(
+                    &HyperlaneMessage {
+                        origin,
+                        ..Default::default()
+                    },
+                    false,
+                )
+            }
+        };
+
+        // Reloads apply while there's no override
+        assert!(updater.reload(blacklisting_origin(1)).await);
+        assert!(is_blacklisted(1).await);
+
+        // Reloads are ignored while an operator's policies are in effect
+        assert!(updater.override_policies(blacklisting_origin(2)).await);
+        assert!(updater.is_overridden().await);
+        assert!(!updater.reload(blacklisting_origin(1)).await);
+        assert!(is_blacklisted(2).await);
+        assert!(!is_blacklisted(1).await);
+
+        // Clearing the override goes back to the config policies
+        assert!(updater.clear_override(blacklisting_origin(1)).await);
+        assert!(!updater.is_overridden().await);
+        assert!(is_blacklisted(1).await);
+        assert!(updater.reload(blacklisting_origin(3)).await);
+        assert!(is_blacklisted(3).await);
+    }
+}
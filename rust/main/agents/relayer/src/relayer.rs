@@ -2,6 +2,7 @@ use std::{
     collections::{HashMap, HashSet},
     fmt::{Debug, Formatter},
     sync::Arc,
+    time::Duration,
 };
 
 use async_trait::async_trait;
@@ -34,14 +35,17 @@ use tracing::{error, info, info_span, instrument::Instrumented, warn, Instrument
 use crate::{
     merkle_tree::builder::MerkleTreeBuilder,
     msg::{
-        blacklist::AddressBlacklist,
         gas_payment::GasPaymentEnforcer,
+        message_filter::{MessageFilter, SharedMessageFilter},
         metadata::{BaseMetadataBuilder, IsmAwareAppContextClassifier},
+        op_queue::OpQueueCommand,
         op_submitter::{SerialSubmitter, SerialSubmitterMetrics},
         pending_message::{MessageContext, MessageSubmissionMetrics},
         processor::{MessageProcessor, MessageProcessorMetrics},
+        profitability::ProfitabilityTracker,
     },
-    server::{self as relayer_server, MessageRetryRequest},
+    policies::PolicyUpdater,
+    server as relayer_server,
     settings::{matching_list::MatchingList, RelayerSettings},
 };
 use crate::{
@@ -72,13 +76,17 @@ pub struct Relayer {
     prover_syncs: HashMap<HyperlaneDomain, Arc<RwLock<MerkleTreeBuilder>>>,
     merkle_tree_hook_syncs: HashMap<HyperlaneDomain, Arc<dyn ContractSyncer<MerkleTreeInsertion>>>,
     dbs: HashMap<HyperlaneDomain, HyperlaneRocksDB>,
-    message_whitelist: Arc<MatchingList>,
-    message_blacklist: Arc<MatchingList>,
-    address_blacklist: Arc<AddressBlacklist>,
+    message_filter: SharedMessageFilter,
+    /// Replaces `message_filter` and the gas payment enforcement policies at runtime
+    policy_updater: Arc<PolicyUpdater>,
+    policies_reload_interval: Option<Duration>,
     transaction_gas_limit: Option<U256>,
     skip_transaction_gas_limit_for: HashSet<u32>,
     allow_local_checkpoint_syncers: bool,
     metric_app_contexts: Vec<(MatchingList, String)>,
+    /// Bearer token for the admin API, which is only served if this is set
+    admin_token: Option<String>,
+    profitability: Arc<ProfitabilityTracker>,
     core_metrics: Arc<CoreMetrics>,
     // TODO: decide whether to consolidate `agent_metrics` and `chain_metrics` into a single struct
     // or move them in `core_metrics`, like the validator metrics
@@ -92,12 +100,10 @@ impl Debug for Relayer {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "Relayer {{ origin_chains: {:?}, destination_chains: {:?}, message_whitelist: {:?}, message_blacklist: {:?}, address_blacklist: {:?}, transaction_gas_limit: {:?}, skip_transaction_gas_limit_for: {:?}, allow_local_checkpoint_syncers: {:?} }}",
+            "Relayer {{ origin_chains: {:?}, destination_chains: {:?}, message_filter: {:?}, transaction_gas_limit: {:?}, skip_transaction_gas_limit_for: {:?}, allow_local_checkpoint_syncers: {:?} }}",
             self.origin_chains,
             self.destination_chains,
-            self.message_whitelist,
-            self.message_blacklist,
-            self.address_blacklist,
+            self.message_filter,
             self.transaction_gas_limit,
             self.skip_transaction_gas_limit_for,
             self.allow_local_checkpoint_syncers
@@ -182,16 +188,16 @@ impl BaseAgent for Relayer {
             .map(|(k, v)| (k, v as _))
             .collect();
 
-        let message_whitelist = Arc::new(settings.whitelist);
-        let message_blacklist = Arc::new(settings.blacklist);
-        let address_blacklist = Arc::new(AddressBlacklist::new(settings.address_blacklist));
+        let message_filter: SharedMessageFilter = Arc::new(RwLock::new(Arc::new(
+            MessageFilter::from(&settings.policies),
+        )));
         let skip_transaction_gas_limit_for = settings.skip_transaction_gas_limit_for;
         let transaction_gas_limit = settings.transaction_gas_limit;
 
         info!(
-            %message_whitelist,
-            %message_blacklist,
-            ?address_blacklist,
+            message_whitelist = %settings.policies.whitelist,
+            message_blacklist = %settings.policies.blacklist,
+            address_blacklist = ?settings.policies.address_blacklist,
             ?transaction_gas_limit,
             ?skip_transaction_gas_limit_for,
             "Whitelist configuration"
@@ -209,7 +215,7 @@ impl BaseAgent for Relayer {
             })
             .collect::<HashMap<_, _>>();
 
-        info!(gas_enforcement_policies=?settings.gas_payment_enforcement, "Gas enforcement configuration");
+        info!(gas_enforcement_policies=?settings.policies.gas_payment_enforcement, "Gas enforcement configuration");
 
         // need one of these per origin chain due to the database scoping even though
         // the config itself is the same
@@ -220,13 +226,25 @@ impl BaseAgent for Relayer {
                 (
                     domain.clone(),
                     Arc::new(GasPaymentEnforcer::new(
-                        settings.gas_payment_enforcement.clone(),
+                        settings.policies.gas_payment_enforcement.clone(),
                         dbs.get(domain).unwrap().clone(),
                     )),
                 )
             })
             .collect();
 
+        let policy_updater = Arc::new(PolicyUpdater::new(
+            settings.policies.clone(),
+            message_filter.clone(),
+            gas_payment_enforcers.values().cloned().collect(),
+        ));
+
+        let profitability = Arc::new(ProfitabilityTracker::new(
+            &core_metrics,
+            settings.token_prices.clone().map(Into::into),
+            dbs.values().cloned(),
+        ));
+
         let mut msg_ctxs = HashMap::new();
         let mut destination_chains = HashMap::new();
         for destination in &settings.destination_chains {
@@ -267,6 +285,8 @@ impl BaseAgent for Relayer {
                         origin_gas_payment_enforcer: gas_payment_enforcers[origin].clone(),
                         transaction_gas_limit,
                         metrics: MessageSubmissionMetrics::new(&core_metrics, origin, destination),
+                        profitability: profitability.clone(),
+                        message_filter: message_filter.clone(),
                     }),
                 );
             }
@@ -282,13 +302,15 @@ impl BaseAgent for Relayer {
             interchain_gas_payment_syncs,
             prover_syncs,
             merkle_tree_hook_syncs,
-            message_whitelist,
-            message_blacklist,
-            address_blacklist,
+            message_filter,
+            policy_updater,
+            policies_reload_interval: settings.policies_reload_interval,
             transaction_gas_limit,
             skip_transaction_gas_limit_for,
             allow_local_checkpoint_syncers: settings.allow_local_checkpoint_syncers,
             metric_app_contexts: settings.metric_app_contexts,
+            admin_token: settings.admin_token,
+            profitability,
             core_metrics,
             agent_metrics,
             chain_metrics,
@@ -311,10 +333,11 @@ impl BaseAgent for Relayer {
                 }));
             tasks.push(console_server.instrument(info_span!("Tokio console server")));
         }
-        let sender = BroadcastSender::<MessageRetryRequest>::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
+        let sender = BroadcastSender::<OpQueueCommand>::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
         // send channels by destination chain
         let mut send_channels = HashMap::with_capacity(self.destination_chains.len());
         let mut prep_queues = HashMap::with_capacity(self.destination_chains.len());
+        let mut paused_submitters = HashMap::with_capacity(self.destination_chains.len());
         for (dest_domain, dest_conf) in &self.destination_chains {
             let (send_channel, receive_channel) = mpsc::unbounded_channel::<QueueOperation>();
             send_channels.insert(dest_domain.id(), send_channel);
@@ -329,9 +352,11 @@ impl BaseAgent for Relayer {
                     .operation_batch_config()
                     .map(|c| c.max_batch_size)
                     .unwrap_or(1),
+                self.core.settings.chains[dest_domain.name()].max_concurrent_submissions(),
                 task_monitor.clone(),
             );
             prep_queues.insert(dest_domain.id(), serial_submitter.prepare_queue().await);
+            paused_submitters.insert(dest_domain.id(), serial_submitter.paused());
 
             tasks.push(self.run_destination_submitter(
                 dest_domain,
@@ -377,10 +402,30 @@ impl BaseAgent for Relayer {
             );
         }
         // run server
-        let custom_routes = relayer_server::Server::new()
+        let dbs_by_domain: HashMap<_, _> = self
+            .dbs
+            .iter()
+            .map(|(domain, db)| (domain.id(), db.clone()))
+            .collect();
+        let mut routes_builder = relayer_server::Server::new()
             .with_op_retry(sender.clone())
             .with_message_queue(prep_queues)
-            .routes();
+            .with_operation_history(dbs_by_domain.clone())
+            .with_profitability(self.profitability.clone());
+        if let Some(admin_token) = self.admin_token.clone() {
+            routes_builder = routes_builder.with_admin(
+                admin_token,
+                sender.clone(),
+                paused_submitters,
+                dbs_by_domain,
+                self.policy_updater.clone(),
+            );
+        }
+        let custom_routes = routes_builder.routes();
+
+        if let Some(interval) = self.policies_reload_interval {
+            tasks.push(self.policy_updater.clone().spawn_reloader(interval));
+        }
 
         let server = self
             .core
@@ -397,6 +442,7 @@ impl BaseAgent for Relayer {
             tasks.push(self.run_message_processor(
                 origin,
                 send_channels.clone(),
+                sender.clone(),
                 task_monitor.clone(),
             ));
             tasks.push(self.run_merkle_tree_processor(origin, task_monitor.clone()));
@@ -488,6 +534,7 @@ impl Relayer {
         &self,
         origin: &HyperlaneDomain,
         send_channels: HashMap<u32, UnboundedSender<QueueOperation>>,
+        op_queue_commands: BroadcastSender<OpQueueCommand>,
         task_monitor: TaskMonitor,
     ) -> Instrumented<JoinHandle<()>> {
         let metrics = MessageProcessorMetrics::new(
@@ -513,13 +560,12 @@ impl Relayer {
 
         let message_processor = MessageProcessor::new(
             self.dbs.get(origin).unwrap().clone(),
-            self.message_whitelist.clone(),
-            self.message_blacklist.clone(),
-            self.address_blacklist.clone(),
+            self.message_filter.clone(),
             metrics,
             send_channels,
             destination_ctxs,
             self.metric_app_contexts.clone(),
+            op_queue_commands,
         );
 
         let span = info_span!("MessageProcessor", origin=%message_processor.domain());
@@ -0,0 +1,451 @@
+use axum::{
+    extract::{Query, State},
+    http::{header, Request, StatusCode},
+    middleware::{self, Next},
+    response::Response,
+    routing, Json, Router,
+};
+use derive_new::new;
+use hyperlane_base::{db::HyperlaneRocksDB, LoadableFromSettings};
+use hyperlane_core::{ChainCommunicationError, H256};
+use serde::Deserialize;
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use tokio::sync::broadcast::Sender;
+use tracing::info;
+
+use crate::{
+    msg::op_queue::OpQueueCommand,
+    policies::PolicyUpdater,
+    server::MessageRetryRequest,
+    settings::{matching_list::MatchingList, RelayerSettings},
+};
+
+const ADMIN_API_BASE: &str = "/admin";
+
+/// Endpoints that mutate the state of the relayer. Every request must carry
+/// the configured admin token as a bearer token.
+#[derive(new, Clone)]
+pub struct AdminApi {
+    token: String,
+    tx: Sender<OpQueueCommand>,
+    /// Pause flags of the `SerialSubmitter`s, by destination domain
+    paused_submitters: HashMap<u32, Arc<AtomicBool>>,
+    /// Origin databases, by origin domain
+    dbs: HashMap<u32, HyperlaneRocksDB>,
+    policy_updater: Arc<PolicyUpdater>,
+}
+
+#[derive(Deserialize)]
+struct DestinationDomainRequest {
+    destination_domain: u32,
+}
+
+#[derive(Deserialize)]
+struct RawMessageIdRequest {
+    message_id: String,
+}
+
+impl TryFrom<RawMessageIdRequest> for H256 {
+    type Error = ChainCommunicationError;
+
+    fn try_from(request: RawMessageIdRequest) -> Result<Self, Self::Error> {
+        Ok(H256::from_str(&request.message_id)?)
+    }
+}
+
+impl AdminApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/pause", routing::post(pause_submitter))
+            .route("/resume", routing::post(resume_submitter))
+            .route("/drop", routing::post(drop_message))
+            .route("/prioritize", routing::post(prioritize_message))
+            .route("/retry", routing::post(retry_matching))
+            .route(
+                "/policies",
+                routing::post(update_policies).delete(reset_policies),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(self.token.clone()),
+                require_admin_token,
+            ))
+            .with_state(self.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (ADMIN_API_BASE, self.router())
+    }
+
+    fn set_paused(&self, destination_domain: u32, paused: bool) -> String {
+        let Some(flag) = self.paused_submitters.get(&destination_domain) else {
+            return format!("No submitter found for domain {}", destination_domain);
+        };
+        flag.store(paused, Ordering::Relaxed);
+        let action = if paused { "Paused" } else { "Resumed" };
+        info!(
+            destination_domain,
+            "{} submitter through the admin API", action
+        );
+        format!("{} submitter for domain {}", action, destination_domain)
+    }
+
+    /// Marks the message as dropped in its origin database, so that it isn't
+    /// enqueued again after a restart. Returns whether the message was found.
+    fn persist_drop(&self, message_id: &H256) -> Result<bool, String> {
+        for db in self.dbs.values() {
+            let message = db
+                .retrieve_message_by_id(message_id)
+                .map_err(|err| format!("Failed to read message: {}", err))?;
+            if message.is_some() {
+                db.store_dropped_by_admin_by_message_id(message_id, &true)
+                    .map_err(|err| format!("Failed to persist dropped message: {}", err))?;
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn send(&self, command: OpQueueCommand) -> Result<(), String> {
+        self.tx
+            .send(command)
+            .map(|_| ())
+            .map_err(|err| format!("Failed to send command to the queues: {}", err))
+    }
+}
+
+async fn require_admin_token<B>(
+    State(token): State<Arc<String>>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Result<Response, StatusCode> {
+    let authorized = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|provided| constant_time_eq(provided.as_bytes(), token.as_bytes()))
+        .unwrap_or(false);
+    if !authorized {
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    Ok(next.run(request).await)
+}
+
+/// Compares two byte strings without short-circuiting on the first difference
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+async fn pause_submitter(
+    State(api): State<AdminApi>,
+    Query(request): Query<DestinationDomainRequest>,
+) -> String {
+    api.set_paused(request.destination_domain, true)
+}
+
+async fn resume_submitter(
+    State(api): State<AdminApi>,
+    Query(request): Query<DestinationDomainRequest>,
+) -> String {
+    api.set_paused(request.destination_domain, false)
+}
+
+async fn drop_message(
+    State(api): State<AdminApi>,
+    Query(request): Query<RawMessageIdRequest>,
+) -> String {
+    let message_id: H256 = match request.try_into() {
+        Ok(message_id) => message_id,
+        Err(err) => return format!("Failed to parse message id: {}", err),
+    };
+    match api.persist_drop(&message_id) {
+        Ok(true) => {}
+        Ok(false) => return format!("No message found with id {:?}", message_id),
+        Err(err) => return err,
+    }
+    info!(?message_id, "Dropped message through the admin API");
+    if let Err(err) = api.send(OpQueueCommand::Drop(message_id)) {
+        return err;
+    }
+    format!("Dropped message {:?}", message_id)
+}
+
+async fn prioritize_message(
+    State(api): State<AdminApi>,
+    Query(request): Query<RawMessageIdRequest>,
+) -> String {
+    let message_id: H256 = match request.try_into() {
+        Ok(message_id) => message_id,
+        Err(err) => return format!("Failed to parse message id: {}", err),
+    };
+    if let Err(err) = api.send(OpQueueCommand::Prioritize(message_id)) {
+        return err;
+    }
+    format!("Moved message {:?} to the front of its queue", message_id)
+}
+
+async fn retry_matching(
+    State(api): State<AdminApi>,
+    Json(matching_list): Json<MatchingList>,
+) -> String {
+    if matching_list == MatchingList::default() {
+        return "The matching list is empty, so no messages would be retried".to_string();
+    }
+    if let Err(err) = api.send(OpQueueCommand::Retry(MessageRetryRequest::MatchingList(
+        matching_list,
+    ))) {
+        return err;
+    }
+    "Moved matching message(s) to the front of the queue".to_string()
+}
+
+/// Replace the whitelist, blacklist, address blacklist and gas payment
+/// enforcement policies. The body uses the same shape as the relayer config,
+/// and any policy that isn't set keeps its current value.
+async fn update_policies(State(api): State<AdminApi>, Json(raw): Json<Value>) -> String {
+    let policies = match api.policy_updater.current().await.with_overrides(&raw) {
+        Ok(policies) => policies,
+        Err(err) => return format!("Failed to parse policies: {}", err),
+    };
+    info!("Overriding relayer policies through the admin API");
+    if api.policy_updater.override_policies(policies).await {
+        "Applied new relayer policies, config file reloads are ignored until they are reset"
+            .to_string()
+    } else {
+        "Relayer policies are unchanged".to_string()
+    }
+}
+
+/// Drops the policies set through the admin API in favour of the ones in the
+/// config files
+async fn reset_policies(State(api): State<AdminApi>) -> String {
+    let settings = match RelayerSettings::load() {
+        Ok(settings) => settings,
+        Err(err) => return format!("Failed to load the relayer config: {}", err),
+    };
+    info!("Resetting relayer policies to the config through the admin API");
+    if api.policy_updater.clear_override(settings.policies).await {
+        "Applied the relayer policies from the config".to_string()
+    } else {
+        "Relayer policies are unchanged".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::server::ENDPOINT_MESSAGES_QUEUE_SIZE;
+
+    use super::*;
+    use crate::{msg::message_filter::SharedMessageFilter, settings::RelayerPolicies};
+    use ethers::utils::hex::ToHex;
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{HyperlaneDomain, HyperlaneMessage};
+    use std::net::SocketAddr;
+    use tokio::sync::broadcast::Receiver;
+
+    const TOKEN: &str = "test-admin-token";
+
+    fn dummy_policy_updater(message_filter: SharedMessageFilter) -> Arc<PolicyUpdater> {
+        Arc::new(PolicyUpdater::new(
+            RelayerPolicies::default(),
+            message_filter,
+            vec![],
+        ))
+    }
+
+    fn setup_test_server(
+        paused_submitters: HashMap<u32, Arc<AtomicBool>>,
+        dbs: HashMap<u32, HyperlaneRocksDB>,
+        policy_updater: Arc<PolicyUpdater>,
+    ) -> (SocketAddr, Receiver<OpQueueCommand>) {
+        let broadcast_tx = Sender::<OpQueueCommand>::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
+        let api = AdminApi::new(
+            TOKEN.to_string(),
+            broadcast_tx.clone(),
+            paused_submitters,
+            dbs,
+            policy_updater,
+        );
+        let (path, router) = api.get_route();
+        let app = Router::new().nest(path, router);
+
+        // Running the app in the background using a test server
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        (addr, broadcast_tx.subscribe())
+    }
+
+    #[tokio::test]
+    async fn test_requires_token() {
+        let (addr, _rx) = setup_test_server(
+            HashMap::new(),
+            HashMap::new(),
+            dummy_policy_updater(Default::default()),
+        );
+        let url = format!(
+            "http://{}{}/prioritize?message_id=0x00",
+            addr, ADMIN_API_BASE
+        );
+
+        let response = reqwest::Client::new().post(&url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = reqwest::Client::new()
+            .post(&url)
+            .bearer_auth("wrong-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume() {
+        let paused = Arc::new(AtomicBool::new(false));
+        let (addr, _rx) = setup_test_server(
+            HashMap::from([(42, paused.clone())]),
+            HashMap::new(),
+            dummy_policy_updater(Default::default()),
+        );
+        let client = reqwest::Client::new();
+
+        let response = client
+            .post(format!(
+                "http://{}{}/pause?destination_domain=42",
+                addr, ADMIN_API_BASE
+            ))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(paused.load(Ordering::Relaxed));
+
+        client
+            .post(format!(
+                "http://{}{}/resume?destination_domain=42",
+                addr, ADMIN_API_BASE
+            ))
+            .bearer_auth(TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert!(!paused.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn test_drop_message() {
+        test_utils::run_test_db(|db| async move {
+            let domain = HyperlaneDomain::new_test_domain("test_drop_message");
+            let db = HyperlaneRocksDB::new(&domain, db);
+            let message = HyperlaneMessage {
+                origin: domain.id(),
+                ..Default::default()
+            };
+            db.store_message(&message, 1).unwrap();
+
+            let (addr, mut rx) = setup_test_server(
+                HashMap::new(),
+                HashMap::from([(domain.id(), db.clone())]),
+                dummy_policy_updater(Default::default()),
+            );
+
+            let response = reqwest::Client::new()
+                .post(format!(
+                    "http://{}{}/drop?message_id={}",
+                    addr,
+                    ADMIN_API_BASE,
+                    message.id().encode_hex::<String>()
+                ))
+                .bearer_auth(TOKEN)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+
+            assert_eq!(rx.try_recv().unwrap(), OpQueueCommand::Drop(message.id()));
+            assert_eq!(
+                db.retrieve_dropped_by_admin_by_message_id(&message.id())
+                    .unwrap(),
+                Some(true)
+            );
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_retry_matching_list() {
+        let (addr, mut rx) = setup_test_server(
+            HashMap::new(),
+            HashMap::new(),
+            dummy_policy_updater(Default::default()),
+        );
+        let sender = H256::random();
+        let body = serde_json::json!([{ "senderaddress": format!("{:?}", sender) }]);
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}{}/retry", addr, ADMIN_API_BASE))
+            .bearer_auth(TOKEN)
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let expected: MatchingList = serde_json::from_value(body).unwrap();
+        assert_eq!(
+            rx.try_recv().unwrap(),
+            OpQueueCommand::Retry(MessageRetryRequest::MatchingList(expected))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_update_policies() {
+        let message_filter = SharedMessageFilter::default();
+        let policy_updater = dummy_policy_updater(message_filter.clone());
+        let (addr, _rx) = setup_test_server(HashMap::new(), HashMap::new(), policy_updater.clone());
+        let blacklisted = HyperlaneMessage {
+            origin: 5,
+            ..Default::default()
+        };
+        assert!(!message_filter
+            .read()
+            .await
+            .blacklist
+            .msg_matches(&blacklisted, false));
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}{}/policies", addr, ADMIN_API_BASE))
+            .bearer_auth(TOKEN)
+            .json(&serde_json::json!({ "blacklist": [{ "originDomain": 5 }] }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        assert!(message_filter
+            .read()
+            .await
+            .blacklist
+            .msg_matches(&blacklisted, false));
+        // Policies that weren't part of the update are kept
+        assert_eq!(
+            policy_updater.current().await.gas_payment_enforcement,
+            RelayerPolicies::default().gas_payment_enforcement
+        );
+        // and the operator's policies win over config reloads
+        assert!(policy_updater.is_overridden().await);
+        assert!(!policy_updater.reload(RelayerPolicies::default()).await);
+    }
+}
@@ -8,12 +8,15 @@ use serde::Deserialize;
 use std::str::FromStr;
 use tokio::sync::broadcast::Sender;
 
+use crate::{msg::op_queue::OpQueueCommand, settings::matching_list::MatchingList};
+
 const MESSAGE_RETRY_API_BASE: &str = "/message_retry";
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum MessageRetryRequest {
     MessageId(H256),
     DestinationDomain(u32),
+    MatchingList(MatchingList),
 }
 
 impl PartialEq<QueueOperation> for &MessageRetryRequest {
@@ -23,13 +26,16 @@ impl PartialEq<QueueOperation> for &MessageRetryRequest {
             MessageRetryRequest::DestinationDomain(destination_domain) => {
                 destination_domain == &other.destination_domain().id()
             }
+            MessageRetryRequest::MatchingList(matching_list) => {
+                matching_list.op_matches(other, false)
+            }
         }
     }
 }
 
 #[derive(new, Clone)]
 pub struct MessageRetryApi {
-    tx: Sender<MessageRetryRequest>,
+    tx: Sender<OpQueueCommand>,
 }
 
 #[derive(Deserialize)]
@@ -54,7 +60,7 @@ impl TryFrom<RawMessageRetryRequest> for Vec<MessageRetryRequest> {
 }
 
 async fn retry_message(
-    State(tx): State<Sender<MessageRetryRequest>>,
+    State(tx): State<Sender<OpQueueCommand>>,
     Query(request): Query<RawMessageRetryRequest>,
 ) -> String {
     let retry_requests: Vec<MessageRetryRequest> = match request.try_into() {
@@ -72,7 +78,7 @@ async fn retry_message(
 
     if let Err(err) = retry_requests
         .into_iter()
-        .map(|req| tx.send(req))
+        .map(|req| tx.send(OpQueueCommand::Retry(req)))
         .collect::<Result<Vec<_>, _>>()
     {
         return format!("Failed to send retry request to the queue: {}", err);
@@ -103,8 +109,8 @@ mod tests {
     use std::net::SocketAddr;
     use tokio::sync::broadcast::{Receiver, Sender};
 
-    fn setup_test_server() -> (SocketAddr, Receiver<MessageRetryRequest>) {
-        let broadcast_tx = Sender::<MessageRetryRequest>::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
+    fn setup_test_server() -> (SocketAddr, Receiver<OpQueueCommand>) {
+        let broadcast_tx = Sender::<OpQueueCommand>::new(ENDPOINT_MESSAGES_QUEUE_SIZE);
         let message_retry_api = MessageRetryApi::new(broadcast_tx.clone());
         let (path, retry_router) = message_retry_api.get_route();
         let app = Router::new().nest(path, retry_router);
@@ -140,7 +146,7 @@ mod tests {
 
         assert_eq!(
             rx.try_recv().unwrap(),
-            MessageRetryRequest::MessageId(message_id)
+            OpQueueCommand::Retry(MessageRetryRequest::MessageId(message_id))
         );
     }
 
@@ -164,7 +170,7 @@ mod tests {
 
         assert_eq!(
             rx.try_recv().unwrap(),
-            MessageRetryRequest::DestinationDomain(destination_domain)
+            OpQueueCommand::Retry(MessageRetryRequest::DestinationDomain(destination_domain))
         );
     }
 }
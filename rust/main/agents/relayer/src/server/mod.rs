@@ -1,28 +1,50 @@
 use axum::Router;
 use derive_new::new;
-use std::collections::HashMap;
+use hyperlane_base::db::HyperlaneRocksDB;
+use std::{
+    collections::HashMap,
+    sync::{atomic::AtomicBool, Arc},
+};
 use tokio::sync::broadcast::Sender;
 
-use crate::msg::op_queue::OperationPriorityQueue;
+use crate::{
+    msg::{
+        op_queue::{OpQueueCommand, OperationPriorityQueue},
+        profitability::ProfitabilityTracker,
+    },
+    policies::PolicyUpdater,
+};
 
 pub const ENDPOINT_MESSAGES_QUEUE_SIZE: usize = 100;
 
+pub use admin::*;
 pub use list_messages::*;
 pub use message_retry::*;
+pub use operation_history::*;
+pub use profitability::*;
 
+mod admin;
 mod list_messages;
 mod message_retry;
+mod operation_history;
+mod profitability;
 
 #[derive(new)]
 pub struct Server {
     #[new(default)]
-    retry_transmitter: Option<Sender<MessageRetryRequest>>,
+    retry_transmitter: Option<Sender<OpQueueCommand>>,
     #[new(default)]
     op_queues: Option<HashMap<u32, OperationPriorityQueue>>,
+    #[new(default)]
+    dbs: Option<HashMap<u32, HyperlaneRocksDB>>,
+    #[new(default)]
+    admin: Option<AdminApi>,
+    #[new(default)]
+    profitability: Option<Arc<ProfitabilityTracker>>,
 }
 
 impl Server {
-    pub fn with_op_retry(mut self, transmitter: Sender<MessageRetryRequest>) -> Self {
+    pub fn with_op_retry(mut self, transmitter: Sender<OpQueueCommand>) -> Self {
         self.retry_transmitter = Some(transmitter);
         self
     }
@@ -32,6 +54,34 @@ impl Server {
         self
     }
 
+    pub fn with_operation_history(mut self, dbs: HashMap<u32, HyperlaneRocksDB>) -> Self {
+        self.dbs = Some(dbs);
+        self
+    }
+
+    pub fn with_profitability(mut self, tracker: Arc<ProfitabilityTracker>) -> Self {
+        self.profitability = Some(tracker);
+        self
+    }
+
+    pub fn with_admin(
+        mut self,
+        token: String,
+        transmitter: Sender<OpQueueCommand>,
+        paused_submitters: HashMap<u32, Arc<AtomicBool>>,
+        dbs: HashMap<u32, HyperlaneRocksDB>,
+        policy_updater: Arc<PolicyUpdater>,
+    ) -> Self {
+        self.admin = Some(AdminApi::new(
+            token,
+            transmitter,
+            paused_submitters,
+            dbs,
+            policy_updater,
+        ));
+        self
+    }
+
     /// Returns a vector of agent-specific endpoint routes to be served.
     /// Can be extended with additional routes and feature flags to enable/disable individually.
     pub fn routes(self) -> Vec<(&'static str, Router)> {
@@ -42,6 +92,15 @@ impl Server {
         if let Some(op_queues) = self.op_queues {
             routes.push(ListOperationsApi::new(op_queues).get_route());
         }
+        if let Some(dbs) = self.dbs {
+            routes.push(OperationHistoryApi::new(dbs).get_route());
+        }
+        if let Some(tracker) = self.profitability {
+            routes.push(ProfitabilityApi::new(tracker).get_route());
+        }
+        if let Some(admin) = self.admin {
+            routes.push(admin.get_route());
+        }
 
         routes
     }
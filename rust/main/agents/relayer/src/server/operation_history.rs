@@ -0,0 +1,483 @@
+use axum::{
+    extract::{Query, State},
+    routing, Router,
+};
+use derive_new::new;
+use hyperlane_base::db::{DbResult, HyperlaneRocksDB};
+use hyperlane_core::{
+    ChainCommunicationError, GasPaymentKey, HyperlaneMessage, ModuleType, PendingOperationStatus,
+    PendingOperationStatusTransition, H256, H512, U256,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, str::FromStr};
+
+const OPERATION_HISTORY_API_BASE: &str = "/operation_history";
+
+/// The number of operations returned when no `limit` is given
+const DEFAULT_PAGE_SIZE: usize = 50;
+/// The maximum number of operations returned in a single page
+const MAX_PAGE_SIZE: usize = 500;
+/// The maximum number of nonces looked at to fill a single page, so that filters
+/// that rarely match don't make a request scan a whole database
+const MAX_SCANNED_NONCES_PER_PAGE: u32 = 10_000;
+
+/// The status name used for messages that have been delivered
+const PROCESSED_STATUS: &str = "Processed";
+
+/// Everything the relayer knows about a message's journey, read from its origin database.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OperationHistory {
+    pub message_id: H256,
+    pub nonce: u32,
+    pub origin_domain: u32,
+    pub destination_domain: u32,
+    pub sender: H256,
+    pub recipient: H256,
+    pub dispatched_block_number: Option<u64>,
+    /// Unix timestamp, in seconds, at which the message was indexed
+    pub indexed_at: Option<u64>,
+    pub status: Option<PendingOperationStatus>,
+    pub processed: bool,
+    pub status_transitions: Vec<PendingOperationStatusTransition>,
+    pub retry_count: Option<u32>,
+    pub gas_payment: Option<U256>,
+    pub gas_amount_paid: Option<U256>,
+    pub gas_used: U256,
+    pub tokens_used: U256,
+    pub ism_type: Option<ModuleType>,
+    pub metadata_size: Option<u64>,
+    pub destination_tx_hash: Option<H512>,
+}
+
+/// Where to continue listing operations from: the origin database and the
+/// highest nonce in it that hasn't been looked at yet
+#[derive(Clone, Copy, Debug, PartialEq, Serialize)]
+pub struct OperationHistoryCursor {
+    pub origin_domain: u32,
+    pub nonce: u32,
+}
+
+/// A page of operations matching a filter
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct OperationHistoryPage {
+    pub operations: Vec<OperationHistory>,
+    /// The cursor to request the next page with, if there may be more results
+    pub next_cursor: Option<OperationHistoryCursor>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct OperationHistoryFilter {
+    pub origin_domain: Option<u32>,
+    pub destination_domain: Option<u32>,
+    pub sender: Option<H256>,
+    pub recipient: Option<H256>,
+    /// Name of the status variant, e.g. `Retry`, or `Processed` for delivered messages
+    pub status: Option<String>,
+}
+
+impl OperationHistoryFilter {
+    fn matches_message(&self, message: &HyperlaneMessage) -> bool {
+        self.destination_domain
+            .map_or(true, |d| d == message.destination)
+            && self.sender.map_or(true, |s| s == message.sender)
+            && self.recipient.map_or(true, |r| r == message.recipient)
+    }
+
+    fn matches_history(&self, history: &OperationHistory) -> bool {
+        match &self.status {
+            None => true,
+            Some(status) => status == status_name(history),
+        }
+    }
+}
+
+fn status_name(history: &OperationHistory) -> &'static str {
+    if history.processed {
+        return PROCESSED_STATUS;
+    }
+    match history.status {
+        None | Some(PendingOperationStatus::FirstPrepareAttempt) => "FirstPrepareAttempt",
+        Some(PendingOperationStatus::Retry(_)) => "Retry",
+        Some(PendingOperationStatus::ReadyToSubmit) => "ReadyToSubmit",
+        Some(PendingOperationStatus::Confirm(_)) => "Confirm",
+    }
+}
+
+#[derive(Deserialize)]
+struct RawOperationHistoryRequest {
+    message_id: Option<String>,
+    origin_domain: Option<u32>,
+    destination_domain: Option<u32>,
+    sender: Option<String>,
+    recipient: Option<String>,
+    status: Option<String>,
+    cursor_origin_domain: Option<u32>,
+    cursor_nonce: Option<u32>,
+    limit: Option<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OperationHistoryRequest {
+    MessageId {
+        message_id: H256,
+        origin_domain: Option<u32>,
+    },
+    List {
+        filter: OperationHistoryFilter,
+        cursor: Option<OperationHistoryCursor>,
+        limit: usize,
+    },
+}
+
+impl TryFrom<RawOperationHistoryRequest> for OperationHistoryRequest {
+    type Error = ChainCommunicationError;
+
+    fn try_from(request: RawOperationHistoryRequest) -> Result<Self, Self::Error> {
+        if let Some(message_id) = request.message_id {
+            return Ok(OperationHistoryRequest::MessageId {
+                message_id: H256::from_str(&message_id)?,
+                origin_domain: request.origin_domain,
+            });
+        }
+        let cursor = match (request.cursor_origin_domain, request.cursor_nonce) {
+            (Some(origin_domain), Some(nonce)) => Some(OperationHistoryCursor {
+                origin_domain,
+                nonce,
+            }),
+            (None, None) => None,
+            _ => {
+                return Err(ChainCommunicationError::from_other_str(
+                    "cursor_origin_domain and cursor_nonce must be given together",
+                ))
+            }
+        };
+        Ok(OperationHistoryRequest::List {
+            filter: OperationHistoryFilter {
+                origin_domain: request.origin_domain,
+                destination_domain: request.destination_domain,
+                sender: request.sender.as_deref().map(H256::from_str).transpose()?,
+                recipient: request
+                    .recipient
+                    .as_deref()
+                    .map(H256::from_str)
+                    .transpose()?,
+                status: request.status,
+            },
+            cursor,
+            limit: request
+                .limit
+                .unwrap_or(DEFAULT_PAGE_SIZE)
+                .min(MAX_PAGE_SIZE),
+        })
+    }
+}
+
+/// Read API over the relayer's origin databases, for debugging messages.
+/// The databases are keyed by origin domain id.
+#[derive(new, Clone)]
+pub struct OperationHistoryApi {
+    dbs: HashMap<u32, HyperlaneRocksDB>,
+}
+
+impl OperationHistoryApi {
+    /// Finds a message by id, in the given origin's database or in all of them.
+    pub fn get_operation(
+        &self,
+        message_id: H256,
+        origin_domain: Option<u32>,
+    ) -> DbResult<Option<OperationHistory>> {
+        for (domain, db) in self.dbs.iter() {
+            if origin_domain.map_or(false, |origin| origin != *domain) {
+                continue;
+            }
+            if let Some(message) = db.retrieve_message_by_id(&message_id)? {
+                return Ok(Some(Self::operation_history(db, &message)?));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Lists operations matching `filter`, by ascending origin domain id and then
+    /// descending nonce, so the most recent messages come first. Listing starts
+    /// at `cursor`, or at the most recent message of the first origin if there
+    /// is none.
+    /// At most `MAX_SCANNED_NONCES_PER_PAGE` nonces are looked at per call, so a
+    /// page may hold fewer than `limit` operations and still have a next cursor.
+    pub fn list_operations(
+        &self,
+        filter: &OperationHistoryFilter,
+        cursor: Option<OperationHistoryCursor>,
+        limit: usize,
+    ) -> DbResult<OperationHistoryPage> {
+        let mut domains = self
+            .dbs
+            .keys()
+            .copied()
+            .filter(|domain| {
+                filter
+                    .origin_domain
+                    .map_or(true, |origin| origin == *domain)
+            })
+            .filter(|domain| cursor.map_or(true, |cursor| cursor.origin_domain <= *domain))
+            .collect::<Vec<_>>();
+        domains.sort();
+
+        let mut operations = Vec::with_capacity(limit);
+        let mut scanned = 0;
+        for domain in domains {
+            let db = &self.dbs[&domain];
+            let Some(highest_nonce) = db.retrieve_highest_seen_message_nonce()? else {
+                continue;
+            };
+            let start_nonce = match cursor {
+                Some(cursor) if cursor.origin_domain == domain => cursor.nonce.min(highest_nonce),
+                _ => highest_nonce,
+            };
+            for nonce in (0..=start_nonce).rev() {
+                if operations.len() == limit || scanned == MAX_SCANNED_NONCES_PER_PAGE {
+                    return Ok(OperationHistoryPage {
+                        operations,
+                        next_cursor: Some(OperationHistoryCursor {
+                            origin_domain: domain,
+                            nonce,
+                        }),
+                    });
+                }
+                scanned += 1;
+                let Some(message) = db.retrieve_message_by_nonce(nonce)? else {
+                    continue;
+                };
+                if !filter.matches_message(&message) {
+                    continue;
+                }
+                let history = Self::operation_history(db, &message)?;
+                if !filter.matches_history(&history) {
+                    continue;
+                }
+                operations.push(history);
+            }
+        }
+        Ok(OperationHistoryPage {
+            operations,
+            next_cursor: None,
+        })
+    }
+
+    fn operation_history(
+        db: &HyperlaneRocksDB,
+        message: &HyperlaneMessage,
+    ) -> DbResult<OperationHistory> {
+        let id = message.id();
+        let gas_payment = db.retrieve_gas_payment_by_gas_payment_key(GasPaymentKey {
+            message_id: id,
+            destination: message.destination,
+        })?;
+        let gas_expenditure = db.retrieve_gas_expenditure_by_message_id(id)?;
+
+        Ok(OperationHistory {
+            message_id: id,
+            nonce: message.nonce,
+            origin_domain: message.origin,
+            destination_domain: message.destination,
+            sender: message.sender,
+            recipient: message.recipient,
+            dispatched_block_number: db
+                .retrieve_dispatched_block_number_by_nonce(&message.nonce)?,
+            indexed_at: db.retrieve_message_indexed_at_by_message_id(&id)?,
+            status: db.retrieve_status_by_message_id(&id)?,
+            processed: db
+                .retrieve_processed_by_nonce(&message.nonce)?
+                .unwrap_or(false),
+            status_transitions: db
+                .retrieve_status_history_by_message_id(&id)?
+                .unwrap_or_default()
+                .0,
+            retry_count: db.retrieve_pending_message_retry_count_by_message_id(&id)?,
+            gas_payment: gas_payment.map(|p| p.payment),
+            gas_amount_paid: gas_payment.map(|p| p.gas_amount),
+            gas_used: gas_expenditure.gas_used,
+            tokens_used: gas_expenditure.tokens_used,
+            ism_type: db.retrieve_ism_type_by_message_id(&id)?,
+            metadata_size: db.retrieve_metadata_size_by_message_id(&id)?,
+            destination_tx_hash: db.retrieve_destination_tx_hash_by_message_id(&id)?,
+        })
+    }
+
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(operation_history))
+            .with_state(self.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (OPERATION_HISTORY_API_BASE, self.router())
+    }
+}
+
+async fn operation_history(
+    State(api): State<OperationHistoryApi>,
+    Query(request): Query<RawOperationHistoryRequest>,
+) -> String {
+    let request: OperationHistoryRequest = match request.try_into() {
+        Ok(request) => request,
+        // Technically it's bad practice to print the error message to the user, but
+        // this endpoint is for debugging purposes only.
+        Err(err) => {
+            return format!("Failed to parse operation history request: {}", err);
+        }
+    };
+
+    let res = match request {
+        OperationHistoryRequest::MessageId {
+            message_id,
+            origin_domain,
+        } => match api.get_operation(message_id, origin_domain) {
+            Ok(Some(history)) => serde_json::to_string_pretty(&history),
+            Ok(None) => return format!("No message found with id {:?}", message_id),
+            Err(err) => return format!("Error reading operation history: {}", err),
+        },
+        OperationHistoryRequest::List {
+            filter,
+            cursor,
+            limit,
+        } => match api.list_operations(&filter, cursor, limit) {
+            Ok(page) => serde_json::to_string_pretty(&page),
+            Err(err) => return format!("Error reading operation history: {}", err),
+        },
+    };
+    match res {
+        Ok(s) => s,
+        Err(e) => format!("Error formatting operation history: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+    use ethers::utils::hex::ToHex;
+    use hyperlane_base::db::test_utils;
+    use hyperlane_core::{HyperlaneDomain, ReprepareReason};
+    use std::net::SocketAddr;
+
+    fn setup_test_server(dbs: HashMap<u32, HyperlaneRocksDB>) -> SocketAddr {
+        let api = OperationHistoryApi::new(dbs);
+        let (path, router) = api.get_route();
+        let app = Router::new().nest(path, router);
+
+        // Running the app in the background using a test server
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        addr
+    }
+
+    fn dummy_message(nonce: u32, destination: u32) -> HyperlaneMessage {
+        HyperlaneMessage {
+            nonce,
+            origin: 1000,
+            destination,
+            sender: H256::from_low_u64_be(1),
+            recipient: H256::from_low_u64_be(2),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_operation_history() {
+        test_utils::run_test_db(|db| async move {
+            let domain = HyperlaneDomain::new_test_domain("test_operation_history");
+            let db = HyperlaneRocksDB::new(&domain, db);
+
+            let delivered = dummy_message(0, 2000);
+            let retrying = dummy_message(1, 2000);
+            let other_destination = dummy_message(2, 3000);
+            for message in [&delivered, &retrying, &other_destination] {
+                db.store_message(message, 1).unwrap();
+            }
+            db.store_processed_by_nonce(&delivered.nonce, &true)
+                .unwrap();
+            let retry_status = PendingOperationStatus::Retry(ReprepareReason::ErrorSubmitting);
+            db.store_status_transition(
+                &retrying.id(),
+                &PendingOperationStatus::FirstPrepareAttempt,
+            )
+            .unwrap();
+            db.store_status_transition(&retrying.id(), &retry_status)
+                .unwrap();
+            db.store_ism_type_by_message_id(&retrying.id(), &ModuleType::MessageIdMultisig)
+                .unwrap();
+            db.store_metadata_size_by_message_id(&retrying.id(), &100)
+                .unwrap();
+
+            let addr = setup_test_server(HashMap::from([(domain.id(), db)]));
+
+            // Look up a single message
+            let response = reqwest::get(format!(
+                "http://{}{}?message_id={}",
+                addr,
+                OPERATION_HISTORY_API_BASE,
+                retrying.id().encode_hex::<String>()
+            ))
+            .await
+            .unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let history: serde_json::Value =
+                serde_json::from_str(&response.text().await.unwrap()).unwrap();
+            assert_eq!(history["nonce"], 1);
+            assert_eq!(history["status_transitions"].as_array().unwrap().len(), 2);
+            assert_eq!(
+                history["status"],
+                serde_json::to_value(&retry_status).unwrap()
+            );
+            assert_eq!(history["ism_type"], "MessageIdMultisig");
+            assert_eq!(history["metadata_size"], 100);
+
+            // Filter by destination, one result per page
+            let response = reqwest::get(format!(
+                "http://{}{}?destination_domain=2000&limit=1",
+                addr, OPERATION_HISTORY_API_BASE
+            ))
+            .await
+            .unwrap();
+            let page: serde_json::Value =
+                serde_json::from_str(&response.text().await.unwrap()).unwrap();
+            assert_eq!(page["operations"][0]["nonce"], 1);
+            assert_eq!(
+                page["next_cursor"],
+                serde_json::json!({ "origin_domain": domain.id(), "nonce": 0 })
+            );
+
+            // Continue from the cursor
+            let response = reqwest::get(format!(
+                "http://{}{}?destination_domain=2000&limit=1&cursor_origin_domain={}&cursor_nonce=0",
+                addr,
+                OPERATION_HISTORY_API_BASE,
+                domain.id()
+            ))
+            .await
+            .unwrap();
+            let page: serde_json::Value =
+                serde_json::from_str(&response.text().await.unwrap()).unwrap();
+            assert_eq!(page["operations"][0]["nonce"], 0);
+            assert_eq!(page["next_cursor"], serde_json::Value::Null);
+
+            // Filter by status
+            let response = reqwest::get(format!(
+                "http://{}{}?status={}",
+                addr, OPERATION_HISTORY_API_BASE, PROCESSED_STATUS
+            ))
+            .await
+            .unwrap();
+            let page: serde_json::Value =
+                serde_json::from_str(&response.text().await.unwrap()).unwrap();
+            let operations = page["operations"].as_array().unwrap();
+            assert_eq!(operations.len(), 1);
+            assert_eq!(operations[0]["nonce"], 0);
+            assert_eq!(page["next_cursor"], serde_json::Value::Null);
+        })
+        .await;
+    }
+}
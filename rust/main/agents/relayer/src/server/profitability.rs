@@ -0,0 +1,153 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    routing, Router,
+};
+use derive_new::new;
+use serde::Deserialize;
+
+use crate::msg::profitability::ProfitabilityTracker;
+
+const PROFITABILITY_API_BASE: &str = "/profitability";
+
+#[derive(Deserialize)]
+struct ProfitabilityRequest {
+    /// Only return routes running at a loss
+    #[serde(default)]
+    at_loss: bool,
+}
+
+/// Serves the gas paid and spent per (origin, destination, app context) since
+/// the relayer started.
+#[derive(new, Clone)]
+pub struct ProfitabilityApi {
+    tracker: Arc<ProfitabilityTracker>,
+}
+
+impl ProfitabilityApi {
+    pub fn router(&self) -> Router {
+        Router::new()
+            .route("/", routing::get(profitability))
+            .with_state(self.clone())
+    }
+
+    pub fn get_route(&self) -> (&'static str, Router) {
+        (PROFITABILITY_API_BASE, self.router())
+    }
+}
+
+async fn profitability(
+    State(api): State<ProfitabilityApi>,
+    Query(request): Query<ProfitabilityRequest>,
+) -> String {
+    let routes = api
+        .tracker
+        .routes()
+        .await
+        .into_iter()
+        .filter(|route| !request.at_loss || route.at_loss == Some(true))
+        .collect::<Vec<_>>();
+    match serde_json::to_string_pretty(&routes) {
+        Ok(s) => s,
+        Err(e) => format!("Error formatting profitability: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, net::SocketAddr};
+
+    use axum::http::StatusCode;
+    use hyperlane_base::CoreMetrics;
+    use hyperlane_core::{FixedPointNumber, HyperlaneDomain, KnownHyperlaneDomain, U256};
+    use prometheus::Registry;
+
+    use super::*;
+    use crate::msg::gas_payment::price_oracle::{StaticTokenPriceOracle, UsdValuer};
+
+    fn setup_test_server(tracker: Arc<ProfitabilityTracker>) -> SocketAddr {
+        let api = ProfitabilityApi::new(tracker);
+        let (path, router) = api.get_route();
+        let app = Router::new().nest(path, router);
+
+        // Running the app in the background using a test server
+        let server =
+            axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+        let addr = server.local_addr();
+        tokio::spawn(server);
+
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_profitability() {
+        let origin = HyperlaneDomain::Known(KnownHyperlaneDomain::Arbitrum);
+        let valuer = UsdValuer::new(
+            HashMap::new(),
+            Box::new(StaticTokenPriceOracle::new(HashMap::from([
+                (origin.id(), FixedPointNumber::from(1)),
+                (
+                    KnownHyperlaneDomain::Ethereum as u32,
+                    FixedPointNumber::from(1),
+                ),
+                (
+                    KnownHyperlaneDomain::Optimism as u32,
+                    FixedPointNumber::from(1),
+                ),
+            ]))),
+        );
+        let tracker = Arc::new(ProfitabilityTracker::new(
+            &CoreMetrics::new("test", 9090, Registry::new()).unwrap(),
+            Some(valuer),
+            [],
+        ));
+        // Profitable
+        tracker
+            .record_delivery(
+                &origin,
+                &HyperlaneDomain::Known(KnownHyperlaneDomain::Ethereum),
+                None,
+                U256::from(10u32),
+                U256::one(),
+                U256::from(5u32),
+            )
+            .await;
+        // At a loss
+        tracker
+            .record_delivery(
+                &origin,
+                &HyperlaneDomain::Known(KnownHyperlaneDomain::Optimism),
+                None,
+                U256::from(5u32),
+                U256::one(),
+                U256::from(10u32),
+            )
+            .await;
+        let addr = setup_test_server(tracker);
+
+        let client = reqwest::Client::new();
+        let response = client
+            .get(format!("http://{}{}", addr, PROFITABILITY_API_BASE))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let routes: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(routes.as_array().unwrap().len(), 2);
+
+        let response = client
+            .get(format!(
+                "http://{}{}?at_loss=true",
+                addr, PROFITABILITY_API_BASE
+            ))
+            .send()
+            .await
+            .unwrap();
+        let routes: serde_json::Value = response.json().await.unwrap();
+        let routes = routes.as_array().unwrap();
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0]["destination"], "optimism");
+        assert_eq!(routes[0]["at_loss"], true);
+    }
+}
@@ -6,9 +6,14 @@ use std::{
     fmt,
     fmt::{Debug, Display, Formatter},
     marker::PhantomData,
+    ops::RangeInclusive,
 };
 
-use hyperlane_core::{config::StrOrInt, utils::hex_or_base58_to_h256, HyperlaneMessage, H256};
+use hyperlane_core::{
+    config::StrOrInt,
+    utils::{bytes_to_hex, hex_or_base58_to_h256},
+    HyperlaneMessage, QueueOperation, H256,
+};
 use serde::{
     de::{Error, SeqAccess, Visitor},
     Deserialize, Deserializer,
@@ -22,10 +27,13 @@ use serde::{
 /// - wildcard "*"
 /// - single value in decimal or hex (must start with `0x`) format
 /// - list of values in decimal or hex format
-#[derive(Debug, Default, Clone)]
+///
+/// Rules may additionally restrict the message version, nonce, and body (by
+/// prefix or length), and exclude messages matching a nested list with `not`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct MatchingList(Option<Vec<ListElement>>);
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum Filter<T> {
     Wildcard,
     Enumerated(Vec<T>),
@@ -37,15 +45,43 @@ impl<T> Default for Filter<T> {
     }
 }
 
-impl<T: PartialEq> Filter<T> {
-    fn matches(&self, v: &T) -> bool {
+impl<T> Filter<T> {
+    fn matches_by(&self, f: impl Fn(&T) -> bool) -> bool {
         match self {
             Filter::Wildcard => true,
-            Filter::Enumerated(list) => list.iter().any(|i| i == v),
+            Filter::Enumerated(list) => list.iter().any(f),
         }
     }
 }
 
+impl<T: PartialEq> Filter<T> {
+    fn matches(&self, v: &T) -> bool {
+        self.matches_by(|i| i == v)
+    }
+}
+
+impl Filter<BodyPrefix> {
+    fn matches_body(&self, body: &[u8]) -> bool {
+        self.matches_by(|prefix| body.starts_with(&prefix.0))
+    }
+}
+
+impl Filter<RangeInclusive<u32>> {
+    fn matches_nonce(&self, nonce: u32) -> bool {
+        self.matches_by(|range| range.contains(&nonce))
+    }
+}
+
+/// A prefix of the message body, such as a function selector.
+#[derive(Clone, PartialEq, Eq)]
+struct BodyPrefix(Vec<u8>);
+
+impl Debug for BodyPrefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", bytes_to_hex(&self.0))
+    }
+}
+
 impl<T: Debug> Display for Filter<T> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -193,6 +229,94 @@ impl<'de> Visitor<'de> for FilterVisitor<H256> {
     }
 }
 
+impl<'de> Visitor<'de> for FilterVisitor<BodyPrefix> {
+    type Value = Filter<BodyPrefix>;
+
+    fn expecting(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "Expecting either a wildcard \"*\", hex bytes string, or list of hex bytes strings"
+        )
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(if v == "*" {
+            Self::Value::Wildcard
+        } else {
+            Self::Value::Enumerated(vec![parse_body_prefix(v)?])
+        })
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(i) = seq.next_element::<String>()? {
+            values.push(parse_body_prefix(&i)?)
+        }
+        Ok(Self::Value::Enumerated(values))
+    }
+}
+
+impl<'de> Visitor<'de> for FilterVisitor<RangeInclusive<u32>> {
+    type Value = Filter<RangeInclusive<u32>>;
+
+    fn expecting(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(
+            fmt,
+            "Expecting either a wildcard \"*\", nonce, nonce range string (e.g. \"10..20\", \"10..=20\", \"10..\"), or list of nonces and nonce ranges"
+        )
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let nonce = u32::try_from(v).map_err(to_serde_err)?;
+        Ok(Self::Value::Enumerated(vec![nonce..=nonce]))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let nonce = u32::try_from(v).map_err(to_serde_err)?;
+        Ok(Self::Value::Enumerated(vec![nonce..=nonce]))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        Ok(if v == "*" {
+            Self::Value::Wildcard
+        } else {
+            Self::Value::Enumerated(vec![parse_nonce_range(v)?])
+        })
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut values = Vec::new();
+        while let Some(i) = seq.next_element::<StrOrInt>()? {
+            values.push(match i {
+                StrOrInt::Str(s) => parse_nonce_range(&s)?,
+                StrOrInt::Int(v) => {
+                    let nonce = u32::try_from(v).map_err(to_serde_err)?;
+                    nonce..=nonce
+                }
+            });
+        }
+        Ok(Self::Value::Enumerated(values))
+    }
+}
+
 impl<'de> Deserialize<'de> for MatchingList {
     fn deserialize<D>(d: D) -> Result<Self, D::Error>
     where
@@ -220,7 +344,42 @@ impl<'de> Deserialize<'de> for Filter<H256> {
     }
 }
 
-#[derive(Debug, Deserialize, Clone)]
+impl<'de> Deserialize<'de> for Filter<u8> {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match Filter::<u32>::deserialize(d)? {
+            Filter::Wildcard => Filter::Wildcard,
+            Filter::Enumerated(values) => Filter::Enumerated(
+                values
+                    .into_iter()
+                    .map(|v| u8::try_from(v).map_err(to_serde_err))
+                    .collect::<Result<_, _>>()?,
+            ),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Filter<BodyPrefix> {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        d.deserialize_any(FilterVisitor::<BodyPrefix>(Default::default()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Filter<RangeInclusive<u32>> {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        d.deserialize_any(FilterVisitor::<RangeInclusive<u32>>(Default::default()))
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
 #[serde(tag = "type")]
 struct ListElement {
     #[serde(default, rename = "origindomain")]
@@ -231,18 +390,80 @@ struct ListElement {
     destination_domain: Filter<u32>,
     #[serde(default, rename = "recipientaddress")]
     recipient_address: Filter<H256>,
+    #[serde(default)]
+    version: Filter<u8>,
+    /// Nonces or inclusive ranges of nonces on the origin domain.
+    #[serde(default)]
+    nonce: Filter<RangeInclusive<u32>>,
+    /// Hex prefixes of the message body, e.g. a function selector.
+    #[serde(default, rename = "bodyprefix")]
+    body_prefix: Filter<BodyPrefix>,
+    #[serde(
+        default,
+        rename = "minbodylength",
+        deserialize_with = "deserialize_body_length"
+    )]
+    min_body_length: Option<u32>,
+    #[serde(
+        default,
+        rename = "maxbodylength",
+        deserialize_with = "deserialize_body_length"
+    )]
+    max_body_length: Option<u32>,
+    /// Messages matching any of these rules are excluded, even if they match
+    /// all the other predicates of this rule.
+    #[serde(default)]
+    not: MatchingList,
+}
+
+impl ListElement {
+    fn matches(&self, info: MatchInfo) -> bool {
+        self.origin_domain.matches(&info.src_domain)
+            && self.sender_address.matches(info.src_addr)
+            && self.destination_domain.matches(&info.dst_domain)
+            && self.recipient_address.matches(info.dst_addr)
+            && self.version.matches(&info.version)
+            && self.nonce.matches_nonce(info.nonce)
+            && self.body_prefix.matches_body(info.body)
+            && self
+                .min_body_length
+                .map_or(true, |min| info.body.len() >= min as usize)
+            && self
+                .max_body_length
+                .map_or(true, |max| info.body.len() <= max as usize)
+            && !self.not.matches(info, false)
+    }
 }
 
 impl Display for ListElement {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{originDomain: {}, senderAddress: {}, destinationDomain: {}, recipientAddress: {}}}",
+            "{{originDomain: {}, senderAddress: {}, destinationDomain: {}, recipientAddress: {}",
             self.origin_domain,
             self.sender_address,
             self.destination_domain,
             self.recipient_address
-        )
+        )?;
+        if self.version != Filter::Wildcard {
+            write!(f, ", version: {}", self.version)?;
+        }
+        if self.nonce != Filter::Wildcard {
+            write!(f, ", nonce: {}", self.nonce)?;
+        }
+        if self.body_prefix != Filter::Wildcard {
+            write!(f, ", bodyPrefix: {}", self.body_prefix)?;
+        }
+        if let Some(min) = self.min_body_length {
+            write!(f, ", minBodyLength: {min}")?;
+        }
+        if let Some(max) = self.max_body_length {
+            write!(f, ", maxBodyLength: {max}")?;
+        }
+        if self.not.0.is_some() {
+            write!(f, ", not: {}", self.not)?;
+        }
+        write!(f, "}}")
     }
 }
 
@@ -252,6 +473,9 @@ struct MatchInfo<'a> {
     src_addr: &'a H256,
     dst_domain: u32,
     dst_addr: &'a H256,
+    version: u8,
+    nonce: u32,
+    body: &'a [u8],
 }
 
 impl<'a> From<&'a HyperlaneMessage> for MatchInfo<'a> {
@@ -261,6 +485,23 @@ impl<'a> From<&'a HyperlaneMessage> for MatchInfo<'a> {
             src_addr: &msg.sender,
             dst_domain: msg.destination,
             dst_addr: &msg.recipient,
+            version: msg.version,
+            nonce: msg.nonce,
+            body: &msg.body,
+        }
+    }
+}
+
+impl<'a> From<&'a QueueOperation> for MatchInfo<'a> {
+    fn from(op: &'a QueueOperation) -> Self {
+        Self {
+            src_domain: op.origin_domain_id(),
+            src_addr: op.sender_address(),
+            dst_domain: op.destination_domain().id(),
+            dst_addr: op.recipient_address(),
+            version: op.version(),
+            nonce: op.nonce(),
+            body: op.body(),
         }
     }
 }
@@ -272,26 +513,23 @@ impl MatchingList {
         self.matches(msg.into(), default)
     }
 
+    /// Check if a queued operation matches any of the rules.
+    /// - `default`: What to return if the matching list is empty.
+    pub fn op_matches(&self, op: &QueueOperation, default: bool) -> bool {
+        self.matches(op.into(), default)
+    }
+
     /// Check if a message matches any of the rules.
     /// - `default`: What to return if the matching list is empty.
     fn matches(&self, info: MatchInfo, default: bool) -> bool {
         if let Some(rules) = &self.0 {
-            matches_any_rule(rules.iter(), info)
+            rules.iter().any(|rule| rule.matches(info))
         } else {
             default
         }
     }
 }
 
-fn matches_any_rule<'a>(mut rules: impl Iterator<Item = &'a ListElement>, info: MatchInfo) -> bool {
-    rules.any(|rule| {
-        rule.origin_domain.matches(&info.src_domain)
-            && rule.sender_address.matches(info.src_addr)
-            && rule.destination_domain.matches(&info.dst_domain)
-            && rule.recipient_address.matches(info.dst_addr)
-    })
-}
-
 impl Display for MatchingList {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         if let Some(wl) = &self.0 {
@@ -314,11 +552,53 @@ fn parse_addr<E: Error>(addr_str: &str) -> Result<H256, E> {
     hex_or_base58_to_h256(addr_str).map_err(to_serde_err)
 }
 
+fn deserialize_body_length<'de, D: Deserializer<'de>>(d: D) -> Result<Option<u32>, D::Error> {
+    let len = StrOrInt::deserialize(d)?;
+    Ok(Some(len.try_into().map_err(to_serde_err)?))
+}
+
+fn parse_body_prefix<E: Error>(prefix_str: &str) -> Result<BodyPrefix, E> {
+    let prefix_str = prefix_str.strip_prefix("0x").unwrap_or(prefix_str);
+    hex::decode(prefix_str)
+        .map(BodyPrefix)
+        .map_err(to_serde_err)
+}
+
+/// Parses a single nonce (`"5"`) or a range of nonces in Rust range syntax
+/// (`"5..10"`, `"5..=10"`, `"5.."`, `"..10"`) into an inclusive range.
+fn parse_nonce_range<E: Error>(range_str: &str) -> Result<RangeInclusive<u32>, E> {
+    let parse_bound = |s: &str| s.trim().parse::<u32>().map_err(to_serde_err::<_, E>);
+    let Some((start, end)) = range_str.split_once("..") else {
+        let nonce = parse_bound(range_str)?;
+        return Ok(nonce..=nonce);
+    };
+    let start = if start.trim().is_empty() {
+        0
+    } else {
+        parse_bound(start)?
+    };
+    let end = if let Some(end) = end.strip_prefix('=') {
+        parse_bound(end)?
+    } else if end.trim().is_empty() {
+        u32::MAX
+    } else {
+        parse_bound(end)?
+            .checked_sub(1)
+            .ok_or_else(|| E::custom(format!("Empty nonce range: {range_str}")))?
+    };
+    if start > end {
+        return Err(E::custom(format!("Empty nonce range: {range_str}")));
+    }
+    Ok(start..=end)
+}
+
 #[cfg(test)]
 mod test {
     use hyperlane_core::{H160, H256};
 
-    use super::{Filter::*, MatchingList};
+    use hyperlane_core::HyperlaneMessage;
+
+    use super::{BodyPrefix, Filter::*, MatchingList};
     use crate::settings::matching_list::MatchInfo;
 
     #[test]
@@ -343,7 +623,10 @@ mod test {
                 src_domain: 0,
                 src_addr: &H256::default(),
                 dst_domain: 0,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                version: 3,
+                nonce: 0,
+                body: &[],
             },
             false
         ));
@@ -356,7 +639,10 @@ mod test {
                     .unwrap()
                     .into(),
                 dst_domain: 5456,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                version: 3,
+                nonce: 0,
+                body: &[],
             },
             false
         ))
@@ -396,7 +682,10 @@ mod test {
                 dst_addr: &"9d4454B023096f34B160D6B654540c56A1F81688"
                     .parse::<H160>()
                     .unwrap()
-                    .into()
+                    .into(),
+                version: 3,
+                nonce: 0,
+                body: &[],
             },
             false
         ));
@@ -409,7 +698,10 @@ mod test {
                     .unwrap()
                     .into(),
                 dst_domain: 5456,
-                dst_addr: &H256::default()
+                dst_addr: &H256::default(),
+                version: 3,
+                nonce: 0,
+                body: &[],
             },
             false
         ));
@@ -441,6 +733,9 @@ mod test {
             src_addr: &H256::default(),
             dst_domain: 0,
             dst_addr: &H256::default(),
+            version: 3,
+            nonce: 0,
+            body: &[],
         };
         // whitelist use
         assert!(MatchingList(None).matches(info, true));
@@ -469,4 +764,123 @@ mod test {
             hyperlane_base::settings::parser::ValueParser::new(Default::default(), &val);
         crate::settings::parse_matching_list(value_parser).unwrap();
     }
+
+    #[test]
+    fn config_with_message_predicates() {
+        let list: MatchingList = serde_json::from_str(r#"[{"version": 3, "nonce": ["5", "10..20", "30..=40", "100.."], "bodyprefix": "0xdeadbeef", "minbodylength": 4, "maxbodylength": "64"}]"#).unwrap();
+        let elem = &list.0.as_ref().unwrap()[0];
+        assert_eq!(elem.version, Enumerated(vec![3]));
+        assert_eq!(
+            elem.nonce,
+            Enumerated(vec![5..=5, 10..=19, 30..=40, 100..=u32::MAX])
+        );
+        assert_eq!(
+            elem.body_prefix,
+            Enumerated(vec![BodyPrefix(vec![0xde, 0xad, 0xbe, 0xef])])
+        );
+        assert_eq!(elem.min_body_length, Some(4));
+        assert_eq!(elem.max_body_length, Some(64));
+
+        let msg = HyperlaneMessage {
+            nonce: 15,
+            body: vec![0xde, 0xad, 0xbe, 0xef, 0x01],
+            ..Default::default()
+        };
+        assert!(list.msg_matches(&msg, false));
+
+        // nonce outside of all ranges
+        let msg = HyperlaneMessage { nonce: 20, ..msg };
+        assert!(!list.msg_matches(&msg, false));
+
+        // wrong version
+        let msg = HyperlaneMessage {
+            nonce: 5,
+            version: 2,
+            ..msg
+        };
+        assert!(!list.msg_matches(&msg, false));
+
+        // different selector
+        let msg = HyperlaneMessage {
+            version: 3,
+            body: vec![0xca, 0xfe, 0xba, 0xbe, 0x01],
+            ..msg
+        };
+        assert!(!list.msg_matches(&msg, false));
+
+        // body too long
+        let msg = HyperlaneMessage {
+            body: [vec![0xde, 0xad, 0xbe, 0xef], vec![0; 61]].concat(),
+            ..msg
+        };
+        assert!(!list.msg_matches(&msg, false));
+    }
+
+    #[test]
+    fn config_with_not() {
+        let list: MatchingList = serde_json::from_str(
+            r#"[{"origindomain": 1, "not": [{"recipientaddress": "0x9d4454B023096f34B160D6B654540c56A1F81688"}, {"nonce": "..10"}]}]"#,
+        )
+        .unwrap();
+        let excluded: H256 = "0x9d4454B023096f34B160D6B654540c56A1F81688"
+            .parse::<H160>()
+            .unwrap()
+            .into();
+
+        let msg = HyperlaneMessage {
+            origin: 1,
+            nonce: 10,
+            ..Default::default()
+        };
+        assert!(list.msg_matches(&msg, false));
+        assert!(!list.msg_matches(
+            &HyperlaneMessage {
+                recipient: excluded,
+                ..msg.clone()
+            },
+            false
+        ));
+        assert!(!list.msg_matches(
+            &HyperlaneMessage {
+                nonce: 9,
+                ..msg.clone()
+            },
+            false
+        ));
+        assert!(!list.msg_matches(&HyperlaneMessage { origin: 2, ..msg }, false));
+
+        // a rule consisting only of a negation matches everything else
+        let list: MatchingList =
+            serde_json::from_str(r#"[{"not": [{"origindomain": 1}]}]"#).unwrap();
+        assert!(list.msg_matches(
+            &HyperlaneMessage {
+                origin: 2,
+                ..Default::default()
+            },
+            false
+        ));
+        assert!(!list.msg_matches(
+            &HyperlaneMessage {
+                origin: 1,
+                ..Default::default()
+            },
+            false
+        ));
+    }
+
+    #[test]
+    fn rejects_invalid_message_predicates() {
+        for json in [
+            r#"[{"version": 256}]"#,
+            r#"[{"nonce": "20..10"}]"#,
+            r#"[{"nonce": "10..10"}]"#,
+            r#"[{"nonce": "abc"}]"#,
+            r#"[{"bodyprefix": "0xabc"}]"#,
+        ] {
+            assert!(
+                serde_json::from_str::<MatchingList>(json).is_err(),
+                "{json}"
+            );
+        }
+    }
 }
@@ -4,7 +4,12 @@
 //! and validations it defines are not applied here, we should mirror them.
 //! ANY CHANGES HERE NEED TO BE REFLECTED IN THE TYPESCRIPT SDK.
 
-use std::{collections::HashSet, path::PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
 
 use convert_case::Case;
 use derive_more::{AsMut, AsRef, Deref, DerefMut};
@@ -17,7 +22,7 @@ use hyperlane_base::{
         Settings,
     },
 };
-use hyperlane_core::{cfg_unwrap_all, config::*, HyperlaneDomain, U256};
+use hyperlane_core::{cfg_unwrap_all, config::*, FixedPointNumber, HyperlaneDomain, U256};
 use itertools::Itertools;
 use serde::Deserialize;
 use serde_json::Value;
@@ -41,16 +46,11 @@ pub struct RelayerSettings {
     pub origin_chains: HashSet<HyperlaneDomain>,
     /// Chains to relay messages to
     pub destination_chains: HashSet<HyperlaneDomain>,
-    /// The gas payment enforcement policies
-    pub gas_payment_enforcement: Vec<GasPaymentEnforcementConf>,
-    /// Filter for what messages to relay.
-    pub whitelist: MatchingList,
-    /// Filter for what messages to block.
-    pub blacklist: MatchingList,
-    /// Filter for what addresses to block interactions with.
-    /// This is intentionally not an H256 to allow for addresses of any length without
-    /// adding any padding.
-    pub address_blacklist: Vec<Vec<u8>>,
+    /// Message filters and gas payment enforcement policies
+    pub policies: RelayerPolicies,
+    /// How often to reload the config files and apply any changes to
+    /// `policies`. The config files are not watched if this is not set.
+    pub policies_reload_interval: Option<Duration>,
     /// This is optional. If not specified, any amount of gas will be valid, otherwise this
     /// is the max allowed gas in wei to relay a transaction.
     pub transaction_gas_limit: Option<U256>,
@@ -61,10 +61,16 @@ pub struct RelayerSettings {
     pub allow_local_checkpoint_syncers: bool,
     /// App contexts used for metrics.
     pub metric_app_contexts: Vec<(MatchingList, String)>,
+    /// Bearer token required by the admin API. The admin API is disabled if
+    /// this is not set.
+    pub admin_token: Option<String>,
+    /// Used to value gas payments and spending in USD for profitability
+    /// accounting. Routes can't be flagged as running at a loss without it.
+    pub token_prices: Option<TokenPricesConf>,
 }
 
 /// Config for gas payment enforcement
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct GasPaymentEnforcementConf {
     /// The gas payment enforcement policy
     pub policy: GasPaymentEnforcementPolicy,
@@ -74,7 +80,7 @@ pub struct GasPaymentEnforcementConf {
 }
 
 /// Config for a GasPaymentEnforcementPolicy
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub enum GasPaymentEnforcementPolicy {
     /// No requirement - all messages are processed regardless of gas payment
     #[default]
@@ -87,6 +93,178 @@ pub enum GasPaymentEnforcementPolicy {
         gas_fraction_numerator: u64,
         gas_fraction_denominator: u64,
     },
+    /// The payment, valued in USD, covers the estimated USD cost of delivering
+    /// the message on the destination multiplied by `margin`.
+    UsdCost {
+        /// Multiplier applied to the estimated cost, e.g. 1.1 to require a
+        /// 10% profit.
+        margin: FixedPointNumber,
+        /// Decimals of the native token of each domain, defaults to 18.
+        native_token_decimals: HashMap<u32, u32>,
+        /// Where the USD prices of the native tokens are read from.
+        price_source: TokenPriceSourceConf,
+    },
+}
+
+/// Config for valuing native tokens in USD
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenPricesConf {
+    /// Decimals of the native token of each domain, defaults to 18.
+    pub native_token_decimals: HashMap<u32, u32>,
+    /// Where the USD prices of the native tokens are read from.
+    pub price_source: TokenPriceSourceConf,
+}
+
+/// Config for a source of native token prices in USD
+#[derive(Debug, Clone, PartialEq)]
+pub enum TokenPriceSourceConf {
+    /// Fixed prices of the native token of each domain
+    Static {
+        prices: HashMap<u32, FixedPointNumber>,
+    },
+    /// A JSON object mapping domain ids to the price of their native token,
+    /// served over HTTP, e.g. `{"1": 3000.5, "1399811149": "150.2"}`
+    Http {
+        url: String,
+        /// How long fetched prices are used before being refreshed
+        cache_duration: Duration,
+    },
+}
+
+/// The policies deciding which messages are relayed and how much gas they must
+/// pay. These can be replaced while the relayer is running.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelayerPolicies {
+    /// The gas payment enforcement policies
+    pub gas_payment_enforcement: Vec<GasPaymentEnforcementConf>,
+    /// Filter for what messages to relay.
+    pub whitelist: MatchingList,
+    /// Filter for what messages to block.
+    pub blacklist: MatchingList,
+    /// Filter for what addresses to block interactions with.
+    /// This is intentionally not an H256 to allow for addresses of any length without
+    /// adding any padding.
+    pub address_blacklist: Vec<Vec<u8>>,
+}
+
+impl Default for RelayerPolicies {
+    fn default() -> Self {
+        Self {
+            gas_payment_enforcement: vec![GasPaymentEnforcementConf::default()],
+            whitelist: Default::default(),
+            blacklist: Default::default(),
+            address_blacklist: Default::default(),
+        }
+    }
+}
+
+impl RelayerPolicies {
+    /// Parses the policies set in a raw JSON config (e.g. an update sent to the
+    /// relayer server), keeping the current value of any policy that isn't set.
+    pub fn with_overrides(&self, raw: &Value) -> ConfigResult<Self> {
+        let mut err = ConfigParsingError::default();
+        let raw = recase_json_value(raw.clone(), Case::Flat);
+        let p = ValueParser::new(ConfigPath::default(), &raw);
+        let policies = self.with_overrides_from(&p, &mut err);
+        err.into_result(policies)
+    }
+
+    /// Parses the policies set in `p`, keeping the current value of any policy
+    /// that isn't set.
+    fn with_overrides_from(&self, p: &ValueParser, err: &mut ConfigParsingError) -> Self {
+        let mut policies = self.clone();
+
+        if let Some((raw_gas_payment_enforcement_path, raw_gas_payment_enforcement)) = p
+            .get_opt_key("gasPaymentEnforcement")
+            .take_config_err_flat(err)
+            .and_then(parse_json_array)
+        {
+            let gas_payment_enforcement_parser = ValueParser::new(
+                raw_gas_payment_enforcement_path,
+                &raw_gas_payment_enforcement,
+            );
+            let mut gas_payment_enforcement = gas_payment_enforcement_parser.into_array_iter().map(|itr| {
+                itr.filter_map(|policy| {
+                    let policy_type = policy.chain(err).get_opt_key("type").parse_string().end();
+                    let minimum_is_defined = matches!(policy.get_opt_key("minimum"), Ok(Some(_)));
+
+                    let matching_list = policy.chain(err).get_opt_key("matchingList").and_then(parse_matching_list).unwrap_or_default();
+
+                    let parse_minimum = |p| GasPaymentEnforcementPolicy::Minimum { payment: p };
+                    match policy_type {
+                        Some("minimum") => policy.chain(err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
+                        None if minimum_is_defined => policy.chain(err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
+                        Some("none") | None => Some(GasPaymentEnforcementPolicy::None),
+                        Some("onChainFeeQuoting") => {
+                            let gas_fraction = policy.chain(err)
+                                .get_opt_key("gasFraction")
+                                .parse_string()
+                                .map(|v| v.replace(' ', ""))
+                                .unwrap_or_else(|| "1/2".to_owned());
+                            let (numerator, denominator) = gas_fraction
+                                .split_once('/')
+                                .ok_or_else(|| eyre!("Invalid `gas_fraction` for OnChainFeeQuoting gas payment enforcement policy; expected `numerator / denominator`"))
+                                .take_err(err, || &policy.cwp + "gas_fraction")
+                                .unwrap_or(("1", "1"));
+
+                            Some(GasPaymentEnforcementPolicy::OnChainFeeQuoting {
+                                gas_fraction_numerator: numerator
+                                    .parse()
+                                    .context("Error parsing gas fraction numerator")
+                                    .take_err(err, || &policy.cwp + "gas_fraction")
+                                    .unwrap_or(1),
+                                gas_fraction_denominator: denominator
+                                    .parse()
+                                    .context("Error parsing gas fraction denominator")
+                                    .take_err(err, || &policy.cwp + "gas_fraction")
+                                    .unwrap_or(1),
+                            })
+                        }
+                        Some("usdCost") => parse_usd_cost_policy(&policy, err),
+                        Some(pt) => Err(eyre!("Unknown gas payment enforcement policy type `{pt}`"))
+                            .take_err(err, || &p.cwp + "type"),
+                    }.map(|policy| GasPaymentEnforcementConf {
+                        policy,
+                        matching_list,
+                    })
+                }).collect_vec()
+            }).unwrap_or_default();
+
+            if gas_payment_enforcement.is_empty() {
+                gas_payment_enforcement.push(GasPaymentEnforcementConf::default());
+            }
+            policies.gas_payment_enforcement = gas_payment_enforcement;
+        }
+
+        if let Some(whitelist) = p
+            .chain(err)
+            .get_opt_key("whitelist")
+            .and_then(parse_matching_list)
+            .end()
+        {
+            policies.whitelist = whitelist;
+        }
+        if let Some(blacklist) = p
+            .chain(err)
+            .get_opt_key("blacklist")
+            .and_then(parse_matching_list)
+            .end()
+        {
+            policies.blacklist = blacklist;
+        }
+
+        if let Some(address_blacklist) = p
+            .chain(err)
+            .get_opt_key("addressBlacklist")
+            .parse_string()
+            .end()
+        {
+            policies.address_blacklist =
+                parse_address_list(address_blacklist, err, || &p.cwp + "address_blacklist");
+        }
+
+        policies
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -125,84 +303,7 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .parse_from_str("Expected database path")
             .unwrap_or_else(|| std::env::current_dir().unwrap().join("hyperlane_db"));
 
-        let (raw_gas_payment_enforcement_path, raw_gas_payment_enforcement) = p
-            .get_opt_key("gasPaymentEnforcement")
-            .take_config_err_flat(&mut err)
-            .and_then(parse_json_array)
-            .unwrap_or_else(|| (&p.cwp + "gas_payment_enforcement", Value::Array(vec![])));
-
-        let gas_payment_enforcement_parser = ValueParser::new(
-            raw_gas_payment_enforcement_path,
-            &raw_gas_payment_enforcement,
-        );
-        let mut gas_payment_enforcement = gas_payment_enforcement_parser.into_array_iter().map(|itr| {
-            itr.filter_map(|policy| {
-                let policy_type = policy.chain(&mut err).get_opt_key("type").parse_string().end();
-                let minimum_is_defined = matches!(policy.get_opt_key("minimum"), Ok(Some(_)));
-
-                let matching_list = policy.chain(&mut err).get_opt_key("matchingList").and_then(parse_matching_list).unwrap_or_default();
-
-                let parse_minimum = |p| GasPaymentEnforcementPolicy::Minimum { payment: p };
-                match policy_type {
-                    Some("minimum") => policy.chain(&mut err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
-                    None if minimum_is_defined => policy.chain(&mut err).get_opt_key("payment").parse_u256().end().map(parse_minimum),
-                    Some("none") | None => Some(GasPaymentEnforcementPolicy::None),
-                    Some("onChainFeeQuoting") => {
-                        let gas_fraction = policy.chain(&mut err)
-                            .get_opt_key("gasFraction")
-                            .parse_string()
-                            .map(|v| v.replace(' ', ""))
-                            .unwrap_or_else(|| "1/2".to_owned());
-                        let (numerator, denominator) = gas_fraction
-                            .split_once('/')
-                            .ok_or_else(|| eyre!("Invalid `gas_fraction` for OnChainFeeQuoting gas payment enforcement policy; expected `numerator / denominator`"))
-                            .take_err(&mut err, || &policy.cwp + "gas_fraction")
-                            .unwrap_or(("1", "1"));
-
-                        Some(GasPaymentEnforcementPolicy::OnChainFeeQuoting {
-                            gas_fraction_numerator: numerator
-                                .parse()
-                                .context("Error parsing gas fraction numerator")
-                                .take_err(&mut err, || &policy.cwp + "gas_fraction")
-                                .unwrap_or(1),
-                            gas_fraction_denominator: denominator
-                                .parse()
-                                .context("Error parsing gas fraction denominator")
-                                .take_err(&mut err, || &policy.cwp + "gas_fraction")
-                                .unwrap_or(1),
-                        })
-                    }
-                    Some(pt) => Err(eyre!("Unknown gas payment enforcement policy type `{pt}`"))
-                        .take_err(&mut err, || cwp + "type"),
-                }.map(|policy| GasPaymentEnforcementConf {
-                    policy,
-                    matching_list,
-                })
-            }).collect_vec()
-        }).unwrap_or_default();
-
-        if gas_payment_enforcement.is_empty() {
-            gas_payment_enforcement.push(GasPaymentEnforcementConf::default());
-        }
-
-        let whitelist = p
-            .chain(&mut err)
-            .get_opt_key("whitelist")
-            .and_then(parse_matching_list)
-            .unwrap_or_default();
-        let blacklist = p
-            .chain(&mut err)
-            .get_opt_key("blacklist")
-            .and_then(parse_matching_list)
-            .unwrap_or_default();
-
-        let address_blacklist = p
-            .chain(&mut err)
-            .get_opt_key("addressBlacklist")
-            .parse_string()
-            .end()
-            .map(|str| parse_address_list(str, &mut err, || &p.cwp + "address_blacklist"))
-            .unwrap_or_default();
+        let policies = RelayerPolicies::default().with_overrides_from(&p, &mut err);
 
         let transaction_gas_limit = p
             .chain(&mut err)
@@ -223,6 +324,35 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             .parse_bool()
             .unwrap_or(false);
 
+        let policies_reload_interval = p
+            .chain(&mut err)
+            .get_opt_key("policiesReloadInterval")
+            .parse_u64()
+            .end()
+            .map(Duration::from_secs);
+
+        let admin_token = p
+            .chain(&mut err)
+            .get_opt_key("adminToken")
+            .parse_string()
+            .end()
+            .map(|v| v.to_owned());
+        if admin_token
+            .as_deref()
+            .map_or(false, |token| token.trim().is_empty())
+        {
+            err.push(
+                &p.cwp + "admin_token",
+                eyre!("`adminToken` must not be empty, leave it unset to disable the admin API"),
+            );
+        }
+
+        let token_prices = p
+            .chain(&mut err)
+            .get_opt_key("tokenPrices")
+            .end()
+            .and_then(|token_prices| parse_token_prices(&token_prices, &mut err));
+
         cfg_unwrap_all!(cwp, err: [base]);
 
         let skip_transaction_gas_limit_for = skip_transaction_gas_limit_for_names
@@ -278,14 +408,14 @@ impl FromRawConf<RawRelayerSettings> for RelayerSettings {
             db,
             origin_chains: relay_chains.clone(),
             destination_chains: relay_chains,
-            gas_payment_enforcement,
-            whitelist,
-            blacklist,
-            address_blacklist,
+            policies,
+            policies_reload_interval,
             transaction_gas_limit,
             skip_transaction_gas_limit_for,
             allow_local_checkpoint_syncers,
             metric_app_contexts,
+            admin_token,
+            token_prices,
         })
     }
 }
@@ -326,6 +456,113 @@ fn parse_matching_list(p: ValueParser) -> ConfigResult<MatchingList> {
     err.into_result(ml)
 }
 
+fn parse_usd_cost_policy(
+    policy: &ValueParser,
+    err: &mut ConfigParsingError,
+) -> Option<GasPaymentEnforcementPolicy> {
+    let margin = policy
+        .chain(err)
+        .get_opt_key("margin")
+        .and_then(parse_decimal)
+        .unwrap_or_else(|| FixedPointNumber::from(1));
+    let TokenPricesConf {
+        native_token_decimals,
+        price_source,
+    } = parse_token_prices(policy, err)?;
+
+    Some(GasPaymentEnforcementPolicy::UsdCost {
+        margin,
+        native_token_decimals,
+        price_source,
+    })
+}
+
+/// Expects `nativeTokenDecimals` and `priceSource` keys.
+fn parse_token_prices(p: &ValueParser, err: &mut ConfigParsingError) -> Option<TokenPricesConf> {
+    let native_token_decimals = p
+        .chain(err)
+        .get_opt_key("nativeTokenDecimals")
+        .into_obj_iter()
+        .map(|itr| {
+            itr.filter_map(|(domain, decimals)| {
+                let domain = parse_domain_key(&domain, &decimals, err)?;
+                decimals.chain(err).parse_u32().end().map(|d| (domain, d))
+            })
+            .collect()
+        })
+        .unwrap_or_default();
+
+    let price_source = p.chain(err).get_key("priceSource").end()?;
+    let price_source = match price_source
+        .chain(err)
+        .get_key("type")
+        .parse_string()
+        .end()?
+    {
+        "static" => TokenPriceSourceConf::Static {
+            prices: price_source
+                .chain(err)
+                .get_key("prices")
+                .into_obj_iter()
+                .map(|itr| {
+                    itr.filter_map(|(domain, price)| {
+                        let domain = parse_domain_key(&domain, &price, err)?;
+                        price
+                            .chain(err)
+                            .and_then(parse_decimal)
+                            .end()
+                            .map(|p| (domain, p))
+                    })
+                    .collect()
+                })
+                .unwrap_or_default(),
+        },
+        "http" => TokenPriceSourceConf::Http {
+            url: price_source
+                .chain(err)
+                .get_key("url")
+                .parse_string()
+                .end()?
+                .to_owned(),
+            cache_duration: price_source
+                .chain(err)
+                .get_opt_key("cacheDuration")
+                .parse_u64()
+                .map(Duration::from_secs)
+                .unwrap_or(Duration::from_secs(60)),
+        },
+        t => {
+            return Err(eyre!("Unknown token price source type `{t}`"))
+                .take_err(err, || &price_source.cwp + "type")
+        }
+    };
+
+    Some(TokenPricesConf {
+        native_token_decimals,
+        price_source,
+    })
+}
+
+/// Parses a decimal number that may be represented as a string or number.
+/// Strings are parsed as is, so prices with more precision than an f64 has
+/// should be given as strings.
+fn parse_decimal(p: ValueParser) -> ConfigResult<FixedPointNumber> {
+    match p.val {
+        Value::Number(num) => FixedPointNumber::from_str(&num.to_string())
+            .with_context(|| format!("Expected a decimal number, got number `{num}`")),
+        Value::String(s) => FixedPointNumber::from_str(s)
+            .with_context(|| format!("Expected a decimal number, got string `{s}`")),
+        _ => Err(eyre!("Expected a decimal number, got `{:?}`", p.val)),
+    }
+    .into_config_result(|| p.cwp.clone())
+}
+
+fn parse_domain_key(key: &str, p: &ValueParser, err: &mut ConfigParsingError) -> Option<u32> {
+    key.parse()
+        .context("Expected a domain id")
+        .take_err(err, || p.cwp.clone())
+}
+
 fn parse_address_list(
     str: &str,
     err: &mut ConfigParsingError,
@@ -374,4 +611,91 @@ mod test {
         assert_eq!(res, vec![valid_address1, valid_address2]);
         assert!(!err.is_ok());
     }
+
+    #[test]
+    fn test_policy_overrides() {
+        let current = RelayerPolicies {
+            address_blacklist: vec![b"blocked".to_vec()],
+            ..Default::default()
+        };
+        let raw = serde_json::json!({
+            "gasPaymentEnforcement": [{ "type": "minimum", "payment": "1" }],
+            "whitelist": "[{\"destinationDomain\": 5}]",
+        });
+
+        let updated = current.with_overrides(&raw).unwrap();
+        assert_eq!(
+            updated.gas_payment_enforcement,
+            vec![GasPaymentEnforcementConf {
+                policy: GasPaymentEnforcementPolicy::Minimum {
+                    payment: U256::one()
+                },
+                matching_list: Default::default(),
+            }]
+        );
+        assert_ne!(updated.whitelist, current.whitelist);
+        // Policies that aren't set are kept
+        assert_eq!(updated.blacklist, current.blacklist);
+        assert_eq!(updated.address_blacklist, current.address_blacklist);
+
+        // Invalid policies are rejected
+        let raw = serde_json::json!({ "gasPaymentEnforcement": [{ "type": "unknown" }] });
+        assert!(current.with_overrides(&raw).is_err());
+    }
+
+    #[test]
+    fn test_parse_usd_cost_policy() {
+        let raw = serde_json::json!({
+            "gasPaymentEnforcement": [{
+                "type": "usdCost",
+                "margin": "1.1",
+                "nativeTokenDecimals": { "1399811149": 9 },
+                "priceSource": {
+                    "type": "static",
+                    "prices": { "1": 3000.5, "1399811149": "150.123456789012345678" }
+                }
+            }, {
+                "type": "usdCost",
+                "priceSource": { "type": "http", "url": "http://localhost:8080/prices" }
+            }],
+        });
+
+        let policies = RelayerPolicies::default().with_overrides(&raw).unwrap();
+        let policies = policies
+            .gas_payment_enforcement
+            .into_iter()
+            .map(|c| c.policy)
+            .collect_vec();
+        assert_eq!(
+            policies,
+            vec![
+                GasPaymentEnforcementPolicy::UsdCost {
+                    margin: FixedPointNumber::from_str("1.1").unwrap(),
+                    native_token_decimals: HashMap::from([(1399811149, 9)]),
+                    price_source: TokenPriceSourceConf::Static {
+                        prices: HashMap::from([
+                            (1, FixedPointNumber::from_str("3000.5").unwrap()),
+                            // Strings keep more precision than an f64
+                            (
+                                1399811149,
+                                FixedPointNumber::from_str("150.123456789012345678").unwrap(),
+                            ),
+                        ]),
+                    },
+                },
+                GasPaymentEnforcementPolicy::UsdCost {
+                    margin: FixedPointNumber::from(1),
+                    native_token_decimals: HashMap::new(),
+                    price_source: TokenPriceSourceConf::Http {
+                        url: "http://localhost:8080/prices".to_owned(),
+                        cache_duration: Duration::from_secs(60),
+                    },
+                },
+            ]
+        );
+
+        // A price source is required
+        let raw = serde_json::json!({ "gasPaymentEnforcement": [{ "type": "usdCost" }] });
+        assert!(RelayerPolicies::default().with_overrides(&raw).is_err());
+    }
 }
@@ -3,18 +3,18 @@
 //! This module (and children) are responsible for scraping blockchain data and
 //! keeping things updated.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, fmt::Debug, sync::Arc};
 
 use async_trait::async_trait;
-use eyre::Result;
+use eyre::{bail, Result};
 use hyperlane_base::settings::IndexSettings;
 use hyperlane_core::{
     unwrap_or_none_result, BlockInfo, Delivery, HyperlaneDomain, HyperlaneLogStore,
     HyperlaneMessage, HyperlaneProvider, HyperlaneSequenceAwareIndexerStoreReader,
-    HyperlaneWatermarkedLogStore, Indexed, InterchainGasPayment, LogMeta, H256,
+    HyperlaneWatermarkedLogStore, Indexed, InterchainGasPayment, LogMeta, H256, H512,
 };
 use itertools::Itertools;
-use tracing::trace;
+use tracing::{trace, warn};
 
 use crate::db::{
     BasicBlock, BlockCursor, ScraperDb, StorableDelivery, StorableMessage, StorablePayment,
@@ -78,21 +78,14 @@ impl HyperlaneSqlDb {
         &self,
         log_meta: impl Iterator<Item = &LogMeta>,
     ) -> Result<impl Iterator<Item = TxnWithId>> {
-        let block_hash_by_txn_hash: HashMap<H256, H256> = log_meta
-            .map(|meta| {
-                (
-                    meta.transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                    meta.block_hash,
-                )
-            })
+        let block_by_txn_hash: HashMap<H512, (H256, u64)> = log_meta
+            .map(|meta| (meta.transaction_id, (meta.block_hash, meta.block_number)))
             .collect();
 
         // all blocks we care about
         // hash of block maps to the block id and timestamp
         let blocks: HashMap<_, _> = self
-            .ensure_blocks(block_hash_by_txn_hash.values().copied())
+            .ensure_blocks(block_by_txn_hash.values().copied())
             .await?
             .map(|block| (block.hash, block))
             .collect();
@@ -100,8 +93,8 @@ impl HyperlaneSqlDb {
 
         // all txns we care about
         let txns_with_ids =
-            self.ensure_txns(block_hash_by_txn_hash.into_iter().map(
-                move |(txn_hash, block_hash)| {
+            self.ensure_txns(block_by_txn_hash.into_iter().map(
+                move |(txn_hash, (block_hash, _))| {
                     let block_info = *blocks.get(&block_hash).as_ref().unwrap();
                     TxnWithBlockId {
                         txn_hash,
@@ -125,7 +118,7 @@ impl HyperlaneSqlDb {
         txns: impl Iterator<Item = TxnWithBlockId>,
     ) -> Result<impl Iterator<Item = TxnWithId>> {
         // mapping of txn hash to (txn_id, block_id).
-        let mut txns: HashMap<H256, (Option<i64>, i64)> = txns
+        let mut txns: HashMap<H512, (Option<i64>, i64)> = txns
             .map(|TxnWithBlockId { txn_hash, block_id }| (txn_hash, (None, block_id)))
             .collect();
 
@@ -149,9 +142,9 @@ impl HyperlaneSqlDb {
         let mut txns_to_fetch = txns.iter_mut().filter(|(_, id)| id.0.is_none());
 
         let mut txns_to_insert: Vec<StorableTxn> = Vec::with_capacity(CHUNK_SIZE);
-        let mut hashes_to_insert: Vec<&H256> = Vec::with_capacity(CHUNK_SIZE);
+        let mut hashes_to_insert: Vec<&H512> = Vec::with_capacity(CHUNK_SIZE);
 
-        for mut chunk in as_chunks::<(&H256, &mut (Option<i64>, i64))>(txns_to_fetch, CHUNK_SIZE) {
+        for mut chunk in as_chunks::<(&H512, &mut (Option<i64>, i64))>(txns_to_fetch, CHUNK_SIZE) {
             for (hash, (_, block_id)) in chunk.iter() {
                 let info = self.provider.get_txn_by_hash(hash).await?;
                 hashes_to_insert.push(*hash);
@@ -177,21 +170,23 @@ impl HyperlaneSqlDb {
             }))
     }
 
-    /// Takes a list of block hashes for each block
+    /// Takes a list of block hashes and heights for each block
     /// if it is in the database already:
     ///     Fetches its associated database id
     /// if it is not in the database already:
-    ///     Looks up its data with ethers and then returns the database id after
+    ///     Looks up its data by height and then returns the database id after
     ///     inserting it into the database.
     async fn ensure_blocks(
         &self,
-        block_hashes: impl Iterator<Item = H256>,
+        block_hashes: impl Iterator<Item = (H256, u64)>,
     ) -> Result<impl Iterator<Item = BasicBlock>> {
+        let heights: HashMap<H256, u64> = block_hashes.collect();
+
         // mapping of block hash to the database id and block timestamp. Optionals are
         // in place because we will find the timestamp first if the block was not
         // already in the db.
         let mut blocks: HashMap<H256, Option<BasicBlock>> =
-            block_hashes.map(|b| (b, None)).collect();
+            heights.keys().map(|b| (*b, None)).collect();
 
         let db_blocks: Vec<BasicBlock> = if !blocks.is_empty() {
             // check database to see which blocks we already know and fetch their IDs
@@ -222,7 +217,17 @@ impl HyperlaneSqlDb {
         for chunk in as_chunks(blocks_to_fetch, CHUNK_SIZE) {
             debug_assert!(!chunk.is_empty());
             for (hash, block_info) in chunk {
-                let info = self.provider.get_block_by_hash(hash).await?;
+                // Not every chain supports looking blocks up by hash, so query by
+                // height and make sure we got the block the log was indexed in.
+                let info = self.provider.get_block_by_height(heights[hash]).await?;
+                if info.hash != *hash {
+                    bail!(
+                        "Block at height {} has hash {:?}, expected {:?}",
+                        heights[hash],
+                        info.hash,
+                        hash
+                    );
+                }
                 let basic_info_ref = block_info.insert(BasicBlock {
                     id: -1,
                     hash: *hash,
@@ -272,22 +277,17 @@ impl HyperlaneSqlDb {
 impl HyperlaneLogStore<HyperlaneMessage> for HyperlaneSqlDb {
     /// Store messages from the origin mailbox into the database.
     async fn store_logs(&self, messages: &[(Indexed<HyperlaneMessage>, LogMeta)]) -> Result<u32> {
+        let messages = &with_txn_and_block(messages);
         if messages.is_empty() {
             return Ok(0);
         }
-        let txns: HashMap<H256, TxnWithId> = self
+        let txns: HashMap<H512, TxnWithId> = self
             .ensure_blocks_and_txns(messages.iter().map(|r| &r.1))
             .await?
             .map(|t| (t.hash, t))
             .collect();
         let storable = messages.iter().map(|m| {
-            let txn = txns
-                .get(
-                    &m.1.transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                )
-                .unwrap();
+            let txn = txns.get(&m.1.transaction_id).unwrap();
             StorableMessage {
                 msg: m.0.inner().clone(),
                 meta: &m.1,
@@ -305,24 +305,17 @@ impl HyperlaneLogStore<HyperlaneMessage> for HyperlaneSqlDb {
 #[async_trait]
 impl HyperlaneLogStore<Delivery> for HyperlaneSqlDb {
     async fn store_logs(&self, deliveries: &[(Indexed<Delivery>, LogMeta)]) -> Result<u32> {
+        let deliveries = &with_txn_and_block(deliveries);
         if deliveries.is_empty() {
             return Ok(0);
         }
-        let txns: HashMap<Delivery, TxnWithId> = self
+        let txns: HashMap<H512, TxnWithId> = self
             .ensure_blocks_and_txns(deliveries.iter().map(|r| &r.1))
             .await?
             .map(|t| (t.hash, t))
             .collect();
         let storable = deliveries.iter().map(|(message_id, meta)| {
-            let txn_id = txns
-                .get(
-                    &meta
-                        .transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                )
-                .unwrap()
-                .id;
+            let txn_id = txns.get(&meta.transaction_id).unwrap().id;
             StorableDelivery {
                 message_id: *message_id.inner(),
                 meta,
@@ -344,24 +337,17 @@ impl HyperlaneLogStore<InterchainGasPayment> for HyperlaneSqlDb {
         &self,
         payments: &[(Indexed<InterchainGasPayment>, LogMeta)],
     ) -> Result<u32> {
+        let payments = &with_txn_and_block(payments);
         if payments.is_empty() {
             return Ok(0);
         }
-        let txns: HashMap<H256, TxnWithId> = self
+        let txns: HashMap<H512, TxnWithId> = self
             .ensure_blocks_and_txns(payments.iter().map(|r| &r.1))
             .await?
             .map(|t| (t.hash, t))
             .collect();
         let storable = payments.iter().map(|(payment, meta)| {
-            let txn_id = txns
-                .get(
-                    &meta
-                        .transaction_id
-                        .try_into()
-                        .expect("256-bit transaction ids are the maximum supported at this time"),
-                )
-                .unwrap()
-                .id;
+            let txn_id = txns.get(&meta.transaction_id).unwrap().id;
             StorablePayment {
                 payment: payment.inner(),
                 meta,
@@ -415,16 +401,36 @@ where
 
 #[derive(Debug, Clone)]
 struct TxnWithId {
-    hash: H256,
+    hash: H512,
     id: i64,
 }
 
 #[derive(Debug, Clone)]
 struct TxnWithBlockId {
-    txn_hash: H256,
+    txn_hash: H512,
     block_id: i64,
 }
 
+/// Drops the logs whose transaction id or block hash couldn't be determined
+/// by the indexer, e.g. because the RPC no longer has the history for them.
+/// They can't be linked to a transaction and block, so storing them would
+/// fail on every attempt.
+fn with_txn_and_block<T: Debug>(logs: &[(Indexed<T>, LogMeta)]) -> Vec<&(Indexed<T>, LogMeta)> {
+    logs.iter()
+        .filter(|(log, meta)| {
+            let known = !meta.transaction_id.is_zero() && !meta.block_hash.is_zero();
+            if !known {
+                warn!(
+                    ?log,
+                    ?meta,
+                    "Skipping log without a transaction id or block hash"
+                );
+            }
+            known
+        })
+        .collect()
+}
+
 fn as_chunks<T>(iter: impl Iterator<Item = T>, chunk_size: usize) -> impl Iterator<Item = Vec<T>> {
     // the itertools chunks function uses refcell which cannot be used across an
     // await so this stabilizes the result by putting it into a vec of vecs and
@@ -3,12 +3,12 @@ use std::str::FromStr;
 
 use async_trait::async_trait;
 use cosmrs::proto::cosmos::base::abci::v1beta1::TxResponse;
-use tracing::instrument;
+use tracing::{debug, instrument};
 
 use hyperlane_core::{
-    utils::bytes_to_hex, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
-    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Mailbox, RawHyperlaneMessage,
-    TxCostEstimate, TxOutcome, H256, U256,
+    utils::bytes_to_hex, BatchItem, BatchResult, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Mailbox,
+    QueueOperation, RawHyperlaneMessage, TxCostEstimate, TxOutcome, H256, U256,
 };
 
 use crate::address::CosmosAddress;
@@ -161,12 +161,7 @@ impl Mailbox for CosmosMailbox {
         metadata: &[u8],
         tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
-        let process_message = ProcessMessageRequest {
-            process: ProcessMessageRequestInner {
-                message: hex::encode(RawHyperlaneMessage::from(message)),
-                metadata: hex::encode(metadata),
-            },
-        };
+        let process_message = process_message_request(message, metadata);
 
         let response: TxResponse = self
             .provider
@@ -177,6 +172,65 @@ impl Mailbox for CosmosMailbox {
         Ok(tx_response_to_outcome(response)?)
     }
 
+    #[instrument(err, ret, skip(self, ops), fields(size=%ops.len()))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn try_process_batch<'a>(
+        &self,
+        ops: Vec<&'a QueueOperation>,
+    ) -> ChainResult<BatchResult> {
+        let messages = ops
+            .iter()
+            .map(|op| op.try_batch())
+            .collect::<ChainResult<Vec<BatchItem<HyperlaneMessage>>>>()?;
+        let mut included = messages
+            .iter()
+            .enumerate()
+            .map(|(index, item)| {
+                (
+                    index,
+                    process_message_request(&item.data, &item.submission_data.metadata),
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut failed_indexes = vec![];
+
+        // A simulation fails as a whole if any of its messages fails, and the error only
+        // names the first failing message. Exclude failing messages one at a time until
+        // the rest of the batch simulates successfully.
+        let gas_limit = loop {
+            // only send a batch if there are at least two successful messages
+            if included.len() < 2 {
+                return Ok(BatchResult::failed(messages.len()));
+            }
+            let payloads = included.iter().map(|(_, p)| p.clone()).collect();
+            match self.provider.grpc().wasm_estimate_gas_batch(payloads).await {
+                Ok(gas_limit) => break gas_limit,
+                Err(err) => {
+                    let Some(position) = failed_message_index(&err.to_string())
+                        .filter(|&position| position < included.len())
+                    else {
+                        return Err(err);
+                    };
+                    let (index, _) = included.remove(position);
+                    debug!(index, error=?err, "Excluding message that would fail from batch");
+                    failed_indexes.push(index);
+                }
+            }
+        };
+
+        let payloads = included.into_iter().map(|(_, p)| p).collect();
+        let response = self
+            .provider
+            .grpc()
+            .wasm_send_batch(payloads, Some(gas_limit.into()))
+            .await?;
+
+        Ok(BatchResult::new(
+            Some(tx_response_to_outcome(response)?),
+            failed_indexes,
+        ))
+    }
+
     #[instrument(err, ret, skip(self), fields(msg=%message, metadata=%bytes_to_hex(metadata)))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn process_estimate_costs(
@@ -184,12 +238,7 @@ impl Mailbox for CosmosMailbox {
         message: &HyperlaneMessage,
         metadata: &[u8],
     ) -> ChainResult<TxCostEstimate> {
-        let process_message = ProcessMessageRequest {
-            process: ProcessMessageRequestInner {
-                message: hex::encode(RawHyperlaneMessage::from(message)),
-                metadata: hex::encode(metadata),
-            },
-        };
+        let process_message = process_message_request(message, metadata);
 
         let gas_limit = self
             .provider
@@ -211,6 +260,27 @@ impl Mailbox for CosmosMailbox {
     }
 }
 
+fn process_message_request(message: &HyperlaneMessage, metadata: &[u8]) -> ProcessMessageRequest {
+    ProcessMessageRequest {
+        process: ProcessMessageRequestInner {
+            message: hex::encode(RawHyperlaneMessage::from(message)),
+            metadata: hex::encode(metadata),
+        },
+    }
+}
+
+/// Extracts the index of the message that failed from the error of a failed
+/// simulation, e.g. `failed to execute message; message index: 2: ...`
+fn failed_message_index(error: &str) -> Option<usize> {
+    const MESSAGE_INDEX: &str = "message index: ";
+    let start = error.find(MESSAGE_INDEX)? + MESSAGE_INDEX.len();
+    let digits = error[start..]
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect::<String>();
+    digits.parse().ok()
+}
+
 impl CosmosMailbox {
     #[instrument(level = "debug", err, ret, skip(self))]
     pub(crate) async fn nonce_at_block(&self, block_height: Option<u64>) -> ChainResult<u32> {
@@ -229,3 +299,20 @@ impl CosmosMailbox {
         Ok(response.nonce)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::failed_message_index;
+
+    #[test]
+    fn test_failed_message_index() {
+        assert_eq!(
+            failed_message_index(
+                "status: Unknown, message: \"failed to execute message; message index: 12: delivered: execute wasm contract failed\""
+            ),
+            Some(12)
+        );
+        assert_eq!(failed_message_index("out of gas in location: wasm"), None);
+        assert_eq!(failed_message_index("message index: abc"), None);
+    }
+}
@@ -10,7 +10,7 @@ use tracing::instrument;
 
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, ContractLocator, Delivery, HyperlaneMessage, Indexed,
-    Indexer, LogMeta, SequenceAwareIndexer, H256, H512,
+    Indexer, LogMeta, LogSubscription, SequenceAwareIndexer, H256, H512,
 };
 
 use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
@@ -139,6 +139,14 @@ impl Indexer<H256> for CosmosMailboxDeliveryIndexer {
         .await
         .map(|v| v.into_iter().map(|(m, l)| (m.into(), l)).collect())
     }
+
+    async fn subscribe_logs(&self) -> ChainResult<Option<Box<dyn LogSubscription<H256>>>> {
+        let subscription = self
+            .provider
+            .subscribe_logs(Self::hyperlane_delivery_parser, "DeliverySubscription")
+            .await?;
+        Ok(subscription.map(|s| Box::new(s) as Box<dyn LogSubscription<H256>>))
+    }
 }
 
 #[async_trait]
@@ -9,7 +9,7 @@ use tracing::instrument;
 
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, ContractLocator, Decode, HyperlaneMessage, Indexed,
-    Indexer, LogMeta, SequenceAwareIndexer, H512,
+    Indexer, LogMeta, LogSubscription, SequenceAwareIndexer, H512,
 };
 
 use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
@@ -141,6 +141,19 @@ impl Indexer<HyperlaneMessage> for CosmosMailboxDispatchIndexer {
         .await
         .map(|v| v.into_iter().map(|(m, l)| (m.into(), l)).collect())
     }
+
+    async fn subscribe_logs(
+        &self,
+    ) -> ChainResult<Option<Box<dyn LogSubscription<HyperlaneMessage>>>> {
+        let subscription = self
+            .provider
+            .subscribe_logs(
+                Self::hyperlane_message_parser,
+                "HyperlaneMessageSubscription",
+            )
+            .await?;
+        Ok(subscription.map(|s| Box::new(s) as Box<dyn LogSubscription<HyperlaneMessage>>))
+    }
 }
 
 #[async_trait]
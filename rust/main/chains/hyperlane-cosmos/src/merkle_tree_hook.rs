@@ -10,7 +10,7 @@ use hyperlane_core::accumulator::incremental::IncrementalMerkle;
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, Checkpoint, ContractLocator, HyperlaneChain,
     HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer, LogMeta,
-    MerkleTreeHook, MerkleTreeInsertion, SequenceAwareIndexer, H256, H512,
+    LogSubscription, MerkleTreeHook, MerkleTreeInsertion, SequenceAwareIndexer, H256, H512,
 };
 
 use crate::grpc::WasmProvider;
@@ -312,6 +312,19 @@ impl Indexer<MerkleTreeInsertion> for CosmosMerkleTreeHookIndexer {
         .await
         .map(|v| v.into_iter().map(|(m, l)| (m.into(), l)).collect())
     }
+
+    async fn subscribe_logs(
+        &self,
+    ) -> ChainResult<Option<Box<dyn LogSubscription<MerkleTreeInsertion>>>> {
+        let subscription = self
+            .provider
+            .subscribe_logs(
+                Self::merkle_tree_insertion_parser,
+                "MerkleTreeInsertionSubscription",
+            )
+            .await?;
+        Ok(subscription.map(|s| Box::new(s) as Box<dyn LogSubscription<MerkleTreeInsertion>>))
+    }
 }
 
 #[async_trait]
@@ -16,7 +16,7 @@ use tracing::{error, warn};
 use crypto::decompress_public_key;
 use hyperlane_core::{
     BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, ContractLocator, HyperlaneChain,
-    HyperlaneDomain, HyperlaneProvider, TxnInfo, TxnReceiptInfo, H256, U256,
+    HyperlaneDomain, HyperlaneProvider, TxnInfo, TxnReceiptInfo, H256, H512, U256,
 };
 
 use crate::address::CosmosAddress;
@@ -317,7 +317,29 @@ impl HyperlaneProvider for CosmosProvider {
         Ok(block_info)
     }
 
-    async fn get_txn_by_hash(&self, hash: &H256) -> ChainResult<TxnInfo> {
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+        let tendermint_height = u32::try_from(height).map_err(|_| {
+            ChainCommunicationError::from_other_str(&format!(
+                "block height {height} exceeds u32::MAX"
+            ))
+        })?;
+        let response = self.rpc_client.get_block(tendermint_height).await?;
+
+        let time: OffsetDateTime = response.block.header.time.into();
+
+        let block_info = BlockInfo {
+            hash: H256::from_slice(response.block_id.hash.as_bytes()),
+            timestamp: time.unix_timestamp() as u64,
+            number: response.block.header.height.value(),
+        };
+
+        Ok(block_info)
+    }
+
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+        let hash: H256 = H256::try_from(*hash).map_err(|_| {
+            ChainCommunicationError::from_other_str("Cosmos transaction hashes are 256 bits")
+        })?;
         let tendermint_hash = Hash::from_bytes(Algorithm::Sha256, hash.as_bytes())
             .expect("transaction hash should be of correct size");
 
@@ -325,7 +347,7 @@ impl HyperlaneProvider for CosmosProvider {
 
         let received_hash = H256::from_slice(response.hash.as_bytes());
 
-        if &received_hash != hash {
+        if received_hash != hash {
             return Err(ChainCommunicationError::from_other_str(&format!(
                 "received incorrect transaction, expected hash: {:?}, received hash: {:?}",
                 hash, received_hash,
@@ -334,11 +356,11 @@ impl HyperlaneProvider for CosmosProvider {
 
         let tx = Tx::from_bytes(&response.tx)?;
 
-        let contract = Self::contract(&tx, hash)?;
+        let contract = Self::contract(&tx, &hash)?;
         let (sender, nonce) = self.sender_and_nonce(&tx)?;
 
         // TODO support multiple denominations for amount
-        self.report_unsupported_denominations(&tx, hash);
+        self.report_unsupported_denominations(&tx, &hash);
 
         let gas_limit = U256::from(tx.auth_info.fee.gas_limit);
         let fee = tx
@@ -358,7 +380,7 @@ impl HyperlaneProvider for CosmosProvider {
         };
 
         let tx_info = TxnInfo {
-            hash: hash.to_owned(),
+            hash: hash.into(),
             gas_limit: U256::from(response.tx_result.gas_wanted),
             max_priority_fee_per_gas: None,
             max_fee_per_gas: None,
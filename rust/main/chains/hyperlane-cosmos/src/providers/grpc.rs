@@ -107,11 +107,25 @@ pub trait WasmProvider: Send + Sync {
         gas_limit: Option<U256>,
     ) -> ChainResult<TxResponse>;
 
+    /// Send a single wasm tx that executes the stored contract once per payload.
+    async fn wasm_send_batch<T: Serialize + Sync + Send + Clone + Debug>(
+        &self,
+        payloads: Vec<T>,
+        gas_limit: Option<U256>,
+    ) -> ChainResult<TxResponse>;
+
     /// Estimate gas for a wasm tx.
     async fn wasm_estimate_gas<T: Serialize + Sync + Send + Clone + Debug>(
         &self,
         payload: T,
     ) -> ChainResult<u64>;
+
+    /// Estimate gas for a wasm tx that executes the stored contract once per payload.
+    /// Fails if executing any of the payloads would fail.
+    async fn wasm_estimate_gas_batch<T: Serialize + Sync + Send + Clone + Debug>(
+        &self,
+        payloads: Vec<T>,
+    ) -> ChainResult<u64>;
 }
 
 #[derive(Debug, Clone)]
@@ -309,6 +323,25 @@ impl WasmGrpcProvider {
         Ok(gas_estimate)
     }
 
+    /// Builds a message executing the stored contract for each of `payloads`.
+    fn execute_contract_msgs<T: Serialize>(&self, payloads: &[T]) -> ChainResult<Vec<cosmrs::Any>> {
+        let signer = self.get_signer()?;
+        let contract_address = self.get_contract_address();
+        payloads
+            .iter()
+            .map(|payload| {
+                MsgExecuteContract {
+                    sender: signer.address.clone(),
+                    contract: contract_address.address(),
+                    msg: serde_json::to_string(payload)?.as_bytes().to_vec(),
+                    funds: vec![],
+                }
+                .to_any()
+                .map_err(ChainCommunicationError::from_other)
+            })
+            .collect()
+    }
+
     /// Fetches balance for a given `address` and `denom`
     pub async fn get_balance(&self, address: String, denom: String) -> ChainResult<U256> {
         let response = self
@@ -544,21 +577,24 @@ impl WasmProvider for WasmGrpcProvider {
         Ok(response)
     }
 
-    #[instrument(skip(self))]
     async fn wasm_send<T>(&self, payload: T, gas_limit: Option<U256>) -> ChainResult<TxResponse>
+    where
+        T: Serialize + Send + Sync + Clone + Debug,
+    {
+        self.wasm_send_batch(vec![payload], gas_limit).await
+    }
+
+    #[instrument(skip(self))]
+    async fn wasm_send_batch<T>(
+        &self,
+        payloads: Vec<T>,
+        gas_limit: Option<U256>,
+    ) -> ChainResult<TxResponse>
     where
         T: Serialize + Send + Sync + Clone + Debug,
     {
         let signer = self.get_signer()?;
-        let contract_address = self.get_contract_address();
-        let msgs = vec![MsgExecuteContract {
-            sender: signer.address.clone(),
-            contract: contract_address.address(),
-            msg: serde_json::to_string(&payload)?.as_bytes().to_vec(),
-            funds: vec![],
-        }
-        .to_any()
-        .map_err(ChainCommunicationError::from_other)?];
+        let msgs = self.execute_contract_msgs(&payloads)?;
         let gas_limit: Option<u64> = gas_limit.and_then(|limit| match limit.try_into() {
             Ok(limit) => Some(limit),
             Err(err) => {
@@ -607,32 +643,25 @@ impl WasmProvider for WasmGrpcProvider {
                 Box::pin(future)
             })
             .await?;
-        debug!(tx_result=?tx_res, domain=?self.domain, ?payload, "Wasm transaction sent");
+        debug!(tx_result=?tx_res, domain=?self.domain, ?payloads, "Wasm transaction sent");
         Ok(tx_res)
     }
 
     async fn wasm_estimate_gas<T>(&self, payload: T) -> ChainResult<u64>
     where
-        T: Serialize + Send + Sync,
+        T: Serialize + Send + Sync + Clone + Debug,
+    {
+        self.wasm_estimate_gas_batch(vec![payload]).await
+    }
+
+    async fn wasm_estimate_gas_batch<T>(&self, payloads: Vec<T>) -> ChainResult<u64>
+    where
+        T: Serialize + Send + Sync + Clone + Debug,
     {
         // Estimating gas requires a signer, which we can reasonably expect to have
         // since we need one to send a tx with the estimated gas anyways.
-        let signer = self.get_signer()?;
-        let contract_address = self.get_contract_address();
-        let msg = MsgExecuteContract {
-            sender: signer.address.clone(),
-            contract: contract_address.address(),
-            msg: serde_json::to_string(&payload)?.as_bytes().to_vec(),
-            funds: vec![],
-        };
-
-        let response = self
-            .estimate_gas(vec![msg
-                .to_any()
-                .map_err(ChainCommunicationError::from_other)?])
-            .await?;
-
-        Ok(response)
+        let msgs = self.execute_contract_msgs(&payloads)?;
+        self.estimate_gas(msgs).await
     }
 }
 
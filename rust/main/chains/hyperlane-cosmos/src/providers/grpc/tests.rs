@@ -55,7 +55,7 @@ fn provider(address: &str) -> WasmGrpcProvider {
         domain.clone(),
         ConnectionConf::new(
             vec![Url::parse("http://grpc-kralum.neutron-1.neutron.org:80").unwrap()],
-            "https://rpc-kralum.neutron-1.neutron.org".to_owned(),
+            vec![Url::parse("https://rpc-kralum.neutron-1.neutron.org").unwrap()],
             "neutron-1".to_owned(),
             "neutron".to_owned(),
             "untrn".to_owned(),
@@ -69,6 +69,7 @@ fn provider(address: &str) -> WasmGrpcProvider {
                 decimals: 6,
                 denom: "untrn".to_owned(),
             },
+            false,
         ),
         CosmosAmount {
             denom: "untrn".to_owned(),
@@ -1,5 +1,7 @@
 pub use client::CosmosRpcClient;
 pub use provider::{CosmosWasmRpcProvider, ParsedEvent, WasmRpcProvider};
+pub use subscription::CosmosLogSubscription;
 
 mod client;
 mod provider;
+mod subscription;
@@ -1,24 +1,29 @@
+use async_trait::async_trait;
 use cosmrs::proto::tendermint::blocksync::BlockResponse;
 use tendermint::Hash;
 use tendermint_rpc::client::CompatMode;
 use tendermint_rpc::endpoint::{block, block_by_hash, block_results, tx};
 use tendermint_rpc::{Client, HttpClient};
+use url::Url;
 
-use hyperlane_core::ChainResult;
+use hyperlane_core::rpc_clients::{BlockNumberGetter, FallbackProvider};
+use hyperlane_core::{ChainCommunicationError, ChainResult};
 
+use crate::rpc_clients::CosmosFallbackProvider;
 use crate::{ConnectionConf, HyperlaneCosmosError};
 
-/// Thin wrapper around Cosmos RPC client with error mapping
 #[derive(Clone, Debug)]
-pub struct CosmosRpcClient {
+struct CosmosHttpClient {
     client: HttpClient,
+    /// The url that this client is connected to.
+    /// Not explicitly used, but useful for debugging.
+    _url: Url,
 }
 
-impl CosmosRpcClient {
-    /// Create new `CosmosRpcClient`
-    pub fn new(conf: &ConnectionConf) -> ChainResult<Self> {
+impl CosmosHttpClient {
+    fn new(url: Url) -> ChainResult<Self> {
         let client = HttpClient::builder(
-            conf.get_rpc_url()
+            url.as_str()
                 .parse()
                 .map_err(Into::<HyperlaneCosmosError>::into)?,
         )
@@ -27,51 +32,258 @@ impl CosmosRpcClient {
         .build()
         .map_err(Into::<HyperlaneCosmosError>::into)?;
 
-        Ok(Self { client })
+        Ok(Self { client, _url: url })
+    }
+}
+
+#[async_trait]
+impl BlockNumberGetter for CosmosHttpClient {
+    async fn get_block_number(&self) -> Result<u64, ChainCommunicationError> {
+        let block = self
+            .client
+            .latest_block()
+            .await
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        Ok(block.block.header.height.value())
+    }
+}
+
+/// Thin wrapper around Cosmos RPC client with error mapping, which falls back
+/// to the next configured RPC url when a request fails
+#[derive(Clone, Debug)]
+pub struct CosmosRpcClient {
+    provider: CosmosFallbackProvider<CosmosHttpClient>,
+}
+
+impl CosmosRpcClient {
+    /// Create new `CosmosRpcClient`
+    pub fn new(conf: &ConnectionConf) -> ChainResult<Self> {
+        Self::from_urls(conf.get_rpc_urls())
+    }
+
+    /// Create new `CosmosRpcClient` over `urls`, in order of priority
+    fn from_urls(urls: Vec<Url>) -> ChainResult<Self> {
+        let clients = urls
+            .into_iter()
+            .map(CosmosHttpClient::new)
+            .collect::<ChainResult<Vec<_>>>()?;
+        let fallback_provider = FallbackProvider::builder().add_providers(clients).build();
+        let provider = CosmosFallbackProvider::new(fallback_provider);
+
+        Ok(Self { provider })
     }
 
     /// Request block by block height
     pub async fn get_block(&self, height: u32) -> ChainResult<block::Response> {
-        Ok(self
-            .client
-            .block(height)
+        self.provider
+            .call(move |client| {
+                let future = async move {
+                    Ok(client
+                        .client
+                        .block(height)
+                        .await
+                        .map_err(Into::<HyperlaneCosmosError>::into)?)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
     }
 
     /// Request block results by block height
     pub async fn get_block_results(&self, height: u32) -> ChainResult<block_results::Response> {
-        Ok(self
-            .client
-            .block_results(height)
+        self.provider
+            .call(move |client| {
+                let future = async move {
+                    Ok(client
+                        .client
+                        .block_results(height)
+                        .await
+                        .map_err(Into::<HyperlaneCosmosError>::into)?)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
     }
 
     /// Request block by block hash
     pub async fn get_block_by_hash(&self, hash: Hash) -> ChainResult<block_by_hash::Response> {
-        Ok(self
-            .client
-            .block_by_hash(hash)
+        self.provider
+            .call(move |client| {
+                let future = async move {
+                    Ok(client
+                        .client
+                        .block_by_hash(hash)
+                        .await
+                        .map_err(Into::<HyperlaneCosmosError>::into)?)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
     }
 
     /// Request the latest block
     pub async fn get_latest_block(&self) -> ChainResult<block::Response> {
-        Ok(self
-            .client
-            .latest_block()
+        self.provider
+            .call(move |client| {
+                let future = async move {
+                    Ok(client
+                        .client
+                        .latest_block()
+                        .await
+                        .map_err(Into::<HyperlaneCosmosError>::into)?)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
     }
 
     /// Request transaction by transaction hash
     pub async fn get_tx_by_hash(&self, hash: Hash) -> ChainResult<tx::Response> {
-        Ok(self
-            .client
-            .tx(hash, false)
+        self.provider
+            .call(move |client| {
+                let future = async move {
+                    Ok(client
+                        .client
+                        .tx(hash, false)
+                        .await
+                        .map_err(Into::<HyperlaneCosmosError>::into)?)
+                };
+                Box::pin(future)
+            })
             .await
-            .map_err(Into::<HyperlaneCosmosError>::into)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    /// Serves JSON-RPC requests on a local port, answering each with `result`
+    /// or with an error if there is none. Returns the url and the number of
+    /// requests served so far.
+    fn serve(result: Option<Value>) -> (Url, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let served = requests.clone();
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let id = read_request_id(&mut stream);
+                served.fetch_add(1, Ordering::SeqCst);
+                let body = match &result {
+                    Some(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+                    None => json!({
+                        "jsonrpc": "2.0",
+                        "id": id,
+                        "error": { "code": -32603, "message": "Internal error", "data": "" },
+                    }),
+                }
+                .to_string();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        (url, requests)
+    }
+
+    /// Reads an HTTP request with a JSON-RPC body, returning the request id.
+    fn read_request_id(stream: &mut impl Read) -> Value {
+        let mut request = vec![];
+        let mut buf = [0; 1024];
+        loop {
+            let Ok(read) = stream.read(&mut buf) else {
+                return Value::Null;
+            };
+            if read == 0 {
+                return Value::Null;
+            }
+            request.extend_from_slice(&buf[..read]);
+            let text = String::from_utf8_lossy(&request);
+            let Some(header_end) = text.find("\r\n\r\n") else {
+                continue;
+            };
+            let content_length = text[..header_end]
+                .lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length")
+                        .then(|| value.trim().parse::<usize>().ok())
+                        .flatten()
+                })
+                .unwrap_or(0);
+            let body = &request[header_end + 4..];
+            if body.len() >= content_length {
+                return serde_json::from_slice::<Value>(body)
+                    .ok()
+                    .and_then(|body| body.get("id").cloned())
+                    .unwrap_or(Value::Null);
+            }
+        }
+    }
+
+    /// A url that refuses connections
+    fn unreachable_url() -> Url {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        drop(listener);
+        url
+    }
+
+    fn block_results(height: u64) -> Value {
+        json!({
+            "height": height.to_string(),
+            "txs_results": null,
+            "begin_block_events": null,
+            "end_block_events": null,
+            "validator_updates": [],
+            "consensus_param_updates": null,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_first_url_is_used() {
+        let (first_url, first_requests) = serve(Some(block_results(10)));
+        let (second_url, second_requests) = serve(Some(block_results(10)));
+        let client = CosmosRpcClient::from_urls(vec![first_url, second_url]).unwrap();
+
+        let response = client.get_block_results(10).await.unwrap();
+
+        assert_eq!(response.height.value(), 10);
+        assert_eq!(first_requests.load(Ordering::SeqCst), 1);
+        assert_eq!(second_requests.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn test_falls_back_to_next_url() {
+        let (failing_url, failing_requests) = serve(None);
+        let (url, requests) = serve(Some(block_results(10)));
+        let client = CosmosRpcClient::from_urls(vec![unreachable_url(), failing_url, url]).unwrap();
+
+        let response = client.get_block_results(10).await.unwrap();
+
+        assert_eq!(response.height.value(), 10);
+        assert_eq!(failing_requests.load(Ordering::SeqCst), 1);
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fails_if_every_url_fails() {
+        let (failing_url, failing_requests) = serve(None);
+        let client = CosmosRpcClient::from_urls(vec![unreachable_url(), failing_url]).unwrap();
+
+        assert!(client.get_block_results(10).await.is_err());
+        // Every url is retried a few times before giving up
+        assert!(failing_requests.load(Ordering::SeqCst) > 1);
     }
 }
@@ -6,22 +6,26 @@ use cosmrs::rpc::client::Client;
 use futures::StreamExt;
 use sha256::digest;
 use tendermint::abci::{Event, EventAttribute};
+use tendermint::block::Height;
 use tendermint::hash::Algorithm;
 use tendermint::Hash;
 use tendermint_rpc::client::CompatMode;
 use tendermint_rpc::endpoint::block::Response as BlockResponse;
 use tendermint_rpc::endpoint::block_results::Response as BlockResultsResponse;
 use tendermint_rpc::endpoint::tx;
+use tendermint_rpc::query::{EventType, Query};
 use tendermint_rpc::HttpClient;
 use time::OffsetDateTime;
 use tracing::{debug, instrument, trace};
+use url::Url;
 
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, ContractLocator, HyperlaneDomain, LogMeta, H256, U256,
 };
 
 use crate::address::CosmosAddress;
-use crate::rpc::CosmosRpcClient;
+use crate::rpc::{CosmosLogSubscription, CosmosRpcClient};
+use crate::utils::CONTRACT_ADDRESS_ATTRIBUTE_KEY;
 use crate::{ConnectionConf, CosmosProvider, HyperlaneCosmosError};
 
 #[async_trait]
@@ -81,6 +85,7 @@ pub struct CosmosWasmRpcProvider {
     target_event_kind: String,
     reorg_period: u32,
     rpc_client: CosmosRpcClient,
+    websocket_urls: Vec<Url>,
 }
 
 impl CosmosWasmRpcProvider {
@@ -94,6 +99,7 @@ impl CosmosWasmRpcProvider {
         reorg_period: u32,
     ) -> ChainResult<Self> {
         let rpc_client = CosmosRpcClient::new(&conf)?;
+        let websocket_urls = conf.get_websocket_urls();
 
         Ok(Self {
             domain: locator.domain.clone(),
@@ -105,11 +111,45 @@ impl CosmosWasmRpcProvider {
             target_event_kind: format!("{}-{}", Self::WASM_TYPE, event_type),
             reorg_period,
             rpc_client,
+            websocket_urls,
         })
     }
 }
 
 impl CosmosWasmRpcProvider {
+    /// Subscribe to the target events of the indexed contract over the first
+    /// Tendermint websocket that can be connected to. Returns `None` if event
+    /// subscriptions aren't enabled.
+    pub async fn subscribe_logs<T>(
+        &self,
+        parser: for<'a> fn(&'a Vec<EventAttribute>) -> ChainResult<ParsedEvent<T>>,
+        cursor_label: &'static str,
+    ) -> ChainResult<Option<CosmosLogSubscription<T>>>
+    where
+        T: Send + Sync + PartialEq + Debug + 'static,
+    {
+        if self.websocket_urls.is_empty() {
+            return Ok(None);
+        }
+        let query = Query::from(EventType::Tx).and_eq(
+            format!(
+                "{}.{}",
+                self.target_event_kind, CONTRACT_ADDRESS_ATTRIBUTE_KEY
+            ),
+            self.contract_address.address(),
+        );
+        debug!(%query, cursor_label, domain=?self.domain, "Subscribing to logs");
+        CosmosLogSubscription::subscribe(&self.websocket_urls, query, self.clone(), parser)
+            .await
+            .map(Some)
+    }
+
+    /// The hash of the block at the given height.
+    pub(crate) async fn get_block_hash(&self, height: u32) -> ChainResult<H256> {
+        let block = self.rpc_client.get_block(height).await?;
+        Ok(H256::from_slice(block.block_id.hash.as_bytes()))
+    }
+
     // Iterate through all txs, filter out failed txs, find target events
     // in successful txs, and parse them.
     fn handle_txs<T>(
@@ -167,8 +207,6 @@ impl CosmosWasmRpcProvider {
             .collect()
     }
 
-    // Iter through all events in the tx, looking for any target events
-    // made by the contract we are indexing.
     fn handle_tx<T>(
         &self,
         tx: tx::Response,
@@ -178,11 +216,30 @@ impl CosmosWasmRpcProvider {
     where
         T: PartialEq + 'static,
     {
-        let tx_events = tx.tx_result.events;
-        let tx_hash = tx.hash;
-        let tx_index = tx.index;
-        let block_height = tx.height;
+        self.handle_events(
+            tx.tx_result.events,
+            tx.hash,
+            tx.index,
+            tx.height,
+            block_hash,
+            parser,
+        )
+    }
 
+    // Iter through all events in the tx, looking for any target events
+    // made by the contract we are indexing.
+    pub(crate) fn handle_events<T>(
+        &self,
+        tx_events: Vec<Event>,
+        tx_hash: Hash,
+        tx_index: u32,
+        block_height: Height,
+        block_hash: H256,
+        parser: for<'a> fn(&'a Vec<EventAttribute>) -> ChainResult<ParsedEvent<T>>,
+    ) -> impl Iterator<Item = (T, LogMeta)> + '_
+    where
+        T: PartialEq + 'static,
+    {
         tx_events.into_iter().enumerate().filter_map(move |(log_idx, event)| {
             if event.kind.as_str() != self.target_event_kind {
                 return None;
@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use sha256::digest;
+use tendermint::abci::EventAttribute;
+use tendermint::block::Height;
+use tendermint::hash::Algorithm;
+use tendermint::Hash;
+use tendermint_rpc::client::CompatMode;
+use tendermint_rpc::event::{Event, EventData};
+use tendermint_rpc::query::Query;
+use tendermint_rpc::{Subscription, SubscriptionClient, WebSocketClient, WebSocketClientUrl};
+use tracing::{debug, warn};
+use url::Url;
+
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, Indexed, LogMeta, LogSubscription, H256,
+};
+
+use crate::rpc::{CosmosWasmRpcProvider, ParsedEvent};
+use crate::HyperlaneCosmosError;
+
+/// Target events of a contract, received from a subscription to the
+/// `tm.event='Tx'` events of the Tendermint websocket. The subscription ends
+/// when the websocket connection drops.
+pub struct CosmosLogSubscription<T: PartialEq> {
+    /// Kept so that the websocket connection isn't closed
+    _client: WebSocketClient,
+    subscription: Subscription,
+    provider: CosmosWasmRpcProvider,
+    parser: for<'a> fn(&'a Vec<EventAttribute>) -> ChainResult<ParsedEvent<T>>,
+    /// Parsed logs of received txs that weren't returned yet
+    pending: VecDeque<(T, LogMeta)>,
+    /// The height and hash of the block the last received tx was included in.
+    /// The txs of a block are received one after the other, so its hash is
+    /// only fetched once.
+    last_block: Option<(u32, H256)>,
+}
+
+impl<T> CosmosLogSubscription<T>
+where
+    T: Send + Sync + PartialEq + Debug + 'static,
+{
+    /// Subscribe to the txs matching a query over the first of the websocket
+    /// urls that can be connected to.
+    pub(crate) async fn subscribe(
+        urls: &[Url],
+        query: Query,
+        provider: CosmosWasmRpcProvider,
+        parser: for<'a> fn(&'a Vec<EventAttribute>) -> ChainResult<ParsedEvent<T>>,
+    ) -> ChainResult<Self> {
+        let client = connect(urls).await?;
+        let subscription = client
+            .subscribe(query)
+            .await
+            .map_err(Into::<HyperlaneCosmosError>::into)?;
+        Ok(Self {
+            _client: client,
+            subscription,
+            provider,
+            parser,
+            pending: VecDeque::new(),
+            last_block: None,
+        })
+    }
+
+    /// Parses the target events of a received tx, fetching the hash of the
+    /// block it was included in.
+    async fn handle_event(&mut self, event: Event) -> ChainResult<Vec<(T, LogMeta)>> {
+        let EventData::Tx { tx_result } = event.data else {
+            return Ok(vec![]);
+        };
+        let height =
+            u32::try_from(tx_result.height).map_err(ChainCommunicationError::from_other)?;
+        let tx_hash = Hash::from_bytes(
+            Algorithm::Sha256,
+            &hex::decode(digest(tx_result.tx.as_slice()))?,
+        )
+        .map_err(Into::<HyperlaneCosmosError>::into)?;
+        let block_hash = self.get_block_hash(height).await?;
+
+        Ok(self
+            .provider
+            .handle_events(
+                tx_result.result.events,
+                tx_hash,
+                tx_result.index.unwrap_or_default(),
+                Height::from(height),
+                block_hash,
+                self.parser,
+            )
+            .collect())
+    }
+
+    async fn get_block_hash(&mut self, height: u32) -> ChainResult<H256> {
+        if let Some((last_height, hash)) = self.last_block {
+            if last_height == height {
+                return Ok(hash);
+            }
+        }
+        let hash = self.provider.get_block_hash(height).await?;
+        self.last_block = Some((height, hash));
+        Ok(hash)
+    }
+}
+
+#[async_trait]
+impl<T> LogSubscription<T> for CosmosLogSubscription<T>
+where
+    T: Into<Indexed<T>> + Send + Sync + PartialEq + Debug + 'static,
+{
+    async fn next_log(&mut self) -> Option<(Indexed<T>, LogMeta)> {
+        loop {
+            if let Some((log, meta)) = self.pending.pop_front() {
+                return Some((log.into(), meta));
+            }
+            let event = match self.subscription.next().await? {
+                Ok(event) => event,
+                Err(err) => {
+                    warn!(?err, "Error receiving event from subscription");
+                    continue;
+                }
+            };
+            match self.handle_event(event).await {
+                Ok(logs) => self.pending.extend(logs),
+                // Range polling picks up the logs of txs that couldn't be handled
+                Err(err) => warn!(?err, "Failed to handle tx received from subscription"),
+            }
+        }
+    }
+}
+
+async fn connect(urls: &[Url]) -> ChainResult<WebSocketClient> {
+    let mut last_err = None;
+    for url in urls {
+        match connect_to(url).await {
+            Ok(client) => return Ok(client),
+            Err(err) => {
+                warn!(?err, %url, "Failed to connect to websocket");
+                last_err = Some(err);
+            }
+        }
+    }
+    Err(last_err.unwrap_or_else(|| {
+        ChainCommunicationError::from_other_str("No websocket urls to subscribe to logs")
+    }))
+}
+
+async fn connect_to(url: &Url) -> ChainResult<WebSocketClient> {
+    let url: WebSocketClientUrl = url
+        .as_str()
+        .parse()
+        .map_err(Into::<HyperlaneCosmosError>::into)?;
+    let (client, driver) = WebSocketClient::builder(url)
+        // Consider supporting different compatibility modes.
+        .compat_mode(CompatMode::latest())
+        .build()
+        .await
+        .map_err(Into::<HyperlaneCosmosError>::into)?;
+    tokio::spawn(async move {
+        if let Err(err) = driver.run().await {
+            warn!(?err, "Websocket connection closed with an error");
+        } else {
+            debug!("Websocket connection closed");
+        }
+    });
+    Ok(client)
+}
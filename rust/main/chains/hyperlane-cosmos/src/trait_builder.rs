@@ -10,8 +10,8 @@ use hyperlane_core::{config::OperationBatchConfig, ChainCommunicationError, Fixe
 pub struct ConnectionConf {
     /// The GRPC url to connect to
     grpc_urls: Vec<Url>,
-    /// The RPC url to connect to
-    rpc_url: String,
+    /// The RPC urls to connect to
+    rpc_urls: Vec<Url>,
     /// The chain ID
     chain_id: String,
     /// The human readable address prefix for the chains using bech32.
@@ -30,6 +30,9 @@ pub struct ConnectionConf {
     pub operation_batch: OperationBatchConfig,
     /// Native Token
     native_token: NativeToken,
+    /// Whether to subscribe to the events of indexed contracts over the
+    /// Tendermint websocket of the RPC urls, on top of querying block ranges.
+    subscribe_logs: bool,
 }
 
 /// Untyped cosmos amount
@@ -95,9 +98,9 @@ impl ConnectionConf {
         self.grpc_urls.clone()
     }
 
-    /// Get the RPC url
-    pub fn get_rpc_url(&self) -> String {
-        self.rpc_url.clone()
+    /// Get the RPC urls
+    pub fn get_rpc_urls(&self) -> Vec<Url> {
+        self.rpc_urls.clone()
     }
 
     /// Get the chain ID
@@ -130,11 +133,33 @@ impl ConnectionConf {
         self.contract_address_bytes
     }
 
+    /// Get the Tendermint websocket urls to subscribe to events over, in the
+    /// order of the RPC urls. Empty unless event subscriptions are enabled.
+    pub fn get_websocket_urls(&self) -> Vec<Url> {
+        if !self.subscribe_logs {
+            return vec![];
+        }
+        self.rpc_urls
+            .iter()
+            .filter_map(|url| {
+                let mut url = url.clone();
+                let scheme = match url.scheme() {
+                    "https" => "wss",
+                    _ => "ws",
+                };
+                url.set_scheme(scheme).ok()?;
+                let path = format!("{}/websocket", url.path().trim_end_matches('/'));
+                url.set_path(&path);
+                Some(url)
+            })
+            .collect()
+    }
+
     /// Create a new connection configuration
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         grpc_urls: Vec<Url>,
-        rpc_url: String,
+        rpc_urls: Vec<Url>,
         chain_id: String,
         bech32_prefix: String,
         canonical_asset: String,
@@ -142,10 +167,11 @@ impl ConnectionConf {
         contract_address_bytes: usize,
         operation_batch: OperationBatchConfig,
         native_token: NativeToken,
+        subscribe_logs: bool,
     ) -> Self {
         Self {
             grpc_urls,
-            rpc_url,
+            rpc_urls,
             chain_id,
             bech32_prefix,
             canonical_asset,
@@ -153,6 +179,62 @@ impl ConnectionConf {
             contract_address_bytes,
             operation_batch,
             native_token,
+            subscribe_logs,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conf(rpc_urls: &[&str], subscribe_logs: bool) -> ConnectionConf {
+        ConnectionConf::new(
+            vec![],
+            rpc_urls
+                .iter()
+                .map(|url| Url::parse(url).unwrap())
+                .collect(),
+            "neutron-1".to_owned(),
+            "neutron".to_owned(),
+            "untrn".to_owned(),
+            RawCosmosAmount::new("untrn".to_owned(), "0".to_owned()),
+            32,
+            OperationBatchConfig {
+                batch_contract_address: None,
+                max_batch_size: 1,
+            },
+            NativeToken::default(),
+            subscribe_logs,
+        )
+    }
+
+    #[test]
+    fn test_websocket_urls() {
+        let conf = conf(
+            &[
+                "https://rpc-kralum.neutron-1.neutron.org",
+                "http://localhost:26657/rpc/",
+            ],
+            true,
+        );
+        let urls: Vec<_> = conf
+            .get_websocket_urls()
+            .iter()
+            .map(Url::to_string)
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                "wss://rpc-kralum.neutron-1.neutron.org/websocket",
+                "ws://localhost:26657/rpc/websocket",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_websocket_urls_unless_subscribing() {
+        let conf = conf(&["https://rpc-kralum.neutron-1.neutron.org"], false);
+        assert!(conf.get_websocket_urls().is_empty());
+    }
+}
@@ -1,6 +1,8 @@
 use hyperlane_core::{config::OperationBatchConfig, U256};
 use url::Url;
 
+use crate::UnifiedLogFetcher;
+
 /// Ethereum RPC connection configuration
 #[derive(Debug, Clone)]
 pub enum RpcConnectionConf {
@@ -35,6 +37,37 @@ pub struct ConnectionConf {
     pub transaction_overrides: TransactionOverrides,
     /// Operation batching configuration
     pub operation_batch: OperationBatchConfig,
+    /// How many transactions each signer may have in flight at the same time.
+    /// Transactions after the first are sent with pipelined nonces.
+    pub max_in_flight_transactions: u32,
+    /// How to bump the fees of transactions that aren't included in time. If
+    /// unset, a transaction is only replaced once it timed out.
+    pub fee_escalation: Option<FeeEscalation>,
+    /// Fetches the logs of all indexed contracts with a single query per
+    /// block range, if unified log fetching is enabled. Shared by the indexers
+    /// built from this config and its clones.
+    pub log_fetcher: Option<UnifiedLogFetcher>,
+    /// Websocket urls to subscribe to the logs of indexed contracts over, in
+    /// order of priority. Empty unless log subscriptions are enabled, in which
+    /// case logs are pushed to the indexers as they're emitted and range
+    /// polling fills in the gaps.
+    pub subscription_urls: Vec<Url>,
+}
+
+/// Escalation of the fees of transactions that aren't included in time. Such
+/// transactions are replaced by the same transaction with the same nonce and
+/// bumped fees.
+#[derive(Debug, Clone)]
+pub struct FeeEscalation {
+    /// How many blocks to wait for a transaction to be included before
+    /// replacing it
+    pub blocks_before_bump: u32,
+    /// How much to bump the fees by each time, in percent. Nodes reject
+    /// replacements that bump fees by less than 10%.
+    pub bump_percent: u32,
+    /// Fees are never bumped above this max fee per gas (or gas price for
+    /// non-EIP-1559 transactions), in wei.
+    pub max_fee_per_gas: U256,
 }
 
 /// Ethereum transaction overrides.
@@ -10,17 +10,17 @@ use ethers::prelude::Middleware;
 use hyperlane_core::rpc_clients::call_and_retry_indefinitely;
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, ContractLocator, HyperlaneAbi, HyperlaneChain,
-    HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer,
+    HyperlaneContract, HyperlaneDomain, HyperlaneLogStore, HyperlaneProvider, Indexed, Indexer,
     InterchainGasPaymaster, InterchainGasPayment, LogMeta, SequenceAwareIndexer, H160, H256, H512,
 };
 use tracing::instrument;
 
-use super::utils::fetch_raw_logs_and_meta;
+use super::utils::{fetch_raw_logs_and_meta, get_block_hash};
 use crate::interfaces::i_interchain_gas_paymaster::{
     GasPaymentFilter, IInterchainGasPaymaster as EthereumInterchainGasPaymasterInternal,
     IINTERCHAINGASPAYMASTER_ABI,
 };
-use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider};
+use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider, UnifiedLogFetcher};
 
 impl<M> Display for EthereumInterchainGasPaymasterInternal<M>
 where
@@ -44,13 +44,18 @@ impl BuildableWithProvider for InterchainGasPaymasterIndexerBuilder {
     async fn build_with_provider<M: Middleware + 'static>(
         &self,
         provider: M,
-        _conn: &ConnectionConf,
+        conn: &ConnectionConf,
         locator: &ContractLocator,
     ) -> Self::Output {
+        let log_fetcher = conn
+            .log_fetcher
+            .as_ref()
+            .map(|log_fetcher| log_fetcher.register::<GasPaymentFilter>(locator.address));
         Box::new(EthereumInterchainGasPaymasterIndexer::new(
             Arc::new(provider),
             locator,
             self.reorg_period,
+            log_fetcher,
         ))
     }
 }
@@ -64,6 +69,9 @@ where
     contract: Arc<EthereumInterchainGasPaymasterInternal<M>>,
     provider: Arc<M>,
     reorg_period: u32,
+    /// Fetches the logs along with those of the other indexed contracts, if
+    /// unified log fetching is enabled
+    log_fetcher: Option<UnifiedLogFetcher>,
 }
 
 impl<M> EthereumInterchainGasPaymasterIndexer<M>
@@ -71,7 +79,12 @@ where
     M: Middleware + 'static,
 {
     /// Create new EthereumInterchainGasPaymasterIndexer
-    pub fn new(provider: Arc<M>, locator: &ContractLocator, reorg_period: u32) -> Self {
+    pub fn new(
+        provider: Arc<M>,
+        locator: &ContractLocator,
+        reorg_period: u32,
+        log_fetcher: Option<UnifiedLogFetcher>,
+    ) -> Self {
         Self {
             contract: Arc::new(EthereumInterchainGasPaymasterInternal::new(
                 locator.address,
@@ -79,10 +92,20 @@ where
             )),
             provider,
             reorg_period,
+            log_fetcher,
         }
     }
 }
 
+fn indexed_gas_payment(log: GasPaymentFilter) -> Indexed<InterchainGasPayment> {
+    Indexed::new(InterchainGasPayment {
+        message_id: H256::from(log.message_id),
+        destination: log.destination_domain,
+        payment: log.payment.into(),
+        gas_amount: log.gas_amount.into(),
+    })
+}
+
 #[async_trait]
 impl<M> Indexer<InterchainGasPayment> for EthereumInterchainGasPaymasterIndexer<M>
 where
@@ -95,27 +118,27 @@ where
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<InterchainGasPayment>, LogMeta)>> {
-        let events = self
-            .contract
-            .gas_payment_filter()
-            .from_block(*range.start())
-            .to_block(*range.end())
-            .query_with_meta()
-            .await?;
+        let events: Vec<(GasPaymentFilter, LogMeta)> = match &self.log_fetcher {
+            Some(log_fetcher) => {
+                log_fetcher
+                    .fetch_events(self.provider.as_ref(), self.contract.address(), range)
+                    .await?
+            }
+            None => self
+                .contract
+                .gas_payment_filter()
+                .from_block(*range.start())
+                .to_block(*range.end())
+                .query_with_meta()
+                .await?
+                .into_iter()
+                .map(|(log, log_meta)| (log, log_meta.into()))
+                .collect(),
+        };
 
         Ok(events
             .into_iter()
-            .map(|(log, log_meta)| {
-                (
-                    Indexed::new(InterchainGasPayment {
-                        message_id: H256::from(log.message_id),
-                        destination: log.destination_domain,
-                        payment: log.payment.into(),
-                        gas_amount: log.gas_amount.into(),
-                    }),
-                    log_meta.into(),
-                )
-            })
+            .map(|(log, log_meta)| (indexed_gas_payment(log), log_meta))
             .collect())
     }
 
@@ -131,6 +154,27 @@ where
             .saturating_sub(self.reorg_period))
     }
 
+    async fn get_block_hash(&self, block_number: u32) -> ChainResult<Option<H256>> {
+        get_block_hash(&self.provider, block_number).await
+    }
+
+    async fn handle_reorg(&self, first_reorged_block: u32) -> ChainResult<()> {
+        match &self.log_fetcher {
+            Some(log_fetcher) => log_fetcher.handle_reorg(first_reorged_block).await,
+            None => Ok(()),
+        }
+    }
+
+    fn fan_out_logs_to(&self, store: Arc<dyn HyperlaneLogStore<InterchainGasPayment>>) {
+        if let Some(log_fetcher) = &self.log_fetcher {
+            log_fetcher.fan_out_to::<GasPaymentFilter, _>(
+                self.contract.address(),
+                store,
+                indexed_gas_payment,
+            );
+        }
+    }
+
     async fn fetch_logs_by_tx_hash(
         &self,
         tx_hash: H512,
@@ -146,17 +190,7 @@ where
 
         let logs = raw_logs_and_meta
             .into_iter()
-            .map(|(log, log_meta)| {
-                (
-                    Indexed::new(InterchainGasPayment {
-                        message_id: H256::from(log.message_id),
-                        destination: log.destination_domain,
-                        payment: log.payment.into(),
-                        gas_amount: log.gas_amount.into(),
-                    }),
-                    log_meta,
-                )
-            })
+            .map(|(log, log_meta)| (indexed_gas_payment(log), log_meta))
             .collect();
         Ok(logs)
     }
@@ -0,0 +1,551 @@
+//! Fetches the logs of all indexed contracts of a chain with a single
+//! `eth_getLogs` call per block range, so that the indexers of each contract
+//! don't query the same ranges separately.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fmt::{Debug, Formatter};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use ethers::prelude::Middleware;
+use ethers::types::{Filter, Log, ValueOrArray, H160 as EthersH160, H256 as EthersH256};
+use ethers_contract::EthEvent;
+use hyperlane_core::{ChainCommunicationError, ChainResult, HyperlaneLogStore, Indexed, LogMeta};
+use tracing::{debug, instrument, warn};
+
+use super::utils::decode_logs;
+
+/// How long fetched logs are served from the cache. Indexers only query
+/// finalized blocks, so this mostly bounds how stale the logs of a reorg deeper
+/// than the reorg period can be.
+const CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// The max number of fetched block ranges to cache.
+const MAX_CACHED_RANGES: usize = 64;
+
+/// A contract address and the signature of one of its events.
+type Event = (EthersH160, EthersH256);
+
+/// Fetches the logs of the mailbox, IGP and merkle tree hook of a chain at once.
+///
+/// Each indexer registers the address and event signature it indexes, and
+/// fetches its logs through the shared fetcher. The logs of a block range are
+/// fetched for all registered events with one query, and stored right away in
+/// the log stores that were fanned out to, so that their indexers don't have to
+/// fetch them again. They're also cached, so that the other indexers querying
+/// the same range are served from the cache.
+#[derive(Clone, Default)]
+pub struct UnifiedLogFetcher {
+    inner: Arc<UnifiedLogFetcherInner>,
+}
+
+#[derive(Default)]
+struct UnifiedLogFetcherInner {
+    /// The registered contract addresses and event signatures
+    filters: Mutex<BTreeSet<Event>>,
+    /// Incremented whenever an event is registered, since cached ranges don't
+    /// include the logs of events registered after they were fetched
+    filters_generation: AtomicU64,
+    /// The stores that fetched logs are fanned out to
+    sinks: Mutex<Vec<Arc<dyn LogSink>>>,
+    /// Only locked in between fetches, so that cached ranges are served while
+    /// another range is being fetched
+    cache: Mutex<LogCache>,
+}
+
+impl Debug for UnifiedLogFetcher {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UnifiedLogFetcher")
+            .field("filters", &self.inner.filters.lock().unwrap())
+            .finish()
+    }
+}
+
+impl UnifiedLogFetcher {
+    /// Registers an event of a contract to be fetched along with the others,
+    /// and returns the fetcher to query its logs with.
+    pub fn register<T: EthEvent>(&self, address: impl Into<EthersH160>) -> Self {
+        let added = self
+            .inner
+            .filters
+            .lock()
+            .unwrap()
+            .insert((address.into(), T::signature()));
+        if added {
+            self.inner.filters_generation.fetch_add(1, Ordering::SeqCst);
+        }
+        self.clone()
+    }
+
+    /// Stores the logs of a registered event in `store` whenever they're
+    /// fetched for another event, converted with `to_indexed`.
+    /// Fanned out logs of reorged blocks are only deleted while they're cached,
+    /// so `store` must not skip logs it already stores, as sequence-aware
+    /// stores do, for the canonical logs to be stored once they're fetched.
+    pub(crate) fn fan_out_to<E, T>(
+        &self,
+        address: EthersH160,
+        store: Arc<dyn HyperlaneLogStore<T>>,
+        to_indexed: fn(E) -> Indexed<T>,
+    ) where
+        E: EthEvent + 'static,
+        T: Send + Sync + 'static,
+    {
+        let sink = StoreSink {
+            address,
+            store,
+            to_indexed,
+        };
+        let mut sinks = self.inner.sinks.lock().unwrap();
+        sinks.retain(|registered| registered.event() != sink.event());
+        sinks.push(Arc::new(sink));
+    }
+
+    /// Fetches the logs of a contract's event in a block range, decoded along
+    /// with their metadata.
+    pub(crate) async fn fetch_events<T, M>(
+        &self,
+        provider: &M,
+        address: EthersH160,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(T, LogMeta)>>
+    where
+        T: EthEvent,
+        M: Middleware + 'static,
+    {
+        let logs = self
+            .fetch_logs(provider, (address, T::signature()), range)
+            .await?;
+        Ok(decode_logs(logs, address))
+    }
+
+    /// Drops the cached logs of reorged blocks, and deletes the ones fanned
+    /// out to the stores of other events, which their indexers didn't
+    /// necessarily query yet.
+    pub(crate) async fn handle_reorg(&self, first_reorged_block: u32) -> ChainResult<()> {
+        let reorged_logs = self
+            .inner
+            .cache
+            .lock()
+            .unwrap()
+            .remove_from(first_reorged_block);
+        debug!(
+            first_reorged_block,
+            reorged_logs = reorged_logs.len(),
+            "Dropped cached logs of reorged blocks"
+        );
+        for sink in self.sinks() {
+            sink.delete_logs(&reorged_logs).await?;
+        }
+        Ok(())
+    }
+
+    /// Fetches the logs of all registered events in a block range, only
+    /// querying the parts of the range that aren't cached. The logs of other
+    /// events than the `requested` one are fanned out to their stores.
+    #[instrument(level = "debug", err, skip(self, provider))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn fetch_logs<M>(
+        &self,
+        provider: &M,
+        requested: Event,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<Log>>
+    where
+        M: Middleware + 'static,
+    {
+        let mut fetched: Vec<(RangeInclusive<u32>, Vec<Log>, u64)> = vec![];
+        loop {
+            let generation = self.inner.filters_generation.load(Ordering::SeqCst);
+            let gaps = {
+                let mut cache = self.inner.cache.lock().unwrap();
+                cache.remove_stale(generation);
+                for (gap, logs, fetched_generation) in fetched.drain(..) {
+                    // Logs fetched before an event was registered are refetched
+                    if fetched_generation == generation {
+                        cache.insert(gap, logs, generation);
+                    }
+                }
+                let gaps = cache.gaps(&range);
+                if gaps.is_empty() {
+                    let logs = cache.logs_in(&range);
+                    cache.prune();
+                    return Ok(logs);
+                }
+                gaps
+            };
+
+            // Concurrent queries of the same uncached range may both fetch
+            // it, which is cheaper than making every query wait for the
+            // others' fetches
+            let filter = self.filter();
+            for gap in gaps {
+                debug!(?gap, "Fetching logs of all indexed contracts");
+                let logs = provider
+                    .get_logs(&filter.clone().from_block(*gap.start()).to_block(*gap.end()))
+                    .await
+                    .map_err(ChainCommunicationError::from_other)?;
+                self.fan_out(requested, &logs).await;
+                fetched.push((gap, logs, generation));
+            }
+        }
+    }
+
+    /// Stores fetched logs in the stores of all events but the `requested`
+    /// one, whose indexer stores them itself.
+    async fn fan_out(&self, requested: Event, logs: &[Log]) {
+        for sink in self.sinks() {
+            if sink.event() == requested {
+                continue;
+            }
+            if let Err(err) = sink.store_logs(logs).await {
+                // The event's indexer still stores the logs once it queries them
+                warn!(?err, event = ?sink.event(), "Error fanning out fetched logs");
+            }
+        }
+    }
+
+    fn sinks(&self) -> Vec<Arc<dyn LogSink>> {
+        self.inner.sinks.lock().unwrap().clone()
+    }
+
+    fn filter(&self) -> Filter {
+        let filters = self.inner.filters.lock().unwrap();
+        let addresses: BTreeSet<_> = filters.iter().map(|(address, _)| *address).collect();
+        let topics: BTreeSet<_> = filters.iter().map(|(_, topic)| *topic).collect();
+        Filter::new()
+            .address(ValueOrArray::Array(addresses.into_iter().collect()))
+            .topic0(ValueOrArray::Array(topics.into_iter().map(Some).collect()))
+    }
+}
+
+/// A log store that the logs of a registered event are fanned out to.
+#[async_trait]
+trait LogSink: Send + Sync {
+    /// The event whose logs are stored
+    fn event(&self) -> Event;
+
+    /// Stores the logs of the event among `logs`
+    async fn store_logs(&self, logs: &[Log]) -> ChainResult<u32>;
+
+    /// Deletes the logs of the event among `logs`
+    async fn delete_logs(&self, logs: &[Log]) -> ChainResult<u32>;
+}
+
+struct StoreSink<E, T> {
+    address: EthersH160,
+    store: Arc<dyn HyperlaneLogStore<T>>,
+    to_indexed: fn(E) -> Indexed<T>,
+}
+
+impl<E: EthEvent, T> StoreSink<E, T> {
+    fn decode(&self, logs: &[Log]) -> Vec<(Indexed<T>, LogMeta)> {
+        decode_logs::<E>(logs.to_vec(), self.address)
+            .into_iter()
+            .map(|(event, meta)| ((self.to_indexed)(event), meta))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl<E, T> LogSink for StoreSink<E, T>
+where
+    E: EthEvent + 'static,
+    T: Send + Sync + 'static,
+{
+    fn event(&self) -> Event {
+        (self.address, E::signature())
+    }
+
+    async fn store_logs(&self, logs: &[Log]) -> ChainResult<u32> {
+        let logs = self.decode(logs);
+        if logs.is_empty() {
+            return Ok(0);
+        }
+        Ok(self.store.store_logs(&logs).await?)
+    }
+
+    async fn delete_logs(&self, logs: &[Log]) -> ChainResult<u32> {
+        let logs = self.decode(logs);
+        if logs.is_empty() {
+            return Ok(0);
+        }
+        Ok(self.store.delete_logs(&logs).await?)
+    }
+}
+
+#[derive(Debug)]
+struct CachedRange {
+    range: RangeInclusive<u32>,
+    logs: Vec<Log>,
+    fetched_at: Instant,
+    filters_generation: u64,
+}
+
+/// The logs of recently fetched block ranges.
+#[derive(Debug, Default)]
+struct LogCache {
+    ranges: Vec<CachedRange>,
+}
+
+impl LogCache {
+    /// Removes the ranges that expired or were fetched before an event was registered.
+    fn remove_stale(&mut self, filters_generation: u64) {
+        self.ranges.retain(|cached| {
+            cached.filters_generation == filters_generation
+                && cached.fetched_at.elapsed() < CACHE_TTL
+        });
+    }
+
+    /// Removes the ranges that include blocks from `first_block` onwards,
+    /// returning the logs cached from those blocks.
+    fn remove_from(&mut self, first_block: u32) -> Vec<Log> {
+        let (removed, kept) = std::mem::take(&mut self.ranges)
+            .into_iter()
+            .partition(|cached| *cached.range.end() >= first_block);
+        self.ranges = kept;
+        removed
+            .into_iter()
+            .flat_map(|cached: CachedRange| cached.logs)
+            .filter(|log| {
+                log.block_number
+                    .map_or(false, |block| block.as_u32() >= first_block)
+            })
+            .collect()
+    }
+
+    /// The parts of a range that aren't covered by cached ranges.
+    fn gaps(&self, range: &RangeInclusive<u32>) -> Vec<RangeInclusive<u32>> {
+        let mut covered: Vec<_> = self
+            .ranges
+            .iter()
+            .map(|cached| &cached.range)
+            .filter(|cached| cached.start() <= range.end() && cached.end() >= range.start())
+            .collect();
+        covered.sort_by_key(|cached| *cached.start());
+
+        let mut gaps = vec![];
+        // u64, so that a range ending at `u32::MAX` doesn't overflow
+        let mut next_uncovered = u64::from(*range.start());
+        for cached in covered {
+            let start = u64::from(*cached.start());
+            if start > next_uncovered {
+                gaps.push(next_uncovered as u32..=(start - 1) as u32);
+            }
+            next_uncovered = next_uncovered.max(u64::from(*cached.end()) + 1);
+        }
+        if next_uncovered <= u64::from(*range.end()) {
+            gaps.push(next_uncovered as u32..=*range.end());
+        }
+        gaps
+    }
+
+    fn insert(&mut self, range: RangeInclusive<u32>, logs: Vec<Log>, filters_generation: u64) {
+        self.ranges.push(CachedRange {
+            range,
+            logs,
+            fetched_at: Instant::now(),
+            filters_generation,
+        });
+    }
+
+    /// The cached logs in a range, without duplicates and in the order they
+    /// were emitted.
+    fn logs_in(&self, range: &RangeInclusive<u32>) -> Vec<Log> {
+        let mut seen = HashSet::new();
+        let mut logs: Vec<Log> = self
+            .ranges
+            .iter()
+            .flat_map(|cached| cached.logs.iter())
+            .filter(|log| log.removed != Some(true))
+            .filter(|log| {
+                log.block_number
+                    .map_or(false, |block| range.contains(&block.as_u32()))
+            })
+            .filter(|log| seen.insert((log.transaction_hash, log.log_index)))
+            .cloned()
+            .collect();
+        logs.sort_by_key(|log| (log.block_number, log.log_index));
+        logs
+    }
+
+    /// Evicts the oldest ranges beyond the max number of cached ranges.
+    fn prune(&mut self) {
+        if self.ranges.len() > MAX_CACHED_RANGES {
+            self.ranges.sort_by_key(|cached| cached.fetched_at);
+            let excess = self.ranges.len() - MAX_CACHED_RANGES;
+            self.ranges.drain(..excess);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ethers::providers::{MockProvider, Provider};
+    use ethers::types::{U256, U64};
+    use hyperlane_core::H256;
+
+    use crate::interfaces::mailbox::ProcessIdFilter;
+    use crate::interfaces::merkle_tree_hook::InsertedIntoTreeFilter;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockStore {
+        stored: Mutex<Vec<H256>>,
+        deleted: Mutex<Vec<H256>>,
+    }
+
+    #[async_trait]
+    impl HyperlaneLogStore<H256> for MockStore {
+        async fn store_logs(&self, logs: &[(Indexed<H256>, LogMeta)]) -> eyre::Result<u32> {
+            let mut stored = self.stored.lock().unwrap();
+            stored.extend(logs.iter().map(|(log, _)| *log.inner()));
+            Ok(logs.len() as u32)
+        }
+
+        async fn delete_logs(&self, logs: &[(Indexed<H256>, LogMeta)]) -> eyre::Result<u32> {
+            let mut deleted = self.deleted.lock().unwrap();
+            deleted.extend(logs.iter().map(|(log, _)| *log.inner()));
+            Ok(logs.len() as u32)
+        }
+    }
+
+    fn process_id_log(mailbox: EthersH160, message_id: H256, block_number: u32) -> Log {
+        Log {
+            address: mailbox,
+            topics: vec![ProcessIdFilter::signature(), message_id.into()],
+            block_hash: Some(EthersH256::zero()),
+            block_number: Some(U64::from(block_number)),
+            transaction_hash: Some(EthersH256::zero()),
+            transaction_index: Some(U64::zero()),
+            log_index: Some(U256::zero()),
+            ..Default::default()
+        }
+    }
+
+    fn log(block_number: u32, log_index: u32) -> Log {
+        Log {
+            block_number: Some(U64::from(block_number)),
+            transaction_hash: Some(EthersH256::from_low_u64_be(block_number.into())),
+            log_index: Some(U256::from(log_index)),
+            ..Default::default()
+        }
+    }
+
+    fn cache(ranges: Vec<(RangeInclusive<u32>, Vec<Log>)>) -> LogCache {
+        let mut cache = LogCache::default();
+        for (range, logs) in ranges {
+            cache.insert(range, logs, 0);
+        }
+        cache
+    }
+
+    #[test]
+    fn test_gaps_of_uncached_range() {
+        let cache = cache(vec![]);
+        assert_eq!(cache.gaps(&(10..=20)), vec![10..=20]);
+    }
+
+    #[test]
+    fn test_gaps_between_cached_ranges() {
+        let cache = cache(vec![(15..=17, vec![]), (5..=11, vec![]), (16..=18, vec![])]);
+        assert_eq!(cache.gaps(&(10..=20)), vec![12..=14, 19..=20]);
+        assert_eq!(cache.gaps(&(15..=18)), vec![]);
+        assert_eq!(
+            cache.gaps(&(0..=u32::MAX)),
+            vec![0..=4, 12..=14, 19..=u32::MAX]
+        );
+    }
+
+    #[test]
+    fn test_logs_in_range_are_deduplicated_and_sorted() {
+        let cache = cache(vec![
+            (10..=15, vec![log(14, 1), log(12, 0), log(14, 0)]),
+            (14..=20, vec![log(14, 0), log(14, 1), log(20, 0)]),
+        ]);
+        let logs = cache.logs_in(&(13..=19));
+        let positions: Vec<_> = logs
+            .iter()
+            .map(|log| {
+                (
+                    log.block_number.unwrap().as_u32(),
+                    log.log_index.unwrap().as_u32(),
+                )
+            })
+            .collect();
+        assert_eq!(positions, vec![(14, 0), (14, 1)]);
+    }
+
+    #[test]
+    fn test_ranges_fetched_before_registering_an_event_are_stale() {
+        let mut cache = cache(vec![(10..=20, vec![])]);
+        cache.remove_stale(0);
+        assert_eq!(cache.gaps(&(10..=20)), vec![]);
+        cache.remove_stale(1);
+        assert_eq!(cache.gaps(&(10..=20)), vec![10..=20]);
+    }
+
+    #[tokio::test]
+    async fn test_fetched_logs_are_fanned_out_and_rolled_back() {
+        let mailbox = EthersH160::repeat_byte(1);
+        let merkle_tree_hook = EthersH160::repeat_byte(2);
+        let fetcher = UnifiedLogFetcher::default();
+        fetcher.register::<ProcessIdFilter>(mailbox);
+        fetcher.register::<InsertedIntoTreeFilter>(merkle_tree_hook);
+        let store = Arc::new(MockStore::default());
+        fetcher.fan_out_to::<ProcessIdFilter, H256>(mailbox, store.clone(), |event| {
+            Indexed::new(H256::from(event.message_id))
+        });
+
+        let mock_provider = Arc::new(MockProvider::new());
+        let provider = Provider::new(mock_provider.clone());
+        let message_id = H256::random();
+        mock_provider
+            .push(vec![process_id_log(mailbox, message_id, 15)])
+            .unwrap();
+
+        // Fetching the tree insertions stores the deliveries fetched along with them
+        let insertions: Vec<(InsertedIntoTreeFilter, LogMeta)> = fetcher
+            .fetch_events(&provider, merkle_tree_hook, 10..=20)
+            .await
+            .unwrap();
+        assert!(insertions.is_empty());
+        assert_eq!(*store.stored.lock().unwrap(), vec![message_id]);
+
+        // The deliveries are then served from the cache, without another query
+        let deliveries: Vec<(ProcessIdFilter, LogMeta)> = fetcher
+            .fetch_events(&provider, mailbox, 10..=20)
+            .await
+            .unwrap();
+        assert_eq!(deliveries.len(), 1);
+
+        // Once reorged, the fanned out logs are deleted and the range is fetched again
+        fetcher.handle_reorg(15).await.unwrap();
+        assert_eq!(*store.deleted.lock().unwrap(), vec![message_id]);
+        mock_provider.push(Vec::<Log>::new()).unwrap();
+        let deliveries: Vec<(ProcessIdFilter, LogMeta)> = fetcher
+            .fetch_events(&provider, mailbox, 10..=20)
+            .await
+            .unwrap();
+        assert!(deliveries.is_empty());
+    }
+
+    #[test]
+    fn test_remove_ranges_of_reorged_blocks() {
+        let mut cache = cache(vec![
+            (10..=15, vec![log(12, 0)]),
+            (16..=20, vec![log(17, 0), log(19, 0)]),
+        ]);
+        let removed = cache.remove_from(18);
+        let removed_blocks: Vec<_> = removed
+            .iter()
+            .map(|log| log.block_number.unwrap().as_u32())
+            .collect();
+        assert_eq!(removed_blocks, vec![19]);
+        assert_eq!(cache.gaps(&(10..=20)), vec![16..=20]);
+    }
+}
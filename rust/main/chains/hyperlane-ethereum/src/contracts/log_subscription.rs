@@ -0,0 +1,103 @@
+//! Subscriptions to the logs of an event as they're emitted, over a websocket.
+
+use async_trait::async_trait;
+use ethers::prelude::{Middleware, Provider, Ws};
+use ethers::types::{Filter, Log, H160 as EthersH160};
+use ethers_contract::EthEvent;
+use futures_util::StreamExt;
+use hyperlane_core::{ChainCommunicationError, ChainResult, Indexed, LogMeta, LogSubscription};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+use url::Url;
+
+use super::utils::decode_logs;
+use crate::EthereumProviderConnectionError;
+
+/// How many received logs may be buffered until they're indexed.
+const LOG_CHANNEL_CAPACITY: usize = 1_000;
+
+/// Logs of an event received from an `eth_subscribe("logs")` subscription.
+/// The subscription is driven by a task that owns the websocket connection,
+/// and ends when the connection drops or the subscription is dropped.
+pub(crate) struct EthereumLogSubscription<E, T> {
+    receiver: mpsc::Receiver<Log>,
+    address: EthersH160,
+    to_indexed: fn(E) -> Indexed<T>,
+}
+
+impl<E, T> EthereumLogSubscription<E, T>
+where
+    E: EthEvent + Send + 'static,
+    T: Send + 'static,
+{
+    /// Subscribes to the logs of an event emitted by a contract, over the
+    /// first of the websocket urls that can be connected to.
+    pub async fn subscribe(
+        urls: &[Url],
+        address: EthersH160,
+        to_indexed: fn(E) -> Indexed<T>,
+    ) -> ChainResult<Self> {
+        let provider = connect(urls).await?;
+        let filter = Filter::new().address(address).topic0(E::signature());
+        let (sender, receiver) = mpsc::channel(LOG_CHANNEL_CAPACITY);
+        tokio::spawn(async move {
+            let mut stream = match provider.subscribe_logs(&filter).await {
+                Ok(stream) => stream,
+                Err(err) => {
+                    warn!(?err, ?filter, "Failed to subscribe to logs");
+                    return;
+                }
+            };
+            while let Some(log) = stream.next().await {
+                // The subscription is cancelled when the stream is dropped
+                if sender.send(log).await.is_err() {
+                    break;
+                }
+            }
+            debug!(?filter, "Log subscription ended");
+        });
+        Ok(Self {
+            receiver,
+            address,
+            to_indexed,
+        })
+    }
+}
+
+#[async_trait]
+impl<E, T> LogSubscription<T> for EthereumLogSubscription<E, T>
+where
+    E: EthEvent + Send + 'static,
+    T: Send + 'static,
+{
+    async fn next_log(&mut self) -> Option<(Indexed<T>, LogMeta)> {
+        loop {
+            let log = self.receiver.recv().await?;
+            // Logs of reorged blocks are re-sent as removed, they're rolled back
+            // by the cursor instead
+            if log.removed == Some(true) {
+                continue;
+            }
+            if let Some((event, meta)) = decode_logs::<E>(vec![log], self.address).pop() {
+                return Some(((self.to_indexed)(event), meta));
+            }
+        }
+    }
+}
+
+async fn connect(urls: &[Url]) -> ChainResult<Provider<Ws>> {
+    let mut last_err = None;
+    for url in urls {
+        match Ws::connect(url).await {
+            Ok(ws) => return Ok(Provider::new(ws)),
+            Err(err) => {
+                warn!(?err, %url, "Failed to connect to websocket");
+                last_err = Some(err);
+            }
+        }
+    }
+    Err(match last_err {
+        Some(err) => EthereumProviderConnectionError::from(err).into(),
+        None => ChainCommunicationError::from_other_str("No websocket urls to subscribe to logs"),
+    })
+}
@@ -9,7 +9,7 @@ use std::sync::Arc;
 use async_trait::async_trait;
 use derive_new::new;
 use ethers::abi::{AbiEncode, Detokenize};
-use ethers::prelude::Middleware;
+use ethers::prelude::{Middleware, TransactionReceipt};
 use ethers_contract::builders::ContractCall;
 use ethers_contract::{Multicall, MulticallResult};
 use futures_util::future::join_all;
@@ -17,12 +17,14 @@ use hyperlane_core::rpc_clients::call_and_retry_indefinitely;
 use hyperlane_core::{BatchResult, QueueOperation, H512};
 use itertools::Itertools;
 use tracing::instrument;
+use url::Url;
 
 use hyperlane_core::{
     utils::bytes_to_hex, BatchItem, ChainCommunicationError, ChainResult, ContractLocator,
-    HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProtocolError, HyperlaneProvider, Indexed, Indexer, LogMeta, Mailbox,
-    RawHyperlaneMessage, SequenceAwareIndexer, TxCostEstimate, TxOutcome, H160, H256, U256,
+    HyperlaneAbi, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneLogStore,
+    HyperlaneMessage, HyperlaneProtocolError, HyperlaneProvider, Indexed, Indexer, LogMeta,
+    LogSubscription, Mailbox, RawHyperlaneMessage, SequenceAwareIndexer, TxCostEstimate, TxOutcome,
+    H160, H256, U256,
 };
 
 use crate::error::HyperlaneEthereumError;
@@ -30,12 +32,17 @@ use crate::interfaces::arbitrum_node_interface::ArbitrumNodeInterface;
 use crate::interfaces::i_mailbox::{
     IMailbox as EthereumMailboxInternal, ProcessCall, IMAILBOX_ABI,
 };
-use crate::interfaces::mailbox::DispatchFilter;
-use crate::tx::{call_with_lag, fill_tx_gas_params, report_tx};
-use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider, TransactionOverrides};
+use crate::interfaces::mailbox::{DispatchFilter, ProcessIdFilter};
+use crate::nonce::NonceManager;
+use crate::tx::{call_with_lag, fill_tx_gas_params, report_tx, report_tx_with_nonce};
+use crate::{
+    BuildableWithProvider, ConnectionConf, EthereumProvider, FeeEscalation, TransactionOverrides,
+    UnifiedLogFetcher,
+};
 
+use super::log_subscription::EthereumLogSubscription;
 use super::multicall::{self, build_multicall};
-use super::utils::fetch_raw_logs_and_meta;
+use super::utils::{fetch_raw_logs_and_meta, get_block_hash};
 
 impl<M> std::fmt::Display for EthereumMailboxInternal<M>
 where
@@ -58,13 +65,19 @@ impl BuildableWithProvider for SequenceIndexerBuilder {
     async fn build_with_provider<M: Middleware + 'static>(
         &self,
         provider: M,
-        _conn: &ConnectionConf,
+        conn: &ConnectionConf,
         locator: &ContractLocator,
     ) -> Self::Output {
+        let log_fetcher = conn
+            .log_fetcher
+            .as_ref()
+            .map(|log_fetcher| log_fetcher.register::<DispatchFilter>(locator.address));
         Box::new(EthereumMailboxIndexer::new(
             Arc::new(provider),
             locator,
             self.reorg_period,
+            log_fetcher,
+            conn.subscription_urls.clone(),
         ))
     }
 }
@@ -81,13 +94,19 @@ impl BuildableWithProvider for DeliveryIndexerBuilder {
     async fn build_with_provider<M: Middleware + 'static>(
         &self,
         provider: M,
-        _conn: &ConnectionConf,
+        conn: &ConnectionConf,
         locator: &ContractLocator,
     ) -> Self::Output {
+        let log_fetcher = conn
+            .log_fetcher
+            .as_ref()
+            .map(|log_fetcher| log_fetcher.register::<ProcessIdFilter>(locator.address));
         Box::new(EthereumMailboxIndexer::new(
             Arc::new(provider),
             locator,
             self.reorg_period,
+            log_fetcher,
+            conn.subscription_urls.clone(),
         ))
     }
 }
@@ -101,6 +120,12 @@ where
     contract: Arc<EthereumMailboxInternal<M>>,
     provider: Arc<M>,
     reorg_period: u32,
+    /// Fetches the logs along with those of the other indexed contracts, if
+    /// unified log fetching is enabled
+    log_fetcher: Option<UnifiedLogFetcher>,
+    /// Websocket urls to subscribe to dispatched messages over, if log
+    /// subscriptions are enabled
+    subscription_urls: Vec<Url>,
 }
 
 impl<M> EthereumMailboxIndexer<M>
@@ -108,7 +133,13 @@ where
     M: Middleware + 'static,
 {
     /// Create new EthereumMailboxIndexer
-    pub fn new(provider: Arc<M>, locator: &ContractLocator, reorg_period: u32) -> Self {
+    pub fn new(
+        provider: Arc<M>,
+        locator: &ContractLocator,
+        reorg_period: u32,
+        log_fetcher: Option<UnifiedLogFetcher>,
+        subscription_urls: Vec<Url>,
+    ) -> Self {
         let contract = Arc::new(EthereumMailboxInternal::new(
             locator.address,
             provider.clone(),
@@ -117,6 +148,8 @@ where
             contract,
             provider,
             reorg_period,
+            log_fetcher,
+            subscription_urls,
         }
     }
 
@@ -132,6 +165,14 @@ where
     }
 }
 
+fn indexed_message(event: DispatchFilter) -> Indexed<HyperlaneMessage> {
+    HyperlaneMessage::from(event.message.to_vec()).into()
+}
+
+fn indexed_delivery(event: ProcessIdFilter) -> Indexed<H256> {
+    Indexed::new(H256::from(event.message_id))
+}
+
 #[async_trait]
 impl<M> Indexer<HyperlaneMessage> for EthereumMailboxIndexer<M>
 where
@@ -141,6 +182,10 @@ where
         self.get_finalized_block_number().await
     }
 
+    async fn get_block_hash(&self, block_number: u32) -> ChainResult<Option<H256>> {
+        get_block_hash(&self.provider, block_number).await
+    }
+
     /// Note: This call may return duplicates depending on the provider used
     #[instrument(err, skip(self))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
@@ -148,26 +193,54 @@ where
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
-        let mut events: Vec<(Indexed<HyperlaneMessage>, LogMeta)> = self
-            .contract
-            .dispatch_filter()
-            .from_block(*range.start())
-            .to_block(*range.end())
-            .query_with_meta()
-            .await?
+        let events: Vec<(DispatchFilter, LogMeta)> = match &self.log_fetcher {
+            Some(log_fetcher) => {
+                log_fetcher
+                    .fetch_events(self.provider.as_ref(), self.contract.address(), range)
+                    .await?
+            }
+            None => self
+                .contract
+                .dispatch_filter()
+                .from_block(*range.start())
+                .to_block(*range.end())
+                .query_with_meta()
+                .await?
+                .into_iter()
+                .map(|(event, meta)| (event, meta.into()))
+                .collect(),
+        };
+        let mut events: Vec<(Indexed<HyperlaneMessage>, LogMeta)> = events
             .into_iter()
-            .map(|(event, meta)| {
-                (
-                    HyperlaneMessage::from(event.message.to_vec()).into(),
-                    meta.into(),
-                )
-            })
+            .map(|(event, meta)| (indexed_message(event), meta))
             .collect();
 
         events.sort_by(|a, b| a.0.inner().nonce.cmp(&b.0.inner().nonce));
         Ok(events)
     }
 
+    async fn handle_reorg(&self, first_reorged_block: u32) -> ChainResult<()> {
+        match &self.log_fetcher {
+            Some(log_fetcher) => log_fetcher.handle_reorg(first_reorged_block).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn subscribe_logs(
+        &self,
+    ) -> ChainResult<Option<Box<dyn LogSubscription<HyperlaneMessage>>>> {
+        if self.subscription_urls.is_empty() {
+            return Ok(None);
+        }
+        let subscription = EthereumLogSubscription::<DispatchFilter, HyperlaneMessage>::subscribe(
+            &self.subscription_urls,
+            self.contract.address(),
+            indexed_message,
+        )
+        .await?;
+        Ok(Some(Box::new(subscription)))
+    }
+
     async fn fetch_logs_by_tx_hash(
         &self,
         tx_hash: H512,
@@ -182,12 +255,7 @@ where
         .await;
         let logs = raw_logs_and_meta
             .into_iter()
-            .map(|(log, log_meta)| {
-                (
-                    HyperlaneMessage::from(log.message.to_vec()).into(),
-                    log_meta,
-                )
-            })
+            .map(|(log, log_meta)| (indexed_message(log), log_meta))
             .collect();
         Ok(logs)
     }
@@ -216,6 +284,10 @@ where
         self.get_finalized_block_number().await
     }
 
+    async fn get_block_hash(&self, block_number: u32) -> ChainResult<Option<H256>> {
+        get_block_hash(&self.provider, block_number).await
+    }
+
     /// Note: This call may return duplicates depending on the provider used
     #[instrument(err, skip(self))]
     #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
@@ -223,17 +295,45 @@ where
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        Ok(self
-            .contract
-            .process_id_filter()
-            .from_block(*range.start())
-            .to_block(*range.end())
-            .query_with_meta()
-            .await?
+        let events: Vec<(ProcessIdFilter, LogMeta)> = match &self.log_fetcher {
+            Some(log_fetcher) => {
+                log_fetcher
+                    .fetch_events(self.provider.as_ref(), self.contract.address(), range)
+                    .await?
+            }
+            None => self
+                .contract
+                .process_id_filter()
+                .from_block(*range.start())
+                .to_block(*range.end())
+                .query_with_meta()
+                .await?
+                .into_iter()
+                .map(|(event, meta)| (event, meta.into()))
+                .collect(),
+        };
+        Ok(events
             .into_iter()
-            .map(|(event, meta)| (Indexed::new(H256::from(event.message_id)), meta.into()))
+            .map(|(event, meta)| (indexed_delivery(event), meta))
             .collect())
     }
+
+    async fn handle_reorg(&self, first_reorged_block: u32) -> ChainResult<()> {
+        match &self.log_fetcher {
+            Some(log_fetcher) => log_fetcher.handle_reorg(first_reorged_block).await,
+            None => Ok(()),
+        }
+    }
+
+    fn fan_out_logs_to(&self, store: Arc<dyn HyperlaneLogStore<H256>>) {
+        if let Some(log_fetcher) = &self.log_fetcher {
+            log_fetcher.fan_out_to::<ProcessIdFilter, _>(
+                self.contract.address(),
+                store,
+                indexed_delivery,
+            );
+        }
+    }
 }
 
 #[async_trait]
@@ -249,7 +349,11 @@ where
     }
 }
 
-pub struct MailboxBuilder {}
+pub struct MailboxBuilder {
+    /// Whether the mailbox is one of several that submit from different
+    /// signers, see `MultiSignerMailbox`
+    pub multiple_signers: bool,
+}
 
 #[async_trait]
 impl BuildableWithProvider for MailboxBuilder {
@@ -262,7 +366,18 @@ impl BuildableWithProvider for MailboxBuilder {
         conn: &ConnectionConf,
         locator: &ContractLocator,
     ) -> Self::Output {
-        Box::new(EthereumMailbox::new(Arc::new(provider), conn, locator))
+        let mailbox = EthereumMailbox::new(Arc::new(provider), conn, locator);
+        // Nonces only need to be allocated locally if several transactions of
+        // a signer can be in flight at once, or if stuck transactions are
+        // replaced with bumped fees
+        if self.multiple_signers
+            || conn.max_in_flight_transactions > 1
+            || conn.fee_escalation.is_some()
+        {
+            Box::new(mailbox.with_nonce_manager())
+        } else {
+            Box::new(mailbox)
+        }
     }
 }
 
@@ -277,6 +392,8 @@ where
     provider: Arc<M>,
     arbitrum_node_interface: Option<Arc<ArbitrumNodeInterface<M>>>,
     conn: ConnectionConf,
+    /// Allocates the nonces of the signer, if enabled and the provider has one
+    nonce_manager: Option<Arc<NonceManager>>,
 }
 
 impl<M> EthereumMailbox<M>
@@ -306,9 +423,20 @@ where
             provider,
             arbitrum_node_interface,
             conn: conn.clone(),
+            nonce_manager: None,
         }
     }
 
+    /// Allocates the signer's nonces locally, so that several of its
+    /// transactions can be in flight at once
+    pub fn with_nonce_manager(mut self) -> Self {
+        self.nonce_manager = self
+            .provider
+            .default_sender()
+            .map(|address| Arc::new(NonceManager::new(address)));
+        self
+    }
+
     /// Returns a ContractCall that processes the provided message.
     async fn process_contract_call(
         &self,
@@ -382,10 +510,32 @@ where
             call,
             provider: self.provider.clone(),
             transaction_overrides: self.conn.transaction_overrides.clone(),
+            nonce_manager: self.nonce_manager.clone(),
+            fee_escalation: self.conn.fee_escalation.clone(),
         }
     }
 }
 
+/// Dispatches a transaction, using the signer's nonce manager if there is one
+/// so that several transactions can be in flight at once.
+async fn dispatch_tx<M, D>(
+    tx: ContractCall<M, D>,
+    provider: &M,
+    nonce_manager: Option<&NonceManager>,
+    fee_escalation: Option<&FeeEscalation>,
+) -> ChainResult<TransactionReceipt>
+where
+    M: Middleware + 'static,
+    D: Detokenize,
+{
+    match nonce_manager {
+        Some(nonce_manager) => {
+            report_tx_with_nonce(tx, provider, nonce_manager, fee_escalation).await
+        }
+        None => report_tx(tx).await,
+    }
+}
+
 #[derive(new)]
 pub struct BatchSimulation<M> {
     pub call: Option<SubmittableBatch<M>>,
@@ -418,13 +568,25 @@ pub struct SubmittableBatch<M> {
     pub call: ContractCall<M, Vec<MulticallResult>>,
     provider: Arc<M>,
     transaction_overrides: TransactionOverrides,
+    nonce_manager: Option<Arc<NonceManager>>,
+    fee_escalation: Option<FeeEscalation>,
 }
 
 impl<M: Middleware + 'static> SubmittableBatch<M> {
     pub async fn submit(self) -> ChainResult<TxOutcome> {
-        let call_with_gas_overrides =
-            fill_tx_gas_params(self.call, self.provider, &self.transaction_overrides).await?;
-        let outcome = report_tx(call_with_gas_overrides).await?;
+        let call_with_gas_overrides = fill_tx_gas_params(
+            self.call,
+            self.provider.clone(),
+            &self.transaction_overrides,
+        )
+        .await?;
+        let outcome = dispatch_tx(
+            call_with_gas_overrides,
+            &self.provider,
+            self.nonce_manager.as_deref(),
+            self.fee_escalation.as_ref(),
+        )
+        .await?;
         Ok(outcome.into())
     }
 }
@@ -496,7 +658,13 @@ where
         let contract_call = self
             .process_contract_call(message, metadata, tx_gas_limit)
             .await?;
-        let receipt = report_tx(contract_call).await?;
+        let receipt = dispatch_tx(
+            contract_call,
+            &self.provider,
+            self.nonce_manager.as_deref(),
+            self.conn.fee_escalation.as_ref(),
+        )
+        .await?;
         Ok(receipt.into())
     }
 
@@ -633,6 +801,10 @@ mod test {
             },
             transaction_overrides: Default::default(),
             operation_batch: Default::default(),
+            max_in_flight_transactions: 1,
+            fee_escalation: None,
+            log_fetcher: None,
+            subscription_urls: vec![],
         };
 
         let mailbox = EthereumMailbox::new(
@@ -8,20 +8,22 @@ use ethers::prelude::Middleware;
 use hyperlane_core::accumulator::incremental::IncrementalMerkle;
 use hyperlane_core::rpc_clients::call_and_retry_indefinitely;
 use tracing::instrument;
+use url::Url;
 
 use hyperlane_core::{
     ChainCommunicationError, ChainResult, Checkpoint, ContractLocator, HyperlaneChain,
     HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer, LogMeta,
-    MerkleTreeHook, MerkleTreeInsertion, SequenceAwareIndexer, H256, H512,
+    LogSubscription, MerkleTreeHook, MerkleTreeInsertion, SequenceAwareIndexer, H256, H512,
 };
 
 use crate::interfaces::merkle_tree_hook::{
     InsertedIntoTreeFilter, MerkleTreeHook as MerkleTreeHookContract, Tree,
 };
 use crate::tx::call_with_lag;
-use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider};
+use crate::{BuildableWithProvider, ConnectionConf, EthereumProvider, UnifiedLogFetcher};
 
-use super::utils::fetch_raw_logs_and_meta;
+use super::log_subscription::EthereumLogSubscription;
+use super::utils::{fetch_raw_logs_and_meta, get_block_hash};
 
 // We don't need the reverse of this impl, so it's ok to disable the clippy lint
 #[allow(clippy::from_over_into)]
@@ -69,13 +71,19 @@ impl BuildableWithProvider for MerkleTreeHookIndexerBuilder {
     async fn build_with_provider<M: Middleware + 'static>(
         &self,
         provider: M,
-        _conn: &ConnectionConf,
+        conn: &ConnectionConf,
         locator: &ContractLocator,
     ) -> Self::Output {
+        let log_fetcher = conn
+            .log_fetcher
+            .as_ref()
+            .map(|log_fetcher| log_fetcher.register::<InsertedIntoTreeFilter>(locator.address));
         Box::new(EthereumMerkleTreeHookIndexer::new(
             Arc::new(provider),
             locator,
             self.reorg_period,
+            log_fetcher,
+            conn.subscription_urls.clone(),
         ))
     }
 }
@@ -89,6 +97,12 @@ where
     contract: Arc<MerkleTreeHookContract<M>>,
     provider: Arc<M>,
     reorg_period: u32,
+    /// Fetches the logs along with those of the other indexed contracts, if
+    /// unified log fetching is enabled
+    log_fetcher: Option<UnifiedLogFetcher>,
+    /// Websocket urls to subscribe to tree insertions over, if log
+    /// subscriptions are enabled
+    subscription_urls: Vec<Url>,
 }
 
 impl<M> EthereumMerkleTreeHookIndexer<M>
@@ -96,7 +110,13 @@ where
     M: Middleware + 'static,
 {
     /// Create new EthereumMerkleTreeHookIndexer
-    pub fn new(provider: Arc<M>, locator: &ContractLocator, reorg_period: u32) -> Self {
+    pub fn new(
+        provider: Arc<M>,
+        locator: &ContractLocator,
+        reorg_period: u32,
+        log_fetcher: Option<UnifiedLogFetcher>,
+        subscription_urls: Vec<Url>,
+    ) -> Self {
         Self {
             contract: Arc::new(MerkleTreeHookContract::new(
                 locator.address,
@@ -104,10 +124,16 @@ where
             )),
             provider,
             reorg_period,
+            log_fetcher,
+            subscription_urls,
         }
     }
 }
 
+fn indexed_insertion(log: InsertedIntoTreeFilter) -> Indexed<MerkleTreeInsertion> {
+    MerkleTreeInsertion::new(log.index, H256::from(log.message_id)).into()
+}
+
 #[async_trait]
 impl<M> Indexer<MerkleTreeInsertion> for EthereumMerkleTreeHookIndexer<M>
 where
@@ -120,22 +146,27 @@ where
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>> {
-        let events = self
-            .contract
-            .inserted_into_tree_filter()
-            .from_block(*range.start())
-            .to_block(*range.end())
-            .query_with_meta()
-            .await?;
+        let events: Vec<(InsertedIntoTreeFilter, LogMeta)> = match &self.log_fetcher {
+            Some(log_fetcher) => {
+                log_fetcher
+                    .fetch_events(self.provider.as_ref(), self.contract.address(), range)
+                    .await?
+            }
+            None => self
+                .contract
+                .inserted_into_tree_filter()
+                .from_block(*range.start())
+                .to_block(*range.end())
+                .query_with_meta()
+                .await?
+                .into_iter()
+                .map(|(log, log_meta)| (log, log_meta.into()))
+                .collect(),
+        };
 
         let logs = events
             .into_iter()
-            .map(|(log, log_meta)| {
-                (
-                    MerkleTreeInsertion::new(log.index, H256::from(log.message_id)).into(),
-                    log_meta.into(),
-                )
-            })
+            .map(|(log, log_meta)| (indexed_insertion(log), log_meta))
             .collect();
         Ok(logs)
     }
@@ -152,6 +183,33 @@ where
             .saturating_sub(self.reorg_period))
     }
 
+    async fn get_block_hash(&self, block_number: u32) -> ChainResult<Option<H256>> {
+        get_block_hash(&self.provider, block_number).await
+    }
+
+    async fn handle_reorg(&self, first_reorged_block: u32) -> ChainResult<()> {
+        match &self.log_fetcher {
+            Some(log_fetcher) => log_fetcher.handle_reorg(first_reorged_block).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn subscribe_logs(
+        &self,
+    ) -> ChainResult<Option<Box<dyn LogSubscription<MerkleTreeInsertion>>>> {
+        if self.subscription_urls.is_empty() {
+            return Ok(None);
+        }
+        let subscription =
+            EthereumLogSubscription::<InsertedIntoTreeFilter, MerkleTreeInsertion>::subscribe(
+                &self.subscription_urls,
+                self.contract.address(),
+                indexed_insertion,
+            )
+            .await?;
+        Ok(Some(Box::new(subscription)))
+    }
+
     async fn fetch_logs_by_tx_hash(
         &self,
         tx_hash: H512,
@@ -167,12 +225,7 @@ where
         .await;
         let logs = raw_logs_and_meta
             .into_iter()
-            .map(|(log, log_meta)| {
-                (
-                    MerkleTreeInsertion::new(log.index, H256::from(log.message_id)).into(),
-                    log_meta,
-                )
-            })
+            .map(|(log, log_meta)| (indexed_insertion(log), log_meta))
             .collect();
         Ok(logs)
     }
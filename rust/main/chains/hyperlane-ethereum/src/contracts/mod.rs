@@ -1,8 +1,14 @@
-pub use {interchain_gas::*, mailbox::*, merkle_tree_hook::*, validator_announce::*};
+pub use {
+    interchain_gas::*, log_fetcher::*, mailbox::*, merkle_tree_hook::*, multi_signer_mailbox::*,
+    validator_announce::*,
+};
 
 mod interchain_gas;
+mod log_fetcher;
+mod log_subscription;
 mod mailbox;
 mod merkle_tree_hook;
+mod multi_signer_mailbox;
 mod multicall;
 mod utils;
 mod validator_announce;
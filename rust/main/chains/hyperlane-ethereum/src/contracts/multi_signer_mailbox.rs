@@ -0,0 +1,302 @@
+use std::num::NonZeroU64;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use async_trait::async_trait;
+use tracing::instrument;
+
+use hyperlane_core::{
+    BatchResult, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
+    HyperlaneProvider, Mailbox, QueueOperation, TxCostEstimate, TxOutcome, H256, U256,
+};
+
+/// A mailbox that submits transactions from a pool of signers, each with its
+/// own mailbox instance. Every submission goes to the signer with the fewest
+/// transactions in flight, while reads are served by the first signer's
+/// mailbox.
+#[derive(Debug)]
+pub struct MultiSignerMailbox {
+    mailboxes: Vec<Box<dyn Mailbox>>,
+    in_flight: Vec<AtomicUsize>,
+}
+
+impl MultiSignerMailbox {
+    /// Create a mailbox that submits from the signers of `primary` and
+    /// `others`
+    pub fn new(primary: Box<dyn Mailbox>, others: Vec<Box<dyn Mailbox>>) -> Self {
+        let mailboxes = std::iter::once(primary).chain(others).collect::<Vec<_>>();
+        let in_flight = mailboxes.iter().map(|_| AtomicUsize::new(0)).collect();
+        Self {
+            mailboxes,
+            in_flight,
+        }
+    }
+
+    fn primary(&self) -> &dyn Mailbox {
+        self.mailboxes[0].as_ref()
+    }
+
+    /// Reserves the signer with the fewest transactions in flight until the
+    /// returned guard is dropped
+    fn least_busy_signer(&self) -> InFlightSubmission<'_> {
+        loop {
+            let (index, in_flight) = self
+                .in_flight
+                .iter()
+                .map(|in_flight| in_flight.load(Ordering::SeqCst))
+                .enumerate()
+                .min_by_key(|&(_, in_flight)| in_flight)
+                .unwrap_or_default();
+            // Only reserve the signer if no concurrent submission reserved it
+            // since its count was read, otherwise it may not be the least busy
+            // anymore
+            if self.in_flight[index]
+                .compare_exchange(in_flight, in_flight + 1, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                return InFlightSubmission {
+                    mailbox: self.mailboxes[index].as_ref(),
+                    in_flight: &self.in_flight[index],
+                };
+            }
+        }
+    }
+}
+
+struct InFlightSubmission<'a> {
+    mailbox: &'a dyn Mailbox,
+    in_flight: &'a AtomicUsize,
+}
+
+impl Drop for InFlightSubmission<'_> {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl HyperlaneChain for MultiSignerMailbox {
+    fn domain(&self) -> &HyperlaneDomain {
+        self.primary().domain()
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        self.primary().provider()
+    }
+}
+
+impl HyperlaneContract for MultiSignerMailbox {
+    fn address(&self) -> H256 {
+        self.primary().address()
+    }
+}
+
+#[async_trait]
+impl Mailbox for MultiSignerMailbox {
+    async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
+        self.primary().count(lag).await
+    }
+
+    async fn delivered(&self, id: H256) -> ChainResult<bool> {
+        self.primary().delivered(id).await
+    }
+
+    async fn default_ism(&self) -> ChainResult<H256> {
+        self.primary().default_ism().await
+    }
+
+    async fn recipient_ism(&self, recipient: H256) -> ChainResult<H256> {
+        self.primary().recipient_ism(recipient).await
+    }
+
+    #[instrument(skip_all)]
+    async fn process(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+        tx_gas_limit: Option<U256>,
+    ) -> ChainResult<TxOutcome> {
+        let signer = self.least_busy_signer();
+        signer
+            .mailbox
+            .process(message, metadata, tx_gas_limit)
+            .await
+    }
+
+    #[instrument(skip_all, fields(size=%ops.len()))]
+    async fn try_process_batch<'a>(
+        &self,
+        ops: Vec<&'a QueueOperation>,
+    ) -> ChainResult<BatchResult> {
+        let signer = self.least_busy_signer();
+        signer.mailbox.try_process_batch(ops).await
+    }
+
+    async fn process_estimate_costs(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<TxCostEstimate> {
+        self.primary()
+            .process_estimate_costs(message, metadata)
+            .await
+    }
+
+    fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
+        self.primary().process_calldata(message, metadata)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, sync::Arc};
+
+    use hyperlane_core::{FixedPointNumber, KnownHyperlaneDomain, H512};
+    use tokio::sync::Barrier;
+
+    use super::*;
+
+    /// A mailbox whose `process` calls wait at `barrier`, so that they're all
+    /// in flight at the same time
+    #[derive(Debug)]
+    struct TestMailbox {
+        signer: u64,
+        domain: HyperlaneDomain,
+        barrier: Arc<Barrier>,
+    }
+
+    impl HyperlaneChain for TestMailbox {
+        fn domain(&self) -> &HyperlaneDomain {
+            &self.domain
+        }
+
+        fn provider(&self) -> Box<dyn HyperlaneProvider> {
+            unimplemented!()
+        }
+    }
+
+    impl HyperlaneContract for TestMailbox {
+        fn address(&self) -> H256 {
+            H256::from_low_u64_be(self.signer)
+        }
+    }
+
+    #[async_trait]
+    impl Mailbox for TestMailbox {
+        async fn count(&self, _lag: Option<NonZeroU64>) -> ChainResult<u32> {
+            Ok(self.signer as u32)
+        }
+
+        async fn delivered(&self, _id: H256) -> ChainResult<bool> {
+            unimplemented!()
+        }
+
+        async fn default_ism(&self) -> ChainResult<H256> {
+            unimplemented!()
+        }
+
+        async fn recipient_ism(&self, _recipient: H256) -> ChainResult<H256> {
+            unimplemented!()
+        }
+
+        async fn process(
+            &self,
+            _message: &HyperlaneMessage,
+            _metadata: &[u8],
+            _tx_gas_limit: Option<U256>,
+        ) -> ChainResult<TxOutcome> {
+            self.barrier.wait().await;
+            Ok(TxOutcome {
+                transaction_id: H512::from_low_u64_be(self.signer),
+                executed: true,
+                gas_used: U256::zero(),
+                gas_price: FixedPointNumber::zero(),
+            })
+        }
+
+        async fn process_estimate_costs(
+            &self,
+            _message: &HyperlaneMessage,
+            _metadata: &[u8],
+        ) -> ChainResult<TxCostEstimate> {
+            unimplemented!()
+        }
+
+        fn process_calldata(&self, _message: &HyperlaneMessage, _metadata: &[u8]) -> Vec<u8> {
+            unimplemented!()
+        }
+    }
+
+    fn multi_signer_mailbox(signers: u64, barrier: Arc<Barrier>) -> MultiSignerMailbox {
+        let mut mailboxes = (0..signers).map(|signer| {
+            Box::new(TestMailbox {
+                signer,
+                domain: HyperlaneDomain::Known(KnownHyperlaneDomain::Ethereum),
+                barrier: barrier.clone(),
+            }) as Box<dyn Mailbox>
+        });
+        let primary = mailboxes.next().unwrap();
+        MultiSignerMailbox::new(primary, mailboxes.collect())
+    }
+
+    fn in_flight(mailbox: &MultiSignerMailbox) -> Vec<usize> {
+        mailbox
+            .in_flight
+            .iter()
+            .map(|in_flight| in_flight.load(Ordering::SeqCst))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_least_busy_signer() {
+        let mailbox = multi_signer_mailbox(2, Arc::new(Barrier::new(1)));
+
+        let first = mailbox.least_busy_signer();
+        let second = mailbox.least_busy_signer();
+        let third = mailbox.least_busy_signer();
+        assert_eq!(in_flight(&mailbox), vec![2, 1]);
+
+        // A signer is released when its submission is done
+        drop(second);
+        assert_eq!(in_flight(&mailbox), vec![2, 0]);
+        let fourth = mailbox.least_busy_signer();
+        assert_eq!(fourth.mailbox.address(), H256::from_low_u64_be(1));
+
+        drop((first, third, fourth));
+        assert_eq!(in_flight(&mailbox), vec![0, 0]);
+
+        // Reads go to the primary signer's mailbox
+        assert_eq!(mailbox.count(None).await.unwrap(), 0);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_submissions_are_spread_across_signers() {
+        const SIGNERS: u64 = 4;
+        const SUBMISSIONS: usize = 12;
+        // Every submission waits for all the others, so they're all in flight
+        // at once
+        let mailbox = Arc::new(multi_signer_mailbox(
+            SIGNERS,
+            Arc::new(Barrier::new(SUBMISSIONS)),
+        ));
+
+        let submissions = (0..SUBMISSIONS).map(|_| {
+            let mailbox = mailbox.clone();
+            tokio::spawn(async move {
+                mailbox
+                    .process(&HyperlaneMessage::default(), &[], None)
+                    .await
+                    .unwrap()
+                    .transaction_id
+            })
+        });
+        let mut per_signer = HashMap::new();
+        for submission in futures_util::future::join_all(submissions).await {
+            *per_signer.entry(submission.unwrap()).or_insert(0) += 1;
+        }
+
+        assert_eq!(per_signer.len(), SIGNERS as usize);
+        assert!(per_signer
+            .values()
+            .all(|&count| count == SUBMISSIONS / SIGNERS as usize));
+        assert_eq!(in_flight(&mailbox), vec![0; SIGNERS as usize]);
+    }
+}
@@ -3,10 +3,10 @@ use std::sync::Arc;
 use ethers::{
     abi::RawLog,
     providers::Middleware,
-    types::{H160 as EthersH160, H256 as EthersH256},
+    types::{Log, H160 as EthersH160, H256 as EthersH256},
 };
 use ethers_contract::{ContractError, EthEvent, LogMeta as EthersLogMeta};
-use hyperlane_core::{ChainResult, LogMeta, H512};
+use hyperlane_core::{ChainCommunicationError, ChainResult, LogMeta, H256, H512};
 
 pub async fn fetch_raw_logs_and_meta<T: EthEvent, M>(
     tx_hash: H512,
@@ -25,9 +25,13 @@ where
         return Err(eyre::eyre!("No receipt found for tx hash {:?}", tx_hash).into());
     };
 
-    let logs: Vec<(T, LogMeta)> = receipt
-        .logs
-        .into_iter()
+    Ok(decode_logs(receipt.logs, contract_address))
+}
+
+/// Decodes the logs of an event emitted by a contract, skipping logs of other
+/// contracts and events.
+pub fn decode_logs<T: EthEvent>(logs: Vec<Log>, contract_address: EthersH160) -> Vec<(T, LogMeta)> {
+    logs.into_iter()
         .filter_map(|log| {
             // Filter out logs that aren't emitted by this contract
             if log.address != contract_address {
@@ -41,6 +45,19 @@ where
             let event_filter = T::decode_log(&raw_log).ok();
             event_filter.map(|log| (log, log_meta.into()))
         })
-        .collect();
-    Ok(logs)
+        .collect()
+}
+
+/// Gets the hash of the block with the given number, if the block exists.
+pub async fn get_block_hash<M>(provider: &M, block_number: u32) -> ChainResult<Option<H256>>
+where
+    M: Middleware + 'static,
+{
+    let block = provider
+        .get_block(u64::from(block_number))
+        .await
+        .map_err(ChainCommunicationError::from_other)?;
+    Ok(block
+        .and_then(|block| block.hash)
+        .map(|hash| H256::from(hash.0)))
 }
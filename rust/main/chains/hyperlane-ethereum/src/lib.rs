@@ -12,6 +12,8 @@ pub use self::{config::*, contracts::*, ism::*, rpc_clients::*, signer::*};
 
 mod tx;
 
+mod nonce;
+
 mod contracts;
 
 mod ism;
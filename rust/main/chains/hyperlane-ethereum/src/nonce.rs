@@ -0,0 +1,147 @@
+use ethers::prelude::{Address, Middleware};
+use ethers_core::types::{BlockNumber, U256 as EthersU256};
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+use tokio::sync::Mutex;
+use tracing::debug;
+
+/// Allocates nonces for the transactions of a single signer, so that several
+/// of its transactions can be in flight at the same time.
+///
+/// Nonces are read from the chain once and then incremented locally. If a
+/// nonce goes unused because sending its transaction failed, it's handed out
+/// again when no later nonce was allocated yet. Otherwise the later
+/// transactions would queue up behind it, so the gap has to be filled, see
+/// `release`.
+#[derive(Debug)]
+pub(crate) struct NonceManager {
+    address: Address,
+    next_nonce: Mutex<Option<EthersU256>>,
+}
+
+impl NonceManager {
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            next_nonce: Mutex::new(None),
+        }
+    }
+
+    /// The address of the signer whose nonces are managed
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Allocates the next nonce of the signer
+    pub async fn next_nonce<M: Middleware>(&self, provider: &M) -> ChainResult<EthersU256> {
+        let mut next_nonce = self.next_nonce.lock().await;
+        let nonce = match *next_nonce {
+            Some(nonce) => nonce,
+            None => {
+                let nonce = provider
+                    .get_transaction_count(self.address, Some(BlockNumber::Pending.into()))
+                    .await
+                    .map_err(ChainCommunicationError::from_other)?;
+                debug!(address=?self.address, ?nonce, "Read next nonce from the chain");
+                nonce
+            }
+        };
+        *next_nonce = Some(nonce + 1);
+        Ok(nonce)
+    }
+
+    /// Forgets the locally tracked nonce, so that the next one is read from the
+    /// chain again.
+    pub async fn reset(&self) {
+        *self.next_nonce.lock().await = None;
+    }
+
+    /// Gives back `nonce` after sending its transaction failed. Returns whether
+    /// later nonces were already allocated, in which case `nonce` is a gap
+    /// that the caller must fill for their transactions to be included.
+    pub async fn release(&self, nonce: EthersU256) -> bool {
+        let mut next_nonce = self.next_nonce.lock().await;
+        match *next_nonce {
+            Some(next) if next == nonce + 1 => {
+                *next_nonce = Some(nonce);
+                false
+            }
+            Some(next) => next > nonce,
+            // The next nonce will be read from the chain, which accounts for
+            // any gap
+            None => false,
+        }
+    }
+
+    /// Whether `nonce` hasn't been used by an included transaction yet
+    pub async fn is_unused<M: Middleware>(
+        &self,
+        provider: &M,
+        nonce: EthersU256,
+    ) -> ChainResult<bool> {
+        let included_count = provider
+            .get_transaction_count(self.address, Some(BlockNumber::Latest.into()))
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(included_count <= nonce)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use ethers::providers::{MockProvider, Provider};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_nonces_are_pipelined_and_reset() {
+        let mock_provider = Arc::new(MockProvider::new());
+        let provider = Provider::new(mock_provider.clone());
+        let nonce_manager = NonceManager::new(Address::zero());
+
+        // Only the first nonce is read from the chain
+        mock_provider.push(EthersU256::from(7)).unwrap();
+        assert_eq!(nonce_manager.next_nonce(&provider).await.unwrap(), 7.into());
+        assert_eq!(nonce_manager.next_nonce(&provider).await.unwrap(), 8.into());
+        assert_eq!(nonce_manager.next_nonce(&provider).await.unwrap(), 9.into());
+
+        // Nonce 8 was never used, so it's handed out again after a reset
+        nonce_manager.reset().await;
+        mock_provider.push(EthersU256::from(8)).unwrap();
+        assert_eq!(nonce_manager.next_nonce(&provider).await.unwrap(), 8.into());
+        assert_eq!(nonce_manager.next_nonce(&provider).await.unwrap(), 9.into());
+    }
+
+    #[tokio::test]
+    async fn test_release() {
+        let mock_provider = Arc::new(MockProvider::new());
+        let provider = Provider::new(mock_provider.clone());
+        let nonce_manager = NonceManager::new(Address::zero());
+
+        mock_provider.push(EthersU256::from(7)).unwrap();
+        assert_eq!(nonce_manager.next_nonce(&provider).await.unwrap(), 7.into());
+        assert_eq!(nonce_manager.next_nonce(&provider).await.unwrap(), 8.into());
+
+        // The latest nonce is simply handed out again
+        assert!(!nonce_manager.release(8.into()).await);
+        assert_eq!(nonce_manager.next_nonce(&provider).await.unwrap(), 8.into());
+
+        // Nonce 8 was allocated after 7, so releasing 7 leaves a gap
+        assert!(nonce_manager.release(7.into()).await);
+        assert_eq!(nonce_manager.next_nonce(&provider).await.unwrap(), 9.into());
+    }
+
+    #[tokio::test]
+    async fn test_is_unused() {
+        let mock_provider = Arc::new(MockProvider::new());
+        let provider = Provider::new(mock_provider.clone());
+        let nonce_manager = NonceManager::new(Address::zero());
+
+        // 5 transactions were included, i.e. nonces 0 to 4 were used
+        mock_provider.push(EthersU256::from(5)).unwrap();
+        assert!(!nonce_manager.is_unused(&provider, 4.into()).await.unwrap());
+        mock_provider.push(EthersU256::from(5)).unwrap();
+        assert!(nonce_manager.is_unused(&provider, 5.into()).await.unwrap());
+    }
+}
@@ -1,12 +1,15 @@
 use std::num::NonZeroU64;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use ethers::{
     abi::Detokenize,
     prelude::{NameOrAddress, TransactionReceipt},
     providers::{JsonRpcClient, PendingTransaction, ProviderError},
-    types::{Block, Eip1559TransactionRequest, TxHash},
+    types::{
+        transaction::eip2718::TypedTransaction, Block, Eip1559TransactionRequest,
+        TransactionRequest, TxHash,
+    },
 };
 use ethers_contract::builders::ContractCall;
 use ethers_core::{
@@ -17,9 +20,10 @@ use ethers_core::{
     },
 };
 use hyperlane_core::{utils::bytes_to_hex, ChainCommunicationError, ChainResult, H256, U256};
+use tokio::time::sleep;
 use tracing::{debug, error, info, warn};
 
-use crate::{Middleware, TransactionOverrides};
+use crate::{nonce::NonceManager, FeeEscalation, Middleware, TransactionOverrides};
 
 /// An amount of gas to add to the estimated gas
 pub const GAS_ESTIMATE_BUFFER: u32 = 75_000;
@@ -30,6 +34,19 @@ pub fn apply_gas_estimate_buffer(gas: U256) -> U256 {
 
 const PENDING_TRANSACTION_POLLING_INTERVAL: Duration = Duration::from_secs(2);
 
+/// How long to wait for a transaction to be included after sending it
+const PENDING_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(150);
+
+/// How much to bump the fees of a transaction that replaces a stuck one, in
+/// percent.
+const REPLACEMENT_FEE_BUMP_PERCENT: u64 = 15;
+
+/// The minimum fee bump, in percent, for nodes to accept a replacement
+const MIN_REPLACEMENT_BUMP_PERCENT: u64 = 10;
+
+/// Gas limit of the no-op transfer used to fill a nonce gap
+const NOOP_TRANSFER_GAS_LIMIT: u64 = 21_000;
+
 /// Dispatches a transaction, logs the tx id, and returns the result
 pub(crate) async fn report_tx<M, D>(tx: ContractCall<M, D>) -> ChainResult<TransactionReceipt>
 where
@@ -57,6 +74,409 @@ where
     track_pending_tx(dispatched).await
 }
 
+/// Dispatches a transaction with a nonce allocated by `nonce_manager`, so
+/// that it can be in flight at the same time as other transactions of the same
+/// signer.
+///
+/// With `fee_escalation`, the transaction is replaced with bumped fees whenever
+/// it isn't included in time. Otherwise, if the transaction isn't included
+/// before timing out and its nonce is still unused, it's stuck and would hold
+/// up every later nonce of the signer. It's then replaced once by the same
+/// transaction with bumped fees. Either way, the receipt of whichever
+/// transaction is included is returned.
+pub(crate) async fn report_tx_with_nonce<M, D>(
+    mut tx: ContractCall<M, D>,
+    provider: &M,
+    nonce_manager: &NonceManager,
+    fee_escalation: Option<&FeeEscalation>,
+) -> ChainResult<TransactionReceipt>
+where
+    M: Middleware + 'static,
+    D: Detokenize,
+{
+    let nonce = nonce_manager.next_nonce(provider).await?;
+    tx.tx.set_nonce(nonce);
+
+    let to = tx
+        .tx
+        .to()
+        .cloned()
+        .unwrap_or_else(|| NameOrAddress::Address(Default::default()));
+    info!(?to, ?nonce, from=?nonce_manager.address(), "Dispatching transaction");
+
+    let pending_tx = match tx.send().await {
+        Ok(pending_tx) => pending_tx.interval(PENDING_TRANSACTION_POLLING_INTERVAL),
+        Err(err) => {
+            // The nonce may be left unused
+            release_nonce(&tx.tx, provider, nonce_manager, nonce).await;
+            return Err(err.into());
+        }
+    };
+    let tx_hash: TxHash = *pending_tx;
+    if let Some(fee_escalation) = fee_escalation {
+        drop(pending_tx);
+        return track_with_fee_escalation(
+            tx,
+            provider,
+            nonce_manager,
+            nonce,
+            tx_hash,
+            fee_escalation,
+        )
+        .await;
+    }
+    match track_pending_tx(pending_tx).await {
+        Ok(receipt) => return Ok(receipt),
+        Err(
+            ChainCommunicationError::TransactionTimeout()
+            | ChainCommunicationError::TransactionDropped(_),
+        ) => {}
+        Err(err) => return Err(err),
+    }
+
+    if !nonce_manager.is_unused(provider, nonce).await? {
+        // The nonce was used, possibly by this very transaction
+        return fetch_receipt(provider, tx_hash)
+            .await?
+            .ok_or(ChainCommunicationError::TransactionDropped(tx_hash.into()));
+    }
+
+    warn!(
+        ?tx_hash,
+        ?nonce,
+        "Transaction wasn't included and its nonce is stuck, replacing it with bumped fees"
+    );
+    fill_missing_fees(&mut tx.tx, provider, tx_hash).await?;
+    bump_fees(&mut tx.tx, REPLACEMENT_FEE_BUMP_PERCENT, None);
+    let result = match tx.send().await {
+        Ok(pending_tx) => {
+            track_pending_tx(pending_tx.interval(PENDING_TRANSACTION_POLLING_INTERVAL)).await
+        }
+        Err(err) => Err(err.into()),
+    };
+    match result {
+        Ok(receipt) => Ok(receipt),
+        Err(err) => {
+            // The original transaction may still have been included
+            if let Some(receipt) = fetch_receipt(provider, tx_hash).await? {
+                return Ok(receipt);
+            }
+            // Read the nonce from the chain again, so that later transactions
+            // don't queue up behind a nonce that may never be used
+            nonce_manager.reset().await;
+            Err(err)
+        }
+    }
+}
+
+/// Waits for one of the transactions sent with `nonce` to be included. Whenever
+/// the latest of them isn't included within `blocks_before_bump` blocks, it's
+/// replaced by the same transaction with bumped fees, until the fees can't be
+/// bumped enough for a replacement without exceeding the cap.
+async fn track_with_fee_escalation<M, D>(
+    mut tx: ContractCall<M, D>,
+    provider: &M,
+    nonce_manager: &NonceManager,
+    nonce: EthersU256,
+    tx_hash: TxHash,
+    fee_escalation: &FeeEscalation,
+) -> ChainResult<TransactionReceipt>
+where
+    M: Middleware + 'static,
+    D: Detokenize,
+{
+    info!(?tx_hash, "Dispatched tx");
+
+    let mut tx_hashes = vec![tx_hash];
+    let mut escalator = FeeEscalator::new(
+        fee_escalation,
+        Instant::now(),
+        block_number(provider).await?,
+    );
+    loop {
+        sleep(PENDING_TRANSACTION_POLLING_INTERVAL).await;
+
+        if !nonce_manager.is_unused(provider, nonce).await? {
+            // One of the transactions was included, most likely the latest
+            for tx_hash in tx_hashes.iter().rev() {
+                if let Some(receipt) = fetch_receipt(provider, *tx_hash).await? {
+                    info!(
+                        ?tx_hash,
+                        replacements = tx_hashes.len() - 1,
+                        "confirmed transaction"
+                    );
+                    return Ok(receipt);
+                }
+            }
+            // The nonce was used by a transaction that wasn't sent from here
+            return Err(ChainCommunicationError::TransactionDropped(tx_hash.into()));
+        }
+        if escalator.timed_out(Instant::now()) {
+            error!(?tx_hashes, "waiting for receipt timed out");
+            nonce_manager.reset().await;
+            return Err(ChainCommunicationError::TransactionTimeout());
+        }
+        if !escalator.is_escalating() {
+            continue;
+        }
+
+        let block = block_number(provider).await?;
+        if !escalator.bump_due(block) {
+            continue;
+        }
+        let latest_hash = tx_hashes[tx_hashes.len() - 1];
+        fill_missing_fees(&mut tx.tx, provider, latest_hash).await?;
+        if !escalator.bump(&mut tx.tx, block) {
+            warn!(
+                ?latest_hash,
+                ?nonce,
+                max_fee_per_gas = ?fee_escalation.max_fee_per_gas,
+                "Fees can't be bumped any further without exceeding the cap, waiting for the pending transaction"
+            );
+            continue;
+        }
+        match tx.send().await {
+            Ok(pending_tx) => {
+                let replacement_hash: TxHash = *pending_tx;
+                warn!(
+                    ?replacement_hash,
+                    replaced = ?latest_hash,
+                    ?nonce,
+                    gas_price = ?tx.tx.gas_price(),
+                    "Transaction wasn't included in time, replaced it with bumped fees"
+                );
+                tx_hashes.push(replacement_hash);
+            }
+            Err(err) => {
+                // e.g. if one of the transactions was included in the meantime
+                warn!(error=?err, ?nonce, "Failed to send replacement transaction");
+            }
+        }
+    }
+}
+
+/// Decides when the pending transactions of a nonce are replaced with bumped
+/// fees, and when to give up on them.
+#[derive(Debug)]
+struct FeeEscalator<'a> {
+    conf: &'a FeeEscalation,
+    /// When the first transaction was sent. Replacements don't extend the
+    /// timeout, so that a nonce that's never used doesn't stall forever.
+    first_sent_at: Instant,
+    /// The block at which the latest transaction was sent
+    sent_at_block: u64,
+    /// Whether the fees can still be bumped enough without exceeding the cap
+    escalating: bool,
+}
+
+impl<'a> FeeEscalator<'a> {
+    fn new(conf: &'a FeeEscalation, first_sent_at: Instant, sent_at_block: u64) -> Self {
+        Self {
+            conf,
+            first_sent_at,
+            sent_at_block,
+            escalating: true,
+        }
+    }
+
+    fn timed_out(&self, now: Instant) -> bool {
+        now.saturating_duration_since(self.first_sent_at) >= PENDING_TRANSACTION_TIMEOUT
+    }
+
+    fn is_escalating(&self) -> bool {
+        self.escalating
+    }
+
+    /// Whether the latest transaction has been pending for long enough at
+    /// `block` to be replaced
+    fn bump_due(&self, block: u64) -> bool {
+        self.escalating && block >= self.sent_at_block + u64::from(self.conf.blocks_before_bump)
+    }
+
+    /// Bumps the fees of `tx` to replace the latest transaction at `block`.
+    /// Returns false and stops escalating if that's not possible without
+    /// exceeding the cap.
+    fn bump(&mut self, tx: &mut TypedTransaction, block: u64) -> bool {
+        self.sent_at_block = block;
+        let cap: EthersU256 = self.conf.max_fee_per_gas.into();
+        if !bump_fees(tx, self.conf.bump_percent.into(), Some(cap)) {
+            self.escalating = false;
+        }
+        self.escalating
+    }
+}
+
+/// Gives back `nonce` after sending its transaction failed. If the signer's
+/// later nonces were already allocated, they can't be included until `nonce`
+/// is used, so it's filled with a no-op transfer from the signer to itself.
+async fn release_nonce<M: Middleware>(
+    tx: &TypedTransaction,
+    provider: &M,
+    nonce_manager: &NonceManager,
+    nonce: EthersU256,
+) {
+    if !nonce_manager.release(nonce).await {
+        return;
+    }
+    let signer = nonce_manager.address();
+    let noop: TypedTransaction = match tx {
+        TypedTransaction::Eip1559(_) => Eip1559TransactionRequest::new()
+            .from(signer)
+            .to(signer)
+            .value(EthersU256::zero())
+            .nonce(nonce)
+            .gas(NOOP_TRANSFER_GAS_LIMIT)
+            .into(),
+        _ => TransactionRequest::new()
+            .from(signer)
+            .to(signer)
+            .value(EthersU256::zero())
+            .nonce(nonce)
+            .gas(NOOP_TRANSFER_GAS_LIMIT)
+            .into(),
+    };
+    match provider.send_transaction(noop, None).await {
+        Ok(pending_tx) => {
+            let tx_hash: TxHash = *pending_tx;
+            warn!(
+                ?tx_hash,
+                ?nonce,
+                "Filling the nonce gap left by a failed transaction with a no-op"
+            );
+        }
+        Err(err) => {
+            error!(error=?err, ?nonce, "Failed to fill the nonce gap left by a failed transaction, later transactions of the signer are stuck until it's used");
+        }
+    }
+}
+
+async fn block_number<M: Middleware>(provider: &M) -> ChainResult<u64> {
+    Ok(provider
+        .get_block_number()
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+        .as_u64())
+}
+
+async fn fetch_receipt<M: Middleware>(
+    provider: &M,
+    tx_hash: TxHash,
+) -> ChainResult<Option<TransactionReceipt>> {
+    provider
+        .get_transaction_receipt(tx_hash)
+        .await
+        .map_err(ChainCommunicationError::from_other)
+}
+
+fn fees_missing(tx: &TypedTransaction) -> bool {
+    match tx {
+        TypedTransaction::Eip1559(inner) => {
+            inner.max_fee_per_gas.is_none() || inner.max_priority_fee_per_gas.is_none()
+        }
+        _ => tx.gas_price().is_none(),
+    }
+}
+
+/// Fees that weren't set explicitly are filled in by the middleware when
+/// sending, so they're taken from the transaction with hash `sent` to be able
+/// to bump them. If it isn't known anymore, the current gas price is used.
+async fn fill_missing_fees<M: Middleware>(
+    tx: &mut TypedTransaction,
+    provider: &M,
+    sent: TxHash,
+) -> ChainResult<()> {
+    if !fees_missing(tx) {
+        return Ok(());
+    }
+    if let Some(sent) = provider
+        .get_transaction(sent)
+        .await
+        .map_err(ChainCommunicationError::from_other)?
+    {
+        match tx {
+            TypedTransaction::Eip1559(inner) => {
+                inner.max_fee_per_gas = inner.max_fee_per_gas.or(sent.max_fee_per_gas);
+                inner.max_priority_fee_per_gas = inner
+                    .max_priority_fee_per_gas
+                    .or(sent.max_priority_fee_per_gas);
+            }
+            _ => {
+                if let Some(gas_price) = sent.gas_price {
+                    tx.set_gas_price(gas_price);
+                }
+            }
+        }
+    }
+    if fees_missing(tx) {
+        let gas_price = provider
+            .get_gas_price()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        match tx {
+            TypedTransaction::Eip1559(inner) => {
+                inner.max_fee_per_gas.get_or_insert(gas_price);
+                inner.max_priority_fee_per_gas.get_or_insert(gas_price);
+            }
+            _ => {
+                tx.set_gas_price(gas_price);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Bumps the fees of a transaction by `percent`, without raising them above
+/// `cap`. Nodes only accept a replacement if every fee is bumped by at least
+/// `MIN_REPLACEMENT_BUMP_PERCENT`, so if that's not possible under the cap the
+/// transaction is left as it is. Returns whether the fees were bumped.
+fn bump_fees(tx: &mut TypedTransaction, percent: u64, cap: Option<EthersU256>) -> bool {
+    let bump = |fee: EthersU256| {
+        let bumped = fee * EthersU256::from(100 + percent) / 100 + EthersU256::one();
+        match cap {
+            Some(cap) => bumped.min(cap),
+            None => bumped,
+        }
+    };
+    let is_valid_bump = |fee: EthersU256, bumped: EthersU256| {
+        // Rounded up, like nodes do
+        let min_bumped = (fee * EthersU256::from(100 + MIN_REPLACEMENT_BUMP_PERCENT)
+            + EthersU256::from(99))
+            / 100;
+        bumped >= min_bumped
+    };
+    match tx {
+        TypedTransaction::Eip1559(inner) => {
+            let (Some(max_fee), Some(max_priority_fee)) =
+                (inner.max_fee_per_gas, inner.max_priority_fee_per_gas)
+            else {
+                return false;
+            };
+            let bumped_max_fee = bump(max_fee);
+            // The priority fee can't be higher than the max fee
+            let bumped_max_priority_fee = bump(max_priority_fee).min(bumped_max_fee);
+            if !is_valid_bump(max_fee, bumped_max_fee)
+                || !is_valid_bump(max_priority_fee, bumped_max_priority_fee)
+            {
+                return false;
+            }
+            inner.max_fee_per_gas = Some(bumped_max_fee);
+            inner.max_priority_fee_per_gas = Some(bumped_max_priority_fee);
+            true
+        }
+        _ => match tx.gas_price() {
+            Some(gas_price) => {
+                let bumped = bump(gas_price);
+                if !is_valid_bump(gas_price, bumped) {
+                    return false;
+                }
+                tx.set_gas_price(bumped);
+                true
+            }
+            None => false,
+        },
+    }
+}
+
 pub(crate) async fn track_pending_tx<P: JsonRpcClient>(
     pending_tx: PendingTransaction<'_, P>,
 ) -> ChainResult<TransactionReceipt> {
@@ -64,7 +484,7 @@ pub(crate) async fn track_pending_tx<P: JsonRpcClient>(
 
     info!(?tx_hash, "Dispatched tx");
 
-    match tokio::time::timeout(Duration::from_secs(150), pending_tx).await {
+    match tokio::time::timeout(PENDING_TRANSACTION_TIMEOUT, pending_tx).await {
         // all good
         Ok(Ok(Some(receipt))) => {
             info!(?tx_hash, "confirmed transaction");
@@ -237,3 +657,126 @@ where
         Ok(call)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bump_legacy_fees() {
+        let mut tx = TypedTransaction::Legacy(TransactionRequest::new().gas_price(100));
+        assert!(bump_fees(&mut tx, 10, None));
+        assert_eq!(tx.gas_price(), Some(111.into()));
+
+        // Bumped up to the cap
+        assert!(bump_fees(&mut tx, 20, Some(125.into())));
+        assert_eq!(tx.gas_price(), Some(125.into()));
+
+        // A bump of less than 10% would be rejected as a replacement, so the
+        // fees are left as they are
+        assert!(!bump_fees(&mut tx, 20, Some(130.into())));
+        assert_eq!(tx.gas_price(), Some(125.into()));
+
+        // Fees above the cap aren't lowered
+        assert!(!bump_fees(&mut tx, 10, Some(100.into())));
+        assert_eq!(tx.gas_price(), Some(125.into()));
+    }
+
+    #[test]
+    fn test_bump_eip1559_fees() {
+        let mut tx = TypedTransaction::Eip1559(
+            Eip1559TransactionRequest::new()
+                .max_fee_per_gas(100)
+                .max_priority_fee_per_gas(50),
+        );
+        assert!(bump_fees(&mut tx, 20, Some(130.into())));
+        let TypedTransaction::Eip1559(inner) = &tx else {
+            panic!("Expected an EIP-1559 transaction");
+        };
+        assert_eq!(inner.max_fee_per_gas, Some(121.into()));
+        assert_eq!(inner.max_priority_fee_per_gas, Some(61.into()));
+
+        // The max fee can only be bumped by 7% under the cap, so neither fee is
+        // bumped even though the priority fee could be
+        assert!(!bump_fees(&mut tx, 20, Some(130.into())));
+        let TypedTransaction::Eip1559(inner) = &tx else {
+            panic!("Expected an EIP-1559 transaction");
+        };
+        assert_eq!(inner.max_fee_per_gas, Some(121.into()));
+        assert_eq!(inner.max_priority_fee_per_gas, Some(61.into()));
+    }
+
+    #[test]
+    fn test_bump_eip1559_priority_fee_is_capped_by_max_fee() {
+        let mut tx = TypedTransaction::Eip1559(
+            Eip1559TransactionRequest::new()
+                .max_fee_per_gas(100)
+                .max_priority_fee_per_gas(100),
+        );
+        assert!(bump_fees(&mut tx, 20, Some(115.into())));
+        let TypedTransaction::Eip1559(inner) = &tx else {
+            panic!("Expected an EIP-1559 transaction");
+        };
+        assert_eq!(inner.max_fee_per_gas, Some(115.into()));
+        assert_eq!(inner.max_priority_fee_per_gas, Some(115.into()));
+    }
+
+    fn fee_escalation(blocks_before_bump: u32, max_fee_per_gas: u64) -> FeeEscalation {
+        FeeEscalation {
+            blocks_before_bump,
+            bump_percent: 20,
+            max_fee_per_gas: max_fee_per_gas.into(),
+        }
+    }
+
+    #[test]
+    fn test_fee_escalation_bumps_every_few_blocks_until_the_cap() {
+        let conf = fee_escalation(3, 200);
+        let mut tx = TypedTransaction::Legacy(TransactionRequest::new().gas_price(100));
+        let mut escalator = FeeEscalator::new(&conf, Instant::now(), 10);
+
+        // Replace the transaction whenever it's due, for as long as possible
+        let mut replaced_at = vec![];
+        for block in 10..40 {
+            if escalator.bump_due(block) && escalator.bump(&mut tx, block) {
+                replaced_at.push((block, tx.gas_price().unwrap().as_u64()));
+            }
+        }
+        // 100 -> 121 -> 146 -> 176 -> 200 (capped), then a bump to at least 220
+        // isn't possible anymore
+        assert_eq!(
+            replaced_at,
+            vec![(13, 121), (16, 146), (19, 176), (22, 200)]
+        );
+        assert!(!escalator.is_escalating());
+        assert!(!escalator.bump_due(100));
+        assert_eq!(tx.gas_price(), Some(200.into()));
+    }
+
+    #[test]
+    fn test_fee_escalation_stops_if_first_bump_exceeds_the_cap() {
+        let conf = fee_escalation(1, 105);
+        let mut tx = TypedTransaction::Legacy(TransactionRequest::new().gas_price(100));
+        let mut escalator = FeeEscalator::new(&conf, Instant::now(), 10);
+
+        assert!(!escalator.bump_due(10));
+        assert!(escalator.bump_due(11));
+        assert!(!escalator.bump(&mut tx, 11));
+        assert!(!escalator.is_escalating());
+        assert!(!escalator.bump_due(12));
+        assert_eq!(tx.gas_price(), Some(100.into()));
+    }
+
+    #[test]
+    fn test_fee_escalation_timeout_is_not_extended_by_replacements() {
+        let conf = fee_escalation(1, 1_000);
+        let mut tx = TypedTransaction::Legacy(TransactionRequest::new().gas_price(100));
+        let first_sent_at = Instant::now();
+        let mut escalator = FeeEscalator::new(&conf, first_sent_at, 10);
+
+        assert!(escalator.bump(&mut tx, 11));
+        assert!(escalator.bump(&mut tx, 12));
+        assert!(!escalator.timed_out(first_sent_at + PENDING_TRANSACTION_TIMEOUT / 2));
+        assert!(escalator.timed_out(first_sent_at + PENDING_TRANSACTION_TIMEOUT));
+    }
+}
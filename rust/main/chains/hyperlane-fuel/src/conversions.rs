@@ -58,3 +58,41 @@ impl_h256!(
     |v| fuels::types::Bytes32::new(v.0),
     |v| H256::from(*v)
 );
+
+impl_h256!(
+    fuels::types::Address,
+    |v| fuels::types::Address::new(v.0),
+    |v| H256::from(<[u8; 32]>::from(v))
+);
+
+#[cfg(test)]
+mod tests {
+    use fuels::{
+        prelude::{Bech32ContractId, ContractId},
+        types::{Address, Bits256, Bytes32},
+    };
+    use hyperlane_core::H256;
+
+    use super::{FuelFromH256, FuelIntoH256};
+
+    fn h256() -> H256 {
+        H256::from_low_u64_be(0xdead_beef)
+    }
+
+    #[test]
+    fn test_h256_round_trips() {
+        let v = h256();
+        assert_eq!(Bech32ContractId::from_h256(&v).into_h256(), v);
+        assert_eq!(Bits256::from_h256(&v).into_h256(), v);
+        assert_eq!(ContractId::from_h256(&v).into_h256(), v);
+        assert_eq!(Bytes32::from_h256(&v).into_h256(), v);
+        assert_eq!(Address::from_h256(&v).into_h256(), v);
+    }
+
+    #[test]
+    fn test_into_h256_by_reference() {
+        let v = h256();
+        let id = ContractId::from_h256(&v);
+        assert_eq!((&id).into_h256(), id.into_h256());
+    }
+}
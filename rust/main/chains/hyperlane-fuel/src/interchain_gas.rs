@@ -1,29 +1,62 @@
 use std::ops::RangeInclusive;
 
 use async_trait::async_trait;
-
+use fuels::prelude::{Bech32ContractId, WalletUnlocked};
 use hyperlane_core::{
-    ChainResult, HyperlaneChain, HyperlaneContract, Indexed, Indexer, InterchainGasPaymaster,
+    ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract, Indexed, Indexer,
+    InterchainGasPaymaster, SequenceAwareIndexer, U256,
 };
 use hyperlane_core::{HyperlaneDomain, HyperlaneProvider, InterchainGasPayment, LogMeta, H256};
 
+use crate::{
+    contracts::interchain_gas_paymaster::{
+        GasPaymentEvent, InterchainGasPaymaster as FuelInterchainGasPaymasterInner,
+    },
+    conversions::*,
+    ConnectionConf, FuelProvider,
+};
+
 /// A reference to an IGP contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelInterchainGasPaymaster {}
+pub struct FuelInterchainGasPaymaster {
+    contract: FuelInterchainGasPaymasterInner<WalletUnlocked>,
+    domain: HyperlaneDomain,
+    provider: FuelProvider,
+}
+
+impl FuelInterchainGasPaymaster {
+    /// Create a new fuel IGP contract interface
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        wallet: Option<WalletUnlocked>,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        let wallet = fuel_provider.wallet_or_read_only(wallet);
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelInterchainGasPaymaster {
+            contract: FuelInterchainGasPaymasterInner::new(address, wallet),
+            domain: locator.domain.clone(),
+            provider: fuel_provider,
+        })
+    }
+}
 
 impl HyperlaneContract for FuelInterchainGasPaymaster {
     fn address(&self) -> H256 {
-        todo!()
+        self.contract.contract_id().into_h256()
     }
 }
 
 impl HyperlaneChain for FuelInterchainGasPaymaster {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
@@ -31,7 +64,29 @@ impl InterchainGasPaymaster for FuelInterchainGasPaymaster {}
 
 /// Struct that retrieves event data for a Fuel IGP contract
 #[derive(Debug)]
-pub struct FuelInterchainGasPaymasterIndexer {}
+pub struct FuelInterchainGasPaymasterIndexer {
+    contract: FuelInterchainGasPaymasterInner<WalletUnlocked>,
+    provider: FuelProvider,
+}
+
+impl FuelInterchainGasPaymasterIndexer {
+    /// Create a new fuel IGP indexer
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        wallet: Option<WalletUnlocked>,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        let wallet = fuel_provider.wallet_or_read_only(wallet);
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelInterchainGasPaymasterIndexer {
+            contract: FuelInterchainGasPaymasterInner::new(address, wallet),
+            provider: fuel_provider,
+        })
+    }
+}
 
 #[async_trait]
 impl Indexer<InterchainGasPayment> for FuelInterchainGasPaymasterIndexer {
@@ -39,10 +94,32 @@ impl Indexer<InterchainGasPayment> for FuelInterchainGasPaymasterIndexer {
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<InterchainGasPayment>, LogMeta)>> {
-        todo!()
+        self.provider
+            .index_logs_in_range(
+                range,
+                self.contract.contract_id(),
+                &self.contract.log_decoder(),
+                |event: GasPaymentEvent| InterchainGasPayment {
+                    message_id: event.message_id.into_h256(),
+                    destination: event.destination_domain,
+                    payment: U256::from(event.payment),
+                    gas_amount: U256::from(event.gas_amount),
+                },
+            )
+            .await
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
-        todo!()
+        self.provider.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<InterchainGasPayment> for FuelInterchainGasPaymasterIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = self.get_finalized_block_number().await?;
+
+        // No sequence for gas payments.
+        Ok((None, tip))
     }
 }
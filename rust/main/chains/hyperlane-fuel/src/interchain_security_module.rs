@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use fuels::{
+    prelude::{Bech32ContractId, WalletUnlocked},
+    types::Bytes,
+};
+use tracing::instrument;
+
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, InterchainSecurityModule, ModuleType,
+    RawHyperlaneMessage, H256, U256,
+};
+
+use crate::{
+    contracts::interchain_security_module::{
+        InterchainSecurityModule as FuelInterchainSecurityModuleInner, ModuleType as FuelModuleType,
+    },
+    conversions::*,
+    ConnectionConf, FuelProvider,
+};
+
+/// A reference to an InterchainSecurityModule contract on some Fuel chain
+#[derive(Debug)]
+pub struct FuelInterchainSecurityModule {
+    contract: FuelInterchainSecurityModuleInner<WalletUnlocked>,
+    domain: HyperlaneDomain,
+    provider: FuelProvider,
+}
+
+impl FuelInterchainSecurityModule {
+    /// Create a new fuel ISM contract interface
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        wallet: Option<WalletUnlocked>,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        let wallet = fuel_provider.wallet_or_read_only(wallet);
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelInterchainSecurityModule {
+            contract: FuelInterchainSecurityModuleInner::new(address, wallet),
+            domain: locator.domain.clone(),
+            provider: fuel_provider,
+        })
+    }
+}
+
+impl HyperlaneContract for FuelInterchainSecurityModule {
+    fn address(&self) -> H256 {
+        self.contract.contract_id().into_h256()
+    }
+}
+
+impl HyperlaneChain for FuelInterchainSecurityModule {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl InterchainSecurityModule for FuelInterchainSecurityModule {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn module_type(&self) -> ChainResult<ModuleType> {
+        self.contract
+            .methods()
+            .module_type()
+            .simulate()
+            .await
+            .map(|r| match r.value {
+                FuelModuleType::UNUSED => ModuleType::Unused,
+                FuelModuleType::ROUTING => ModuleType::Routing,
+                FuelModuleType::AGGREGATION => ModuleType::Aggregation,
+                FuelModuleType::LEGACY_MULTISIG => ModuleType::LegacyMultisig,
+                FuelModuleType::MERKLE_ROOT_MULTISIG => ModuleType::MerkleRootMultisig,
+                FuelModuleType::MESSAGE_ID_MULTISIG => ModuleType::MessageIdMultisig,
+                FuelModuleType::NULL => ModuleType::Null,
+                FuelModuleType::CCIP_READ => ModuleType::CcipRead,
+            })
+            .map_err(ChainCommunicationError::from_other)
+    }
+
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn dry_run_verify(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<Option<U256>> {
+        self.contract
+            .methods()
+            .verify(
+                Bytes(metadata.to_vec()),
+                Bytes(RawHyperlaneMessage::from(message)),
+            )
+            .determine_missing_contracts(Some(3))
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .simulate()
+            .await
+            .map(|r| r.value.then_some(r.gas_used.into()))
+            .map_err(ChainCommunicationError::from_other)
+    }
+}
@@ -1,5 +1,7 @@
 use crate::{
-    contracts::mailbox::Mailbox as FuelMailboxInner, conversions::*, ConnectionConf, FuelProvider,
+    contracts::mailbox::{DispatchEvent, Mailbox as FuelMailboxInner, ProcessEvent},
+    conversions::*,
+    ConnectionConf, FuelProvider,
 };
 use async_trait::async_trait;
 use fuels::{
@@ -24,6 +26,8 @@ use tracing::{instrument, warn};
 /// A reference to a Mailbox contract on some Fuel chain
 pub struct FuelMailbox {
     contract: FuelMailboxInner<WalletUnlocked>,
+    /// Whether the contract instance was built with a configured signer
+    has_signer: bool,
     provider: FuelProvider,
     domain: HyperlaneDomain,
 }
@@ -33,15 +37,17 @@ impl FuelMailbox {
     pub async fn new(
         conf: &ConnectionConf,
         locator: ContractLocator<'_>,
-        mut wallet: WalletUnlocked,
+        wallet: Option<WalletUnlocked>,
     ) -> ChainResult<Self> {
-        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await;
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
 
-        wallet.set_provider(fuel_provider.provider().clone());
+        let has_signer = wallet.is_some();
+        let wallet = fuel_provider.wallet_or_read_only(wallet);
         let address = Bech32ContractId::from_h256(&locator.address);
 
         Ok(FuelMailbox {
             contract: FuelMailboxInner::new(address, wallet),
+            has_signer,
             domain: locator.domain.clone(),
             provider: fuel_provider,
         })
@@ -132,6 +138,10 @@ impl Mailbox for FuelMailbox {
         metadata: &[u8],
         tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
+        if !self.has_signer {
+            return Err(ChainCommunicationError::SignerUnavailable);
+        }
+
         // The max gas limit per transaction is 30000000 so it should always be safe to convert to u64
         let tx_policies = match tx_gas_limit {
             Some(gas_limit) if gas_limit <= U256::from(u64::MAX) => {
@@ -213,8 +223,8 @@ impl Mailbox for FuelMailbox {
         })
     }
 
-    fn process_calldata(&self, message: &HyperlaneMessage, metadata: &[u8]) -> Vec<u8> {
-        // Seems like this is not needed for Fuel, as it's only used in mocks
+    fn process_calldata(&self, _message: &HyperlaneMessage, _metadata: &[u8]) -> Vec<u8> {
+        // Fuel has no notion of EVM-style calldata and this is only used in mocks
         todo!()
     }
 }
@@ -231,10 +241,11 @@ impl FuelMailboxIndexer {
     pub async fn new(
         conf: &ConnectionConf,
         locator: ContractLocator<'_>,
-        wallet: WalletUnlocked,
+        wallet: Option<WalletUnlocked>,
     ) -> ChainResult<Self> {
-        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await;
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
 
+        let wallet = fuel_provider.wallet_or_read_only(wallet);
         let address = Bech32ContractId::from_h256(&locator.address);
         let contract = FuelMailboxInner::new(address, wallet);
 
@@ -251,9 +262,13 @@ impl Indexer<HyperlaneMessage> for FuelMailboxIndexer {
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<HyperlaneMessage>, LogMeta)>> {
-        let mailbox_address = self.contract.contract_id().clone();
         self.provider
-            .index_logs_in_range(range, mailbox_address)
+            .index_logs_in_range(
+                range,
+                self.contract.contract_id(),
+                &self.contract.log_decoder(),
+                |event: DispatchEvent| HyperlaneMessage::from(event.message.bytes.0),
+            )
             .await
     }
 
@@ -268,7 +283,14 @@ impl Indexer<H256> for FuelMailboxIndexer {
         &self,
         range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        todo!() // Needed for scraper
+        self.provider
+            .index_logs_in_range(
+                range,
+                self.contract.contract_id(),
+                &self.contract.log_decoder(),
+                |event: ProcessEvent| event.message_id.into_h256(),
+            )
+            .await
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
@@ -0,0 +1,203 @@
+use std::{num::NonZeroU64, ops::RangeInclusive};
+
+use async_trait::async_trait;
+use fuels::prelude::{Bech32ContractId, WalletUnlocked};
+use tracing::instrument;
+
+use hyperlane_core::{
+    accumulator::incremental::IncrementalMerkle, ChainCommunicationError, ChainResult, Checkpoint,
+    ContractLocator, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneProvider,
+    Indexed, Indexer, LogMeta, MerkleTreeHook, MerkleTreeInsertion, SequenceAwareIndexer, H256,
+};
+
+use crate::{
+    contracts::merkle_tree_hook::{
+        InsertedIntoTreeEvent, MerkleTreeHook as FuelMerkleTreeHookInner,
+    },
+    conversions::*,
+    ConnectionConf, FuelProvider,
+};
+
+/// Fuel has instant finality, so point-in-time queries are not supported.
+fn ensure_no_lag(lag: Option<NonZeroU64>) -> ChainResult<()> {
+    match lag {
+        None => Ok(()),
+        Some(_) => Err(ChainCommunicationError::from_other_str(
+            "Fuel does not support querying point-in-time",
+        )),
+    }
+}
+
+/// A reference to a MerkleTreeHook contract on some Fuel chain
+#[derive(Debug)]
+pub struct FuelMerkleTreeHook {
+    contract: FuelMerkleTreeHookInner<WalletUnlocked>,
+    domain: HyperlaneDomain,
+    provider: FuelProvider,
+}
+
+impl FuelMerkleTreeHook {
+    /// Create a new fuel merkle tree hook contract interface
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        wallet: Option<WalletUnlocked>,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        let wallet = fuel_provider.wallet_or_read_only(wallet);
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelMerkleTreeHook {
+            contract: FuelMerkleTreeHookInner::new(address, wallet),
+            domain: locator.domain.clone(),
+            provider: fuel_provider,
+        })
+    }
+}
+
+impl HyperlaneContract for FuelMerkleTreeHook {
+    fn address(&self) -> H256 {
+        self.contract.contract_id().into_h256()
+    }
+}
+
+impl HyperlaneChain for FuelMerkleTreeHook {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        Box::new(self.provider.clone())
+    }
+}
+
+#[async_trait]
+impl MerkleTreeHook for FuelMerkleTreeHook {
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn tree(&self, lag: Option<NonZeroU64>) -> ChainResult<IncrementalMerkle> {
+        ensure_no_lag(lag)?;
+
+        let tree = self
+            .contract
+            .methods()
+            .tree()
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+
+        let branch: [H256; 32] = tree
+            .branch
+            .into_iter()
+            .map(|node| node.into_h256())
+            .collect::<Vec<_>>()
+            .try_into()
+            .map_err(|_| {
+                ChainCommunicationError::from_other_str("Failed to build merkle branch array")
+            })?;
+
+        Ok(IncrementalMerkle::new(branch, tree.count as usize))
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn count(&self, lag: Option<NonZeroU64>) -> ChainResult<u32> {
+        ensure_no_lag(lag)?;
+
+        self.contract
+            .methods()
+            .count()
+            .simulate()
+            .await
+            .map(|r| r.value)
+            .map_err(ChainCommunicationError::from_other)
+    }
+
+    #[instrument(level = "debug", err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
+    async fn latest_checkpoint(&self, lag: Option<NonZeroU64>) -> ChainResult<Checkpoint> {
+        ensure_no_lag(lag)?;
+
+        let (root, index) = self
+            .contract
+            .methods()
+            .latest_checkpoint()
+            .simulate()
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+
+        Ok(Checkpoint {
+            merkle_tree_hook_address: self.address(),
+            mailbox_domain: self.domain.id(),
+            root: root.into_h256(),
+            index,
+        })
+    }
+}
+
+/// Struct that retrieves event data for a Fuel MerkleTreeHook contract
+#[derive(Debug)]
+pub struct FuelMerkleTreeHookIndexer {
+    contract: FuelMerkleTreeHookInner<WalletUnlocked>,
+    provider: FuelProvider,
+}
+
+impl FuelMerkleTreeHookIndexer {
+    /// Create a new fuel merkle tree hook indexer
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        wallet: Option<WalletUnlocked>,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        let wallet = fuel_provider.wallet_or_read_only(wallet);
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelMerkleTreeHookIndexer {
+            contract: FuelMerkleTreeHookInner::new(address, wallet),
+            provider: fuel_provider,
+        })
+    }
+}
+
+#[async_trait]
+impl Indexer<MerkleTreeInsertion> for FuelMerkleTreeHookIndexer {
+    async fn fetch_logs_in_range(
+        &self,
+        range: RangeInclusive<u32>,
+    ) -> ChainResult<Vec<(Indexed<MerkleTreeInsertion>, LogMeta)>> {
+        self.provider
+            .index_logs_in_range(
+                range,
+                self.contract.contract_id(),
+                &self.contract.log_decoder(),
+                |event: InsertedIntoTreeEvent| {
+                    MerkleTreeInsertion::new(event.index, event.message_id.into_h256())
+                },
+            )
+            .await
+    }
+
+    async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+        self.provider.get_finalized_block_number().await
+    }
+}
+
+#[async_trait]
+impl SequenceAwareIndexer<MerkleTreeInsertion> for FuelMerkleTreeHookIndexer {
+    async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
+        let tip = self.get_finalized_block_number().await?;
+
+        self.contract
+            .methods()
+            .count()
+            .simulate()
+            .await
+            .map(|r| (Some(r.value), tip))
+            .map_err(ChainCommunicationError::from_other)
+    }
+}
@@ -1,37 +1,83 @@
 use async_trait::async_trait;
+use fuels::{
+    prelude::{Bech32ContractId, WalletUnlocked},
+    types::Bytes,
+};
+use tracing::instrument;
 
 use hyperlane_core::{
-    ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage,
-    HyperlaneProvider, MultisigIsm, H256,
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, MultisigIsm, RawHyperlaneMessage, H256,
+};
+
+use crate::{
+    contracts::multisig_ism::MultisigIsm as FuelMultisigIsmInner, conversions::*, ConnectionConf,
+    FuelProvider,
 };
 
 /// A reference to a MultisigIsm contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelMultisigIsm {}
+pub struct FuelMultisigIsm {
+    contract: FuelMultisigIsmInner<WalletUnlocked>,
+    domain: HyperlaneDomain,
+    provider: FuelProvider,
+}
+
+impl FuelMultisigIsm {
+    /// Create a new fuel multisig ISM contract interface
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        wallet: Option<WalletUnlocked>,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        let wallet = fuel_provider.wallet_or_read_only(wallet);
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelMultisigIsm {
+            contract: FuelMultisigIsmInner::new(address, wallet),
+            domain: locator.domain.clone(),
+            provider: fuel_provider,
+        })
+    }
+}
 
 impl HyperlaneContract for FuelMultisigIsm {
     fn address(&self) -> H256 {
-        todo!()
+        self.contract.contract_id().into_h256()
     }
 }
 
 impl HyperlaneChain for FuelMultisigIsm {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
 #[async_trait]
 impl MultisigIsm for FuelMultisigIsm {
     /// Returns the validator and threshold needed to verify message
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn validators_and_threshold(
         &self,
         message: &HyperlaneMessage,
     ) -> ChainResult<(Vec<H256>, u8)> {
-        todo!()
+        self.contract
+            .methods()
+            .validators_and_threshold(Bytes(RawHyperlaneMessage::from(message)))
+            .simulate()
+            .await
+            .map(|r| {
+                let (validators, threshold) = r.value;
+                let validators = validators.into_iter().map(|v| v.into_h256()).collect();
+                (validators, threshold)
+            })
+            .map_err(ChainCommunicationError::from_other)
     }
 }
@@ -1,44 +1,203 @@
 use async_trait::async_trait;
+use fuels::{
+    prelude::{Bech32ContractId, WalletUnlocked},
+    tx::{Receipt, ScriptExecutionResult},
+    types::{Address, Bytes},
+};
+use tracing::{instrument, trace};
 
 use hyperlane_core::{
-    Announcement, ChainResult, HyperlaneChain, HyperlaneContract, HyperlaneDomain,
-    HyperlaneProvider, SignedType, TxOutcome, ValidatorAnnounce, H256, U256,
+    Announcement, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneProvider, SignedType, TxOutcome,
+    ValidatorAnnounce, H256, H512, U256,
+};
+
+use crate::{
+    contracts::validator_announce::ValidatorAnnounce as FuelValidatorAnnounceInner, conversions::*,
+    ConnectionConf, FuelProvider,
 };
 
 /// A reference to a ValidatorAnnounce contract on some Fuel chain
 #[derive(Debug)]
-pub struct FuelValidatorAnnounce {}
+pub struct FuelValidatorAnnounce {
+    contract: FuelValidatorAnnounceInner<WalletUnlocked>,
+    /// Whether the contract instance was built with a configured signer
+    has_signer: bool,
+    domain: HyperlaneDomain,
+    provider: FuelProvider,
+}
+
+impl FuelValidatorAnnounce {
+    /// Create a new fuel validator announce contract interface
+    pub async fn new(
+        conf: &ConnectionConf,
+        locator: ContractLocator<'_>,
+        wallet: Option<WalletUnlocked>,
+    ) -> ChainResult<Self> {
+        let fuel_provider = FuelProvider::new(locator.domain.clone(), conf).await?;
+
+        let has_signer = wallet.is_some();
+        let wallet = fuel_provider.wallet_or_read_only(wallet);
+        let address = Bech32ContractId::from_h256(&locator.address);
+
+        Ok(FuelValidatorAnnounce {
+            contract: FuelValidatorAnnounceInner::new(address, wallet),
+            has_signer,
+            domain: locator.domain.clone(),
+            provider: fuel_provider,
+        })
+    }
+
+    fn announce_args(announcement: SignedType<Announcement>) -> (Address, Bytes, Bytes) {
+        let validator = H256::from(announcement.value.validator);
+        (
+            Address::from_h256(&validator),
+            Bytes(announcement.value.storage_location.into_bytes()),
+            Bytes(announcement.signature.to_vec()),
+        )
+    }
+}
 
 impl HyperlaneContract for FuelValidatorAnnounce {
     fn address(&self) -> H256 {
-        todo!()
+        self.contract.contract_id().into_h256()
     }
 }
 
 impl HyperlaneChain for FuelValidatorAnnounce {
     fn domain(&self) -> &HyperlaneDomain {
-        todo!()
+        &self.domain
     }
 
     fn provider(&self) -> Box<dyn HyperlaneProvider> {
-        todo!()
+        Box::new(self.provider.clone())
     }
 }
 
 #[async_trait]
 impl ValidatorAnnounce for FuelValidatorAnnounce {
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn get_announced_storage_locations(
         &self,
         validators: &[H256],
     ) -> ChainResult<Vec<Vec<String>>> {
-        todo!()
+        let validators = validators.iter().map(Address::from_h256).collect();
+
+        self.contract
+            .methods()
+            .get_announced_storage_locations(validators)
+            .simulate()
+            .await
+            .map(|r| {
+                r.value
+                    .into_iter()
+                    .map(|locations| {
+                        locations
+                            .into_iter()
+                            .map(|location| String::from_utf8_lossy(&location.0).into_owned())
+                            .collect()
+                    })
+                    .collect()
+            })
+            .map_err(ChainCommunicationError::from_other)
     }
 
+    #[instrument(err, ret, skip(self))]
+    #[allow(clippy::blocks_in_conditions)] // TODO: `rustc` 1.80.1 clippy issue
     async fn announce(&self, announcement: SignedType<Announcement>) -> ChainResult<TxOutcome> {
-        todo!()
+        if !self.has_signer {
+            return Err(ChainCommunicationError::SignerUnavailable);
+        }
+        let (validator, storage_location, signature) = Self::announce_args(announcement);
+        let gas_price = self.provider.get_gas_price().await?;
+
+        let call_res = self
+            .contract
+            .methods()
+            .announce(validator, storage_location, signature)
+            .call()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        let success = call_res
+            .receipts
+            .iter()
+            .filter_map(|r| match r {
+                Receipt::ScriptResult { result, .. } => Some(result),
+                _ => None,
+            })
+            .any(|result| matches!(result, ScriptExecutionResult::Success));
+
+        let tx_id = call_res
+            .tx_id
+            .map(|id| H512::from(id.into_h256()))
+            .unwrap_or_default();
+        Ok(TxOutcome {
+            transaction_id: tx_id,
+            executed: success,
+            gas_used: call_res.gas_used.into(),
+            gas_price: gas_price.into(),
+        })
     }
 
     async fn announce_tokens_needed(&self, announcement: SignedType<Announcement>) -> Option<U256> {
-        todo!()
+        if !self.has_signer {
+            return None;
+        }
+        let (validator, storage_location, signature) = Self::announce_args(announcement);
+
+        let Ok(cost) = self
+            .contract
+            .methods()
+            .announce(validator, storage_location, signature)
+            .estimate_transaction_cost(None, None)
+            .await
+        else {
+            trace!("Unable to estimate announce transaction cost");
+            return None;
+        };
+
+        let signer = self.contract.account().address().to_string();
+        let Ok(balance) = self.provider.get_balance(signer).await else {
+            trace!("Unable to query balance");
+            return None;
+        };
+
+        Some(U256::from(cost.total_fee).saturating_sub(balance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hyperlane_core::{Announcement, Signature, SignedType, H160, H256, U256};
+
+    use super::FuelValidatorAnnounce;
+    use crate::conversions::FuelIntoH256;
+
+    #[test]
+    fn test_announce_args() {
+        let validator = H160::from_low_u64_be(42);
+        let signature = Signature {
+            r: U256::from(1),
+            s: U256::from(2),
+            v: 27,
+        };
+        let announcement = SignedType {
+            value: Announcement {
+                validator,
+                mailbox_address: H256::zero(),
+                mailbox_domain: 1,
+                storage_location: "s3://bucket/region".to_owned(),
+            },
+            signature,
+        };
+
+        let (address, storage_location, sig) = FuelValidatorAnnounce::announce_args(announcement);
+
+        assert_eq!(address.into_h256(), H256::from(validator));
+        assert_eq!(storage_location.0, b"s3://bucket/region".to_vec());
+        assert_eq!(sig.0, signature.to_vec());
+        assert_eq!(sig.0.len(), 65);
     }
 }
@@ -0,0 +1,25 @@
+#!/bin/sh
+
+# Copies the ABIs emitted by `forc build` into `./abis`, from which `build.rs`
+# generates the contract bindings.
+#
+# Usage: ./update_abis.sh <path to fuel contracts checkout> [debug|release]
+# Must be ran from the `rust/main/chains/hyperlane-fuel` directory.
+
+CONTRACTS_DIR="${1:?path to the fuel contracts checkout is required}"
+PROFILE="${2:-release}"
+
+copy() {
+    # $1 is the forc project directory relative to the checkout, $2 the ABI name
+    # used by the bindings in this crate
+    PROJECT_NAME="${1##*/}"
+    jq . < "$CONTRACTS_DIR"/"$1"/out/"$PROFILE"/"$PROJECT_NAME"-abi.json > ./abis/"$2".abi.json
+}
+
+copy contracts/mailbox Mailbox && \
+copy contracts/igp/gas-paymaster InterchainGasPaymaster && \
+copy contracts/validator-announce ValidatorAnnounce && \
+copy contracts/hooks/merkle-tree-hook MerkleTreeHook && \
+copy contracts/ism/multisig/message-id-multisig-ism MultisigIsm && \
+copy contracts/ism/routing/default-fallback-domain-routing-ism RoutingIsm && \
+copy contracts/test/test-ism InterchainSecurityModule
@@ -0,0 +1,115 @@
+use account_utils::DiscriminatorEncode;
+use async_trait::async_trait;
+
+use hyperlane_core::{
+    AggregationIsm, ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain,
+    HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, H256,
+};
+use hyperlane_sealevel_aggregation_ism::{
+    instruction::{Instruction as AggregationIsmInstruction, ModulesAndThreshold},
+    modules_and_threshold_pda_seeds,
+};
+use serializable_account_meta::SimulationReturnData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+};
+
+use crate::{utils::simulate_instruction, ConnectionConf, RpcClientWithDebug, SealevelProvider};
+
+/// A reference to a AggregationIsm contract on some Sealevel chain
+#[derive(Debug)]
+pub struct SealevelAggregationIsm {
+    payer: Option<Keypair>,
+    program_id: Pubkey,
+    domain: HyperlaneDomain,
+    provider: SealevelProvider,
+}
+
+impl SealevelAggregationIsm {
+    /// Create a new Sealevel AggregationIsm.
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator, payer: Option<Keypair>) -> Self {
+        let provider = SealevelProvider::new(locator.domain.clone(), conf);
+        let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
+
+        Self {
+            payer,
+            program_id,
+            domain: locator.domain.clone(),
+            provider,
+        }
+    }
+
+    fn rpc(&self) -> &RpcClientWithDebug {
+        self.provider.rpc()
+    }
+}
+
+impl HyperlaneContract for SealevelAggregationIsm {
+    fn address(&self) -> H256 {
+        self.program_id.to_bytes().into()
+    }
+}
+
+impl HyperlaneChain for SealevelAggregationIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        self.provider.provider()
+    }
+}
+
+#[async_trait]
+impl AggregationIsm for SealevelAggregationIsm {
+    /// Returns the `m` ISMs and `n` threshold needed to n-of-m verify the message
+    async fn modules_and_threshold(
+        &self,
+        _message: &HyperlaneMessage,
+    ) -> ChainResult<(Vec<H256>, u8)> {
+        let (modules_and_threshold_pda_key, _modules_and_threshold_pda_bump) =
+            Pubkey::try_find_program_address(modules_and_threshold_pda_seeds!(), &self.program_id)
+                .ok_or_else(|| {
+                    ChainCommunicationError::from_other_str(
+                        "Could not find program address for modules and threshold",
+                    )
+                })?;
+
+        let instruction = Instruction::new_with_bytes(
+            self.program_id,
+            &AggregationIsmInstruction::GetModulesAndThreshold
+                .encode()
+                .map_err(ChainCommunicationError::from_other)?[..],
+            vec![AccountMeta::new_readonly(
+                modules_and_threshold_pda_key,
+                false,
+            )],
+        );
+
+        let modules_and_threshold =
+            simulate_instruction::<SimulationReturnData<ModulesAndThreshold>>(
+                self.rpc(),
+                self.payer
+                    .as_ref()
+                    .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?,
+                instruction,
+            )
+            .await?
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str(
+                    "No return data was returned from the aggregation ism",
+                )
+            })?
+            .return_data;
+
+        let modules = modules_and_threshold
+            .modules
+            .into_iter()
+            .map(|module| module.to_bytes().into())
+            .collect();
+
+        Ok((modules, modules_and_threshold.threshold))
+    }
+}
@@ -1,16 +1,31 @@
-use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_client::{nonblocking::rpc_client::RpcClient, rpc_client::RpcClientConfig};
 use solana_sdk::commitment_config::CommitmentConfig;
+use url::Url;
+
+use crate::rpc_clients::SealevelFallbackRpcSender;
 
 /// Kludge to implement Debug for RpcClient.
 pub struct RpcClientWithDebug(RpcClient);
 
 impl RpcClientWithDebug {
-    pub fn new(rpc_endpoint: String) -> Self {
-        Self(RpcClient::new(rpc_endpoint))
+    pub fn new(rpc_endpoints: Vec<Url>) -> Self {
+        Self::new_with_commitment(rpc_endpoints, CommitmentConfig::default())
+    }
+
+    /// Requests are sent to the endpoints in order of priority, falling back
+    /// to the next one when an endpoint is unreachable or unhealthy.
+    pub fn new_with_commitment(rpc_endpoints: Vec<Url>, commitment: CommitmentConfig) -> Self {
+        Self(RpcClient::new_sender(
+            SealevelFallbackRpcSender::new(rpc_endpoints),
+            RpcClientConfig::with_commitment(commitment),
+        ))
     }
 
-    pub fn new_with_commitment(rpc_endpoint: String, commitment: CommitmentConfig) -> Self {
-        Self(RpcClient::new_with_commitment(rpc_endpoint, commitment))
+    /// A client that answers the given requests without any network access,
+    /// for tests. Each mocked response is only returned once.
+    #[cfg(test)]
+    pub(crate) fn new_mock(mocks: solana_client::mock_sender::Mocks) -> Self {
+        Self(RpcClient::new_mock_with_mocks("succeeds".to_owned(), mocks))
     }
 }
 
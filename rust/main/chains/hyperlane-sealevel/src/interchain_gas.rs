@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use hyperlane_core::{
     config::StrOrIntParseError, ChainCommunicationError, ChainResult, ContractLocator,
     HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneProvider, Indexed, Indexer,
-    InterchainGasPaymaster, InterchainGasPayment, LogMeta, SequenceAwareIndexer, H256, H512,
+    InterchainGasPaymaster, InterchainGasPayment, LogMeta, SequenceAwareIndexer, H256,
 };
 use hyperlane_sealevel_igp::{
     accounts::{GasPaymentAccount, ProgramDataAccount},
@@ -119,7 +119,7 @@ impl SealevelInterchainGasPaymasterIndexer {
     ) -> ChainResult<Self> {
         // Set the `processed` commitment at rpc level
         let rpc_client = RpcClientWithDebug::new_with_commitment(
-            conf.url.to_string(),
+            conf.urls.clone(),
             CommitmentConfig::processed(),
         );
 
@@ -215,6 +215,12 @@ impl SealevelInterchainGasPaymasterIndexer {
 
         tracing::debug!(gas_payment_account=?gas_payment_account, "Found gas payment account");
 
+        let (transaction_id, block_hash) = self
+            .igp
+            .provider
+            .get_account_txn_id_and_block_hash(&valid_payment_pda_pubkey, gas_payment_account.slot)
+            .await?;
+
         let igp_payment = InterchainGasPayment {
             message_id: gas_payment_account.message_id,
             destination: gas_payment_account.destination_domain,
@@ -231,10 +237,8 @@ impl SealevelInterchainGasPaymasterIndexer {
             LogMeta {
                 address: self.igp.program_id.to_bytes().into(),
                 block_number: gas_payment_account.slot,
-                // TODO: get these when building out scraper support.
-                // It's inconvenient to get these :|
-                block_hash: H256::zero(),
-                transaction_id: H512::zero(),
+                block_hash,
+                transaction_id,
                 transaction_index: 0,
                 log_index: sequence_number.into(),
             },
@@ -339,3 +343,113 @@ fn test_unique_gas_payment_pubkey_offset() {
         sliced_unique_gas_payment_pubkey
     );
 }
+
+#[cfg(test)]
+fn mock_igp_indexer_with_payment(
+    payment: hyperlane_sealevel_igp::accounts::GasPaymentData,
+    provider_mocks: solana_client::mock_sender::Mocks,
+) -> SealevelInterchainGasPaymasterIndexer {
+    use account_utils::SizedData;
+    use hyperlane_core::KnownHyperlaneDomain;
+
+    use crate::test_utils::mock_sequenced_pda;
+
+    let program_id = Pubkey::new_unique();
+    let (pda, _) = Pubkey::find_program_address(
+        igp_gas_payment_pda_seeds!(payment.unique_gas_payment_pubkey),
+        &program_id,
+    );
+    let pda_seed = payment.unique_gas_payment_pubkey.to_bytes();
+    let account = GasPaymentAccount::new(payment.into());
+    let mut data = vec![0; account.size()];
+    account.store_in_slice(&mut data).unwrap();
+
+    let mut mocks = solana_client::mock_sender::Mocks::default();
+    mock_sequenced_pda(&mut mocks, &program_id, &pda, &pda_seed, &data);
+
+    let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::SealevelTest1);
+    SealevelInterchainGasPaymasterIndexer {
+        rpc_client: RpcClientWithDebug::new_mock(mocks),
+        igp: SealevelInterchainGasPaymaster {
+            program_id,
+            data_pda_pubkey: Pubkey::find_program_address(
+                igp_program_data_pda_seeds!(),
+                &program_id,
+            )
+            .0,
+            domain: domain.clone(),
+            igp_account: H256::zero(),
+            provider: SealevelProvider::new_mock(domain, provider_mocks),
+        },
+    }
+}
+
+#[cfg(test)]
+fn test_gas_payment_data() -> hyperlane_sealevel_igp::accounts::GasPaymentData {
+    hyperlane_sealevel_igp::accounts::GasPaymentData {
+        sequence_number: 7,
+        igp: Pubkey::new_unique(),
+        destination_domain: 1234,
+        message_id: H256::repeat_byte(0xab),
+        gas_amount: 100_000,
+        payment: 5_000,
+        unique_gas_payment_pubkey: Pubkey::new_unique(),
+        slot: 4321,
+    }
+}
+
+#[tokio::test]
+async fn test_get_payment_with_sequence_log_meta() {
+    use hyperlane_core::{H512, U256};
+    use solana_sdk::{hash::Hash, signature::Signature};
+
+    use crate::test_utils::mock_txn_and_block;
+
+    let payment = test_gas_payment_data();
+    let slot = payment.slot;
+    let signature = Signature::new(&[3; 64]);
+    let block_hash = Hash::new_unique();
+    let mut provider_mocks = solana_client::mock_sender::Mocks::default();
+    mock_txn_and_block(&mut provider_mocks, &signature, slot, &block_hash);
+    let indexer = mock_igp_indexer_with_payment(payment, provider_mocks);
+
+    let payment = indexer.get_payment_with_sequence(7).await.unwrap();
+
+    assert_eq!(payment.payment.sequence, Some(7));
+    assert_eq!(payment.payment.inner().gas_amount, U256::from(100_000));
+    assert_eq!(payment.log_meta.block_number, slot);
+    assert_eq!(
+        payment.log_meta.transaction_id,
+        H512::from_slice(signature.as_ref())
+    );
+    assert_eq!(
+        payment.log_meta.block_hash,
+        H256::from(block_hash.to_bytes())
+    );
+    assert_eq!(payment.log_meta.log_index, U256::from(7));
+}
+
+#[tokio::test]
+async fn test_get_payment_with_sequence_without_signature_history() {
+    use hyperlane_core::H512;
+    use solana_sdk::hash::Hash;
+
+    use crate::test_utils::mock_no_signature_history;
+
+    let payment = test_gas_payment_data();
+    let slot = payment.slot;
+    let block_hash = Hash::new_unique();
+    let mut provider_mocks = solana_client::mock_sender::Mocks::default();
+    mock_no_signature_history(&mut provider_mocks, slot, &block_hash);
+    let indexer = mock_igp_indexer_with_payment(payment, provider_mocks);
+
+    // The payment is still indexed, just without the transaction id
+    let payment = indexer.get_payment_with_sequence(7).await.unwrap();
+
+    assert_eq!(payment.log_meta.block_number, slot);
+    assert_eq!(payment.log_meta.transaction_id, H512::zero());
+    assert_eq!(
+        payment.log_meta.block_hash,
+        H256::from(block_hash.to_bytes())
+    );
+}
@@ -0,0 +1,414 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use hyperlane_core::{ChainCommunicationError, ChainResult, H256};
+use solana_account_decoder::UiAccountEncoding;
+use solana_address_lookup_table_program::{
+    instruction::{create_lookup_table, extend_lookup_table},
+    state::AddressLookupTable,
+};
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
+};
+use solana_sdk::{
+    address_lookup_table_account::AddressLookupTableAccount,
+    clock::Slot,
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signer::{keypair::Keypair, Signer as _},
+    transaction::Transaction,
+};
+use tokio::sync::Mutex;
+use tracing::{debug, info};
+
+use crate::RpcClientWithDebug;
+
+/// The max number of addresses a lookup table can hold.
+const MAX_LOOKUP_TABLE_ADDRESSES: usize = 256;
+
+/// The max number of addresses added to a lookup table in a single transaction,
+/// to stay within the transaction size limit.
+const MAX_ADDRESSES_PER_EXTENSION: usize = 20;
+
+/// The offset of the authority in a lookup table account, after the type
+/// discriminant (4 bytes), the deactivation slot (8), the last extended slot (8),
+/// its start index (1) and the authority's option tag (1).
+const LOOKUP_TABLE_AUTHORITY_OFFSET: usize = 22;
+
+/// How many times to check whether newly added addresses can be used, i.e.
+/// whether the slot they were added in has passed.
+const ACTIVATION_RETRIES: usize = 20;
+
+/// Address lookup tables owned by a payer, one per message recipient.
+///
+/// The first address of every table is the recipient it was created for, which
+/// is how tables are matched to recipients when they're loaded from the chain.
+/// Tables only hold accounts that are shared between the recipient's messages,
+/// so that they don't fill up with per-message accounts like a token
+/// recipient's associated token account.
+#[derive(Debug, Default)]
+pub(crate) struct LookupTables {
+    /// Lookup tables by recipient. Loaded from the chain on first use.
+    tables: Mutex<Option<HashMap<Pubkey, AddressLookupTableAccount>>>,
+    /// The handle accounts of the latest message seen for each recipient, to
+    /// tell which of them are shared between messages.
+    latest_handle_accounts: Mutex<HashMap<Pubkey, LatestHandleAccounts>>,
+}
+
+#[derive(Debug)]
+struct LatestHandleAccounts {
+    message_id: H256,
+    accounts: Vec<Pubkey>,
+    /// The accounts shared with the message seen before
+    shared: Vec<Pubkey>,
+}
+
+impl LookupTables {
+    /// The lookup table of `recipient`, if `payer` created one.
+    pub async fn get(
+        &self,
+        rpc: &RpcClientWithDebug,
+        payer: &Pubkey,
+        recipient: &Pubkey,
+    ) -> ChainResult<Option<AddressLookupTableAccount>> {
+        let mut tables = self.tables.lock().await;
+        let tables = Self::loaded(&mut tables, rpc, payer).await?;
+        Ok(tables.get(recipient).cloned())
+    }
+
+    /// The lookup table of `recipient` as it would be once extended with
+    /// `addresses`, without creating or extending it. The key of a table that
+    /// doesn't exist yet is a placeholder, so this is only good for sizing
+    /// transactions.
+    pub async fn get_extended(
+        &self,
+        rpc: &RpcClientWithDebug,
+        payer: &Pubkey,
+        recipient: &Pubkey,
+        addresses: &[Pubkey],
+    ) -> ChainResult<AddressLookupTableAccount> {
+        let mut table =
+            self.get(rpc, payer, recipient)
+                .await?
+                .unwrap_or_else(|| AddressLookupTableAccount {
+                    key: Pubkey::default(),
+                    addresses: vec![],
+                });
+        let new_addresses = new_table_addresses(&table.addresses, recipient, addresses);
+        table.addresses.extend(new_addresses);
+        Ok(table)
+    }
+
+    /// The accounts of `handle_accounts` that the message with `message_id`
+    /// shares with the message seen before it for `recipient`. Accounts that
+    /// differ between messages aren't worth looking up.
+    pub async fn shared_handle_accounts(
+        &self,
+        recipient: &Pubkey,
+        message_id: H256,
+        handle_accounts: &[Pubkey],
+    ) -> Vec<Pubkey> {
+        let mut latest = self.latest_handle_accounts.lock().await;
+        if let Some(latest) = latest.get(recipient) {
+            // e.g. when a message is processed right after estimating its costs
+            if latest.message_id == message_id {
+                return latest.shared.clone();
+            }
+        }
+        // Nothing is known to be shared until a second message is seen
+        let shared = latest
+            .get(recipient)
+            .map(|latest| {
+                handle_accounts
+                    .iter()
+                    .filter(|account| latest.accounts.contains(account))
+                    .copied()
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        latest.insert(
+            *recipient,
+            LatestHandleAccounts {
+                message_id,
+                accounts: handle_accounts.to_vec(),
+                shared: shared.clone(),
+            },
+        );
+        shared
+    }
+
+    /// The lookup table of `recipient`, created if it doesn't exist yet and
+    /// extended with any of `addresses` it's missing, as far as it has room.
+    /// Only returns once every address in the table can be used.
+    pub async fn get_or_extend(
+        &self,
+        rpc: &RpcClientWithDebug,
+        payer: &Keypair,
+        recipient: &Pubkey,
+        addresses: &[Pubkey],
+    ) -> ChainResult<AddressLookupTableAccount> {
+        let mut tables = self.tables.lock().await;
+        let tables = Self::loaded(&mut tables, rpc, &payer.pubkey()).await?;
+
+        let mut instructions = vec![];
+        let (table_key, mut table_addresses) = match tables.get(recipient) {
+            Some(table) => (table.key, table.addresses.clone()),
+            None => {
+                let recent_slot = rpc
+                    .get_slot_with_commitment(CommitmentConfig::finalized())
+                    .await
+                    .map_err(ChainCommunicationError::from_other)?;
+                let (instruction, table_key) =
+                    create_lookup_table(payer.pubkey(), payer.pubkey(), recent_slot);
+                instructions.push(instruction);
+                (table_key, vec![])
+            }
+        };
+
+        let new_addresses = new_table_addresses(&table_addresses, recipient, addresses);
+        if instructions.is_empty() && new_addresses.is_empty() {
+            return Ok(AddressLookupTableAccount {
+                key: table_key,
+                addresses: table_addresses,
+            });
+        }
+
+        let mut chunks = new_addresses.chunks(MAX_ADDRESSES_PER_EXTENSION);
+        // The first chunk is added in the same transaction that creates the table, if any
+        if let Some(chunk) = chunks.next() {
+            instructions.push(extend_lookup_table(
+                table_key,
+                payer.pubkey(),
+                Some(payer.pubkey()),
+                chunk.to_vec(),
+            ));
+        }
+        Self::send(rpc, payer, &instructions).await?;
+        for chunk in chunks {
+            let instruction = extend_lookup_table(
+                table_key,
+                payer.pubkey(),
+                Some(payer.pubkey()),
+                chunk.to_vec(),
+            );
+            Self::send(rpc, payer, &[instruction]).await?;
+        }
+        info!(
+            ?recipient,
+            lookup_table=?table_key,
+            added=new_addresses.len(),
+            "Extended address lookup table"
+        );
+
+        // Addresses can only be looked up once the slot they were added in has passed
+        let extended_slot = rpc
+            .get_slot_with_commitment(CommitmentConfig::processed())
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        Self::wait_for_slot_after(rpc, extended_slot).await?;
+
+        table_addresses.extend(new_addresses);
+        let table = AddressLookupTableAccount {
+            key: table_key,
+            addresses: table_addresses,
+        };
+        tables.insert(*recipient, table.clone());
+        Ok(table)
+    }
+
+    async fn loaded<'a>(
+        tables: &'a mut Option<HashMap<Pubkey, AddressLookupTableAccount>>,
+        rpc: &RpcClientWithDebug,
+        payer: &Pubkey,
+    ) -> ChainResult<&'a mut HashMap<Pubkey, AddressLookupTableAccount>> {
+        if tables.is_none() {
+            *tables = Some(Self::load(rpc, payer).await?);
+        }
+        Ok(tables.get_or_insert_with(HashMap::new))
+    }
+
+    /// Loads the active lookup tables owned by `payer`, by recipient.
+    async fn load(
+        rpc: &RpcClientWithDebug,
+        payer: &Pubkey,
+    ) -> ChainResult<HashMap<Pubkey, AddressLookupTableAccount>> {
+        let config = RpcProgramAccountsConfig {
+            filters: Some(vec![RpcFilterType::Memcmp(Memcmp {
+                offset: LOOKUP_TABLE_AUTHORITY_OFFSET,
+                bytes: MemcmpEncodedBytes::Base58(payer.to_string()),
+                encoding: None,
+            })]),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                data_slice: None,
+                commitment: Some(CommitmentConfig::confirmed()),
+                min_context_slot: None,
+            },
+            with_context: Some(false),
+        };
+        let accounts = rpc
+            .get_program_accounts_with_config(&solana_address_lookup_table_program::id(), config)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        let mut tables: HashMap<Pubkey, AddressLookupTableAccount> = HashMap::new();
+        for (key, account) in accounts {
+            let Ok(table) = AddressLookupTable::deserialize(&account.data) else {
+                continue;
+            };
+            // Skip tables that are being closed
+            if table.meta.deactivation_slot != Slot::MAX {
+                continue;
+            }
+            let Some(recipient) = table.addresses.first().copied() else {
+                continue;
+            };
+            let addresses = table.addresses.to_vec();
+            // Keep the most complete table if there are several for a recipient
+            if tables
+                .get(&recipient)
+                .map_or(true, |existing| existing.addresses.len() < addresses.len())
+            {
+                tables.insert(recipient, AddressLookupTableAccount { key, addresses });
+            }
+        }
+        debug!(count = tables.len(), ?payer, "Loaded address lookup tables");
+        Ok(tables)
+    }
+
+    async fn send(
+        rpc: &RpcClientWithDebug,
+        payer: &Keypair,
+        instructions: &[Instruction],
+    ) -> ChainResult<()> {
+        let recent_blockhash = rpc
+            .get_latest_blockhash()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        let txn = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer],
+            recent_blockhash,
+        );
+        rpc.send_and_confirm_transaction(&txn)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        Ok(())
+    }
+
+    async fn wait_for_slot_after(rpc: &RpcClientWithDebug, slot: Slot) -> ChainResult<()> {
+        for _ in 0..ACTIVATION_RETRIES {
+            let current_slot = rpc
+                .get_slot_with_commitment(CommitmentConfig::processed())
+                .await
+                .map_err(ChainCommunicationError::from_other)?;
+            if current_slot > slot {
+                return Ok(());
+            }
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+        Err(ChainCommunicationError::from_other_str(
+            "Timed out waiting for address lookup table addresses to become usable",
+        ))
+    }
+}
+
+/// The addresses to add to a lookup table of `recipient` that holds
+/// `table_addresses` for it to also hold `addresses`, as far as it has room.
+/// The recipient always comes first, so that tables can be matched to
+/// recipients.
+fn new_table_addresses(
+    table_addresses: &[Pubkey],
+    recipient: &Pubkey,
+    addresses: &[Pubkey],
+) -> Vec<Pubkey> {
+    let mut new_addresses = vec![];
+    for address in std::iter::once(recipient).chain(addresses) {
+        if !table_addresses.contains(address) && !new_addresses.contains(address) {
+            new_addresses.push(*address);
+        }
+    }
+    new_addresses.truncate(MAX_LOOKUP_TABLE_ADDRESSES.saturating_sub(table_addresses.len()));
+    new_addresses
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_new_table_addresses() {
+        let recipient = Pubkey::new_unique();
+        let shared = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        // A new table starts with the recipient, without duplicates
+        assert_eq!(
+            new_table_addresses(&[], &recipient, &[shared, recipient, shared, other]),
+            vec![recipient, shared, other]
+        );
+
+        // Only missing addresses are added to an existing table
+        assert_eq!(
+            new_table_addresses(&[recipient, shared], &recipient, &[shared, other]),
+            vec![other]
+        );
+
+        // A table never holds more than the max number of addresses
+        let full = (0..MAX_LOOKUP_TABLE_ADDRESSES - 1)
+            .map(|_| Pubkey::new_unique())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            new_table_addresses(&full, &recipient, &[shared, other]),
+            vec![recipient]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shared_handle_accounts() {
+        let lookup_tables = LookupTables::default();
+        let recipient = Pubkey::new_unique();
+        let token_program = Pubkey::new_unique();
+        let escrow = Pubkey::new_unique();
+        let first_ata = Pubkey::new_unique();
+        let second_ata = Pubkey::new_unique();
+        let first_message = H256::repeat_byte(1);
+        let second_message = H256::repeat_byte(2);
+
+        // Nothing is known to be shared from a single message, even if it's seen again
+        for _ in 0..2 {
+            assert!(lookup_tables
+                .shared_handle_accounts(
+                    &recipient,
+                    first_message,
+                    &[token_program, escrow, first_ata],
+                )
+                .await
+                .is_empty());
+        }
+
+        // Accounts that differ between messages are left out, also when the
+        // message is seen again
+        for _ in 0..2 {
+            assert_eq!(
+                lookup_tables
+                    .shared_handle_accounts(
+                        &recipient,
+                        second_message,
+                        &[token_program, escrow, second_ata],
+                    )
+                    .await,
+                vec![token_program, escrow]
+            );
+        }
+
+        // Messages to other recipients don't share accounts
+        assert!(lookup_tables
+            .shared_handle_accounts(&Pubkey::new_unique(), first_message, &[token_program])
+            .await
+            .is_empty());
+    }
+}
@@ -2,23 +2,28 @@
 
 use std::{collections::HashMap, num::NonZeroU64, ops::RangeInclusive, str::FromStr as _};
 
+use account_utils::SizedData;
 use async_trait::async_trait;
 use borsh::{BorshDeserialize, BorshSerialize};
 use jsonrpc_core::futures_util::TryFutureExt;
 use tracing::{debug, info, instrument, warn};
 
 use hyperlane_core::{
-    accumulator::incremental::IncrementalMerkle, BatchItem, ChainCommunicationError, ChainResult,
-    Checkpoint, ContractLocator, Decode as _, Encode as _, FixedPointNumber, HyperlaneAbi,
-    HyperlaneChain, HyperlaneContract, HyperlaneDomain, HyperlaneMessage, HyperlaneProvider,
-    Indexed, Indexer, KnownHyperlaneDomain, LogMeta, Mailbox, MerkleTreeHook, SequenceAwareIndexer,
-    TxCostEstimate, TxOutcome, H256, H512, U256,
+    accumulator::incremental::IncrementalMerkle, config::StrOrIntParseError, BatchItem,
+    BatchResult, ChainCommunicationError, ChainResult, Checkpoint, ContractLocator, Decode as _,
+    Encode as _, FixedPointNumber, HyperlaneAbi, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, Indexed, Indexer, KnownHyperlaneDomain,
+    LogMeta, Mailbox, MerkleTreeHook, QueueOperation, SequenceAwareIndexer, TxCostEstimate,
+    TxOutcome, H256, H512, U256,
 };
 use hyperlane_sealevel_interchain_security_module_interface::{
-    InterchainSecurityModuleInstruction, VerifyInstruction,
+    InterchainSecurityModuleInstruction, VerifyInstruction, MAX_VERIFY_ACCOUNT_METAS_SIMULATIONS,
 };
 use hyperlane_sealevel_mailbox::{
-    accounts::{DispatchedMessageAccount, InboxAccount, OutboxAccount},
+    accounts::{
+        DispatchedMessageAccount, InboxAccount, OutboxAccount, ProcessedMessage,
+        ProcessedMessageAccount,
+    },
     instruction::InboxProcess,
     mailbox_dispatched_message_pda_seeds, mailbox_inbox_pda_seeds, mailbox_outbox_pda_seeds,
     mailbox_process_authority_pda_seeds, mailbox_processed_message_pda_seeds,
@@ -31,21 +36,26 @@ use solana_account_decoder::{UiAccountEncoding, UiDataSliceConfig};
 use solana_client::{
     nonblocking::rpc_client::RpcClient,
     rpc_client::SerializableTransaction,
-    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig},
+    rpc_config::{
+        RpcAccountInfoConfig, RpcProgramAccountsConfig, RpcSendTransactionConfig,
+        RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig,
+    },
     rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
 };
 use solana_sdk::{
     account::Account,
+    address_lookup_table_account::AddressLookupTableAccount,
     bs58,
     commitment_config::CommitmentConfig,
     compute_budget::ComputeBudgetInstruction,
     hash::Hash,
     instruction::{AccountMeta, Instruction},
-    message::Message,
+    message::{v0, Message, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
     pubkey::Pubkey,
     signature::Signature,
     signer::{keypair::Keypair, Signer as _},
-    transaction::{Transaction, VersionedTransaction},
+    transaction::{Transaction, TransactionError, VersionedTransaction},
 };
 use solana_transaction_status::{
     EncodedConfirmedBlock, EncodedTransaction, EncodedTransactionWithStatusMeta,
@@ -55,8 +65,10 @@ use solana_transaction_status::{
 
 use crate::RpcClientWithDebug;
 use crate::{
+    lookup_tables::LookupTables,
+    priority_fee::{bump_compute_unit_price, get_compute_unit_price},
     utils::{get_account_metas, get_finalized_block_number, simulate_instruction},
-    ConnectionConf, SealevelProvider,
+    ConnectionConf, PriorityFeeConf, SealevelProvider,
 };
 
 const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
@@ -66,6 +78,14 @@ const SPL_NOOP: &str = "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV";
 // TODO: consider a more sane value and/or use IGP gas payments instead.
 const PROCESS_COMPUTE_UNITS: u32 = 1_400_000;
 
+/// The percentage of headroom added on top of the compute units consumed
+/// when simulating a process transaction.
+const PROCESS_COMPUTE_UNITS_BUFFER_PERCENT: u64 = 10;
+
+/// How many times a process transaction is submitted, with a higher compute unit
+/// price each time, when its blockhash expires before it's confirmed.
+const PROCESS_SUBMISSION_ATTEMPTS: usize = 3;
+
 /// 0.0005 SOL, in lamports.
 /// A typical tx fee without a prioritization fee is 0.000005 SOL, or
 /// 5000 lamports. (Example: https://explorer.solana.com/tx/fNd3xVeBzFHeuzr8dXQxLGiHMzTeYpykSV25xWzNRaHtzzjvY9A3MzXh1ZsK2JncRHkwtuWrGEwGXVhFaUCYhtx)
@@ -76,7 +96,7 @@ const PROCESS_DESIRED_PRIORITIZATION_FEE_LAMPORTS_PER_TX: u64 = 500000;
 /// In micro-lamports. Multiply this by the compute units to figure out
 /// the additional cost of processing a message, in addition to the mandatory
 /// "base" cost of signature verification.
-const PROCESS_COMPUTE_UNIT_PRICE_MICRO_LAMPORTS: u64 =
+pub(crate) const PROCESS_COMPUTE_UNIT_PRICE_MICRO_LAMPORTS: u64 =
     (
         // Convert to micro-lamports
         (PROCESS_DESIRED_PRIORITIZATION_FEE_LAMPORTS_PER_TX * 1_000_000)
@@ -91,6 +111,10 @@ pub struct SealevelMailbox {
     pub(crate) outbox: (Pubkey, u8),
     pub(crate) provider: SealevelProvider,
     payer: Option<Keypair>,
+    /// Address lookup tables owned by the payer, used to fit process
+    /// transactions with many accounts within the transaction size limit
+    lookup_tables: LookupTables,
+    priority_fee: PriorityFeeConf,
 }
 
 impl SealevelMailbox {
@@ -117,6 +141,8 @@ impl SealevelMailbox {
             outbox,
             provider,
             payer,
+            lookup_tables: LookupTables::default(),
+            priority_fee: conf.priority_fee.clone(),
         })
     }
 
@@ -213,6 +239,10 @@ impl SealevelMailbox {
     }
 
     /// Gets the account metas required for the ISM's `Verify` instruction.
+    /// ISMs that require the accounts of other ISMs, e.g. routing ISMs, return
+    /// account metas that start with their `VERIFY_ACCOUNT_METAS_PDA_SEEDS` PDA.
+    /// `VerifyAccountMetas` is then simulated again with the returned account metas
+    /// until they're unchanged.
     pub async fn get_ism_verify_account_metas(
         &self,
         ism: Pubkey,
@@ -223,15 +253,40 @@ impl SealevelMailbox {
             InterchainSecurityModuleInstruction::VerifyAccountMetas(VerifyInstruction {
                 metadata,
                 message,
-            });
-        self.get_account_metas_with_instruction_bytes(
-            ism,
-            &instruction
-                .encode()
-                .map_err(ChainCommunicationError::from_other)?,
+            })
+            .encode()
+            .map_err(ChainCommunicationError::from_other)?;
+
+        let (account_metas_pda_key, _) = Pubkey::find_program_address(
             hyperlane_sealevel_interchain_security_module_interface::VERIFY_ACCOUNT_METAS_PDA_SEEDS,
-        )
-        .await
+            &ism,
+        );
+        let mut accounts = vec![AccountMeta::new(account_metas_pda_key, false)];
+        for _ in 0..MAX_VERIFY_ACCOUNT_METAS_SIMULATIONS {
+            let account_metas = self
+                .get_account_metas(Instruction::new_with_bytes(
+                    ism,
+                    &instruction,
+                    accounts.clone(),
+                ))
+                .await?;
+            let complete = account_metas
+                .first()
+                .map(|account_meta| account_meta.pubkey)
+                != Some(account_metas_pda_key)
+                || account_metas == accounts;
+            accounts = account_metas;
+            if complete {
+                return Ok(accounts);
+            }
+        }
+
+        warn!(
+            ?ism,
+            "ISM verify account metas did not settle after {} simulations",
+            MAX_VERIFY_ACCOUNT_METAS_SIMULATIONS
+        );
+        Ok(accounts)
     }
 
     /// Gets the account metas required for the recipient's `MessageRecipientInstruction::Handle` instruction.
@@ -273,6 +328,412 @@ impl SealevelMailbox {
         self.get_account_metas(instruction).await
     }
 
+    /// Builds the instructions that set the compute budget of a process
+    /// transaction, which precede the inbox process instructions.
+    /// `compute_unit_price` is in micro-lamports.
+    fn get_compute_budget_instructions(
+        &self,
+        payer: &Pubkey,
+        compute_unit_limit: u32,
+        compute_unit_price: u64,
+    ) -> Vec<Instruction> {
+        compute_budget_instructions(
+            payer,
+            compute_unit_limit,
+            compute_unit_price,
+            self.use_jito(),
+        )
+    }
+
+    /// The compute unit price to pay for a transaction with `instructions`,
+    /// based on recent prioritization fees.
+    async fn get_compute_unit_price(&self, instructions: &[Instruction]) -> ChainResult<u64> {
+        if self.use_jito() {
+            // Jito transactions are prioritized by their tip instead
+            return Ok(0);
+        }
+        get_compute_unit_price(self.rpc(), instructions, &self.priority_fee).await
+    }
+
+    fn get_processed_message_account(&self, message: &HyperlaneMessage) -> ChainResult<Pubkey> {
+        let (processed_message_account_key, _processed_message_account_bump) =
+            Pubkey::try_find_program_address(
+                mailbox_processed_message_pda_seeds!(message.id()),
+                &self.program_id,
+            )
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str(
+                    "Could not find program address for processed message account",
+                )
+            })?;
+        Ok(processed_message_account_key)
+    }
+
+    /// Builds the mailbox instruction that processes `message`, paid for by `payer`.
+    async fn get_inbox_process_instruction(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+        payer: &Pubkey,
+    ) -> ChainResult<InboxProcessInstruction> {
+        let recipient: Pubkey = message.recipient.0.into();
+        let mut encoded_message = vec![];
+        message.write_to(&mut encoded_message).unwrap();
+
+        let (process_authority_key, _process_authority_bump) = Pubkey::try_find_program_address(
+            mailbox_process_authority_pda_seeds!(&recipient),
+            &self.program_id,
+        )
+        .ok_or_else(|| {
+            ChainCommunicationError::from_other_str(
+                "Could not find program address for process authority",
+            )
+        })?;
+        let processed_message_account_key = self.get_processed_message_account(message)?;
+
+        // Get the account metas required for the recipient.InterchainSecurityModule instruction.
+        let ism_getter_account_metas = self.get_ism_getter_account_metas(recipient).await?;
+
+        // Get the recipient ISM.
+        let ism = self
+            .get_recipient_ism(recipient, ism_getter_account_metas.clone())
+            .await?;
+
+        let ixn =
+            hyperlane_sealevel_mailbox::instruction::Instruction::InboxProcess(InboxProcess {
+                metadata: metadata.to_vec(),
+                message: encoded_message.clone(),
+            });
+        let ixn_data = ixn
+            .into_instruction_data()
+            .map_err(ChainCommunicationError::from_other)?;
+
+        // Craft the accounts for the transaction.
+        let mut accounts: Vec<AccountMeta> = vec![
+            AccountMeta::new_readonly(*payer, true),
+            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM).unwrap(), false),
+            AccountMeta::new(self.inbox.0, false),
+            AccountMeta::new_readonly(process_authority_key, false),
+            AccountMeta::new(processed_message_account_key, false),
+        ];
+        accounts.extend(ism_getter_account_metas);
+        accounts.extend([
+            AccountMeta::new_readonly(Pubkey::from_str(SPL_NOOP).unwrap(), false),
+            AccountMeta::new_readonly(ism, false),
+        ]);
+
+        // Get the account metas required for the ISM.Verify instruction.
+        let ism_verify_account_metas = self
+            .get_ism_verify_account_metas(ism, metadata.into(), encoded_message)
+            .await?;
+        accounts.extend(ism_verify_account_metas);
+
+        // The recipient.
+        accounts.extend([AccountMeta::new_readonly(recipient, false)]);
+
+        // Get account metas required for the Handle instruction
+        let handle_account_metas = self.get_handle_account_metas(message).await?;
+        let handle_accounts = handle_account_metas
+            .iter()
+            .map(|account| account.pubkey)
+            .collect();
+        accounts.extend(handle_account_metas);
+
+        Ok(InboxProcessInstruction {
+            instruction: Instruction {
+                program_id: self.program_id,
+                data: ixn_data,
+                accounts,
+            },
+            handle_accounts,
+        })
+    }
+
+    /// The lookup tables to compile a process transaction of `message` with, if
+    /// it fits within the size limit with the payer's existing tables.
+    /// Otherwise, the addresses the recipient's lookup table has to be extended
+    /// with for it to fit. Nothing is created or extended here, so that it's
+    /// safe to call when only estimating costs.
+    async fn plan_process_lookup_tables(
+        &self,
+        payer: &Pubkey,
+        message: &HyperlaneMessage,
+        compute_unit_limit: u32,
+        inbox_instruction: &InboxProcessInstruction,
+    ) -> ChainResult<ProcessLookupTables> {
+        let recipient: Pubkey = message.recipient.0.into();
+        let lookup_tables = self
+            .lookup_tables
+            .get(self.rpc(), payer, &recipient)
+            .await?
+            .into_iter()
+            .collect::<Vec<_>>();
+        // The compute unit price is only known when sending, so size the transaction
+        // as if it had one.
+        let budget_instructions = self.get_compute_budget_instructions(
+            payer,
+            compute_unit_limit,
+            self.priority_fee.max_compute_unit_price,
+        );
+        let inbox_instructions = std::slice::from_ref(&inbox_instruction.instruction);
+        if fits_in_transaction(
+            payer,
+            &budget_instructions,
+            inbox_instructions,
+            &lookup_tables,
+        )? {
+            return Ok(ProcessLookupTables::Ready(lookup_tables));
+        }
+
+        let shared_handle_accounts = self
+            .lookup_tables
+            .shared_handle_accounts(&recipient, message.id(), &inbox_instruction.handle_accounts)
+            .await;
+        let addresses = lookup_table_addresses(
+            inbox_instruction,
+            &self.get_processed_message_account(message)?,
+            &shared_handle_accounts,
+        );
+        let extended_table = self
+            .lookup_tables
+            .get_extended(self.rpc(), payer, &recipient, &addresses)
+            .await?;
+        if !fits_in_transaction(
+            payer,
+            &budget_instructions,
+            inbox_instructions,
+            &[extended_table],
+        )? {
+            return Err(ChainCommunicationError::from_other_str(
+                "Sealevel process transaction is too large, even with an address lookup table",
+            ));
+        }
+        Ok(ProcessLookupTables::Extend(addresses))
+    }
+
+    /// Signs and sends a v0 transaction with `inbox_instructions`, returning once
+    /// it's confirmed. If the blockhash of the transaction expires before then, it's
+    /// resubmitted with a higher compute unit price.
+    async fn send_process_transaction(
+        &self,
+        payer: &Keypair,
+        compute_unit_limit: u32,
+        inbox_instructions: &[Instruction],
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> ChainResult<TxOutcome> {
+        // "processed" level commitment does not guarantee finality.
+        // roughly 5% of blocks end up on a dropped fork.
+        // However we don't want this function to be a bottleneck and there already
+        // is retry logic in the agents.
+        let commitment = CommitmentConfig::processed();
+
+        let mut compute_unit_price = self.get_compute_unit_price(inbox_instructions).await?;
+        for attempt in 0..PROCESS_SUBMISSION_ATTEMPTS {
+            if attempt > 0 {
+                compute_unit_price =
+                    bump_compute_unit_price(compute_unit_price, &self.priority_fee);
+            }
+            let mut instructions = self.get_compute_budget_instructions(
+                &payer.pubkey(),
+                compute_unit_limit,
+                compute_unit_price,
+            );
+            instructions.extend_from_slice(inbox_instructions);
+
+            let (recent_blockhash, _) = self
+                .rpc()
+                .get_latest_blockhash_with_commitment(commitment)
+                .await
+                .map_err(ChainCommunicationError::from_other)?;
+
+            let txn_message = compile_versioned_message(
+                &payer.pubkey(),
+                &instructions,
+                lookup_tables,
+                recent_blockhash,
+            )?;
+            let txn = VersionedTransaction::try_new(txn_message, &[payer])
+                .map_err(ChainCommunicationError::from_other)?;
+
+            tracing::info!(
+                ?txn,
+                attempt,
+                compute_unit_price,
+                "Created sealevel transaction to process message"
+            );
+
+            let Some(signature) = self.send_and_confirm_transaction(&txn).await? else {
+                warn!(
+                    ?txn,
+                    compute_unit_price,
+                    "Blockhash of sealevel transaction expired before it was confirmed"
+                );
+                continue;
+            };
+
+            tracing::info!(?txn, ?signature, "Sealevel transaction sent");
+
+            let executed = self
+                .rpc()
+                .confirm_transaction_with_commitment(&signature, commitment)
+                .await
+                .map_err(|err| warn!("Failed to confirm inbox process transaction: {}", err))
+                .map(|ctx| ctx.value)
+                .unwrap_or(false);
+            let txid = signature.into();
+
+            return Ok(TxOutcome {
+                transaction_id: txid,
+                executed,
+                // TODO use correct data upon integrating IGP support
+                gas_price: U256::zero().try_into()?,
+                gas_used: U256::zero(),
+            });
+        }
+
+        Err(ChainCommunicationError::from_other_str(&format!(
+            "Sealevel process transaction wasn't confirmed after {} attempts",
+            PROCESS_SUBMISSION_ATTEMPTS
+        )))
+    }
+
+    /// Simulates a process transaction, asking for the payer's account afterwards
+    /// so that we can tell how many lamports it spends, including the transaction
+    /// fee. Returns the compute units to limit the transaction to, and the
+    /// lamports the payer spends, if known.
+    async fn simulate_process_transaction(
+        &self,
+        payer: &Pubkey,
+        txn_message: VersionedMessage,
+    ) -> ChainResult<(u64, Option<u64>)> {
+        // The payer's balance before and after the transaction are only comparable
+        // if they're read from the same bank, as other transactions of the payer
+        // may land in between. Confirmed banks don't change once they're read.
+        let commitment = CommitmentConfig::confirmed();
+        let payer_balance = self
+            .rpc()
+            .get_balance_with_commitment(payer, commitment)
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+
+        let simulation = self
+            .rpc()
+            .simulate_transaction_with_config(
+                &unsigned_versioned_transaction(txn_message),
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    // The blockhash may not be confirmed yet
+                    replace_recent_blockhash: true,
+                    commitment: Some(commitment),
+                    accounts: Some(RpcSimulateTransactionAccountsConfig {
+                        encoding: Some(UiAccountEncoding::Base64),
+                        addresses: vec![payer.to_string()],
+                    }),
+                    min_context_slot: Some(payer_balance.context.slot),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        let simulated_slot = simulation.context.slot;
+        let simulation = simulation.value;
+
+        if let Some(err) = simulation.err {
+            return Err(ChainCommunicationError::from_other_str(&format!(
+                "Simulating process transaction failed: {:?}, logs: {:?}",
+                err, simulation.logs
+            )));
+        }
+        let units_consumed = simulation.units_consumed.ok_or_else(|| {
+            ChainCommunicationError::from_other_str(
+                "Simulating process transaction did not return the compute units consumed",
+            )
+        })?;
+        // Leave some headroom, as the units consumed can vary slightly with the
+        // state at the time the transaction lands.
+        let gas_limit = (units_consumed
+            + units_consumed * PROCESS_COMPUTE_UNITS_BUFFER_PERCENT / 100)
+            .min(PROCESS_COMPUTE_UNITS.into());
+
+        let payer_spent = if simulated_slot == payer_balance.context.slot {
+            simulation
+                .accounts
+                .and_then(|accounts| accounts.into_iter().next().flatten())
+                .map(|account| payer_balance.value.saturating_sub(account.lamports))
+        } else {
+            debug!(
+                balance_slot = payer_balance.context.slot,
+                simulated_slot,
+                "Not using the payer's simulated spend, as its balance was read at another slot"
+            );
+            None
+        };
+        Ok((gas_limit, payer_spent))
+    }
+
+    /// The fee of a process transaction limited to `compute_unit_limit`: the base
+    /// fee, paid per signature, plus the prioritization fee. It only depends on the
+    /// signers and the compute budget instructions, so the inbox process
+    /// instruction is left out.
+    async fn get_process_fee(
+        &self,
+        payer: &Pubkey,
+        compute_unit_limit: u32,
+        compute_unit_price: u64,
+        recent_blockhash: &Hash,
+    ) -> ChainResult<u64> {
+        let fee_message = Message::new_with_blockhash(
+            &self.get_compute_budget_instructions(payer, compute_unit_limit, compute_unit_price),
+            Some(payer),
+            recent_blockhash,
+        );
+        self.rpc()
+            .get_fee_for_message(&fee_message)
+            .await
+            .map_err(ChainCommunicationError::from_other)
+    }
+
+    /// Simulates a batch of inbox process instructions, preceded by
+    /// `budget_instruction_count` compute budget instructions. Returns the
+    /// position of the inbox process instruction that failed, if any.
+    async fn simulate_process_batch(
+        &self,
+        payer: &Pubkey,
+        instructions: &[Instruction],
+        lookup_tables: &[AddressLookupTableAccount],
+        budget_instruction_count: usize,
+    ) -> ChainResult<Option<usize>> {
+        let txn_message =
+            compile_versioned_message(payer, instructions, lookup_tables, Hash::default())?;
+        let simulation = self
+            .rpc()
+            .simulate_transaction_with_config(
+                &unsigned_versioned_transaction(txn_message),
+                RpcSimulateTransactionConfig {
+                    sig_verify: false,
+                    replace_recent_blockhash: true,
+                    commitment: Some(CommitmentConfig::processed()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value;
+
+        match simulation.err {
+            None => Ok(None),
+            Some(TransactionError::InstructionError(index, _))
+                if index as usize >= budget_instruction_count =>
+            {
+                Ok(Some(index as usize - budget_instruction_count))
+            }
+            Some(err) => Err(ChainCommunicationError::from_other_str(&format!(
+                "Simulating process batch failed: {:?}, logs: {:?}",
+                err, simulation.logs
+            ))),
+        }
+    }
+
     fn use_jito(&self) -> bool {
         matches!(
             self.domain(),
@@ -280,27 +741,29 @@ impl SealevelMailbox {
         )
     }
 
+    /// Sends `transaction` and waits until it lands. Returns `None` if its
+    /// blockhash expired first, after which it can no longer land.
     async fn send_and_confirm_transaction(
         &self,
-        transaction: &Transaction,
-    ) -> ChainResult<Signature> {
+        transaction: &VersionedTransaction,
+    ) -> ChainResult<Option<Signature>> {
         if self.use_jito() {
-            self.send_and_confirm_transaction_with_jito(transaction)
-                .await
+            self.send_transaction_with_jito(transaction).await?;
         } else {
             self.provider
                 .rpc()
-                .send_and_confirm_transaction(transaction)
+                .send_transaction(transaction)
                 .await
-                .map_err(ChainCommunicationError::from_other)
+                .map_err(ChainCommunicationError::from_other)?;
         }
+        self.wait_for_transaction(transaction).await
     }
 
-    // Stolen from Solana's non-blocking client, but with Jito!
-    pub async fn send_and_confirm_transaction_with_jito(
+    /// Sends `transaction` to Jito as a bundle.
+    pub async fn send_transaction_with_jito(
         &self,
         transaction: &impl SerializableTransaction,
-    ) -> ChainResult<Signature> {
+    ) -> ChainResult<()> {
         let signature = transaction.get_signature();
 
         let base58_txn = bs58::encode(
@@ -308,93 +771,122 @@ impl SealevelMailbox {
         )
         .into_string();
 
-        const SEND_RETRIES: usize = 1;
-        const GET_STATUS_RETRIES: usize = usize::MAX;
-
-        'sending: for _ in 0..SEND_RETRIES {
-            let jito_request_body = serde_json::json!({
-                "jsonrpc": "2.0",
-                "id": 1,
-                "method": "sendBundle",
-                "params": [
-                    [base58_txn]
-                ],
-            });
+        let jito_request_body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendBundle",
+            "params": [
+                [base58_txn]
+            ],
+        });
 
-            tracing::info!(
-                ?jito_request_body,
-                ?signature,
-                "Sending sealevel transaction to Jito as bundle"
-            );
+        tracing::info!(
+            ?jito_request_body,
+            ?signature,
+            "Sending sealevel transaction to Jito as bundle"
+        );
 
-            let jito_response = reqwest::Client::new()
-                .post("https://mainnet.block-engine.jito.wtf:443/api/v1/bundles")
-                .json(&jito_request_body)
-                .send()
-                .await
-                .map_err(ChainCommunicationError::from_other)?;
-            let jito_response_text = jito_response.text().await;
+        let jito_response = reqwest::Client::new()
+            .post("https://mainnet.block-engine.jito.wtf:443/api/v1/bundles")
+            .json(&jito_request_body)
+            .send()
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        let jito_response_text = jito_response.text().await;
 
-            tracing::info!(
-                ?signature,
-                ?jito_response_text,
-                "Got Jito response for sealevel transaction bundle"
-            );
+        tracing::info!(
+            ?signature,
+            ?jito_response_text,
+            "Got Jito response for sealevel transaction bundle"
+        );
+        Ok(())
+    }
 
-            let recent_blockhash = if transaction.uses_durable_nonce() {
-                let (recent_blockhash, ..) = self
-                    .provider
-                    .rpc()
-                    .get_latest_blockhash_with_commitment(CommitmentConfig::processed())
-                    .await
-                    .map_err(ChainCommunicationError::from_other)?;
-                recent_blockhash
-            } else {
-                *transaction.get_recent_blockhash()
-            };
+    // Stolen from Solana's non-blocking client, but without giving up on an
+    // expired blockhash so that the caller can resubmit.
+    /// Waits until a sent `transaction` lands. Returns `None` if its blockhash
+    /// expired first.
+    async fn wait_for_transaction(
+        &self,
+        transaction: &impl SerializableTransaction,
+    ) -> ChainResult<Option<Signature>> {
+        let signature = transaction.get_signature();
+
+        let recent_blockhash = if transaction.uses_durable_nonce() {
+            let (recent_blockhash, ..) = self
+                .provider
+                .rpc()
+                .get_latest_blockhash_with_commitment(CommitmentConfig::processed())
+                .await
+                .map_err(ChainCommunicationError::from_other)?;
+            recent_blockhash
+        } else {
+            *transaction.get_recent_blockhash()
+        };
 
-            for status_retry in 0..GET_STATUS_RETRIES {
-                match self
-                    .provider
-                    .rpc()
-                    .get_signature_status(&signature)
-                    .await
-                    .map_err(ChainCommunicationError::from_other)?
-                {
-                    Some(Ok(_)) => return Ok(*signature),
-                    Some(Err(e)) => return Err(ChainCommunicationError::from_other(e)),
-                    None => {
-                        if !self
-                            .provider
-                            .rpc()
-                            .is_blockhash_valid(&recent_blockhash, CommitmentConfig::processed())
-                            .await
-                            .map_err(ChainCommunicationError::from_other)?
-                        {
-                            // Block hash is not found by some reason
-                            break 'sending;
-                        } else if cfg!(not(test))
-                            // Ignore sleep at last step.
-                            && status_retry < GET_STATUS_RETRIES
-                        {
-                            // Retry twice a second
-                            tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-                            continue;
-                        }
+        loop {
+            match self
+                .provider
+                .rpc()
+                .get_signature_status(signature)
+                .await
+                .map_err(ChainCommunicationError::from_other)?
+            {
+                Some(Ok(_)) => return Ok(Some(*signature)),
+                Some(Err(e)) => return Err(ChainCommunicationError::from_other(e)),
+                None => {
+                    if !self
+                        .provider
+                        .rpc()
+                        .is_blockhash_valid(&recent_blockhash, CommitmentConfig::processed())
+                        .await
+                        .map_err(ChainCommunicationError::from_other)?
+                    {
+                        // Block hash is not found by some reason
+                        return Ok(None);
                     }
+                    // Retry twice a second
+                    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
                 }
             }
         }
+    }
+}
 
-        Err(ChainCommunicationError::from_other(
-            solana_client::rpc_request::RpcError::ForUser(
-                "unable to confirm transaction. \
-             This can happen in situations such as transaction expiration \
-             and insufficient fee-payer funds"
-                    .to_string(),
-            ),
-        ))
+/// The compute budget instructions of a process transaction. Transactions sent
+/// through Jito pay a tip instead of a compute unit price. Otherwise the price
+/// instruction is always included, so that simulations and fee quotes are made
+/// against the same instructions as the transaction that is eventually sent.
+fn compute_budget_instructions(
+    payer: &Pubkey,
+    compute_unit_limit: u32,
+    compute_unit_price: u64,
+    use_jito: bool,
+) -> Vec<Instruction> {
+    let mut instructions = Vec::with_capacity(2);
+    // Set the compute unit limit.
+    instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
+        compute_unit_limit,
+    ));
+
+    // If we're using Jito, we need to send a tip to the Jito fee account.
+    // Otherwise, we need to set the compute unit price.
+    if use_jito {
+        // The tip is a standalone transfer to a Jito fee account.
+        // See https://github.com/jito-labs/mev-protos/blob/master/json_rpc/http.md#sendbundle.
+        instructions.push(solana_sdk::system_instruction::transfer(
+            payer,
+            // A random Jito fee account, taken from the getFeeAccount RPC response:
+            // https://github.com/jito-labs/mev-protos/blob/master/json_rpc/http.md#gettipaccounts
+            &solana_sdk::pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
+            PROCESS_DESIRED_PRIORITIZATION_FEE_LAMPORTS_PER_TX,
+        ));
+    } else {
+        instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+            compute_unit_price,
+        ));
     }
+    instructions
 }
 
 impl HyperlaneContract for SealevelMailbox {
@@ -419,6 +911,169 @@ impl std::fmt::Debug for SealevelMailbox {
     }
 }
 
+/// Compiles a v0 message, looking up accounts in `lookup_tables` where possible.
+fn compile_versioned_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> ChainResult<VersionedMessage> {
+    let message = v0::Message::try_compile(payer, instructions, lookup_tables, recent_blockhash)
+        .map_err(ChainCommunicationError::from_other)?;
+    Ok(VersionedMessage::V0(message))
+}
+
+/// A transaction with placeholder signatures, for simulating and sizing.
+fn unsigned_versioned_transaction(message: VersionedMessage) -> VersionedTransaction {
+    VersionedTransaction {
+        signatures: vec![Signature::default(); message.header().num_required_signatures as usize],
+        message,
+    }
+}
+
+/// The serialized size of a transaction with `message` once it's signed.
+fn versioned_transaction_size(message: VersionedMessage) -> ChainResult<usize> {
+    let size = bincode::serialized_size(&unsigned_versioned_transaction(message))
+        .map_err(ChainCommunicationError::from_other)?;
+    Ok(size as usize)
+}
+
+/// Whether a transaction with `budget_instructions` followed by
+/// `inbox_instructions` fits within the size limit once compiled with
+/// `lookup_tables`.
+fn fits_in_transaction(
+    payer: &Pubkey,
+    budget_instructions: &[Instruction],
+    inbox_instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+) -> ChainResult<bool> {
+    let instructions = budget_instructions
+        .iter()
+        .chain(inbox_instructions)
+        .cloned()
+        .collect::<Vec<_>>();
+    let txn_message =
+        compile_versioned_message(payer, &instructions, lookup_tables, Hash::default())?;
+    Ok(versioned_transaction_size(txn_message)? <= PACKET_DATA_SIZE)
+}
+
+/// An inbox process instruction, along with the accounts of the recipient's
+/// handle instruction it ends with.
+struct InboxProcessInstruction {
+    instruction: Instruction,
+    handle_accounts: Vec<Pubkey>,
+}
+
+/// The lookup tables a process transaction is compiled with.
+#[derive(Debug)]
+enum ProcessLookupTables {
+    /// The transaction fits with these existing lookup tables.
+    Ready(Vec<AddressLookupTableAccount>),
+    /// The transaction only fits once the recipient's lookup table is
+    /// extended with these addresses.
+    Extend(Vec<Pubkey>),
+}
+
+/// The accounts of `inbox_instruction` that are worth adding to the
+/// recipient's lookup table. That's every account that's the same for all of
+/// the recipient's messages: the signer and the processed message account
+/// are left out, as are any handle accounts but the `shared_handle_accounts`.
+fn lookup_table_addresses(
+    inbox_instruction: &InboxProcessInstruction,
+    processed_message_account: &Pubkey,
+    shared_handle_accounts: &[Pubkey],
+) -> Vec<Pubkey> {
+    let accounts = &inbox_instruction.instruction.accounts;
+    let handle_accounts_start = accounts.len() - inbox_instruction.handle_accounts.len();
+    let (accounts, handle_accounts) = accounts.split_at(handle_accounts_start);
+    accounts
+        .iter()
+        .filter(|account| !account.is_signer && account.pubkey != *processed_message_account)
+        .chain(
+            handle_accounts
+                .iter()
+                .filter(|account| shared_handle_accounts.contains(&account.pubkey)),
+        )
+        .map(|account| account.pubkey)
+        .collect()
+}
+
+/// A message that may be included in a batch.
+#[derive(Debug, Clone)]
+struct BatchCandidate {
+    /// The position of the message in the batch
+    index: usize,
+    instruction: Instruction,
+    compute_units: u32,
+    /// The existing lookup table of the message's recipient
+    lookup_table: Option<AddressLookupTableAccount>,
+}
+
+/// The messages that fit in a single process transaction.
+#[derive(Debug)]
+struct PackedBatch {
+    included: Vec<BatchCandidate>,
+    /// The lookup tables to compile the transaction with
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    /// The indexes of the messages that didn't fit
+    excluded: Vec<usize>,
+}
+
+fn total_compute_units(candidates: &[BatchCandidate]) -> u32 {
+    candidates.iter().map(|c| c.compute_units).sum()
+}
+
+/// Packs `candidates` into a single process transaction in order, leaving out
+/// those that would take it over the compute unit or transaction size limit.
+fn pack_process_batch(
+    payer: &Pubkey,
+    candidates: Vec<BatchCandidate>,
+    compute_unit_price: u64,
+    use_jito: bool,
+) -> ChainResult<PackedBatch> {
+    let mut included: Vec<BatchCandidate> = vec![];
+    let mut lookup_tables: Vec<AddressLookupTableAccount> = vec![];
+    let mut excluded = vec![];
+    for candidate in candidates {
+        let compute_units = total_compute_units(&included) + candidate.compute_units;
+        if compute_units > PROCESS_COMPUTE_UNITS {
+            excluded.push(candidate.index);
+            continue;
+        }
+
+        let mut candidate_lookup_tables = lookup_tables.clone();
+        if let Some(lookup_table) = &candidate.lookup_table {
+            if !candidate_lookup_tables
+                .iter()
+                .any(|table| table.key == lookup_table.key)
+            {
+                candidate_lookup_tables.push(lookup_table.clone());
+            }
+        }
+        let inbox_instructions = included
+            .iter()
+            .chain(std::iter::once(&candidate))
+            .map(|c| c.instruction.clone())
+            .collect::<Vec<_>>();
+        if !fits_in_transaction(
+            payer,
+            &compute_budget_instructions(payer, compute_units, compute_unit_price, use_jito),
+            &inbox_instructions,
+            &candidate_lookup_tables,
+        )? {
+            excluded.push(candidate.index);
+            continue;
+        }
+        included.push(candidate);
+        lookup_tables = candidate_lookup_tables;
+    }
+    Ok(PackedBatch {
+        included,
+        lookup_tables,
+        excluded,
+    })
+}
+
 // TODO refactor the sealevel client into a lib and bin, pull in and use the lib here rather than
 // duplicating.
 #[async_trait]
@@ -484,159 +1139,271 @@ impl Mailbox for SealevelMailbox {
         &self,
         message: &HyperlaneMessage,
         metadata: &[u8],
-        _tx_gas_limit: Option<U256>,
+        tx_gas_limit: Option<U256>,
     ) -> ChainResult<TxOutcome> {
-        let recipient: Pubkey = message.recipient.0.into();
-        let mut encoded_message = vec![];
-        message.write_to(&mut encoded_message).unwrap();
-
         let payer = self
             .payer
             .as_ref()
             .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
 
-        let mut instructions = Vec::with_capacity(3);
-        // Set the compute unit limit.
-        instructions.push(ComputeBudgetInstruction::set_compute_unit_limit(
-            PROCESS_COMPUTE_UNITS,
-        ));
-
-        // If we're using Jito, we need to send a tip to the Jito fee account.
-        // Otherwise, we need to set the compute unit price.
-        if self.use_jito() {
-            // The tip is a standalone transfer to a Jito fee account.
-            // See https://github.com/jito-labs/mev-protos/blob/master/json_rpc/http.md#sendbundle.
-            instructions.push(solana_sdk::system_instruction::transfer(
-                &payer.pubkey(),
-                // A random Jito fee account, taken from the getFeeAccount RPC response:
-                // https://github.com/jito-labs/mev-protos/blob/master/json_rpc/http.md#gettipaccounts
-                &solana_sdk::pubkey!("DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh"),
-                PROCESS_DESIRED_PRIORITIZATION_FEE_LAMPORTS_PER_TX,
-            ));
-        }
-        // "processed" level commitment does not guarantee finality.
-        // roughly 5% of blocks end up on a dropped fork.
-        // However we don't want this function to be a bottleneck and there already
-        // is retry logic in the agents.
-        let commitment = CommitmentConfig::processed();
+        let compute_unit_limit = tx_gas_limit
+            .map(|limit| limit.min(PROCESS_COMPUTE_UNITS.into()).as_u32())
+            .unwrap_or(PROCESS_COMPUTE_UNITS);
+        let instruction = self
+            .get_inbox_process_instruction(message, metadata, &payer.pubkey())
+            .await?;
+        // Only now that the message is being delivered is the recipient's lookup
+        // table created or extended, if that's needed.
+        let lookup_tables = match self
+            .plan_process_lookup_tables(&payer.pubkey(), message, compute_unit_limit, &instruction)
+            .await?
+        {
+            ProcessLookupTables::Ready(lookup_tables) => lookup_tables,
+            ProcessLookupTables::Extend(addresses) => {
+                let recipient: Pubkey = message.recipient.0.into();
+                let lookup_table = self
+                    .lookup_tables
+                    .get_or_extend(self.rpc(), payer, &recipient, &addresses)
+                    .await?;
+                vec![lookup_table]
+            }
+        };
 
-        let (process_authority_key, _process_authority_bump) = Pubkey::try_find_program_address(
-            mailbox_process_authority_pda_seeds!(&recipient),
-            &self.program_id,
+        self.send_process_transaction(
+            payer,
+            compute_unit_limit,
+            &[instruction.instruction],
+            &lookup_tables,
         )
-        .ok_or_else(|| {
-            ChainCommunicationError::from_other_str(
-                "Could not find program address for process authority",
-            )
-        })?;
-        let (processed_message_account_key, _processed_message_account_bump) =
-            Pubkey::try_find_program_address(
-                mailbox_processed_message_pda_seeds!(message.id()),
-                &self.program_id,
-            )
-            .ok_or_else(|| {
-                ChainCommunicationError::from_other_str(
-                    "Could not find program address for processed message account",
-                )
-            })?;
-
-        // Get the account metas required for the recipient.InterchainSecurityModule instruction.
-        let ism_getter_account_metas = self.get_ism_getter_account_metas(recipient).await?;
-
-        // Get the recipient ISM.
-        let ism = self
-            .get_recipient_ism(recipient, ism_getter_account_metas.clone())
-            .await?;
+        .await
+    }
 
-        let ixn =
-            hyperlane_sealevel_mailbox::instruction::Instruction::InboxProcess(InboxProcess {
-                metadata: metadata.to_vec(),
-                message: encoded_message.clone(),
+    #[instrument(err, ret, skip(self, ops), fields(size=%ops.len()))]
+    async fn try_process_batch<'a>(
+        &self,
+        ops: Vec<&'a QueueOperation>,
+    ) -> ChainResult<BatchResult> {
+        let payer = self
+            .payer
+            .as_ref()
+            .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
+        let payer_pubkey = payer.pubkey();
+        let messages = ops
+            .iter()
+            .map(|op| op.try_batch())
+            .collect::<ChainResult<Vec<BatchItem<HyperlaneMessage>>>>()?;
+
+        let mut candidates = vec![];
+        let mut failed_indexes = vec![];
+        for (index, item) in messages.iter().enumerate() {
+            let instruction = match self
+                .get_inbox_process_instruction(
+                    &item.data,
+                    &item.submission_data.metadata,
+                    &payer_pubkey,
+                )
+                .await
+            {
+                Ok(instruction) => instruction.instruction,
+                Err(err) => {
+                    debug!(index, ?err, "Excluding message from batch");
+                    failed_indexes.push(index);
+                    continue;
+                }
+            };
+            let recipient: Pubkey = item.data.recipient.0.into();
+            candidates.push(BatchCandidate {
+                index,
+                instruction,
+                compute_units: item
+                    .submission_data
+                    .gas_limit
+                    .min(PROCESS_COMPUTE_UNITS.into())
+                    .as_u32(),
+                // Lookup tables are only created when processing messages one by one
+                lookup_table: self
+                    .lookup_tables
+                    .get(self.rpc(), &payer_pubkey, &recipient)
+                    .await?,
             });
-        let ixn_data = ixn
-            .into_instruction_data()
-            .map_err(ChainCommunicationError::from_other)?;
-
-        // Craft the accounts for the transaction.
-        let mut accounts: Vec<AccountMeta> = vec![
-            AccountMeta::new_readonly(payer.pubkey(), true),
-            AccountMeta::new_readonly(Pubkey::from_str(SYSTEM_PROGRAM).unwrap(), false),
-            AccountMeta::new(self.inbox.0, false),
-            AccountMeta::new_readonly(process_authority_key, false),
-            AccountMeta::new(processed_message_account_key, false),
-        ];
-        accounts.extend(ism_getter_account_metas);
-        accounts.extend([
-            AccountMeta::new_readonly(Pubkey::from_str(SPL_NOOP).unwrap(), false),
-            AccountMeta::new_readonly(ism, false),
-        ]);
+        }
 
-        // Get the account metas required for the ISM.Verify instruction.
-        let ism_verify_account_metas = self
-            .get_ism_verify_account_metas(ism, metadata.into(), encoded_message)
+        // Pack as many messages as fit within the compute and transaction size limits,
+        // which depends on how many accounts they share. The compute unit price is
+        // only known when sending, so size the transaction as if it had one.
+        let PackedBatch {
+            mut included,
+            lookup_tables,
+            excluded,
+        } = pack_process_batch(
+            &payer_pubkey,
+            candidates,
+            self.priority_fee.max_compute_unit_price,
+            self.use_jito(),
+        )?;
+        failed_indexes.extend(excluded);
+
+        // A transaction fails as a whole if any of its instructions fails. Exclude
+        // failing messages one at a time until the rest of the batch succeeds.
+        let total_compute_units = loop {
+            // only send a batch if there are at least two successful messages
+            if included.len() < 2 {
+                return Ok(BatchResult::failed(messages.len()));
+            }
+            let total_compute_units = total_compute_units(&included);
+            let mut instructions = self.get_compute_budget_instructions(
+                &payer_pubkey,
+                total_compute_units,
+                self.priority_fee.max_compute_unit_price,
+            );
+            let budget_instruction_count = instructions.len();
+            instructions.extend(included.iter().map(|c| c.instruction.clone()));
+            match self
+                .simulate_process_batch(
+                    &payer_pubkey,
+                    &instructions,
+                    &lookup_tables,
+                    budget_instruction_count,
+                )
+                .await?
+            {
+                None => break total_compute_units,
+                Some(position) => {
+                    let index = included.remove(position).index;
+                    debug!(index, "Excluding message that would fail from batch");
+                    failed_indexes.push(index);
+                }
+            }
+        };
+        failed_indexes.sort_unstable();
+
+        let inbox_instructions = included
+            .into_iter()
+            .map(|c| c.instruction)
+            .collect::<Vec<_>>();
+        let outcome = self
+            .send_process_transaction(
+                payer,
+                total_compute_units,
+                &inbox_instructions,
+                &lookup_tables,
+            )
             .await?;
-        accounts.extend(ism_verify_account_metas);
-
-        // The recipient.
-        accounts.extend([AccountMeta::new_readonly(recipient, false)]);
+        Ok(BatchResult::new(Some(outcome), failed_indexes))
+    }
 
-        // Get account metas required for the Handle instruction
-        let handle_account_metas = self.get_handle_account_metas(message).await?;
-        accounts.extend(handle_account_metas);
+    #[instrument(err, ret, skip(self))]
+    async fn process_estimate_costs(
+        &self,
+        message: &HyperlaneMessage,
+        metadata: &[u8],
+    ) -> ChainResult<TxCostEstimate> {
+        let payer = self
+            .payer
+            .as_ref()
+            .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?;
+        let payer_pubkey = payer.pubkey();
 
-        let inbox_instruction = Instruction {
-            program_id: self.program_id,
-            data: ixn_data,
-            accounts,
-        };
-        instructions.push(inbox_instruction);
+        let instruction = self
+            .get_inbox_process_instruction(message, metadata, &payer_pubkey)
+            .await?;
+        let lookup_tables = self
+            .plan_process_lookup_tables(&payer_pubkey, message, PROCESS_COMPUTE_UNITS, &instruction)
+            .await?;
+        let instruction = instruction.instruction;
+        let compute_unit_price = self
+            .get_compute_unit_price(std::slice::from_ref(&instruction))
+            .await?;
+        let commitment = CommitmentConfig::processed();
         let (recent_blockhash, _) = self
             .rpc()
             .get_latest_blockhash_with_commitment(commitment)
             .await
             .map_err(ChainCommunicationError::from_other)?;
 
-        let txn = Transaction::new_signed_with_payer(
-            &instructions,
-            Some(&payer.pubkey()),
-            &[payer],
-            recent_blockhash,
-        );
-
-        tracing::info!(?txn, "Created sealevel transaction to process message");
-
-        let signature = self.send_and_confirm_transaction(&txn).await?;
+        let (gas_limit, payer_spent) = match lookup_tables {
+            ProcessLookupTables::Ready(lookup_tables) => {
+                let mut instructions = self.get_compute_budget_instructions(
+                    &payer_pubkey,
+                    PROCESS_COMPUTE_UNITS,
+                    compute_unit_price,
+                );
+                instructions.push(instruction);
+                let txn_message = compile_versioned_message(
+                    &payer_pubkey,
+                    &instructions,
+                    &lookup_tables,
+                    recent_blockhash,
+                )?;
+                let (gas_limit, payer_spent) = self
+                    .simulate_process_transaction(&payer_pubkey, txn_message)
+                    .await?;
+                // The payer's spend includes the fee of the simulated transaction,
+                // which is estimated separately below
+                let payer_spent = match payer_spent {
+                    Some(payer_spent) => {
+                        let simulated_fee = self
+                            .get_process_fee(
+                                &payer_pubkey,
+                                PROCESS_COMPUTE_UNITS,
+                                compute_unit_price,
+                                &recent_blockhash,
+                            )
+                            .await?;
+                        Some(payer_spent.saturating_sub(simulated_fee))
+                    }
+                    None => None,
+                };
+                (gas_limit, payer_spent)
+            }
+            // The transaction can't be simulated before the recipient's lookup table is
+            // extended, which only happens when the message is processed. Assume it uses
+            // all the compute units it may.
+            ProcessLookupTables::Extend(_) => {
+                debug!(
+                    recipient=?message.recipient,
+                    "Estimating process costs without simulating, as the transaction needs an extended address lookup table"
+                );
+                (u64::from(PROCESS_COMPUTE_UNITS), None)
+            }
+        };
 
-        tracing::info!(?txn, ?signature, "Sealevel transaction sent");
+        let base_fee = self
+            .get_process_fee(
+                &payer_pubkey,
+                gas_limit as u32,
+                compute_unit_price,
+                &recent_blockhash,
+            )
+            .await?;
 
-        let executed = self
+        // Lamports the payer spends within the transaction, i.e. any Jito tip and the
+        // rent for accounts created while processing. This is at least the rent of the
+        // processed message PDA created by the mailbox, plus whatever the recipient
+        // creates in its handler.
+        let processed_message_rent = self
             .rpc()
-            .confirm_transaction_with_commitment(&signature, commitment)
+            .get_minimum_balance_for_rent_exemption(
+                ProcessedMessageAccount::from(ProcessedMessage::default()).size(),
+            )
             .await
-            .map_err(|err| warn!("Failed to confirm inbox process transaction: {}", err))
-            .map(|ctx| ctx.value)
-            .unwrap_or(false);
-        let txid = signature.into();
-
-        Ok(TxOutcome {
-            transaction_id: txid,
-            executed,
-            // TODO use correct data upon integrating IGP support
-            gas_price: U256::zero().try_into()?,
-            gas_used: U256::zero(),
-        })
-    }
+            .map_err(ChainCommunicationError::from_other)?;
+        let mut in_txn_spend = processed_message_rent;
+        if self.use_jito() {
+            in_txn_spend += PROCESS_DESIRED_PRIORITIZATION_FEE_LAMPORTS_PER_TX;
+        }
+        if let Some(payer_spent) = payer_spent {
+            in_txn_spend = in_txn_spend.max(payer_spent);
+        }
 
-    #[instrument(err, ret, skip(self))]
-    async fn process_estimate_costs(
-        &self,
-        _message: &HyperlaneMessage,
-        _metadata: &[u8],
-    ) -> ChainResult<TxCostEstimate> {
-        // TODO use correct data upon integrating IGP support
+        let total_cost_lamports = base_fee + in_txn_spend;
+
+        // Sealevel has no gas price, so express the total cost as a price per
+        // compute unit such that `gas_limit * gas_price` is the cost in lamports.
         Ok(TxCostEstimate {
-            gas_limit: U256::zero(),
-            gas_price: FixedPointNumber::zero(),
+            gas_limit: gas_limit.into(),
+            gas_price: FixedPointNumber::from(total_cost_lamports)
+                / FixedPointNumber::from(gas_limit.max(1)),
             l2_gas_limit: None,
         })
     }
@@ -679,35 +1446,37 @@ impl SealevelMailboxIndexer {
         Ok(height)
     }
 
-    async fn get_message_with_nonce(
+    /// Finds the mailbox PDA whose data starts with `discriminator` followed by
+    /// `sequence_bytes`, and returns its pubkey and full account data.
+    ///
+    /// To keep responses small in case there is ever more than 1 match, only the
+    /// 32 byte field at `seed_offset` is requested for each candidate. `derive_pda`
+    /// derives the expected PDA from that field, which proves a candidate is an
+    /// actual PDA of the mailbox rather than some other account with the same data.
+    async fn get_sequenced_pda(
         &self,
-        nonce: u32,
-    ) -> ChainResult<(Indexed<HyperlaneMessage>, LogMeta)> {
-        let target_message_account_bytes = &[
-            &hyperlane_sealevel_mailbox::accounts::DISPATCHED_MESSAGE_DISCRIMINATOR[..],
-            &nonce.to_le_bytes()[..],
-        ]
-        .concat();
-        let target_message_account_bytes = base64::encode(target_message_account_bytes);
+        discriminator: &[u8],
+        sequence_bytes: &[u8],
+        seed_offset: usize,
+        derive_pda: impl Fn(&[u8]) -> Option<(Pubkey, u8)>,
+    ) -> ChainResult<(Pubkey, Account)> {
+        let target_account_bytes = base64::encode([discriminator, sequence_bytes].concat());
 
         // First, find all accounts with the matching account data.
-        // To keep responses small in case there is ever more than 1
-        // match, we don't request the full account data, and just request
-        // the `unique_message_pubkey` field.
         let memcmp = RpcFilterType::Memcmp(Memcmp {
             // Ignore the first byte, which is the `initialized` bool flag.
             offset: 1,
-            bytes: MemcmpEncodedBytes::Base64(target_message_account_bytes),
+            bytes: MemcmpEncodedBytes::Base64(target_account_bytes),
             encoding: None,
         });
         let config = RpcProgramAccountsConfig {
             filters: Some(vec![memcmp]),
             account_config: RpcAccountInfoConfig {
                 encoding: Some(UiAccountEncoding::Base64),
-                // Don't return any data
+                // Only return the field used to derive the PDA
                 data_slice: Some(UiDataSliceConfig {
-                    offset: 1 + 8 + 4 + 8, // the offset to get the `unique_message_pubkey` field
-                    length: 32,            // the length of the `unique_message_pubkey` field
+                    offset: seed_offset,
+                    length: 32,
                 }),
                 commitment: Some(CommitmentConfig::finalized()),
                 min_context_slot: None,
@@ -721,46 +1490,55 @@ impl SealevelMailboxIndexer {
             .map_err(ChainCommunicationError::from_other)?;
 
         // Now loop through matching accounts and find the one with a valid account pubkey
-        // that proves it's an actual message storage PDA.
-        let mut valid_message_storage_pda_pubkey = Option::<Pubkey>::None;
+        // that proves it's an actual PDA.
+        let mut valid_pda_pubkey = Option::<Pubkey>::None;
 
         for (pubkey, account) in accounts {
-            let unique_message_pubkey = Pubkey::new(&account.data);
-            let (expected_pubkey, _bump) = Pubkey::try_find_program_address(
-                mailbox_dispatched_message_pda_seeds!(unique_message_pubkey),
-                &self.mailbox.program_id,
-            )
-            .ok_or_else(|| {
-                ChainCommunicationError::from_other_str(
-                    "Could not find program address for unique_message_pubkey",
-                )
+            let (expected_pubkey, _bump) = derive_pda(&account.data).ok_or_else(|| {
+                ChainCommunicationError::from_other_str("Could not find program address for PDA")
             })?;
             if expected_pubkey == pubkey {
-                valid_message_storage_pda_pubkey = Some(pubkey);
+                valid_pda_pubkey = Some(pubkey);
                 break;
             }
         }
 
-        let valid_message_storage_pda_pubkey =
-            valid_message_storage_pda_pubkey.ok_or_else(|| {
-                ChainCommunicationError::from_other_str(
-                    "Could not find valid message storage PDA pubkey",
-                )
-            })?;
+        let valid_pda_pubkey = valid_pda_pubkey.ok_or_else(|| {
+            ChainCommunicationError::from_other_str("Could not find valid PDA pubkey")
+        })?;
 
-        // Now that we have the valid message storage PDA pubkey, we can get the full account data.
+        // Now that we have the valid PDA pubkey, we can get the full account data.
         let account = self
             .rpc()
-            .get_account_with_commitment(
-                &valid_message_storage_pda_pubkey,
-                CommitmentConfig::finalized(),
-            )
+            .get_account_with_commitment(&valid_pda_pubkey, CommitmentConfig::finalized())
             .await
             .map_err(ChainCommunicationError::from_other)?
             .value
             .ok_or_else(|| {
                 ChainCommunicationError::from_other_str("Could not find account data")
             })?;
+
+        Ok((valid_pda_pubkey, account))
+    }
+
+    async fn get_message_with_nonce(
+        &self,
+        nonce: u32,
+    ) -> ChainResult<(Indexed<HyperlaneMessage>, LogMeta)> {
+        let (pubkey, account) = self
+            .get_sequenced_pda(
+                &hyperlane_sealevel_mailbox::accounts::DISPATCHED_MESSAGE_DISCRIMINATOR[..],
+                &nonce.to_le_bytes(),
+                1 + 8 + 4 + 8, // the offset to get the `unique_message_pubkey` field
+                |unique_message_pubkey| {
+                    Pubkey::try_find_program_address(
+                        mailbox_dispatched_message_pda_seeds!(Pubkey::new(unique_message_pubkey)),
+                        &self.mailbox.program_id,
+                    )
+                },
+            )
+            .await?;
+
         let dispatched_message_account =
             DispatchedMessageAccount::fetch(&mut account.data.as_ref())
                 .map_err(ChainCommunicationError::from_other)?
@@ -768,20 +1546,65 @@ impl SealevelMailboxIndexer {
         let hyperlane_message =
             HyperlaneMessage::read_from(&mut &dispatched_message_account.encoded_message[..])?;
 
+        let (transaction_id, block_hash) = self
+            .mailbox
+            .provider
+            .get_account_txn_id_and_block_hash(&pubkey, dispatched_message_account.slot)
+            .await?;
+
         Ok((
             hyperlane_message.into(),
             LogMeta {
                 address: self.mailbox.program_id.to_bytes().into(),
                 block_number: dispatched_message_account.slot,
-                // TODO: get these when building out scraper support.
-                // It's inconvenient to get these :|
-                block_hash: H256::zero(),
-                transaction_id: H512::zero(),
+                block_hash,
+                transaction_id,
                 transaction_index: 0,
                 log_index: U256::zero(),
             },
         ))
     }
+
+    async fn get_delivery_with_sequence(
+        &self,
+        sequence: u32,
+    ) -> ChainResult<(Indexed<H256>, LogMeta)> {
+        let (pubkey, account) = self
+            .get_sequenced_pda(
+                &hyperlane_sealevel_mailbox::accounts::PROCESSED_MESSAGE_DISCRIMINATOR[..],
+                &u64::from(sequence).to_le_bytes(),
+                1 + 8 + 8, // the offset to get the `message_id` field
+                |message_id| {
+                    Pubkey::try_find_program_address(
+                        mailbox_processed_message_pda_seeds!(H256::from_slice(message_id)),
+                        &self.mailbox.program_id,
+                    )
+                },
+            )
+            .await?;
+
+        let processed_message_account = ProcessedMessageAccount::fetch(&mut account.data.as_ref())
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+
+        let (transaction_id, block_hash) = self
+            .mailbox
+            .provider
+            .get_account_txn_id_and_block_hash(&pubkey, processed_message_account.slot)
+            .await?;
+
+        Ok((
+            Indexed::new(processed_message_account.message_id).with_sequence(sequence),
+            LogMeta {
+                address: self.mailbox.program_id.to_bytes().into(),
+                block_number: processed_message_account.slot,
+                block_hash,
+                transaction_id,
+                transaction_index: 0,
+                log_index: sequence.into(),
+            },
+        ))
+    }
 }
 
 #[async_trait]
@@ -823,9 +1646,16 @@ impl Indexer<HyperlaneMessage> for SealevelMailboxIndexer {
 impl Indexer<H256> for SealevelMailboxIndexer {
     async fn fetch_logs_in_range(
         &self,
-        _range: RangeInclusive<u32>,
+        range: RangeInclusive<u32>,
     ) -> ChainResult<Vec<(Indexed<H256>, LogMeta)>> {
-        todo!()
+        info!(?range, "Fetching SealevelMailboxIndexer H256 delivery logs");
+
+        let delivery_capacity = range.end().saturating_sub(*range.start());
+        let mut deliveries = Vec::with_capacity(delivery_capacity as usize);
+        for sequence in range {
+            deliveries.push(self.get_delivery_with_sequence(sequence).await?);
+        }
+        Ok(deliveries)
     }
 
     async fn get_finalized_block_number(&self) -> ChainResult<u32> {
@@ -835,11 +1665,28 @@ impl Indexer<H256> for SealevelMailboxIndexer {
 
 #[async_trait]
 impl SequenceAwareIndexer<H256> for SealevelMailboxIndexer {
+    #[instrument(err, skip(self))]
     async fn latest_sequence_count_and_tip(&self) -> ChainResult<(Option<u32>, u32)> {
-        // TODO: implement when sealevel scraper support is implemented
-        info!("Message delivery indexing not implemented");
         let tip = Indexer::<H256>::get_finalized_block_number(self).await?;
-        Ok((Some(1), tip))
+
+        let inbox_account = self
+            .rpc()
+            .get_account_with_commitment(&self.mailbox.inbox.0, CommitmentConfig::finalized())
+            .await
+            .map_err(ChainCommunicationError::from_other)?
+            .value
+            .ok_or_else(|| {
+                ChainCommunicationError::from_other_str("Could not find inbox account")
+            })?;
+        let inbox = InboxAccount::fetch(&mut inbox_account.data.as_ref())
+            .map_err(ChainCommunicationError::from_other)?
+            .into_inner();
+        let count = inbox
+            .processed_count
+            .try_into()
+            .map_err(StrOrIntParseError::from)?;
+
+        Ok((Some(count), tip))
     }
 }
 
@@ -853,3 +1700,346 @@ impl HyperlaneAbi for SealevelMailboxAbi {
         todo!()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use account_utils::AccountData;
+    use hyperlane_core::Encode;
+    use hyperlane_sealevel_mailbox::accounts::DispatchedMessage;
+    use solana_client::mock_sender::Mocks;
+    use solana_sdk::hash::Hash;
+
+    use super::*;
+    use crate::test_utils::{mock_no_signature_history, mock_sequenced_pda, mock_txn_and_block};
+
+    const SLOT: u64 = 4321;
+
+    fn mock_indexer(program_id: Pubkey, mocks: Mocks) -> SealevelMailboxIndexer {
+        let domain = HyperlaneDomain::Known(KnownHyperlaneDomain::SealevelTest1);
+        SealevelMailboxIndexer {
+            mailbox: SealevelMailbox {
+                program_id,
+                inbox: Pubkey::find_program_address(mailbox_inbox_pda_seeds!(), &program_id),
+                outbox: Pubkey::find_program_address(mailbox_outbox_pda_seeds!(), &program_id),
+                provider: SealevelProvider::new_mock(domain, mocks),
+                payer: None,
+                lookup_tables: LookupTables::default(),
+                priority_fee: PriorityFeeConf::default(),
+            },
+            program_id,
+        }
+    }
+
+    fn mock_processed_message(mocks: &mut Mocks, program_id: &Pubkey, sequence: u64) -> H256 {
+        let message_id = H256::repeat_byte(0xab);
+        let (pda, _) = Pubkey::find_program_address(
+            mailbox_processed_message_pda_seeds!(message_id),
+            program_id,
+        );
+        let account = AccountData::new(ProcessedMessage::new(sequence, message_id, SLOT));
+        let mut data = vec![0; account.size()];
+        account.store_in_slice(&mut data).unwrap();
+        mock_sequenced_pda(mocks, program_id, &pda, message_id.as_bytes(), &data);
+        message_id
+    }
+
+    #[tokio::test]
+    async fn test_get_delivery_with_sequence() {
+        let program_id = Pubkey::new_unique();
+        let signature = Signature::new(&[3; 64]);
+        let block_hash = Hash::new_unique();
+        let mut mocks = Mocks::default();
+        let message_id = mock_processed_message(&mut mocks, &program_id, 5);
+        mock_txn_and_block(&mut mocks, &signature, SLOT, &block_hash);
+        let indexer = mock_indexer(program_id, mocks);
+
+        let (delivery, log_meta) = indexer.get_delivery_with_sequence(5).await.unwrap();
+
+        assert_eq!(*delivery.inner(), message_id);
+        assert_eq!(delivery.sequence, Some(5));
+        assert_eq!(log_meta.address, H256::from(program_id.to_bytes()));
+        assert_eq!(log_meta.block_number, SLOT);
+        assert_eq!(log_meta.block_hash, H256::from(block_hash.to_bytes()));
+        assert_eq!(
+            log_meta.transaction_id,
+            H512::from_slice(signature.as_ref())
+        );
+        assert_eq!(log_meta.log_index, U256::from(5));
+    }
+
+    #[tokio::test]
+    async fn test_get_delivery_with_sequence_without_signature_history() {
+        let program_id = Pubkey::new_unique();
+        let mut mocks = Mocks::default();
+        let block_hash = Hash::new_unique();
+        let message_id = mock_processed_message(&mut mocks, &program_id, 5);
+        mock_no_signature_history(&mut mocks, SLOT, &block_hash);
+        let indexer = mock_indexer(program_id, mocks);
+
+        // The delivery is still indexed, just without the transaction id
+        let (delivery, log_meta) = indexer.get_delivery_with_sequence(5).await.unwrap();
+
+        assert_eq!(*delivery.inner(), message_id);
+        assert_eq!(log_meta.block_number, SLOT);
+        assert_eq!(log_meta.block_hash, H256::from(block_hash.to_bytes()));
+        assert_eq!(log_meta.transaction_id, H512::zero());
+    }
+
+    #[tokio::test]
+    async fn test_get_message_with_nonce() {
+        let program_id = Pubkey::new_unique();
+        let message = HyperlaneMessage {
+            nonce: 9,
+            ..Default::default()
+        };
+        let unique_message_pubkey = Pubkey::new_unique();
+        let (pda, _) = Pubkey::find_program_address(
+            mailbox_dispatched_message_pda_seeds!(unique_message_pubkey),
+            &program_id,
+        );
+        let account = AccountData::new(DispatchedMessage::new(
+            message.nonce,
+            SLOT,
+            unique_message_pubkey,
+            message.to_vec(),
+        ));
+        let mut data = vec![0; account.size()];
+        account.store_in_slice(&mut data).unwrap();
+
+        let signature = Signature::new(&[3; 64]);
+        let block_hash = Hash::new_unique();
+        let mut mocks = Mocks::default();
+        mock_sequenced_pda(
+            &mut mocks,
+            &program_id,
+            &pda,
+            &unique_message_pubkey.to_bytes(),
+            &data,
+        );
+        mock_txn_and_block(&mut mocks, &signature, SLOT, &block_hash);
+        let indexer = mock_indexer(program_id, mocks);
+
+        let (indexed, log_meta) = indexer.get_message_with_nonce(9).await.unwrap();
+
+        assert_eq!(*indexed.inner(), message);
+        assert_eq!(log_meta.block_number, SLOT);
+        assert_eq!(log_meta.block_hash, H256::from(block_hash.to_bytes()));
+        assert_eq!(
+            log_meta.transaction_id,
+            H512::from_slice(signature.as_ref())
+        );
+    }
+
+    fn readonly_accounts(keys: &[Pubkey]) -> Vec<AccountMeta> {
+        keys.iter()
+            .map(|key| AccountMeta::new_readonly(*key, false))
+            .collect()
+    }
+
+    /// A process instruction with the `shared` accounts and then
+    /// `unique_count` accounts that no other instruction has.
+    fn batch_candidate(
+        index: usize,
+        program_id: Pubkey,
+        shared: &[Pubkey],
+        unique_count: usize,
+        compute_units: u32,
+        lookup_table: Option<AddressLookupTableAccount>,
+    ) -> BatchCandidate {
+        let unique = (0..unique_count)
+            .map(|_| Pubkey::new_unique())
+            .collect::<Vec<_>>();
+        BatchCandidate {
+            index,
+            instruction: Instruction {
+                program_id,
+                data: vec![],
+                accounts: readonly_accounts(&[shared, &unique].concat()),
+            },
+            compute_units,
+            lookup_table,
+        }
+    }
+
+    fn packed_indexes(batch: &PackedBatch) -> Vec<usize> {
+        batch.included.iter().map(|c| c.index).collect()
+    }
+
+    #[test]
+    fn test_fits_in_transaction() {
+        let payer = Pubkey::new_unique();
+        let accounts = (0..35).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let instruction = Instruction {
+            program_id: Pubkey::new_unique(),
+            data: vec![],
+            accounts: readonly_accounts(&accounts),
+        };
+        let budget_instructions = compute_budget_instructions(&payer, 400_000, 1_000, false);
+
+        // 35 account keys alone take up more than 1,100 bytes
+        assert!(!fits_in_transaction(
+            &payer,
+            &budget_instructions,
+            std::slice::from_ref(&instruction),
+            &[],
+        )
+        .unwrap());
+
+        // Looked up, each takes up a single byte
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: accounts,
+        };
+        assert!(fits_in_transaction(
+            &payer,
+            &budget_instructions,
+            std::slice::from_ref(&instruction),
+            &[lookup_table],
+        )
+        .unwrap());
+    }
+
+    #[test]
+    fn test_lookup_table_addresses() {
+        let payer = Pubkey::new_unique();
+        let processed_message_account = Pubkey::new_unique();
+        let inbox = Pubkey::new_unique();
+        let ism = Pubkey::new_unique();
+        let recipient = Pubkey::new_unique();
+        let token_program = Pubkey::new_unique();
+        let recipient_ata = Pubkey::new_unique();
+
+        let mut accounts = vec![AccountMeta::new_readonly(payer, true)];
+        accounts.extend(readonly_accounts(&[
+            inbox,
+            processed_message_account,
+            ism,
+            recipient,
+            token_program,
+            recipient_ata,
+        ]));
+        let inbox_instruction = InboxProcessInstruction {
+            instruction: Instruction {
+                program_id: Pubkey::new_unique(),
+                data: vec![],
+                accounts,
+            },
+            handle_accounts: vec![token_program, recipient_ata],
+        };
+
+        // Only the accounts shared between messages are looked up
+        assert_eq!(
+            lookup_table_addresses(
+                &inbox_instruction,
+                &processed_message_account,
+                &[token_program]
+            ),
+            vec![inbox, ism, recipient, token_program]
+        );
+        assert_eq!(
+            lookup_table_addresses(&inbox_instruction, &processed_message_account, &[]),
+            vec![inbox, ism, recipient]
+        );
+    }
+
+    #[test]
+    fn test_pack_process_batch_within_compute_units() {
+        let payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let candidates = vec![
+            batch_candidate(0, program_id, &[], 1, 600_000, None),
+            batch_candidate(1, program_id, &[], 1, 600_000, None),
+            // Would take the batch over the compute unit limit
+            batch_candidate(2, program_id, &[], 1, 600_000, None),
+            batch_candidate(3, program_id, &[], 1, 100_000, None),
+        ];
+
+        let batch = pack_process_batch(&payer, candidates, 1_000, false).unwrap();
+
+        assert_eq!(packed_indexes(&batch), vec![0, 1, 3]);
+        assert_eq!(batch.excluded, vec![2]);
+        assert!(batch.lookup_tables.is_empty());
+        assert_eq!(total_compute_units(&batch.included), 1_300_000);
+    }
+
+    #[test]
+    fn test_pack_process_batch_within_size() {
+        let payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let candidates = (0..3)
+            .map(|index| batch_candidate(index, program_id, &[], 20, 100_000, None))
+            .collect();
+
+        // Only one instruction with 20 accounts of its own fits
+        let batch = pack_process_batch(&payer, candidates, 1_000, false).unwrap();
+
+        assert_eq!(packed_indexes(&batch), vec![0]);
+        assert_eq!(batch.excluded, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_pack_process_batch_with_lookup_table() {
+        let payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let shared = (0..25).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        let lookup_table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: shared.clone(),
+        };
+        let candidates = |lookup_table: Option<AddressLookupTableAccount>| {
+            (0..5)
+                .map(|index| {
+                    batch_candidate(index, program_id, &shared, 2, 100_000, lookup_table.clone())
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Without a lookup table, the shared accounts leave no room for all messages
+        let batch = pack_process_batch(&payer, candidates(None), 1_000, false).unwrap();
+        assert!(!batch.excluded.is_empty());
+        assert!(batch.lookup_tables.is_empty());
+
+        // With the recipient's lookup table they all fit, and the table is only
+        // used once
+        let batch =
+            pack_process_batch(&payer, candidates(Some(lookup_table.clone())), 1_000, false)
+                .unwrap();
+        assert_eq!(packed_indexes(&batch), vec![0, 1, 2, 3, 4]);
+        assert!(batch.excluded.is_empty());
+        assert_eq!(
+            batch
+                .lookup_tables
+                .iter()
+                .map(|table| table.key)
+                .collect::<Vec<_>>(),
+            vec![lookup_table.key]
+        );
+    }
+
+    #[test]
+    fn test_compute_budget_instructions() {
+        let payer = Pubkey::new_unique();
+
+        // Without Jito, the price is set even if there is no prioritization fee to pay,
+        // so that estimates simulate the same instructions that are sent.
+        for price in [0, 1_000] {
+            assert_eq!(
+                compute_budget_instructions(&payer, 400_000, price, false),
+                vec![
+                    ComputeBudgetInstruction::set_compute_unit_limit(400_000),
+                    ComputeBudgetInstruction::set_compute_unit_price(price),
+                ]
+            );
+        }
+
+        // With Jito, a tip is paid instead.
+        let instructions = compute_budget_instructions(&payer, 400_000, 1_000, true);
+        assert_eq!(instructions.len(), 2);
+        assert_eq!(
+            instructions[0],
+            ComputeBudgetInstruction::set_compute_unit_limit(400_000)
+        );
+        assert_eq!(instructions[1].program_id, solana_sdk::system_program::id());
+        assert_eq!(instructions[1].accounts[0].pubkey, payer);
+    }
+}
@@ -0,0 +1,109 @@
+use hyperlane_core::{ChainCommunicationError, ChainResult};
+use serde::Deserialize;
+use solana_client::rpc_request::RpcRequest;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+
+use crate::{PriorityFeeConf, RpcClientWithDebug};
+
+/// The max number of accounts `getRecentPrioritizationFees` accepts.
+const MAX_PRIORITIZATION_FEE_ACCOUNTS: usize = 128;
+
+/// The percentage the compute unit price is raised by when a transaction
+/// is resubmitted.
+const COMPUTE_UNIT_PRICE_BUMP_PERCENT: u64 = 50;
+
+/// The prioritization fee paid by a transaction in a recent slot
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RecentPrioritizationFee {
+    #[allow(dead_code)]
+    slot: u64,
+    /// In micro-lamports per compute unit
+    prioritization_fee: u64,
+}
+
+/// The compute unit price, in micro-lamports, to pay for a transaction with
+/// `instructions`. It's the configured percentile of the prioritization fees
+/// paid by recent transactions that lock any of the same writable accounts,
+/// capped at the configured max.
+pub(crate) async fn get_compute_unit_price(
+    rpc: &RpcClientWithDebug,
+    instructions: &[Instruction],
+    conf: &PriorityFeeConf,
+) -> ChainResult<u64> {
+    let mut writable_accounts: Vec<Pubkey> = vec![];
+    for account in instructions.iter().flat_map(|ixn| &ixn.accounts) {
+        if account.is_writable && !writable_accounts.contains(&account.pubkey) {
+            writable_accounts.push(account.pubkey);
+        }
+    }
+    writable_accounts.truncate(MAX_PRIORITIZATION_FEE_ACCOUNTS);
+
+    let addresses = writable_accounts
+        .iter()
+        .map(|account| account.to_string())
+        .collect::<Vec<_>>();
+    let fees: Vec<RecentPrioritizationFee> = rpc
+        .send(
+            RpcRequest::Custom {
+                method: "getRecentPrioritizationFees",
+            },
+            serde_json::json!([addresses]),
+        )
+        .await
+        .map_err(ChainCommunicationError::from_other)?;
+
+    let fees = fees
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect::<Vec<_>>();
+    Ok(percentile(fees, conf.percentile).min(conf.max_compute_unit_price))
+}
+
+/// Raises the compute unit price of a transaction that is being resubmitted,
+/// without exceeding the configured max.
+pub(crate) fn bump_compute_unit_price(price: u64, conf: &PriorityFeeConf) -> u64 {
+    let bumped = price.saturating_mul(100 + COMPUTE_UNIT_PRICE_BUMP_PERCENT) / 100;
+    bumped
+        .max(price.saturating_add(1))
+        .min(conf.max_compute_unit_price)
+}
+
+/// The value below which `percentile` percent of `values` fall, or 0 if there
+/// are no values.
+fn percentile(mut values: Vec<u64>, percentile: u8) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+    values.sort_unstable();
+    let index = (values.len() - 1) * percentile.min(100) as usize / 100;
+    values[index]
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        assert_eq!(percentile(vec![], 75), 0);
+        assert_eq!(percentile(vec![7], 0), 7);
+        let fees = vec![50, 0, 10, 40, 20, 30, 0, 100, 60];
+        assert_eq!(percentile(fees.clone(), 0), 0);
+        assert_eq!(percentile(fees.clone(), 50), 30);
+        assert_eq!(percentile(fees.clone(), 75), 50);
+        assert_eq!(percentile(fees, 100), 100);
+    }
+
+    #[test]
+    fn test_bump_compute_unit_price() {
+        let conf = PriorityFeeConf {
+            percentile: 75,
+            max_compute_unit_price: 1_000,
+        };
+        assert_eq!(bump_compute_unit_price(0, &conf), 1);
+        assert_eq!(bump_compute_unit_price(100, &conf), 150);
+        assert_eq!(bump_compute_unit_price(800, &conf), 1_000);
+        assert_eq!(bump_compute_unit_price(1_000, &conf), 1_000);
+    }
+}
@@ -3,10 +3,19 @@ use std::{str::FromStr, sync::Arc};
 use async_trait::async_trait;
 
 use hyperlane_core::{
-    BlockInfo, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain, HyperlaneProvider, TxnInfo,
-    H256, U256,
+    BlockInfo, ChainCommunicationError, ChainInfo, ChainResult, HyperlaneChain, HyperlaneDomain,
+    HyperlaneProvider, HyperlaneProviderError, TxnInfo, TxnReceiptInfo, H256, H512, U256,
 };
-use solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use solana_client::{
+    rpc_client::GetConfirmedSignaturesForAddress2Config,
+    rpc_config::{RpcBlockConfig, RpcTransactionConfig},
+};
+use solana_sdk::{
+    clock::Slot, commitment_config::CommitmentConfig, hash::Hash, pubkey::Pubkey,
+    signature::Signature,
+};
+use solana_transaction_status::{TransactionDetails, UiTransactionEncoding};
+use tracing::warn;
 
 use crate::{client::RpcClientWithDebug, error::HyperlaneSealevelError, ConnectionConf};
 
@@ -22,7 +31,7 @@ impl SealevelProvider {
     pub fn new(domain: HyperlaneDomain, conf: &ConnectionConf) -> Self {
         // Set the `processed` commitment at rpc level
         let rpc_client = Arc::new(RpcClientWithDebug::new_with_commitment(
-            conf.url.to_string(),
+            conf.urls.clone(),
             CommitmentConfig::processed(),
         ));
 
@@ -44,6 +53,73 @@ impl SealevelProvider {
             .map_err(Into::<HyperlaneSealevelError>::into)?;
         Ok(balance.into())
     }
+
+    /// Get the hash of the block at the given slot
+    pub async fn get_block_hash(&self, slot: Slot) -> ChainResult<H256> {
+        Ok(self.get_block_by_height(slot).await?.hash)
+    }
+
+    /// Get the signature of the transaction that created or last wrote to
+    /// `account` in `slot`, together with the hash of that block.
+    ///
+    /// Programs like the mailbox and IGP write a fresh PDA for every dispatch,
+    /// delivery and gas payment, so this is how we recover the transaction id
+    /// and block hash for a `LogMeta`.
+    ///
+    /// RPC errors are returned so that the caller retries. Only if the RPC
+    /// answers but no longer has a successful transaction for `account` in
+    /// `slot` in its signature history, the transaction id falls back to zero.
+    pub async fn get_account_txn_id_and_block_hash(
+        &self,
+        account: &Pubkey,
+        slot: Slot,
+    ) -> ChainResult<(H512, H256)> {
+        let signatures = self
+            .rpc_client
+            .get_signatures_for_address_with_config(
+                account,
+                GetConfirmedSignaturesForAddress2Config {
+                    commitment: Some(CommitmentConfig::finalized()),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(Into::<HyperlaneSealevelError>::into)?;
+
+        let transaction_id = match signatures
+            .into_iter()
+            .find(|s| s.slot == slot && s.err.is_none())
+        {
+            Some(signature) => {
+                let signature = Signature::from_str(&signature.signature)
+                    .map_err(ChainCommunicationError::from_other)?;
+                H512::from_slice(signature.as_ref())
+            }
+            None => {
+                warn!(
+                    ?account,
+                    slot,
+                    "No successful transaction for account in its signature history, using a zero transaction id"
+                );
+                H512::zero()
+            }
+        };
+
+        Ok((transaction_id, self.get_block_hash(slot).await?))
+    }
+
+    /// Create a provider backed by a mock RPC client that answers the given
+    /// requests.
+    #[cfg(test)]
+    pub(crate) fn new_mock(
+        domain: HyperlaneDomain,
+        mocks: solana_client::mock_sender::Mocks,
+    ) -> Self {
+        SealevelProvider {
+            domain,
+            rpc_client: Arc::new(RpcClientWithDebug::new_mock(mocks)),
+        }
+    }
 }
 
 impl HyperlaneChain for SealevelProvider {
@@ -62,11 +138,89 @@ impl HyperlaneChain for SealevelProvider {
 #[async_trait]
 impl HyperlaneProvider for SealevelProvider {
     async fn get_block_by_hash(&self, _hash: &H256) -> ChainResult<BlockInfo> {
-        todo!() // FIXME
+        Err(ChainCommunicationError::from_other_str(
+            "Sealevel does not support querying blocks by hash, use get_block_by_height",
+        ))
     }
 
-    async fn get_txn_by_hash(&self, _hash: &H256) -> ChainResult<TxnInfo> {
-        todo!() // FIXME
+    async fn get_block_by_height(&self, height: u64) -> ChainResult<BlockInfo> {
+        let config = RpcBlockConfig {
+            encoding: None,
+            transaction_details: Some(TransactionDetails::None),
+            rewards: Some(false),
+            commitment: Some(CommitmentConfig::finalized()),
+            max_supported_transaction_version: Some(0),
+        };
+        let block = self
+            .rpc_client
+            .get_block_with_config(height, config)
+            .await
+            .map_err(|_| HyperlaneProviderError::CouldNotFindBlockByHeight(height))?;
+
+        let hash = Hash::from_str(&block.blockhash).map_err(ChainCommunicationError::from_other)?;
+
+        Ok(BlockInfo {
+            hash: H256::from(hash.to_bytes()),
+            timestamp: block.block_time.unwrap_or_default() as u64,
+            number: height,
+        })
+    }
+
+    async fn get_txn_by_hash(&self, hash: &H512) -> ChainResult<TxnInfo> {
+        let signature = Signature::new(hash.as_bytes());
+        let config = RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::finalized()),
+            max_supported_transaction_version: Some(0),
+        };
+        let txn = self
+            .rpc_client
+            .get_transaction_with_config(&signature, config)
+            .await
+            .map_err(|_| HyperlaneProviderError::CouldNotFindTransactionByHash(*hash))?;
+
+        let meta = txn.transaction.meta.ok_or_else(|| {
+            ChainCommunicationError::from_other_str("Transaction is missing its status meta")
+        })?;
+        let transaction = txn.transaction.transaction.decode().ok_or_else(|| {
+            ChainCommunicationError::from_other_str("Could not decode transaction")
+        })?;
+        let message = &transaction.message;
+        let account_keys = message.static_account_keys();
+
+        // The fee payer is always the first account.
+        let sender = account_keys
+            .first()
+            .map(|k| H256::from(k.to_bytes()))
+            .unwrap_or_default();
+        // Treat the program invoked by the last top-level instruction as the
+        // recipient, as that's the one doing the meaningful work after any
+        // compute budget instructions.
+        let recipient = message
+            .instructions()
+            .last()
+            .and_then(|ix| account_keys.get(ix.program_id_index as usize))
+            .map(|k| H256::from(k.to_bytes()));
+
+        // Sealevel doesn't have a gas price, so the fee in lamports is
+        // reported as the gas used with a price of 1.
+        let fee = U256::from(meta.fee);
+
+        Ok(TxnInfo {
+            hash: *hash,
+            gas_limit: fee,
+            max_priority_fee_per_gas: None,
+            max_fee_per_gas: None,
+            gas_price: Some(U256::one()),
+            nonce: 0,
+            sender,
+            recipient,
+            receipt: Some(TxnReceiptInfo {
+                gas_used: fee,
+                cumulative_gas_used: fee,
+                effective_gas_price: Some(U256::one()),
+            }),
+        })
     }
 
     async fn is_contract(&self, _address: &H256) -> ChainResult<bool> {
@@ -0,0 +1,101 @@
+use account_utils::DiscriminatorEncode;
+use async_trait::async_trait;
+
+use hyperlane_core::{
+    ChainCommunicationError, ChainResult, ContractLocator, HyperlaneChain, HyperlaneContract,
+    HyperlaneDomain, HyperlaneMessage, HyperlaneProvider, RoutingIsm, H256,
+};
+use hyperlane_sealevel_routing_ism::{
+    domain_routes_pda_seeds, instruction::Instruction as RoutingIsmInstruction,
+};
+use serializable_account_meta::SimulationReturnData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+};
+
+use crate::{utils::simulate_instruction, ConnectionConf, RpcClientWithDebug, SealevelProvider};
+
+/// A reference to a RoutingIsm contract on some Sealevel chain
+#[derive(Debug)]
+pub struct SealevelRoutingIsm {
+    payer: Option<Keypair>,
+    program_id: Pubkey,
+    domain: HyperlaneDomain,
+    provider: SealevelProvider,
+}
+
+impl SealevelRoutingIsm {
+    /// Create a new Sealevel RoutingIsm.
+    pub fn new(conf: &ConnectionConf, locator: ContractLocator, payer: Option<Keypair>) -> Self {
+        let provider = SealevelProvider::new(locator.domain.clone(), conf);
+        let program_id = Pubkey::from(<[u8; 32]>::from(locator.address));
+
+        Self {
+            payer,
+            program_id,
+            domain: locator.domain.clone(),
+            provider,
+        }
+    }
+
+    fn rpc(&self) -> &RpcClientWithDebug {
+        self.provider.rpc()
+    }
+}
+
+impl HyperlaneContract for SealevelRoutingIsm {
+    fn address(&self) -> H256 {
+        self.program_id.to_bytes().into()
+    }
+}
+
+impl HyperlaneChain for SealevelRoutingIsm {
+    fn domain(&self) -> &HyperlaneDomain {
+        &self.domain
+    }
+
+    fn provider(&self) -> Box<dyn HyperlaneProvider> {
+        self.provider.provider()
+    }
+}
+
+#[async_trait]
+impl RoutingIsm for SealevelRoutingIsm {
+    /// Returns the ISM needed to verify message
+    async fn route(&self, message: &HyperlaneMessage) -> ChainResult<H256> {
+        let (domain_routes_pda_key, _domain_routes_pda_bump) =
+            Pubkey::try_find_program_address(domain_routes_pda_seeds!(), &self.program_id)
+                .ok_or_else(|| {
+                    ChainCommunicationError::from_other_str(
+                        "Could not find program address for domain routes",
+                    )
+                })?;
+
+        let instruction = Instruction::new_with_bytes(
+            self.program_id,
+            &RoutingIsmInstruction::Route(message.origin)
+                .encode()
+                .map_err(ChainCommunicationError::from_other)?[..],
+            vec![AccountMeta::new_readonly(domain_routes_pda_key, false)],
+        );
+
+        let ism = simulate_instruction::<SimulationReturnData<Pubkey>>(
+            self.rpc(),
+            self.payer
+                .as_ref()
+                .ok_or_else(|| ChainCommunicationError::SignerUnavailable)?,
+            instruction,
+        )
+        .await?
+        .ok_or_else(|| {
+            ChainCommunicationError::from_other_str(
+                "No return data was returned from the routing ism",
+            )
+        })?
+        .return_data;
+
+        Ok(ism.to_bytes().into())
+    }
+}
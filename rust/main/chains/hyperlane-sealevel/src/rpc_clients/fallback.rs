@@ -0,0 +1,203 @@
+use std::{
+    fmt::{Debug, Formatter},
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use hyperlane_core::{
+    rpc_clients::{BlockNumberGetter, FallbackProvider},
+    ChainCommunicationError,
+};
+use serde_json::{json, Value};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind, Result as ClientResult},
+    http_sender::HttpSender,
+    rpc_request::{RpcError, RpcRequest, RpcResponseErrorData},
+    rpc_sender::{RpcSender, RpcTransportStats},
+};
+use url::Url;
+
+/// An http sender for a single Sealevel RPC url
+#[derive(Clone)]
+pub struct SealevelRpcSender {
+    sender: Arc<HttpSender>,
+    /// The url that this sender is connected to.
+    url: Url,
+}
+
+impl SealevelRpcSender {
+    /// Create a new sender for `url`
+    pub fn new(url: Url) -> Self {
+        Self {
+            sender: Arc::new(HttpSender::new(url.to_string())),
+            url,
+        }
+    }
+}
+
+impl Debug for SealevelRpcSender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SealevelRpcSender")
+            .field("url", &self.url.as_str())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl BlockNumberGetter for SealevelRpcSender {
+    async fn get_block_number(&self) -> Result<u64, ChainCommunicationError> {
+        let slot = self
+            .sender
+            .send(RpcRequest::GetSlot, json!([{ "commitment": "processed" }]))
+            .await
+            .map_err(ChainCommunicationError::from_other)?;
+        serde_json::from_value(slot).map_err(ChainCommunicationError::from_other)
+    }
+}
+
+/// An `RpcSender` that sends each request to the highest priority RPC url of
+/// a `FallbackProvider`, falling back to the next one if the url can't be
+/// reached or its node is unhealthy.
+pub struct SealevelFallbackRpcSender {
+    fallback_provider: FallbackProvider<SealevelRpcSender, SealevelRpcSender>,
+}
+
+impl SealevelFallbackRpcSender {
+    /// Create a new fallback sender over `urls`, in order of priority
+    pub fn new(urls: Vec<Url>) -> Self {
+        let fallback_provider = FallbackProvider::new(urls.into_iter().map(SealevelRpcSender::new));
+        Self { fallback_provider }
+    }
+}
+
+impl Debug for SealevelFallbackRpcSender {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fallback_provider.fmt(f)
+    }
+}
+
+/// Whether an error returned by a single RPC url should be retried against
+/// the next one. Errors about the request itself, like a failed transaction
+/// simulation, would be returned by every url so they're surfaced directly.
+fn should_fall_back(error: &ClientError) -> bool {
+    match error.kind() {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError { data, .. }) => {
+            matches!(data, RpcResponseErrorData::NodeUnhealthy { .. })
+        }
+        ClientErrorKind::RpcError(RpcError::ForUser(_)) => false,
+        ClientErrorKind::TransactionError(_) | ClientErrorKind::SigningError(_) => false,
+        _ => true,
+    }
+}
+
+#[async_trait]
+impl RpcSender for SealevelFallbackRpcSender {
+    async fn send(&self, request: RpcRequest, params: Value) -> ClientResult<Value> {
+        // The error of the last url tried, returned as is if every url fails so
+        // that callers can still tell what went wrong
+        let last_error: Arc<Mutex<Option<ClientError>>> = Default::default();
+        let result = self
+            .fallback_provider
+            .call(|provider| {
+                let params = params.clone();
+                let last_error = last_error.clone();
+                let future = async move {
+                    match provider.sender.send(request, params).await {
+                        Err(error) if should_fall_back(&error) => {
+                            let fallback_error =
+                                ChainCommunicationError::from_other_str(&error.to_string());
+                            *last_error.lock().unwrap() = Some(error);
+                            Err(fallback_error)
+                        }
+                        result => Ok(result),
+                    }
+                };
+                Box::pin(future)
+            })
+            .await;
+        result.unwrap_or_else(|error| {
+            let last_error = last_error.lock().unwrap().take();
+            Err(last_error.unwrap_or_else(|| {
+                ClientError::new_with_request(ClientErrorKind::Custom(error.to_string()), request)
+            }))
+        })
+    }
+
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        RpcTransportStats::default()
+    }
+
+    fn url(&self) -> String {
+        self.fallback_provider
+            .inner
+            .providers
+            .first()
+            .map(|provider| provider.url.to_string())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use solana_sdk::transaction::TransactionError;
+
+    use super::*;
+
+    fn rpc_response_error(data: RpcResponseErrorData) -> ClientError {
+        ClientErrorKind::RpcError(RpcError::RpcResponseError {
+            code: -32005,
+            message: "error".to_owned(),
+            data,
+        })
+        .into()
+    }
+
+    /// A url that refuses connections
+    fn unreachable_url() -> Url {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+        Url::parse(&format!("http://127.0.0.1:{port}")).unwrap()
+    }
+
+    #[test]
+    fn test_should_fall_back() {
+        // The node is unreachable or unhealthy, so another one may do better
+        let io_error = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "refused");
+        assert!(should_fall_back(&ClientErrorKind::Io(io_error).into()));
+        assert!(should_fall_back(
+            &ClientErrorKind::RpcError(RpcError::RpcRequestError("error".to_owned())).into()
+        ));
+        assert!(should_fall_back(&rpc_response_error(
+            RpcResponseErrorData::NodeUnhealthy {
+                num_slots_behind: Some(100)
+            }
+        )));
+
+        // Every node would return the same error for the request
+        assert!(!should_fall_back(&rpc_response_error(
+            RpcResponseErrorData::Empty
+        )));
+        assert!(!should_fall_back(
+            &ClientErrorKind::RpcError(RpcError::ForUser("error".to_owned())).into()
+        ));
+        assert!(!should_fall_back(
+            &ClientErrorKind::TransactionError(TransactionError::AccountNotFound).into()
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_returns_original_error_if_every_url_fails() {
+        let sender = SealevelFallbackRpcSender::new(vec![unreachable_url(), unreachable_url()]);
+
+        let error = sender
+            .send(RpcRequest::GetSlot, Value::Null)
+            .await
+            .unwrap_err();
+
+        assert!(
+            !matches!(error.kind(), ClientErrorKind::Custom(_)),
+            "Expected the error of the last url, got {error:?}"
+        );
+    }
+}
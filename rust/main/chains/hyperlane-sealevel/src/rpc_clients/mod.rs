@@ -0,0 +1,3 @@
+pub use self::fallback::*;
+
+mod fallback;
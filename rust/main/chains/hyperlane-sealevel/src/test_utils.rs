@@ -0,0 +1,86 @@
+//! Helpers to mock the RPC responses the indexers depend on.
+
+use serde_json::{json, Value};
+use solana_client::{mock_sender::Mocks, rpc_request::RpcRequest};
+use solana_sdk::{clock::Slot, hash::Hash, pubkey::Pubkey, signature::Signature};
+
+#[allow(deprecated)]
+fn ui_account(owner: &Pubkey, data: &[u8]) -> Value {
+    json!({
+        "lamports": 1_000_000,
+        "data": [base64::encode(data), "base64"],
+        "owner": owner.to_string(),
+        "executable": false,
+        "rentEpoch": 0,
+    })
+}
+
+/// Mocks the `getProgramAccounts` and `getAccountInfo` responses used to find
+/// a sequenced PDA: `pda_seed` is the data slice returned by the memcmp query,
+/// and `data` the full account data.
+pub(crate) fn mock_sequenced_pda(
+    mocks: &mut Mocks,
+    program_id: &Pubkey,
+    pda: &Pubkey,
+    pda_seed: &[u8],
+    data: &[u8],
+) {
+    mocks.insert(
+        RpcRequest::GetProgramAccounts,
+        json!([{
+            "pubkey": pda.to_string(),
+            "account": ui_account(program_id, pda_seed),
+        }]),
+    );
+    mocks.insert(
+        RpcRequest::GetAccountInfo,
+        json!({
+            "context": { "slot": 1 },
+            "value": ui_account(program_id, data),
+        }),
+    );
+}
+
+/// Mocks a successful transaction with `signature` in `slot` as the only
+/// entry of an account's signature history, and `block_hash` as the hash of
+/// that slot's block.
+pub(crate) fn mock_txn_and_block(
+    mocks: &mut Mocks,
+    signature: &Signature,
+    slot: Slot,
+    block_hash: &Hash,
+) {
+    mocks.insert(
+        RpcRequest::GetSignaturesForAddress,
+        json!([{
+            "signature": signature.to_string(),
+            "slot": slot,
+            "err": null,
+            "memo": null,
+            "blockTime": null,
+            "confirmationStatus": "finalized",
+        }]),
+    );
+    mock_block(mocks, slot, block_hash);
+}
+
+/// Mocks an account whose signature history the RPC no longer has, and
+/// `block_hash` as the hash of the block at `slot`.
+pub(crate) fn mock_no_signature_history(mocks: &mut Mocks, slot: Slot, block_hash: &Hash) {
+    mocks.insert(RpcRequest::GetSignaturesForAddress, json!([]));
+    mock_block(mocks, slot, block_hash);
+}
+
+/// Mocks `block_hash` as the hash of the block at `slot`.
+fn mock_block(mocks: &mut Mocks, slot: Slot, block_hash: &Hash) {
+    mocks.insert(
+        RpcRequest::GetBlock,
+        json!({
+            "previousBlockhash": Hash::default().to_string(),
+            "blockhash": block_hash.to_string(),
+            "parentSlot": slot.saturating_sub(1),
+            "blockTime": null,
+            "blockHeight": null,
+        }),
+    );
+}
@@ -1,13 +1,36 @@
 use hyperlane_core::{config::OperationBatchConfig, ChainCommunicationError};
 use url::Url;
 
+use crate::mailbox::PROCESS_COMPUTE_UNIT_PRICE_MICRO_LAMPORTS;
+
 /// Sealevel connection configuration
 #[derive(Debug, Clone)]
 pub struct ConnectionConf {
-    /// Fully qualified string to connect to
-    pub url: Url,
+    /// Fully qualified urls to connect to, in order of priority
+    pub urls: Vec<Url>,
     /// Operation batching configuration
     pub operation_batch: OperationBatchConfig,
+    /// Priority fee configuration
+    pub priority_fee: PriorityFeeConf,
+}
+
+/// How the compute unit price of transactions is set
+#[derive(Debug, Clone)]
+pub struct PriorityFeeConf {
+    /// The percentile of the prioritization fees paid by recent transactions
+    /// that lock the same accounts to pay, between 0 and 100
+    pub percentile: u8,
+    /// The max compute unit price to pay, in micro-lamports
+    pub max_compute_unit_price: u64,
+}
+
+impl Default for PriorityFeeConf {
+    fn default() -> Self {
+        Self {
+            percentile: 75,
+            max_compute_unit_price: PROCESS_COMPUTE_UNIT_PRICE_MICRO_LAMPORTS,
+        }
+    }
 }
 
 /// An error type when parsing a connection configuration.
@@ -0,0 +1,233 @@
+//! Adapts the size of the ranges a cursor queries to how the provider copes with them.
+
+use std::ops::RangeInclusive;
+
+use hyperlane_core::ChainCommunicationError;
+use tracing::{debug, warn};
+
+use crate::settings::IndexSettings;
+
+/// Queries that return at most this many logs are considered light, and
+/// the chunk size is grown after them.
+const MAX_LIGHT_QUERY_LOGS: usize = 100;
+
+/// Substrings of the errors that providers return when a queried range
+/// spans too many blocks, its logs exceed the size of a response or the
+/// query timed out.
+const RANGE_TOO_LARGE_ERRORS: &[&str] = &[
+    // geth, Infura
+    "query returned more than",
+    // Alchemy
+    "log response size exceeded",
+    // QuickNode
+    "is limited to a",
+    // Ankr
+    "block range is too wide",
+    // Blast, Cloudflare
+    "range too large",
+    "range is too large",
+    // Erigon
+    "exceed maximum block range",
+    // Polygon
+    "exceeds max block range",
+    "exceeds max results",
+    // Chainstack
+    "block range limit exceeded",
+    // Queries of ranges with many logs
+    "timeout",
+    "timed out",
+];
+
+/// Substrings of the errors that providers return when rate limiting
+/// queries, which a smaller range doesn't help with: the 429 HTTP status or
+/// JSON-RPC error code, as formatted in the debug output of errors, or its reason.
+const RATE_LIMITED_ERRORS: &[&str] = &[
+    "code: 429",
+    "status: 429",
+    "status(429)",
+    "too many requests",
+];
+
+/// The number of blocks (or sequences, when indexing by sequence) a cursor
+/// queries at once. It's doubled after light queries of a full chunk and halved
+/// after queries fail due to the range being too large, within configured bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct AdaptiveChunkSize {
+    current: u32,
+    min: u32,
+    max: u32,
+}
+
+impl AdaptiveChunkSize {
+    /// A chunk size starting at `initial` that's adapted within `min..=max`.
+    pub fn new(initial: u32, min: u32, max: u32) -> Self {
+        let max = u32::max(min, max);
+        Self {
+            current: initial.clamp(min, max),
+            min,
+            max,
+        }
+    }
+
+    /// A chunk size that's never adapted.
+    #[cfg(test)]
+    pub fn fixed(chunk_size: u32) -> Self {
+        Self::new(chunk_size, chunk_size, chunk_size)
+    }
+
+    /// The current chunk size.
+    pub fn get(&self) -> u32 {
+        self.current
+    }
+
+    /// Grows the chunk size if a full chunk was queried and few logs were found in it.
+    pub fn handle_query_success(&mut self, range: &RangeInclusive<u32>, logs_found: usize) {
+        let full_chunk = range.end().saturating_sub(*range.start()) >= self.current;
+        if !full_chunk || logs_found > MAX_LIGHT_QUERY_LOGS || self.current >= self.max {
+            return;
+        }
+        let previous = self.current;
+        self.current = self.current.saturating_mul(2).clamp(self.min, self.max);
+        debug!(
+            previous,
+            chunk_size = self.current,
+            logs_found,
+            "Growing chunk size after a light query"
+        );
+    }
+
+    /// Shrinks the chunk size if the query failed due to the range being too large.
+    pub fn handle_query_error(
+        &mut self,
+        range: &RangeInclusive<u32>,
+        err: &ChainCommunicationError,
+    ) {
+        if !is_range_too_large_error(err) || self.current <= self.min {
+            return;
+        }
+        let previous = self.current;
+        self.current = (self.current / 2).clamp(self.min, self.max);
+        warn!(
+            previous,
+            chunk_size = self.current,
+            ?range,
+            ?err,
+            "Shrinking chunk size after the queried range was too large"
+        );
+    }
+}
+
+impl From<&IndexSettings> for AdaptiveChunkSize {
+    fn from(settings: &IndexSettings) -> Self {
+        Self::new(
+            settings.chunk_size,
+            settings.min_chunk_size,
+            settings.max_chunk_size,
+        )
+    }
+}
+
+fn is_range_too_large_error(err: &ChainCommunicationError) -> bool {
+    // Providers don't agree on error codes for these, so their messages are matched
+    let err = format!("{err:?}").to_lowercase();
+    let matches = |patterns: &[&str]| patterns.iter().any(|pattern| err.contains(pattern));
+    !matches(RATE_LIMITED_ERRORS) && matches(RANGE_TOO_LARGE_ERRORS)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn too_many_results() -> ChainCommunicationError {
+        ChainCommunicationError::from_other_str("query returned more than 10000 results")
+    }
+
+    #[test]
+    fn test_grows_after_light_full_queries() {
+        let mut chunk_size = AdaptiveChunkSize::new(100, 10, 300);
+        chunk_size.handle_query_success(&(0..=100), 0);
+        assert_eq!(chunk_size.get(), 200);
+        chunk_size.handle_query_success(&(101..=301), MAX_LIGHT_QUERY_LOGS);
+        assert_eq!(chunk_size.get(), 300);
+        chunk_size.handle_query_success(&(302..=602), 0);
+        assert_eq!(chunk_size.get(), 300);
+    }
+
+    #[test]
+    fn test_does_not_grow_after_heavy_or_partial_queries() {
+        let mut chunk_size = AdaptiveChunkSize::new(100, 10, 300);
+        chunk_size.handle_query_success(&(0..=100), MAX_LIGHT_QUERY_LOGS + 1);
+        assert_eq!(chunk_size.get(), 100);
+        // Ranges are cut short at the tip
+        chunk_size.handle_query_success(&(101..=150), 0);
+        assert_eq!(chunk_size.get(), 100);
+    }
+
+    #[test]
+    fn test_shrinks_after_range_too_large_errors() {
+        let mut chunk_size = AdaptiveChunkSize::new(100, 30, 300);
+        chunk_size.handle_query_error(&(0..=100), &too_many_results());
+        assert_eq!(chunk_size.get(), 50);
+        chunk_size.handle_query_error(
+            &(0..=50),
+            &ChainCommunicationError::from_other_str(
+                "Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range",
+            ),
+        );
+        assert_eq!(chunk_size.get(), 30);
+        chunk_size.handle_query_error(&(0..=30), &too_many_results());
+        assert_eq!(chunk_size.get(), 30);
+    }
+
+    #[test]
+    fn test_shrinks_after_timeouts() {
+        let mut chunk_size = AdaptiveChunkSize::new(100, 10, 300);
+        chunk_size.handle_query_error(
+            &(0..=100),
+            &ChainCommunicationError::from_other_str("request timed out"),
+        );
+        assert_eq!(chunk_size.get(), 50);
+    }
+
+    #[test]
+    fn test_does_not_shrink_after_other_errors() {
+        let mut chunk_size = AdaptiveChunkSize::new(100, 10, 300);
+        chunk_size.handle_query_error(
+            &(0..=100),
+            &ChainCommunicationError::from_other_str("connection refused"),
+        );
+        assert_eq!(chunk_size.get(), 100);
+    }
+
+    #[test]
+    fn test_does_not_shrink_after_rate_limiting() {
+        let mut chunk_size = AdaptiveChunkSize::new(100, 10, 300);
+        chunk_size.handle_query_error(
+            &(0..=100),
+            &ChainCommunicationError::from_other_str(
+                "HTTP status client error (429 Too Many Requests): query returned more than the allowed requests per second",
+            ),
+        );
+        assert_eq!(chunk_size.get(), 100);
+    }
+
+    #[test]
+    fn test_shrinks_after_range_errors_mentioning_429() {
+        let mut chunk_size = AdaptiveChunkSize::new(100, 10, 300);
+        chunk_size.handle_query_error(
+            &(4290..=4390),
+            &ChainCommunicationError::from_other_str(
+                "query returned more than 10000 results. Try with this block range [0x10c2, 0x1429]",
+            ),
+        );
+        assert_eq!(chunk_size.get(), 50);
+    }
+
+    #[test]
+    fn test_fixed_chunk_size_is_not_adapted() {
+        let mut chunk_size = AdaptiveChunkSize::fixed(100);
+        chunk_size.handle_query_success(&(0..=100), 0);
+        chunk_size.handle_query_error(&(0..=100), &too_many_results());
+        assert_eq!(chunk_size.get(), 100);
+    }
+}
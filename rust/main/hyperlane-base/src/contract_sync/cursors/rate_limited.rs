@@ -9,9 +9,12 @@ use async_trait::async_trait;
 use derive_new::new;
 use eyre::Result;
 use hyperlane_core::{
-    ContractSyncCursor, CursorAction, HyperlaneWatermarkedLogStore, Indexed, Indexer, LogMeta,
+    ChainCommunicationError, ContractSyncCursor, CursorAction, HyperlaneWatermarkedLogStore,
+    Indexed, Indexer, LogMeta,
 };
 
+use super::chunk_size::AdaptiveChunkSize;
+use super::reorg::ReorgDetector;
 use crate::contract_sync::eta_calculator::SyncerEtaCalculator;
 
 /// Time window for the moving average used in the eta calculator in seconds.
@@ -19,7 +22,7 @@ const ETA_TIME_WINDOW: f64 = 2. * 60.;
 
 #[derive(Debug, new)]
 pub(crate) struct SyncState {
-    chunk_size: u32,
+    chunk_size: AdaptiveChunkSize,
     /// The starting block for the cursor
     start_block: u32,
     /// The next block that should be indexed.
@@ -41,19 +44,31 @@ impl SyncState {
         let (from, to) = match self.direction {
             SyncDirection::Forward => {
                 let from = self.next_block;
-                let mut to = from + self.chunk_size;
+                let mut to = from + self.chunk_size.get();
                 to = u32::min(to, tip);
                 (from, to)
             }
             SyncDirection::Backward => {
                 let to = self.next_block;
-                let from = to.saturating_sub(self.chunk_size);
+                let from = to.saturating_sub(self.chunk_size.get());
                 (from, to)
             }
         };
         from..=to
     }
 
+    fn roll_back(&mut self, first_reorged_block: u32) {
+        match self.direction {
+            SyncDirection::Forward => {
+                self.next_block = u32::min(self.next_block, first_reorged_block);
+            }
+            SyncDirection::Backward => {
+                // Blocks indexed going backward are below the tip the cursor
+                // started at, so they're not rolled back.
+            }
+        }
+    }
+
     fn update_range(&mut self, range: RangeInclusive<u32>) {
         match self.direction {
             SyncDirection::Forward => {
@@ -83,17 +98,27 @@ pub(crate) struct RateLimitedContractSyncCursor<T> {
     last_tip_update: Instant,
     eta_calculator: SyncerEtaCalculator,
     sync_state: SyncState,
+    reorg_detector: ReorgDetector<T>,
 }
 
-impl<T> RateLimitedContractSyncCursor<T> {
+impl<T> RateLimitedContractSyncCursor<T>
+where
+    T: Clone + Debug + Send + Sync + 'static,
+{
     /// Construct a new contract sync helper.
     pub async fn new(
         indexer: Arc<dyn Indexer<T>>,
         db: Arc<dyn HyperlaneWatermarkedLogStore<T>>,
-        chunk_size: u32,
+        chunk_size: AdaptiveChunkSize,
         initial_height: u32,
     ) -> Result<Self> {
         let tip = indexer.get_finalized_block_number().await?;
+        let mut reorg_detector = ReorgDetector::new(indexer.clone(), Arc::new(db.clone()));
+        // Keep tracking the last block indexed before a restart, so that a reorg
+        // of it in the meantime is still detected
+        if let Some((block_number, hash)) = db.retrieve_high_watermark_block_hash().await? {
+            reorg_detector.restore_block(block_number, hash);
+        }
         Ok(Self {
             indexer,
             db,
@@ -107,13 +132,47 @@ impl<T> RateLimitedContractSyncCursor<T> {
                 // The rate limited cursor currently only syncs in the forward direction.
                 SyncDirection::Forward,
             ),
+            reorg_detector,
         })
     }
 
+    /// Rolls the cursor back to re-index the blocks the detector found were
+    /// reorged, if any. Returns whether it was rolled back.
+    async fn roll_back_reorged_blocks(&mut self) -> Result<bool> {
+        let Some(reorg) = self.reorg_detector.check().await? else {
+            return Ok(false);
+        };
+        self.sync_state.roll_back(reorg.first_block);
+        // Lower the high watermark right away, so the reorged blocks are
+        // re-indexed even if the agent restarts before the next update
+        self.store_high_watermark().await?;
+        Ok(true)
+    }
+
+    /// Store a relatively conservative view of the high watermark, which should allow a single watermark to be
+    /// safely shared across multiple cursors, so long as they are running sufficiently in sync.
+    /// The hash of the latest indexed block is stored along with it, to detect reorgs across restarts.
+    async fn store_high_watermark(&self) -> Result<()> {
+        self.db
+            .store_high_watermark(u32::max(
+                self.sync_state.start_block,
+                self.sync_state
+                    .next_block
+                    .saturating_sub(self.sync_state.chunk_size.get()),
+            ))
+            .await?;
+        if let Some((block_number, hash)) = self.reorg_detector.latest_block() {
+            self.db
+                .store_high_watermark_block_hash(block_number, hash)
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Wait based on how close we are to the tip and update the tip,
     /// i.e. the highest block we may scrape.
     async fn get_rate_limit(&self) -> Result<Option<Duration>> {
-        if self.sync_state.next_block + self.sync_state.chunk_size < self.tip {
+        if self.sync_state.next_block + self.sync_state.chunk_size.get() < self.tip {
             // If doing the full chunk wouldn't exceed the already known tip we do not need to rate limit.
             return Ok(None);
         }
@@ -137,7 +196,7 @@ impl<T> RateLimitedContractSyncCursor<T> {
     }
 
     fn sync_step(&self) -> u32 {
-        self.sync_state.chunk_size
+        self.sync_state.chunk_size.get()
     }
 
     async fn get_next_range(&self) -> Result<Option<RangeInclusive<u32>>> {
@@ -160,7 +219,7 @@ impl<T> RateLimitedContractSyncCursor<T> {
 #[async_trait]
 impl<T> ContractSyncCursor<T> for RateLimitedContractSyncCursor<T>
 where
-    T: Send + Sync + Debug + 'static,
+    T: Clone + Send + Sync + Debug + 'static,
 {
     async fn next_action(&mut self) -> Result<(CursorAction, Duration)> {
         let eta = self.sync_eta();
@@ -170,7 +229,14 @@ where
             return Ok((CursorAction::Sleep(rate_limit), eta));
         }
 
-        if let Some(range) = self.get_next_range().await? {
+        let mut range = self.get_next_range().await?;
+        // Reorgs are only checked for when there are new blocks to index, rather
+        // than on every poll while the cursor is at the tip
+        if range.is_some() && self.roll_back_reorged_blocks().await? {
+            range = self.get_next_range().await?;
+        }
+        if let Some(range) = range {
+            self.reorg_detector.record_range_end(*range.end()).await?;
             return Ok((CursorAction::Query(range), eta));
         } else {
             // TODO: Define the sleep time from interval flag
@@ -182,21 +248,32 @@ where
         self.sync_state.next_block.saturating_sub(1)
     }
 
+    fn reorgs_detected(&self) -> u64 {
+        self.reorg_detector.reorgs_detected()
+    }
+
+    fn chunk_size(&self) -> u32 {
+        self.sync_state.chunk_size.get()
+    }
+
+    fn handle_query_error(&mut self, range: &RangeInclusive<u32>, err: &ChainCommunicationError) {
+        self.sync_state.chunk_size.handle_query_error(range, err);
+    }
+
+    fn record_stored_logs(&mut self, logs: &[(Indexed<T>, LogMeta)]) {
+        self.reorg_detector.record_logs(logs);
+    }
+
     async fn update(
         &mut self,
-        _: Vec<(Indexed<T>, LogMeta)>,
+        logs: Vec<(Indexed<T>, LogMeta)>,
         range: RangeInclusive<u32>,
     ) -> Result<()> {
-        // Store a relatively conservative view of the high watermark, which should allow a single watermark to be
-        // safely shared across multiple cursors, so long as they are running sufficiently in sync
-        self.db
-            .store_high_watermark(u32::max(
-                self.sync_state.start_block,
-                self.sync_state
-                    .next_block
-                    .saturating_sub(self.sync_state.chunk_size),
-            ))
-            .await?;
+        self.reorg_detector.record_range(&logs);
+        self.sync_state
+            .chunk_size
+            .handle_query_success(&range, logs.len());
+        self.store_high_watermark().await?;
         self.sync_state.update_range(range);
 
         match self.indexer.get_finalized_block_number().await {
@@ -222,6 +299,7 @@ impl<T> Debug for RateLimitedContractSyncCursor<T> {
             .field("tip", &self.tip)
             .field("last_tip_update", &self.last_tip_update)
             .field("sync_state", &self.sync_state)
+            .field("reorg_detector", &self.reorg_detector)
             .finish()
     }
 }
@@ -229,8 +307,8 @@ impl<T> Debug for RateLimitedContractSyncCursor<T> {
 #[cfg(test)]
 pub(crate) mod test {
     use super::*;
-    use hyperlane_core::{ChainResult, HyperlaneLogStore};
-    use mockall::{self, Sequence};
+    use hyperlane_core::{ChainResult, HyperlaneLogStore, H256};
+    use mockall::{self, predicate::eq, Sequence};
 
     const CHUNK_SIZE: u32 = 10;
     const INITIAL_HEIGHT: u32 = 0;
@@ -246,6 +324,7 @@ pub(crate) mod test {
         impl Indexer<()> for Indexer {
             async fn fetch_logs_in_range(&self, range: RangeInclusive<u32>) -> ChainResult<Vec<(hyperlane_core::Indexed<()> , LogMeta)>>;
             async fn get_finalized_block_number(&self) -> ChainResult<u32>;
+            async fn get_block_hash(&self, block_number: u32) -> ChainResult<Option<H256>>;
         }
     }
 
@@ -265,6 +344,8 @@ pub(crate) mod test {
         impl HyperlaneWatermarkedLogStore<()> for Db {
             async fn retrieve_high_watermark(&self) -> Result<Option<u32>>;
             async fn store_high_watermark(&self, block_number: u32) -> Result<()>;
+            async fn retrieve_high_watermark_block_hash(&self) -> Result<Option<(u32, H256)>>;
+            async fn store_high_watermark_block_hash(&self, block_number: u32, hash: H256) -> Result<()>;
         }
     }
 
@@ -293,9 +374,13 @@ pub(crate) mod test {
             }
         }
 
+        indexer.expect_get_block_hash().returning(|_| Ok(None));
+
         let mut db = MockDb::new();
         db.expect_store_high_watermark().returning(|_| Ok(()));
-        let chunk_size = CHUNK_SIZE;
+        db.expect_retrieve_high_watermark_block_hash()
+            .returning(|| Ok(None));
+        let chunk_size = AdaptiveChunkSize::fixed(CHUNK_SIZE);
         let initial_height = INITIAL_HEIGHT;
         RateLimitedContractSyncCursor::new(
             Arc::new(indexer),
@@ -340,4 +425,107 @@ pub(crate) mod test {
         let (action, _) = cursor.next_action().await.unwrap();
         assert!(matches!(action, CursorAction::Sleep(_)));
     }
+
+    fn block_hash(block_number: u32) -> H256 {
+        H256::from_low_u64_be(block_number.into())
+    }
+
+    #[tokio::test]
+    async fn test_latest_block_hash_is_stored_with_watermark() {
+        let mut indexer = MockIndexer::new();
+        indexer
+            .expect_get_finalized_block_number()
+            .returning(|| Ok(100));
+        indexer
+            .expect_get_block_hash()
+            .returning(|block_number| Ok(Some(block_hash(block_number))));
+
+        let mut db = MockDb::new();
+        // The block indexed last before a restart is still canonical
+        db.expect_retrieve_high_watermark_block_hash()
+            .returning(|| Ok(Some((5, block_hash(5)))));
+        db.expect_store_high_watermark().returning(|_| Ok(()));
+        db.expect_store_high_watermark_block_hash()
+            .with(eq(CHUNK_SIZE), eq(block_hash(CHUNK_SIZE)))
+            .times(1)
+            .returning(|_, _| Ok(()));
+
+        let mut cursor = RateLimitedContractSyncCursor::new(
+            Arc::new(indexer),
+            Arc::new(db),
+            AdaptiveChunkSize::fixed(CHUNK_SIZE),
+            INITIAL_HEIGHT,
+        )
+        .await
+        .unwrap();
+
+        let (action, _) = cursor.next_action().await.unwrap();
+        let CursorAction::Query(range) = action else {
+            panic!("Expected Query action");
+        };
+        assert_eq!(range, INITIAL_HEIGHT..=CHUNK_SIZE);
+        cursor.update(vec![], range).await.unwrap();
+        assert_eq!(cursor.reorgs_detected(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_block_reorged_before_restart_is_detected() {
+        let mut indexer = MockIndexer::new();
+        indexer
+            .expect_get_finalized_block_number()
+            .returning(|| Ok(100));
+        indexer
+            .expect_get_block_hash()
+            .returning(|block_number| Ok(Some(block_hash(block_number))));
+
+        let mut db = MockDb::new();
+        // The block indexed last before a restart was reorged in the meantime
+        db.expect_retrieve_high_watermark_block_hash()
+            .returning(|| Ok(Some((5, H256::repeat_byte(0xff)))));
+        db.expect_store_high_watermark().returning(|_| Ok(()));
+        db.expect_store_high_watermark_block_hash()
+            .returning(|_, _| Ok(()));
+
+        let mut cursor = RateLimitedContractSyncCursor::new(
+            Arc::new(indexer),
+            Arc::new(db),
+            AdaptiveChunkSize::fixed(CHUNK_SIZE),
+            INITIAL_HEIGHT,
+        )
+        .await
+        .unwrap();
+
+        let (action, _) = cursor.next_action().await.unwrap();
+        assert!(matches!(action, CursorAction::Query(_)));
+        assert_eq!(cursor.reorgs_detected(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_no_reorg_check_while_idle() {
+        let mut indexer = MockIndexer::new();
+        indexer
+            .expect_get_finalized_block_number()
+            .returning(|| Ok(0));
+        // Checking for reorgs would fetch the hash of the tracked block
+        indexer.expect_get_block_hash().never();
+
+        let mut db = MockDb::new();
+        db.expect_retrieve_high_watermark_block_hash()
+            .returning(|| Ok(Some((0, block_hash(0)))));
+
+        let mut cursor = RateLimitedContractSyncCursor::new(
+            Arc::new(indexer),
+            Arc::new(db),
+            AdaptiveChunkSize::fixed(CHUNK_SIZE),
+            // Synced up to the tip
+            1,
+        )
+        .await
+        .unwrap();
+        // Don't rate limit, so that the cursor looks for a new range
+        cursor.last_tip_update = Instant::now().checked_sub(Duration::from_secs(60)).unwrap();
+
+        let (action, _) = cursor.next_action().await.unwrap();
+        assert!(matches!(action, CursorAction::Sleep(_)));
+    }
 }
@@ -0,0 +1,419 @@
+//! Detects reorgs of blocks that a cursor has already indexed, by recording the
+//! hashes of recently indexed blocks and checking they're still part of the chain.
+
+use std::{collections::VecDeque, fmt::Debug, sync::Arc};
+
+use eyre::Result;
+use hyperlane_core::{HyperlaneLogStore, Indexed, Indexer, LogMeta, H256};
+use tracing::{debug, warn};
+
+/// The max number of recently indexed blocks whose hashes are tracked.
+/// Reorgs deeper than the oldest tracked block are detected, but logs
+/// from blocks before it can't be rolled back.
+const MAX_TRACKED_BLOCKS: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TrackedBlock {
+    number: u32,
+    hash: H256,
+}
+
+/// Indexed blocks that turned out to be reorged, and the logs that were
+/// deleted from the store because they were emitted in them.
+#[derive(Debug)]
+pub(crate) struct Reorg<T> {
+    /// The lowest tracked block that was reorged. Every block after it was reorged too.
+    pub first_block: u32,
+    /// The logs emitted in the reorged blocks.
+    pub logs: Vec<(Indexed<T>, LogMeta)>,
+}
+
+/// Tracks the hashes of the blocks a cursor indexes and the logs it stored
+/// from them, to detect when they're reorged and delete the reorged logs.
+pub(crate) struct ReorgDetector<T> {
+    indexer: Arc<dyn Indexer<T>>,
+    db: Arc<dyn HyperlaneLogStore<T>>,
+    /// Recently indexed blocks, in ascending order of block number.
+    blocks: VecDeque<TrackedBlock>,
+    /// The logs stored from the tracked blocks.
+    logs: Vec<(Indexed<T>, LogMeta)>,
+    /// The end block of the range being queried. Its hash is fetched before the
+    /// range is queried, so that a reorg during the query is detected afterwards.
+    pending_block: Option<TrackedBlock>,
+    reorgs_detected: u64,
+}
+
+impl<T> Debug for ReorgDetector<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReorgDetector")
+            .field("tracked_blocks", &self.blocks.len())
+            .field("latest_tracked_block", &self.blocks.back())
+            .field("reorgs_detected", &self.reorgs_detected)
+            .finish()
+    }
+}
+
+impl<T: Clone + Debug + Send + Sync + 'static> ReorgDetector<T> {
+    pub fn new(indexer: Arc<dyn Indexer<T>>, db: Arc<dyn HyperlaneLogStore<T>>) -> Self {
+        Self {
+            indexer,
+            db,
+            blocks: VecDeque::new(),
+            logs: Vec::new(),
+            pending_block: None,
+            reorgs_detected: 0,
+        }
+    }
+
+    /// The number of reorgs detected so far.
+    pub fn reorgs_detected(&self) -> u64 {
+        self.reorgs_detected
+    }
+
+    /// The number and hash of the latest tracked block, if any.
+    pub fn latest_block(&self) -> Option<(u32, H256)> {
+        self.blocks.back().map(|block| (block.number, block.hash))
+    }
+
+    /// Tracks a block indexed before the detector was created, e.g. before a
+    /// restart, so that a reorg of it is still detected.
+    pub fn restore_block(&mut self, number: u32, hash: H256) {
+        self.track(TrackedBlock { number, hash });
+    }
+
+    /// Records the hash of the block that a range about to be queried ends at.
+    pub async fn record_range_end(&mut self, block_number: u32) -> Result<()> {
+        self.pending_block = self
+            .indexer
+            .get_block_hash(block_number)
+            .await?
+            .map(|hash| TrackedBlock {
+                number: block_number,
+                hash,
+            });
+        Ok(())
+    }
+
+    /// Records the logs that were stored from a queried range, along with the
+    /// blocks they were emitted in and the block the range ended at.
+    pub fn record_range(&mut self, logs: &[(Indexed<T>, LogMeta)]) {
+        // The range end's hash was fetched before the logs, so it's tracked first
+        if let Some(block) = self.pending_block.take() {
+            self.track(block);
+        }
+        self.record_logs(logs);
+    }
+
+    /// Records logs that were stored outside of a queried range, e.g. pushed
+    /// by a log subscription, along with the blocks they were emitted in.
+    pub fn record_logs(&mut self, logs: &[(Indexed<T>, LogMeta)]) {
+        let log_blocks = logs
+            .iter()
+            .filter_map(|(_, meta)| {
+                Some(TrackedBlock {
+                    number: meta.block_number.try_into().ok()?,
+                    hash: meta.block_hash,
+                })
+            })
+            .collect::<Vec<_>>();
+        for block in log_blocks {
+            self.track(block);
+        }
+        self.logs.extend_from_slice(logs);
+        self.prune();
+    }
+
+    /// Checks whether the latest tracked block is still part of the chain. If it
+    /// isn't, finds the first reorged block, deletes the logs stored from it and
+    /// every later block, and stops tracking them.
+    pub async fn check(&mut self) -> Result<Option<Reorg<T>>> {
+        let Some(latest_block) = self.blocks.back().copied() else {
+            return Ok(None);
+        };
+        if self.is_canonical(&latest_block).await? {
+            return Ok(None);
+        }
+
+        // Every block after a reorged block is reorged too, so walk back
+        // from the latest block until one is still part of the chain.
+        let mut first_block = latest_block.number;
+        let mut deeper_than_tracked = true;
+        for block in self.blocks.iter().rev().skip(1) {
+            if self.is_canonical(block).await? {
+                deeper_than_tracked = false;
+                break;
+            }
+            first_block = block.number;
+        }
+
+        let (logs, kept_logs) = std::mem::take(&mut self.logs)
+            .into_iter()
+            .partition(|(_, meta)| meta.block_number >= u64::from(first_block));
+        self.logs = kept_logs;
+        self.blocks.retain(|block| block.number < first_block);
+        self.pending_block = None;
+
+        let deleted = self.db.delete_logs(&logs).await?;
+        self.indexer.handle_reorg(first_block).await?;
+        self.reorgs_detected += 1;
+        warn!(
+            first_block,
+            ?latest_block,
+            reorged_logs = logs.len(),
+            deleted,
+            deeper_than_tracked,
+            "Detected a reorg of indexed blocks, rolling back to re-index them"
+        );
+        Ok(Some(Reorg { first_block, logs }))
+    }
+
+    async fn is_canonical(&self, block: &TrackedBlock) -> Result<bool> {
+        // Blocks whose hash can't be fetched are assumed to not be reorged
+        Ok(self
+            .indexer
+            .get_block_hash(block.number)
+            .await?
+            .map_or(true, |hash| hash == block.hash))
+    }
+
+    fn track(&mut self, block: TrackedBlock) {
+        match self
+            .blocks
+            .binary_search_by_key(&block.number, |tracked| tracked.number)
+        {
+            Ok(index) => {
+                // Keep the earlier hash, so that if the block changed in between
+                // it's detected as a reorg on the next check
+                if self.blocks[index].hash != block.hash {
+                    debug!(
+                        tracked=?self.blocks[index],
+                        ?block,
+                        "Block hash changed since it was tracked"
+                    );
+                }
+            }
+            Err(index) => self.blocks.insert(index, block),
+        }
+    }
+
+    fn prune(&mut self) {
+        while self.blocks.len() > MAX_TRACKED_BLOCKS {
+            self.blocks.pop_front();
+        }
+        if let Some(oldest_block) = self.blocks.front() {
+            let oldest_block = u64::from(oldest_block.number);
+            self.logs
+                .retain(|(_, meta)| meta.block_number >= oldest_block);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, ops::RangeInclusive, sync::Mutex};
+
+    use async_trait::async_trait;
+    use hyperlane_core::ChainResult;
+
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockChain {
+        block_hashes: Mutex<HashMap<u32, H256>>,
+        handled_reorgs: Mutex<Vec<u32>>,
+    }
+
+    impl MockChain {
+        fn set_block_hash(&self, block_number: u32, hash: H256) {
+            self.block_hashes.lock().unwrap().insert(block_number, hash);
+        }
+    }
+
+    #[async_trait]
+    impl Indexer<u32> for MockChain {
+        async fn fetch_logs_in_range(
+            &self,
+            _range: RangeInclusive<u32>,
+        ) -> ChainResult<Vec<(Indexed<u32>, LogMeta)>> {
+            Ok(vec![])
+        }
+
+        async fn get_finalized_block_number(&self) -> ChainResult<u32> {
+            Ok(0)
+        }
+
+        async fn get_block_hash(&self, block_number: u32) -> ChainResult<Option<H256>> {
+            Ok(self
+                .block_hashes
+                .lock()
+                .unwrap()
+                .get(&block_number)
+                .copied())
+        }
+
+        async fn handle_reorg(&self, first_reorged_block: u32) -> ChainResult<()> {
+            self.handled_reorgs
+                .lock()
+                .unwrap()
+                .push(first_reorged_block);
+            Ok(())
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockStore {
+        deleted: Mutex<Vec<u32>>,
+    }
+
+    #[async_trait]
+    impl HyperlaneLogStore<u32> for MockStore {
+        async fn store_logs(&self, logs: &[(Indexed<u32>, LogMeta)]) -> Result<u32> {
+            Ok(logs.len() as u32)
+        }
+
+        async fn delete_logs(&self, logs: &[(Indexed<u32>, LogMeta)]) -> Result<u32> {
+            self.deleted
+                .lock()
+                .unwrap()
+                .extend(logs.iter().map(|(log, _)| *log.inner()));
+            Ok(logs.len() as u32)
+        }
+    }
+
+    fn log(value: u32, block_number: u32, hash: H256) -> (Indexed<u32>, LogMeta) {
+        let meta = LogMeta {
+            address: Default::default(),
+            block_number: block_number.into(),
+            block_hash: hash,
+            transaction_id: Default::default(),
+            transaction_index: 0,
+            log_index: Default::default(),
+        };
+        (Indexed::new(value), meta)
+    }
+
+    fn hash(byte: u8) -> H256 {
+        H256::repeat_byte(byte)
+    }
+
+    fn detector() -> (Arc<MockChain>, Arc<MockStore>, ReorgDetector<u32>) {
+        let chain = Arc::new(MockChain::default());
+        let store = Arc::new(MockStore::default());
+        let detector = ReorgDetector::new(chain.clone(), store.clone());
+        (chain, store, detector)
+    }
+
+    /// Indexes blocks 10..=19 with a log in blocks 12 and 17, and 20..=29
+    /// with a log in block 25.
+    async fn index_ranges(chain: &MockChain, detector: &mut ReorgDetector<u32>) {
+        for block_number in 10..30 {
+            chain.set_block_hash(block_number, hash(1));
+        }
+        detector.record_range_end(19).await.unwrap();
+        detector.record_range(&[log(0, 12, hash(1)), log(1, 17, hash(1))]);
+        detector.record_range_end(29).await.unwrap();
+        detector.record_range(&[log(2, 25, hash(1))]);
+    }
+
+    #[tokio::test]
+    async fn test_no_reorg() {
+        let (chain, store, mut detector) = detector();
+        index_ranges(&chain, &mut detector).await;
+
+        assert!(detector.check().await.unwrap().is_none());
+        assert_eq!(detector.reorgs_detected(), 0);
+        assert!(store.deleted.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reorg_deletes_logs_from_first_reorged_block() {
+        let (chain, store, mut detector) = detector();
+        index_ranges(&chain, &mut detector).await;
+        for block_number in 15..30 {
+            chain.set_block_hash(block_number, hash(2));
+        }
+
+        let reorg = detector.check().await.unwrap().unwrap();
+        // Block 12 is the latest tracked block that wasn't reorged
+        assert_eq!(reorg.first_block, 17);
+        assert_eq!(*store.deleted.lock().unwrap(), vec![1, 2]);
+        // The indexer is told to drop anything it cached from the reorged blocks
+        assert_eq!(*chain.handled_reorgs.lock().unwrap(), vec![17]);
+        assert_eq!(detector.reorgs_detected(), 1);
+
+        // The rolled back blocks aren't tracked anymore
+        assert!(detector.check().await.unwrap().is_none());
+        assert_eq!(detector.reorgs_detected(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_reorg_deeper_than_tracked_blocks() {
+        let (chain, store, mut detector) = detector();
+        index_ranges(&chain, &mut detector).await;
+        for block_number in 0..30 {
+            chain.set_block_hash(block_number, hash(2));
+        }
+
+        let reorg = detector.check().await.unwrap().unwrap();
+        assert_eq!(reorg.first_block, 12);
+        assert_eq!(*store.deleted.lock().unwrap(), vec![0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_reorg_during_query_is_detected() {
+        let (chain, store, mut detector) = detector();
+        chain.set_block_hash(19, hash(1));
+        detector.record_range_end(19).await.unwrap();
+        // The range is reorged while it's being queried
+        chain.set_block_hash(19, hash(2));
+        detector.record_range(&[log(0, 19, hash(2))]);
+
+        let reorg = detector.check().await.unwrap().unwrap();
+        assert_eq!(reorg.first_block, 19);
+        assert_eq!(*store.deleted.lock().unwrap(), vec![0]);
+    }
+
+    #[tokio::test]
+    async fn test_reorg_of_logs_recorded_ahead_of_ranges() {
+        let (chain, store, mut detector) = detector();
+        index_ranges(&chain, &mut detector).await;
+        // A log pushed by a subscription, ahead of the ranges queried so far
+        chain.set_block_hash(35, hash(1));
+        detector.record_logs(&[log(3, 35, hash(1))]);
+        assert!(detector.check().await.unwrap().is_none());
+
+        chain.set_block_hash(35, hash(2));
+        let reorg = detector.check().await.unwrap().unwrap();
+        // Block 29 is the latest tracked block that wasn't reorged
+        assert_eq!(reorg.first_block, 35);
+        assert_eq!(*store.deleted.lock().unwrap(), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn test_restored_block_is_checked() {
+        let (chain, _store, mut indexing) = detector();
+        index_ranges(&chain, &mut indexing).await;
+        assert_eq!(indexing.latest_block(), Some((29, hash(1))));
+
+        // A new detector, e.g. after a restart, picks up the latest block
+        let (restarted_chain, _store, mut restarted) = detector();
+        restarted.restore_block(29, hash(1));
+        restarted_chain.set_block_hash(29, hash(1));
+        assert!(restarted.check().await.unwrap().is_none());
+
+        restarted_chain.set_block_hash(29, hash(2));
+        let reorg = restarted.check().await.unwrap().unwrap();
+        assert_eq!(reorg.first_block, 29);
+        assert_eq!(restarted.latest_block(), None);
+    }
+
+    #[tokio::test]
+    async fn test_blocks_without_hashes_are_not_reorged() {
+        let (_chain, store, mut detector) = detector();
+        detector.record_range_end(19).await.unwrap();
+        detector.record_range(&[log(0, 12, hash(1))]);
+
+        assert!(detector.check().await.unwrap().is_none());
+        assert!(store.deleted.lock().unwrap().is_empty());
+    }
+}
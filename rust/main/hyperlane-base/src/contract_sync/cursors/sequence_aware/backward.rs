@@ -5,7 +5,7 @@ use std::{collections::HashSet, fmt::Debug, ops::RangeInclusive, sync::Arc, time
 use async_trait::async_trait;
 use eyre::Result;
 use hyperlane_core::{
-    indexed_to_sequence_indexed_array, ContractSyncCursor, CursorAction,
+    indexed_to_sequence_indexed_array, ChainCommunicationError, ContractSyncCursor, CursorAction,
     HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, LogMeta, SequenceIndexed,
 };
 use itertools::Itertools;
@@ -13,6 +13,7 @@ use tokio::time::sleep;
 use tracing::{debug, instrument, warn};
 
 use super::{LastIndexedSnapshot, TargetSnapshot};
+use crate::contract_sync::cursors::chunk_size::AdaptiveChunkSize;
 
 const MAX_BACKWARD_SYNC_BLOCKING_TIME: Duration = Duration::from_secs(5);
 
@@ -21,7 +22,7 @@ pub(crate) struct BackwardSequenceAwareSyncCursor<T> {
     /// The max chunk size to query for logs.
     /// If in sequence mode, this is the max number of sequences to query.
     /// If in block mode, this is the max number of blocks to query.
-    chunk_size: u32,
+    chunk_size: AdaptiveChunkSize,
     /// A DB used to check which logs have already been indexed.
     db: Arc<dyn HyperlaneSequenceAwareIndexerStoreReader<T>>,
     /// A snapshot of the last log to be indexed, or if no indexing has occurred yet,
@@ -53,7 +54,7 @@ impl<T: Debug> BackwardSequenceAwareSyncCursor<T> {
         ret
     )]
     pub fn new(
-        chunk_size: u32,
+        chunk_size: AdaptiveChunkSize,
         db: Arc<dyn HyperlaneSequenceAwareIndexerStoreReader<T>>,
         current_sequence_count: u32,
         start_block: u32,
@@ -108,7 +109,7 @@ impl<T: Debug> BackwardSequenceAwareSyncCursor<T> {
         // Query the block range ending at the current_indexing_snapshot's at_block.
         current_indexing_snapshot
             .at_block
-            .saturating_sub(self.chunk_size)..=current_indexing_snapshot.at_block
+            .saturating_sub(self.chunk_size.get())..=current_indexing_snapshot.at_block
     }
 
     /// Gets the next sequence range to index.
@@ -120,7 +121,7 @@ impl<T: Debug> BackwardSequenceAwareSyncCursor<T> {
         // Query the sequence range ending at the current_indexing_snapshot's sequence.
         current_indexing_snapshot
             .sequence
-            .saturating_sub(self.chunk_size)..=current_indexing_snapshot.sequence
+            .saturating_sub(self.chunk_size.get())..=current_indexing_snapshot.sequence
     }
 
     /// Reads the DB to check if the current indexing sequence has already been indexed,
@@ -349,6 +350,14 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
             .unwrap_or(self.last_indexed_snapshot.at_block)
     }
 
+    fn chunk_size(&self) -> u32 {
+        self.chunk_size.get()
+    }
+
+    fn handle_query_error(&mut self, range: &RangeInclusive<u32>, err: &ChainCommunicationError) {
+        self.chunk_size.handle_query_error(range, err);
+    }
+
     /// Updates the cursor with the logs that were found in the range.
     ///
     /// Inconsistencies in the logs are not considered errors, instead they're handled by rewinding the cursor
@@ -364,6 +373,7 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
         logs: Vec<(Indexed<T>, LogMeta)>,
         range: RangeInclusive<u32>,
     ) -> Result<()> {
+        self.chunk_size.handle_query_success(&range, logs.len());
         let Some(current_indexing_snapshot) = self.current_indexing_snapshot.clone() else {
             // We're synced, no need to update at all.
             return Ok(());
@@ -436,7 +446,7 @@ mod test {
         });
 
         let mut cursor = BackwardSequenceAwareSyncCursor::new(
-            chunk_size,
+            AdaptiveChunkSize::fixed(chunk_size),
             db,
             INITIAL_SEQUENCE_COUNT,
             INITIAL_START_BLOCK,
@@ -768,7 +778,7 @@ mod test {
             });
 
             let mut cursor = BackwardSequenceAwareSyncCursor::new(
-                CHUNK_SIZE,
+                AdaptiveChunkSize::fixed(CHUNK_SIZE),
                 db,
                 INITIAL_SEQUENCE_COUNT,
                 INITIAL_START_BLOCK,
@@ -985,7 +995,7 @@ mod test {
             let mut cursor = get_cursor().await;
 
             // Set the chunk size to 100 to make it easier to test.
-            cursor.chunk_size = 100;
+            cursor.chunk_size = AdaptiveChunkSize::fixed(100);
 
             // Expect the range to be:
             // (current - chunk_size, current)
@@ -8,27 +8,29 @@ use std::{
 use async_trait::async_trait;
 use eyre::Result;
 use hyperlane_core::{
-    indexed_to_sequence_indexed_array, ContractSyncCursor, CursorAction,
-    HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, LogMeta, SequenceAwareIndexer,
+    indexed_to_sequence_indexed_array, ChainCommunicationError, ContractSyncCursor, CursorAction,
+    HyperlaneSequenceAwareIndexerStore, IndexMode, Indexed, LogMeta, SequenceAwareIndexer,
     SequenceIndexed,
 };
 use itertools::Itertools;
 use tracing::{debug, instrument, warn};
 
 use super::{LastIndexedSnapshot, TargetSnapshot};
+use crate::contract_sync::cursors::chunk_size::AdaptiveChunkSize;
+use crate::contract_sync::cursors::reorg::{Reorg, ReorgDetector};
 
 /// A sequence-aware cursor that syncs forwards in perpetuity.
 pub(crate) struct ForwardSequenceAwareSyncCursor<T> {
     /// The max chunk size to query for logs.
     /// If in sequence mode, this is the max number of sequences to query.
     /// If in block mode, this is the max number of blocks to query.
-    chunk_size: u32,
+    chunk_size: AdaptiveChunkSize,
     /// The latest sequence count querier.
     /// This is used to check if there are new logs to index and to
     /// establish targets to index towards.
     latest_sequence_querier: Arc<dyn SequenceAwareIndexer<T>>,
     /// A DB used to check which logs have already been indexed.
-    db: Arc<dyn HyperlaneSequenceAwareIndexerStoreReader<T>>,
+    db: Arc<dyn HyperlaneSequenceAwareIndexerStore<T>>,
     /// A snapshot of the last indexed log, or if no indexing has occurred yet,
     /// the initial log to start indexing forward from.
     last_indexed_snapshot: LastIndexedSnapshot,
@@ -39,6 +41,8 @@ pub(crate) struct ForwardSequenceAwareSyncCursor<T> {
     target_snapshot: Option<TargetSnapshot>,
     /// The mode of indexing.
     index_mode: IndexMode,
+    /// Detects reorgs of indexed blocks, deleting the logs stored from them.
+    reorg_detector: ReorgDetector<T>,
 }
 
 impl<T> Debug for ForwardSequenceAwareSyncCursor<T> {
@@ -49,20 +53,21 @@ impl<T> Debug for ForwardSequenceAwareSyncCursor<T> {
             .field("current_indexing_snapshot", &self.current_indexing_snapshot)
             .field("target_snapshot", &self.target_snapshot)
             .field("index_mode", &self.index_mode)
+            .field("reorg_detector", &self.reorg_detector)
             .finish()
     }
 }
 
-impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
+impl<T: Clone + Debug + Send + Sync + 'static> ForwardSequenceAwareSyncCursor<T> {
     #[instrument(
         skip(db, latest_sequence_querier),
         fields(chunk_size, next_sequence, start_block, index_mode),
         ret
     )]
     pub fn new(
-        chunk_size: u32,
+        chunk_size: AdaptiveChunkSize,
         latest_sequence_querier: Arc<dyn SequenceAwareIndexer<T>>,
-        db: Arc<dyn HyperlaneSequenceAwareIndexerStoreReader<T>>,
+        db: Arc<dyn HyperlaneSequenceAwareIndexerStore<T>>,
         next_sequence: u32,
         start_block: u32,
         index_mode: IndexMode,
@@ -73,6 +78,10 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
             sequence: (next_sequence > 0).then(|| next_sequence.saturating_sub(1)),
             at_block: start_block,
         };
+        let reorg_detector = ReorgDetector::new(
+            Arc::new(latest_sequence_querier.clone()),
+            Arc::new(db.clone()),
+        );
 
         Self {
             chunk_size,
@@ -85,6 +94,7 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
             },
             target_snapshot: None,
             index_mode,
+            reorg_detector,
         }
     }
 
@@ -94,6 +104,27 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
     /// depending on the mode.
     #[instrument(ret)]
     pub async fn get_next_range(&mut self) -> Result<Option<RangeInclusive<u32>>> {
+        loop {
+            let Some(range) = self.get_next_unchecked_range().await? else {
+                return Ok(None);
+            };
+            // Reorgs are only checked for when there's a new range to index, rather
+            // than on every poll while the cursor is synced. Re-index any reorged
+            // blocks, whose logs are deleted from the DB, before moving on.
+            if let Some(reorg) = self.reorg_detector.check().await? {
+                self.roll_back(reorg).await?;
+                continue;
+            }
+            if let IndexMode::Block = self.index_mode {
+                self.reorg_detector.record_range_end(*range.end()).await?;
+            }
+            return Ok(Some(range));
+        }
+    }
+
+    /// Gets the next range of logs to index, without checking whether the
+    /// blocks indexed so far were reorged.
+    async fn get_next_unchecked_range(&mut self) -> Result<Option<RangeInclusive<u32>>> {
         // Skip any already indexed logs.
         self.skip_indexed().await?;
 
@@ -174,7 +205,7 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
         Some(
             self.current_indexing_snapshot.at_block
                 ..=u32::min(
-                    self.current_indexing_snapshot.at_block + self.chunk_size,
+                    self.current_indexing_snapshot.at_block + self.chunk_size.get(),
                     tip,
                 ),
         )
@@ -188,7 +219,7 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
         target_sequence: u32,
     ) -> RangeInclusive<u32> {
         // Query the sequence range starting from the cursor count.
-        current_sequence..=u32::min(target_sequence, current_sequence + self.chunk_size)
+        current_sequence..=u32::min(target_sequence, current_sequence + self.chunk_size.get())
     }
 
     /// Reads the DB to check if the current indexing sequence has already been indexed,
@@ -401,6 +432,54 @@ impl<T: Debug> ForwardSequenceAwareSyncCursor<T> {
     fn rewind(&mut self) {
         self.current_indexing_snapshot = self.last_indexed_snapshot.next_target();
     }
+
+    /// Rolls the cursor back to re-index the logs from reorged blocks,
+    /// which were deleted from the DB.
+    async fn roll_back(&mut self, reorg: Reorg<T>) -> Result<()> {
+        // Logs stored ahead of the cursor, e.g. pushed by a subscription, are
+        // indexed again once the cursor gets to them, so they don't move it
+        let lowest_reorged_sequence = reorg
+            .logs
+            .iter()
+            .filter_map(|(log, _)| log.sequence)
+            .filter(|sequence| {
+                self.last_indexed_snapshot
+                    .sequence
+                    .map_or(false, |last_indexed| *sequence <= last_indexed)
+            })
+            .min();
+        if let Some(sequence) = lowest_reorged_sequence {
+            let previous_sequence = sequence.checked_sub(1);
+            let previous_block = match previous_sequence {
+                Some(previous_sequence) => {
+                    self.get_sequence_log_block_number(previous_sequence)
+                        .await?
+                }
+                None => None,
+            };
+            self.last_indexed_snapshot = LastIndexedSnapshot {
+                sequence: previous_sequence,
+                at_block: previous_block.unwrap_or(reorg.first_block),
+            };
+        }
+        self.last_indexed_snapshot.at_block =
+            u32::min(self.last_indexed_snapshot.at_block, reorg.first_block);
+
+        // Target the sequence after the last one that wasn't reorged, searching
+        // for it from the first reorged block.
+        self.current_indexing_snapshot = TargetSnapshot {
+            sequence: self.last_indexed_snapshot.next_target().sequence,
+            at_block: u32::min(self.current_indexing_snapshot.at_block, reorg.first_block),
+        };
+        warn!(
+            first_reorged_block = reorg.first_block,
+            ?lowest_reorged_sequence,
+            current_indexing_snapshot=?self.current_indexing_snapshot,
+            last_indexed_snapshot=?self.last_indexed_snapshot,
+            "Rolled back cursor to re-index reorged blocks",
+        );
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -423,6 +502,22 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
         self.current_indexing_snapshot.at_block
     }
 
+    fn reorgs_detected(&self) -> u64 {
+        self.reorg_detector.reorgs_detected()
+    }
+
+    fn chunk_size(&self) -> u32 {
+        self.chunk_size.get()
+    }
+
+    fn handle_query_error(&mut self, range: &RangeInclusive<u32>, err: &ChainCommunicationError) {
+        self.chunk_size.handle_query_error(range, err);
+    }
+
+    fn record_stored_logs(&mut self, logs: &[(Indexed<T>, LogMeta)]) {
+        self.reorg_detector.record_logs(logs);
+    }
+
     /// Updates the cursor with the logs that were found in the range.
     ///
     /// Inconsistencies in the logs are not considered errors, instead they're handled by rewinding the cursor
@@ -444,8 +539,12 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
         logs: Vec<(Indexed<T>, LogMeta)>,
         range: RangeInclusive<u32>,
     ) -> Result<()> {
+        self.chunk_size.handle_query_success(&range, logs.len());
+        self.reorg_detector.record_range(&logs);
+
         // Remove any sequence duplicates, filter out any logs preceding our current snapshot,
         // and sort in ascending order.
+
         let logs = indexed_to_sequence_indexed_array(logs)?
             .into_iter()
             .unique_by(|(log, _)| log.sequence)
@@ -604,7 +703,7 @@ pub(crate) mod test {
         });
 
         let mut cursor = ForwardSequenceAwareSyncCursor::new(
-            chunk_size,
+            AdaptiveChunkSize::fixed(chunk_size),
             latest_sequence_querier,
             db,
             // Start at sequence 3 and block 70 to illustrate fast forwarding
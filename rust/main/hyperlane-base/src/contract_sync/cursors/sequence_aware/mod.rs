@@ -3,11 +3,13 @@ use std::{fmt::Debug, sync::Arc, time::Duration};
 use async_trait::async_trait;
 use eyre::Result;
 use hyperlane_core::{
-    ChainCommunicationError, ContractSyncCursor, CursorAction,
-    HyperlaneSequenceAwareIndexerStoreReader, IndexMode, Indexed, LogMeta, SequenceAwareIndexer,
+    ChainCommunicationError, ContractSyncCursor, CursorAction, HyperlaneSequenceAwareIndexerStore,
+    IndexMode, Indexed, LogMeta, SequenceAwareIndexer,
 };
 use std::ops::RangeInclusive;
 
+use super::chunk_size::AdaptiveChunkSize;
+
 mod backward;
 mod forward;
 
@@ -62,6 +64,9 @@ pub enum SyncDirection {
 
 /// A cursor that prefers to sync forward, but will sync backward if there is nothing to
 /// sync forward.
+///
+/// Only the forward cursor detects reorgs, as the backward cursor indexes blocks below
+/// the finalized tip at the time the cursor was created.
 #[derive(Debug)]
 pub(crate) struct ForwardBackwardSequenceAwareSyncCursor<T> {
     forward: ForwardSequenceAwareSyncCursor<T>,
@@ -69,12 +74,12 @@ pub(crate) struct ForwardBackwardSequenceAwareSyncCursor<T> {
     last_direction: SyncDirection,
 }
 
-impl<T: Debug> ForwardBackwardSequenceAwareSyncCursor<T> {
+impl<T: Clone + Debug + Send + Sync + 'static> ForwardBackwardSequenceAwareSyncCursor<T> {
     /// Construct a new contract sync helper.
     pub async fn new(
         latest_sequence_querier: Arc<dyn SequenceAwareIndexer<T>>,
-        db: Arc<dyn HyperlaneSequenceAwareIndexerStoreReader<T>>,
-        chunk_size: u32,
+        db: Arc<dyn HyperlaneSequenceAwareIndexerStore<T>>,
+        chunk_size: AdaptiveChunkSize,
         mode: IndexMode,
     ) -> Result<Self> {
         let (sequence_count, tip) = latest_sequence_querier
@@ -84,15 +89,20 @@ impl<T: Debug> ForwardBackwardSequenceAwareSyncCursor<T> {
             "Failed to query sequence",
         ))?;
         let forward_cursor = ForwardSequenceAwareSyncCursor::new(
-            chunk_size,
+            chunk_size.clone(),
             latest_sequence_querier.clone(),
             db.clone(),
             sequence_count,
             tip,
             mode,
         );
-        let backward_cursor =
-            BackwardSequenceAwareSyncCursor::new(chunk_size, db, sequence_count, tip, mode);
+        let backward_cursor = BackwardSequenceAwareSyncCursor::new(
+            chunk_size,
+            Arc::new(db),
+            sequence_count,
+            tip,
+            mode,
+        );
         Ok(Self {
             forward: forward_cursor,
             backward: backward_cursor,
@@ -126,6 +136,26 @@ impl<T: Send + Sync + Clone + Debug + 'static> ContractSyncCursor<T>
         self.forward.latest_queried_block()
     }
 
+    fn reorgs_detected(&self) -> u64 {
+        self.forward.reorgs_detected()
+    }
+
+    fn chunk_size(&self) -> u32 {
+        self.forward.chunk_size()
+    }
+
+    fn handle_query_error(&mut self, range: &RangeInclusive<u32>, err: &ChainCommunicationError) {
+        match self.last_direction {
+            SyncDirection::Forward => self.forward.handle_query_error(range, err),
+            SyncDirection::Backward => self.backward.handle_query_error(range, err),
+        }
+    }
+
+    fn record_stored_logs(&mut self, logs: &[(Indexed<T>, LogMeta)]) {
+        // Stored logs are always newer than the ones the backward cursor indexes
+        self.forward.record_stored_logs(logs);
+    }
+
     async fn update(
         &mut self,
         logs: Vec<(Indexed<T>, LogMeta)>,
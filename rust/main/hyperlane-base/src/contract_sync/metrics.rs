@@ -18,6 +18,20 @@ pub struct ContractSyncMetrics {
     /// - `chain`: Chain the indexer is collecting data from.
     pub stored_events: IntCounterVec,
 
+    /// Reorgs of indexed blocks that were detected and rolled back.
+    ///
+    /// Labels:
+    /// - `data_type`: the data the indexer is recording. E.g. `messages` or `gas_payments`.
+    /// - `chain`: Chain the indexer is collecting data from.
+    pub reorgs_detected: IntCounterVec,
+
+    /// The number of blocks (or sequences) currently queried at once.
+    ///
+    /// Labels:
+    /// - `data_type`: the data the indexer is recording. E.g. `messages` or `gas_payments`.
+    /// - `chain`: Chain the indexer is collecting data from.
+    pub chunk_size: IntGaugeVec,
+
     /// See `last_known_message_nonce` in CoreMetrics.
     pub message_nonce: IntGaugeVec,
 }
@@ -41,11 +55,29 @@ impl ContractSyncMetrics {
             )
             .expect("failed to register stored_events metric");
 
+        let reorgs_detected = metrics
+            .new_int_counter(
+                "contract_sync_reorgs_detected",
+                "Number of reorgs of indexed blocks that were detected",
+                &["data_type", "chain"],
+            )
+            .expect("failed to register reorgs_detected metric");
+
+        let chunk_size = metrics
+            .new_int_gauge(
+                "contract_sync_chunk_size",
+                "Number of blocks or sequences currently queried at once",
+                &["data_type", "chain"],
+            )
+            .expect("failed to register chunk_size metric");
+
         let message_nonce = metrics.last_known_message_nonce();
 
         ContractSyncMetrics {
             indexed_height,
             stored_events,
+            reorgs_detected,
+            chunk_size,
             message_nonce,
         }
     }
@@ -1,5 +1,13 @@
 use std::{
-    collections::HashSet, fmt::Debug, hash::Hash, marker::PhantomData, sync::Arc, time::Duration,
+    collections::HashSet,
+    fmt::Debug,
+    hash::Hash,
+    marker::PhantomData,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
 use axum::async_trait;
@@ -15,7 +23,11 @@ use hyperlane_core::{
 use hyperlane_core::{Indexed, LogMeta, H512};
 pub use metrics::ContractSyncMetrics;
 use prometheus::core::{AtomicI64, AtomicU64, GenericCounter, GenericGauge};
-use tokio::sync::mpsc::{error::TryRecvError, Receiver as MpscReceiver};
+use tokio::sync::mpsc::{
+    error::TryRecvError, unbounded_channel, Receiver as MpscReceiver, UnboundedReceiver,
+    UnboundedSender,
+};
+use tokio::sync::Notify;
 use tokio::time::sleep;
 use tracing::{debug, info, instrument, trace, warn};
 
@@ -27,10 +39,50 @@ pub(crate) mod cursors;
 mod eta_calculator;
 mod metrics;
 
+use cursors::chunk_size::AdaptiveChunkSize;
 use cursors::ForwardBackwardSequenceAwareSyncCursor;
 
 const SLEEP_DURATION: Duration = Duration::from_secs(5);
 
+/// How long a synced cursor sleeps between range queries while a log
+/// subscription is connected, as the logs are pushed by the subscription.
+const SUBSCRIBED_SLEEP_DURATION: Duration = Duration::from_secs(60);
+
+/// Whether a log subscription is connected, which range polling backs off for.
+#[derive(Debug, Default)]
+struct SubscriptionStatus {
+    connected: AtomicBool,
+    /// Wakes up range polling when a subscription connects, to fill in the logs
+    /// emitted while there was none.
+    subscribed: Notify,
+}
+
+impl SubscriptionStatus {
+    fn set_connected(&self, connected: bool) {
+        self.connected.store(connected, Ordering::Relaxed);
+        if connected {
+            self.subscribed.notify_one();
+        }
+    }
+
+    /// How long a synced cursor should sleep for, given the duration it asked for.
+    fn sleep_duration(&self, duration: Duration) -> Duration {
+        if self.connected.load(Ordering::Relaxed) {
+            Duration::max(duration, SUBSCRIBED_SLEEP_DURATION)
+        } else {
+            duration
+        }
+    }
+
+    /// Sleeps for the duration, or until a subscription connects.
+    async fn sleep(&self, duration: Duration) {
+        tokio::select! {
+            _ = sleep(duration) => {}
+            _ = self.subscribed.notified() => {}
+        }
+    }
+}
+
 #[derive(Debug, derive_new::new)]
 #[allow(dead_code)]
 /// Utility struct for pretty-printing indexed items.
@@ -93,15 +145,106 @@ where
             .metrics
             .stored_events
             .with_label_values(&[label, chain_name]);
+        let reorgs_detected_metric = self
+            .metrics
+            .reorgs_detected
+            .with_label_values(&[label, chain_name]);
+        let chunk_size_metric = self
+            .metrics
+            .chunk_size
+            .with_label_values(&[label, chain_name]);
 
-        loop {
-            if let Some(rx) = opts.tx_id_receiver.as_mut() {
-                self.fetch_logs_from_receiver(rx, &stored_logs_metric).await;
+        // Logs pushed by a subscription are stored as they're emitted, while
+        // the cursor keeps querying ranges, less often while it's connected, to
+        // fill in any gaps in between. The pushed logs are recorded by the cursor
+        // before it checks for reorgs, so that they're rolled back along with
+        // the logs it indexed.
+        let (pushed_logs_sender, mut pushed_logs_receiver) = unbounded_channel();
+        let subscription_status = SubscriptionStatus::default();
+        // Without a cursor, pushed logs couldn't be rolled back if they're reorged
+        let has_cursor = opts.cursor.is_some();
+        let poll = async {
+            loop {
+                if let Some(rx) = opts.tx_id_receiver.as_mut() {
+                    self.fetch_logs_from_receiver(rx, &stored_logs_metric).await;
+                }
+                if let Some(cursor) = opts.cursor.as_mut() {
+                    Self::record_pushed_logs(cursor, &mut pushed_logs_receiver);
+                    self.fetch_logs_with_cursor(
+                        cursor,
+                        &subscription_status,
+                        &stored_logs_metric,
+                        &indexed_height_metric,
+                        &reorgs_detected_metric,
+                        &chunk_size_metric,
+                    )
+                    .await;
+                }
             }
-            if let Some(cursor) = opts.cursor.as_mut() {
-                self.fetch_logs_with_cursor(cursor, &stored_logs_metric, &indexed_height_metric)
+        };
+        let subscription = async {
+            if has_cursor {
+                self.sync_from_subscription(
+                    pushed_logs_sender,
+                    &subscription_status,
+                    &stored_logs_metric,
+                )
+                .await;
+            }
+        };
+        tokio::join!(subscription, poll);
+    }
+
+    /// Stores the logs pushed by the indexer's log subscription, resubscribing
+    /// whenever it ends, and sends them to the cursor. Returns right away if the
+    /// indexer doesn't support subscriptions or they aren't enabled.
+    #[instrument(fields(domain=self.domain().name()), skip(self, pushed_logs_sender, status, stored_logs_metric))]
+    async fn sync_from_subscription(
+        &self,
+        pushed_logs_sender: UnboundedSender<Vec<(Indexed<T>, LogMeta)>>,
+        status: &SubscriptionStatus,
+        stored_logs_metric: &GenericCounter<AtomicU64>,
+    ) {
+        loop {
+            let mut subscription = match self.indexer.subscribe_logs().await {
+                Ok(Some(subscription)) => subscription,
+                Ok(None) => return,
+                Err(err) => {
+                    warn!(?err, "Error subscribing to logs");
+                    sleep(SLEEP_DURATION).await;
+                    continue;
+                }
+            };
+            info!("Subscribed to logs");
+            status.set_connected(true);
+            while let Some(log) = subscription.next_log().await {
+                let logs = self
+                    .dedupe_and_store_logs(vec![log], stored_logs_metric)
                     .await;
+                debug!(
+                    sequences = ?logs.iter().map(|(log, meta)| IndexedTxIdAndSequence::new(meta.transaction_id, log.sequence)).collect::<Vec<_>>(),
+                    "Received log from subscription"
+                );
+                self.broadcast_tx_ids(&logs).await;
+                if pushed_logs_sender.send(logs).is_err() {
+                    return;
+                }
             }
+            // Range polling fills in the logs emitted until the next subscription
+            status.set_connected(false);
+            warn!("Log subscription ended, resubscribing");
+            sleep(SLEEP_DURATION).await;
+        }
+    }
+
+    /// Records the logs stored from the subscription since the last call with
+    /// the cursor, so that they're rolled back if their blocks are reorged.
+    fn record_pushed_logs(
+        cursor: &mut Box<dyn ContractSyncCursor<T>>,
+        receiver: &mut UnboundedReceiver<Vec<(Indexed<T>, LogMeta)>>,
+    ) {
+        while let Ok(logs) = receiver.try_recv() {
+            cursor.record_stored_logs(&logs);
         }
     }
 
@@ -143,15 +286,26 @@ where
         }
     }
 
-    #[instrument(fields(domain=self.domain().name()), skip(self, stored_logs_metric, indexed_height_metric))]
+    #[instrument(fields(domain=self.domain().name()), skip(self, subscription_status, stored_logs_metric, indexed_height_metric, reorgs_detected_metric, chunk_size_metric))]
     async fn fetch_logs_with_cursor(
         &self,
         cursor: &mut Box<dyn ContractSyncCursor<T>>,
+        subscription_status: &SubscriptionStatus,
         stored_logs_metric: &GenericCounter<AtomicU64>,
         indexed_height_metric: &GenericGauge<AtomicI64>,
+        reorgs_detected_metric: &GenericCounter<AtomicU64>,
+        chunk_size_metric: &GenericGauge<AtomicI64>,
     ) {
         indexed_height_metric.set(cursor.latest_queried_block() as i64);
-        let (action, eta) = match cursor.next_action().await {
+        chunk_size_metric.set(cursor.chunk_size() as i64);
+        let next_action = cursor.next_action().await;
+        // The cursor checks for reorgs when getting its next action
+        reorgs_detected_metric.inc_by(
+            cursor
+                .reorgs_detected()
+                .saturating_sub(reorgs_detected_metric.get()),
+        );
+        let (action, eta) = match next_action {
             Ok((action, eta)) => (action, eta),
             Err(err) => {
                 warn!(?err, "Error getting next action");
@@ -170,6 +324,7 @@ where
                     Ok(logs) => logs,
                     Err(err) => {
                         warn!(?err, ?range, "Error fetching logs in range");
+                        cursor.handle_query_error(&range, &err);
                         break SLEEP_DURATION;
                     }
                 };
@@ -185,13 +340,7 @@ where
                     "Found log(s) in index range"
                 );
 
-                if let Some(tx) = self.broadcast_sender.as_ref() {
-                    for (_, meta) in &logs {
-                        if let Err(err) = tx.send(meta.transaction_id).await {
-                            trace!(?err, "Error sending txid to receiver");
-                        }
-                    }
-                }
+                self.broadcast_tx_ids(&logs).await;
 
                 // Update cursor
                 if let Err(err) = cursor.update(logs, range).await {
@@ -200,9 +349,19 @@ where
                 };
                 break Default::default();
             },
-            CursorAction::Sleep(duration) => duration,
+            CursorAction::Sleep(duration) => subscription_status.sleep_duration(duration),
         };
-        sleep(sleep_duration).await
+        subscription_status.sleep(sleep_duration).await
+    }
+
+    async fn broadcast_tx_ids(&self, logs: &[(Indexed<T>, LogMeta)]) {
+        if let Some(tx) = self.broadcast_sender.as_ref() {
+            for (_, meta) in logs {
+                if let Err(err) = tx.send(meta.transaction_id).await {
+                    trace!(?err, "Error sending txid to receiver");
+                }
+            }
+        }
     }
 
     async fn dedupe_and_store_logs(
@@ -294,14 +453,13 @@ where
         let watermark = self.db.retrieve_high_watermark().await.unwrap();
         let index_settings = IndexSettings {
             from: watermark.unwrap_or(index_settings.from),
-            chunk_size: index_settings.chunk_size,
-            mode: index_settings.mode,
+            ..index_settings
         };
         Ok(Box::new(
             RateLimitedContractSyncCursor::new(
                 Arc::new(self.indexer.clone()),
                 self.db.clone(),
-                index_settings.chunk_size,
+                AdaptiveChunkSize::from(&index_settings),
                 index_settings.from,
             )
             .await?,
@@ -342,7 +500,7 @@ where
             ForwardBackwardSequenceAwareSyncCursor::new(
                 self.indexer.clone(),
                 Arc::new(self.db.clone()),
-                index_settings.chunk_size,
+                AdaptiveChunkSize::from(&index_settings),
                 index_settings.mode,
             )
             .await?,
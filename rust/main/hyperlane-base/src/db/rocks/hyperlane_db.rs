@@ -1,3 +1,5 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use async_trait::async_trait;
 use eyre::{bail, Result};
 use paste::paste;
@@ -7,7 +9,8 @@ use hyperlane_core::{
     GasPaymentKey, HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage,
     HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, Indexed,
     InterchainGasExpenditure, InterchainGasPayment, InterchainGasPaymentMeta, LogMeta,
-    MerkleTreeInsertion, PendingOperationStatus, H256,
+    MerkleTreeInsertion, ModuleType, PendingOperationStatus, PendingOperationStatusHistory,
+    PendingOperationStatusTransition, H256, H512,
 };
 
 use super::{
@@ -36,6 +39,17 @@ const MERKLE_LEAF_INDEX_BY_MESSAGE_ID: &str = "merkle_leaf_index_by_message_id_"
 const MERKLE_TREE_INSERTION_BLOCK_NUMBER_BY_LEAF_INDEX: &str =
     "merkle_tree_insertion_block_number_by_leaf_index_";
 const LATEST_INDEXED_GAS_PAYMENT_BLOCK: &str = "latest_indexed_gas_payment_block";
+const LATEST_INDEXED_GAS_PAYMENT_BLOCK_HASH: &str = "latest_indexed_gas_payment_block_hash";
+const MESSAGE_INDEXED_AT_BY_MESSAGE_ID: &str = "message_indexed_at_by_message_id_";
+const STATUS_HISTORY_BY_MESSAGE_ID: &str = "status_history_by_message_id_";
+const ISM_TYPE_BY_MESSAGE_ID: &str = "ism_type_by_message_id_";
+const METADATA_SIZE_BY_MESSAGE_ID: &str = "metadata_size_by_message_id_";
+const DESTINATION_TX_HASH_BY_MESSAGE_ID: &str = "destination_tx_hash_by_message_id_";
+const DROPPED_BY_ADMIN_BY_MESSAGE_ID: &str = "dropped_by_admin_by_message_id_";
+
+/// The number of most recent status transitions kept per operation. An operation
+/// that keeps being retried would otherwise grow its history without bound.
+pub const MAX_STATUS_HISTORY_LEN: usize = 100;
 
 /// Rocks DB result type
 pub type DbResult<T> = std::result::Result<T, DbError>;
@@ -81,6 +95,7 @@ impl HyperlaneRocksDB {
     /// - `nonce` --> `id`
     /// - `id` --> `message`
     /// - `nonce` --> `dispatched block number`
+    /// - `id` --> `time the message was indexed`
     pub fn store_message(
         &self,
         message: &HyperlaneMessage,
@@ -102,6 +117,25 @@ impl HyperlaneRocksDB {
         self.try_update_max_seen_message_nonce(message.nonce)?;
         // - `nonce` --> `dispatched block number`
         self.store_dispatched_block_number_by_nonce(&message.nonce, &dispatched_block_number)?;
+        // - `id` --> `time the message was indexed`
+        self.store_message_indexed_at_by_message_id(&id, &unix_timestamp_now())?;
+        Ok(true)
+    }
+
+    /// Delete a message stored by `store_message`, e.g. because it was
+    /// dispatched in a reorged block. Returns whether the message was deleted.
+    pub fn delete_message(&self, message: &HyperlaneMessage) -> DbResult<bool> {
+        let id = message.id();
+        if self.retrieve_message_id_by_nonce(&message.nonce)? != Some(id) {
+            trace!(msg=?message, "Message to delete isn't stored in db");
+            return Ok(false);
+        }
+
+        debug!(msg=?message, "Deleting message from db");
+        self.delete_keyed(MESSAGE_ID, &message.nonce)?;
+        self.delete_keyed(MESSAGE, &id)?;
+        self.delete_keyed(MESSAGE_DISPATCHED_BLOCK_NUMBER, &message.nonce)?;
+        self.delete_keyed(MESSAGE_INDEXED_AT_BY_MESSAGE_ID, &id)?;
         Ok(true)
     }
 
@@ -114,6 +148,28 @@ impl HyperlaneRocksDB {
         }
     }
 
+    /// Store the current status of an operation, and append it to the
+    /// operation's status history, dropping the oldest transitions beyond
+    /// `MAX_STATUS_HISTORY_LEN`
+    pub fn store_status_transition(
+        &self,
+        message_id: &H256,
+        status: &PendingOperationStatus,
+    ) -> DbResult<()> {
+        self.store_status_by_message_id(message_id, status)?;
+
+        let mut history = self
+            .retrieve_status_history_by_message_id(message_id)?
+            .unwrap_or_default();
+        history.0.push(PendingOperationStatusTransition {
+            status: status.clone(),
+            timestamp: unix_timestamp_now(),
+        });
+        let excess = history.0.len().saturating_sub(MAX_STATUS_HISTORY_LEN);
+        history.0.drain(..excess);
+        self.store_status_history_by_message_id(message_id, &history)
+    }
+
     /// Update the nonce of the highest processed message we're aware of
     pub fn try_update_max_seen_message_nonce(&self, nonce: u32) -> DbResult<()> {
         let current_max = self
@@ -194,6 +250,46 @@ impl HyperlaneRocksDB {
         Ok(true)
     }
 
+    /// Revert a gas payment processed by `process_indexed_gas_payment`, e.g.
+    /// because it was made in a reorged block. Returns whether the gas payment
+    /// was reverted.
+    pub fn revert_indexed_gas_payment(
+        &self,
+        indexed_payment: &Indexed<InterchainGasPayment>,
+        log_meta: &LogMeta,
+    ) -> DbResult<bool> {
+        let payment = *indexed_payment.inner();
+        let payment_meta: InterchainGasPaymentMeta = log_meta.into();
+        if !self
+            .retrieve_processed_by_gas_payment_meta(&payment_meta)?
+            .unwrap_or(false)
+        {
+            trace!(
+                ?payment,
+                ?log_meta,
+                "Attempted to revert a gas payment that wasn't processed"
+            );
+            return Ok(false);
+        }
+        self.delete_keyed(GAS_PAYMENT_META_PROCESSED, &payment_meta)?;
+
+        let gas_payment_key = payment.into();
+        if let Some(total) = self.retrieve_gas_payment_by_gas_payment_key(gas_payment_key)? {
+            let total = total - payment;
+            debug!(?payment, new_total_gas_payment=?total, "Reverting gas payment");
+            self.store_interchain_gas_payment_data_by_gas_payment_key(
+                &gas_payment_key,
+                &total.into(),
+            )?;
+        }
+
+        if let Some(gas_payment_sequence) = indexed_payment.sequence {
+            self.delete_keyed(GAS_PAYMENT_BY_SEQUENCE, &gas_payment_sequence)?;
+            self.delete_keyed(GAS_PAYMENT_BLOCK_BY_SEQUENCE, &gas_payment_sequence)?;
+        }
+        Ok(true)
+    }
+
     /// Store the merkle tree insertion event, and also store a mapping from message_id to leaf_index
     pub fn process_tree_insertion(
         &self,
@@ -220,6 +316,27 @@ impl HyperlaneRocksDB {
         Ok(true)
     }
 
+    /// Delete a merkle tree insertion stored by `process_tree_insertion`, e.g.
+    /// because it happened in a reorged block. Returns whether the insertion
+    /// was deleted.
+    pub fn delete_tree_insertion(&self, insertion: &MerkleTreeInsertion) -> DbResult<bool> {
+        if self.retrieve_merkle_tree_insertion_by_leaf_index(&insertion.index())?
+            != Some(*insertion)
+        {
+            trace!(insertion=?insertion, "Tree insertion to delete isn't stored in db");
+            return Ok(false);
+        }
+
+        debug!(insertion=?insertion, "Deleting tree insertion from db");
+        self.delete_keyed(MERKLE_TREE_INSERTION, &insertion.index())?;
+        self.delete_keyed(MERKLE_LEAF_INDEX_BY_MESSAGE_ID, &insertion.message_id())?;
+        self.delete_keyed(
+            MERKLE_TREE_INSERTION_BLOCK_NUMBER_BY_LEAF_INDEX,
+            &insertion.index(),
+        )?;
+        Ok(true)
+    }
+
     /// Processes the gas expenditure and store the total expenditure for the
     /// message.
     pub fn process_gas_expenditure(&self, expenditure: InterchainGasExpenditure) -> DbResult<()> {
@@ -303,6 +420,21 @@ impl HyperlaneLogStore<HyperlaneMessage> for HyperlaneRocksDB {
         }
         Ok(stored)
     }
+
+    /// Delete a list of dispatched messages, e.g. because they were reorged.
+    #[instrument(skip_all)]
+    async fn delete_logs(&self, messages: &[(Indexed<HyperlaneMessage>, LogMeta)]) -> Result<u32> {
+        let mut deleted = 0;
+        for (message, _) in messages {
+            if self.delete_message(message.inner())? {
+                deleted += 1;
+            }
+        }
+        if deleted > 0 {
+            debug!(messages = deleted, "Deleted messages from database");
+        }
+        Ok(deleted)
+    }
 }
 
 async fn store_and_count_new<T: Copy>(
@@ -339,6 +471,24 @@ impl HyperlaneLogStore<InterchainGasPayment> for HyperlaneRocksDB {
         )
         .await
     }
+
+    /// Revert a list of interchain gas payments, e.g. because they were reorged.
+    #[instrument(skip_all)]
+    async fn delete_logs(
+        &self,
+        payments: &[(Indexed<InterchainGasPayment>, LogMeta)],
+    ) -> Result<u32> {
+        let mut reverted = 0;
+        for (payment, meta) in payments {
+            if self.revert_indexed_gas_payment(payment, meta)? {
+                reverted += 1;
+            }
+        }
+        if reverted > 0 {
+            debug!(gas_payments = reverted, "Reverted gas payments in database");
+        }
+        Ok(reverted)
+    }
 }
 
 #[async_trait]
@@ -354,6 +504,18 @@ impl HyperlaneLogStore<MerkleTreeInsertion> for HyperlaneRocksDB {
         }
         Ok(insertions)
     }
+
+    /// Delete a list of tree insertion events, e.g. because they were reorged.
+    #[instrument(skip_all)]
+    async fn delete_logs(&self, leaves: &[(Indexed<MerkleTreeInsertion>, LogMeta)]) -> Result<u32> {
+        let mut deleted = 0;
+        for (insertion, _) in leaves {
+            if self.delete_tree_insertion(insertion.inner())? {
+                deleted += 1;
+            }
+        }
+        Ok(deleted)
+    }
 }
 
 #[async_trait]
@@ -413,6 +575,22 @@ impl HyperlaneWatermarkedLogStore<InterchainGasPayment> for HyperlaneRocksDB {
         let result = self.store_encodable("", LATEST_INDEXED_GAS_PAYMENT_BLOCK, &block_number)?;
         Ok(result)
     }
+
+    /// Gets the number and hash of the latest indexed block
+    async fn retrieve_high_watermark_block_hash(&self) -> Result<Option<(u32, H256)>> {
+        let block = self.retrieve_decodable("", LATEST_INDEXED_GAS_PAYMENT_BLOCK_HASH)?;
+        Ok(block)
+    }
+
+    /// Stores the number and hash of the latest indexed block
+    async fn store_high_watermark_block_hash(&self, block_number: u32, hash: H256) -> Result<()> {
+        let result = self.store_encodable(
+            "",
+            LATEST_INDEXED_GAS_PAYMENT_BLOCK_HASH,
+            &(block_number, hash),
+        )?;
+        Ok(result)
+    }
 }
 
 // Keep this implementation for type compatibility with the `contract_syncs` sync builder
@@ -443,6 +621,13 @@ impl HyperlaneWatermarkedLogStore<MerkleTreeInsertion> for HyperlaneRocksDB {
     }
 }
 
+fn unix_timestamp_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
 /// Database interface required for processing messages
 pub trait ProcessMessage: Send + Sync {
     /// Retrieve the nonce of the highest processed message we're aware of
@@ -503,8 +688,14 @@ macro_rules! make_store_and_retrieve {
 }
 
 make_store_and_retrieve!(pub, message_id_by_nonce, MESSAGE_ID, u32, H256);
-make_store_and_retrieve!(pub(self), message_by_id, MESSAGE, H256, HyperlaneMessage);
-make_store_and_retrieve!(pub(self), dispatched_block_number_by_nonce, MESSAGE_DISPATCHED_BLOCK_NUMBER, u32, u64);
+make_store_and_retrieve!(pub, message_by_id, MESSAGE, H256, HyperlaneMessage);
+make_store_and_retrieve!(
+    pub,
+    dispatched_block_number_by_nonce,
+    MESSAGE_DISPATCHED_BLOCK_NUMBER,
+    u32,
+    u64
+);
 make_store_and_retrieve!(pub, processed_by_nonce, NONCE_PROCESSED, u32, bool);
 make_store_and_retrieve!(pub(self), processed_by_gas_payment_meta, GAS_PAYMENT_META_PROCESSED, InterchainGasPaymentMeta, bool);
 make_store_and_retrieve!(pub(self), interchain_gas_expenditure_data_by_message_id, GAS_EXPENDITURE_FOR_MESSAGE_ID, H256, InterchainGasExpenditureData);
@@ -549,3 +740,45 @@ make_store_and_retrieve!(
 // There's no unit struct Encode/Decode impl, so just use `bool`, have visibility be private (by omitting the first argument), and wrap
 // with a function that always uses the `Default::default()` key
 make_store_and_retrieve!(, highest_seen_message_nonce_number, HIGHEST_SEEN_MESSAGE_NONCE, bool, u32);
+make_store_and_retrieve!(
+    pub,
+    message_indexed_at_by_message_id,
+    MESSAGE_INDEXED_AT_BY_MESSAGE_ID,
+    H256,
+    u64
+);
+make_store_and_retrieve!(
+    pub,
+    status_history_by_message_id,
+    STATUS_HISTORY_BY_MESSAGE_ID,
+    H256,
+    PendingOperationStatusHistory
+);
+make_store_and_retrieve!(
+    pub,
+    ism_type_by_message_id,
+    ISM_TYPE_BY_MESSAGE_ID,
+    H256,
+    ModuleType
+);
+make_store_and_retrieve!(
+    pub,
+    metadata_size_by_message_id,
+    METADATA_SIZE_BY_MESSAGE_ID,
+    H256,
+    u64
+);
+make_store_and_retrieve!(
+    pub,
+    destination_tx_hash_by_message_id,
+    DESTINATION_TX_HASH_BY_MESSAGE_ID,
+    H256,
+    H512
+);
+make_store_and_retrieve!(
+    pub,
+    dropped_by_admin_by_message_id,
+    DROPPED_BY_ADMIN_BY_MESSAGE_ID,
+    H256,
+    bool
+);
@@ -32,10 +32,10 @@ where
 mod test {
     use hyperlane_core::{
         HyperlaneDomain, HyperlaneLogStore, HyperlaneMessage, Indexed, LogMeta,
-        RawHyperlaneMessage, H256, H512, U256,
+        PendingOperationStatus, RawHyperlaneMessage, ReprepareReason, H256, H512, U256,
     };
 
-    use crate::db::HyperlaneRocksDB;
+    use crate::db::{HyperlaneRocksDB, MAX_STATUS_HISTORY_LEN};
 
     use super::*;
 
@@ -77,4 +77,35 @@ mod test {
         })
         .await;
     }
+
+    #[tokio::test]
+    async fn db_caps_status_history() {
+        run_test_db(|db| async move {
+            let db = HyperlaneRocksDB::new(
+                &HyperlaneDomain::new_test_domain("db_caps_status_history"),
+                db,
+            );
+            let message_id = H256::from_low_u64_be(1);
+
+            db.store_status_transition(&message_id, &PendingOperationStatus::FirstPrepareAttempt)
+                .unwrap();
+            let retry = PendingOperationStatus::Retry(ReprepareReason::ErrorSubmitting);
+            for _ in 0..MAX_STATUS_HISTORY_LEN {
+                db.store_status_transition(&message_id, &retry).unwrap();
+            }
+
+            let history = db
+                .retrieve_status_history_by_message_id(&message_id)
+                .unwrap()
+                .unwrap();
+            // The oldest transition was dropped
+            assert_eq!(history.0.len(), MAX_STATUS_HISTORY_LEN);
+            assert!(history.0.iter().all(|t| t.status == retry));
+            assert_eq!(
+                db.retrieve_status_by_message_id(&message_id).unwrap(),
+                Some(retry)
+            );
+        })
+        .await;
+    }
 }
@@ -85,4 +85,10 @@ impl TypedDB {
     ) -> Result<Option<V>> {
         self.retrieve_decodable(prefix, key.to_vec())
     }
+
+    /// Delete the value of an encodable key
+    pub fn delete_keyed<K: Encode>(&self, prefix: impl AsRef<[u8]>, key: &K) -> Result<()> {
+        self.db
+            .delete(&self.prefixed_key(prefix.as_ref(), key.to_vec().as_ref()))
+    }
 }
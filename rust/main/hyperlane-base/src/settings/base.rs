@@ -4,8 +4,9 @@ use eyre::{eyre, Context, Result};
 use futures_util::future::try_join_all;
 use hyperlane_core::{
     HyperlaneChain, HyperlaneDomain, HyperlaneLogStore, HyperlaneProvider,
-    HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, InterchainGasPaymaster,
-    Mailbox, MerkleTreeHook, MultisigIsm, SequenceAwareIndexer, ValidatorAnnounce, H256,
+    HyperlaneSequenceAwareIndexerStoreReader, HyperlaneWatermarkedLogStore, Indexer,
+    InterchainGasPaymaster, Mailbox, MerkleTreeHook, MultisigIsm, SequenceAwareIndexer,
+    ValidatorAnnounce, H256,
 };
 
 use crate::{
@@ -167,6 +168,9 @@ impl Settings {
         let setup = self.chain_setup(domain)?;
         // Currently, all indexers are of the `SequenceIndexer` type
         let indexer = SequenceIndexer::<T>::try_from_with_metrics(setup, metrics).await?;
+        // Logs aren't fanned out to sequence-aware stores, since they skip the sequences
+        // they already store. A fanned out log of a reorged block would then block the
+        // canonical one once its cursor's reorg detector can no longer roll it back.
         Ok(Arc::new(ContractSync::new(
             domain.clone(),
             db.clone() as SequenceAwareLogStore<_>,
@@ -191,6 +195,7 @@ impl Settings {
         let setup = self.chain_setup(domain)?;
         // Currently, all indexers are of the `SequenceIndexer` type
         let indexer = SequenceIndexer::<T>::try_from_with_metrics(setup, metrics).await?;
+        indexer.fan_out_logs_to(db.clone());
         Ok(Arc::new(ContractSync::new(
             domain.clone(),
             db.clone() as WatermarkLogStore<_>,
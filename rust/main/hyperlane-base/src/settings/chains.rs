@@ -44,6 +44,9 @@ pub struct ChainConf {
     pub domain: HyperlaneDomain,
     /// Signer configuration for this chain
     pub signer: Option<SignerConf>,
+    /// Signers to submit transactions from in addition to `signer`, so that
+    /// more transactions can be in flight at once. Only supported on EVM chains.
+    pub additional_signers: Vec<SignerConf>,
     /// The reorg period of the chain, i.e. the number of blocks until finality
     pub reorg_period: u32,
     /// Addresses of contracts on the chain
@@ -155,8 +158,14 @@ pub struct CoreContractAddresses {
 pub struct IndexSettings {
     /// The height at which to start indexing contracts.
     pub from: u32,
-    /// The number of blocks to query at once when indexing contracts.
+    /// The number of blocks to initially query at once when indexing contracts.
     pub chunk_size: u32,
+    /// The min number of blocks to query at once. The number is shrunk down
+    /// to it when queries fail because their range is too large.
+    pub min_chunk_size: u32,
+    /// The max number of blocks to query at once. The number is grown up to
+    /// it after queries that find few logs.
+    pub max_chunk_size: u32,
     /// The indexing mode.
     pub mode: IndexMode,
 }
@@ -167,6 +176,19 @@ impl ChainConf {
         self.index.clone()
     }
 
+    /// How many transactions may be submitted to this chain at the same time.
+    /// On EVM chains this is the number of signers multiplied by the
+    /// transactions each may have in flight, and one everywhere else.
+    pub fn max_concurrent_submissions(&self) -> u32 {
+        match &self.connection {
+            ChainConnectionConf::Ethereum(conf) => {
+                let signers = 1 + self.additional_signers.len() as u32;
+                (signers * conf.max_in_flight_transactions).max(1)
+            }
+            _ => 1,
+        }
+    }
+
     /// Try to convert the chain settings into an HyperlaneProvider.
     pub async fn build_provider(
         &self,
@@ -179,7 +201,10 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::HyperlaneProviderBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let provider = h_fuel::FuelProvider::new(locator.domain.clone(), conf).await?;
+                Ok(Box::new(provider) as Box<dyn HyperlaneProvider>)
+            }
             ChainConnectionConf::Sealevel(conf) => Ok(Box::new(h_sealevel::SealevelProvider::new(
                 locator.domain.clone(),
                 conf,
@@ -203,9 +228,39 @@ impl ChainConf {
         let locator = self.locator(self.addresses.mailbox);
 
         match &self.connection {
+            ChainConnectionConf::Ethereum(conf) if !self.additional_signers.is_empty() => {
+                let builder = || h_eth::MailboxBuilder {
+                    multiple_signers: true,
+                };
+                let primary = self
+                    .build_ethereum(conf, &locator, metrics, builder())
+                    .await?;
+                let mut others = Vec::with_capacity(self.additional_signers.len());
+                for signer in &self.additional_signers {
+                    let signer = signer.build::<h_eth::Signers>().await.context(ctx)?;
+                    others.push(
+                        self.build_ethereum_with_signer(
+                            conf,
+                            &locator,
+                            metrics,
+                            builder(),
+                            Some(signer),
+                        )
+                        .await?,
+                    );
+                }
+                Ok(Box::new(h_eth::MultiSignerMailbox::new(primary, others)) as Box<dyn Mailbox>)
+            }
             ChainConnectionConf::Ethereum(conf) => {
-                self.build_ethereum(conf, &locator, metrics, h_eth::MailboxBuilder {})
-                    .await
+                self.build_ethereum(
+                    conf,
+                    &locator,
+                    metrics,
+                    h_eth::MailboxBuilder {
+                        multiple_signers: false,
+                    },
+                )
+                .await
             }
             ChainConnectionConf::Fuel(conf) => {
                 let wallet = self.fuel_signer().await.context(ctx)?;
@@ -243,8 +298,10 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::MerkleTreeHookBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_conf) => {
-                todo!("Fuel does not support merkle tree hooks yet")
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let hook = h_fuel::FuelMerkleTreeHook::new(conf, locator, wallet).await?;
+                Ok(Box::new(hook) as Box<dyn MerkleTreeHook>)
             }
             ChainConnectionConf::Sealevel(conf) => {
                 h_sealevel::SealevelMailbox::new(conf, locator, None)
@@ -282,7 +339,12 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let indexer =
+                    Box::new(h_fuel::FuelMailboxIndexer::new(conf, locator, wallet).await?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(h_sealevel::SealevelMailboxIndexer::new(conf, locator)?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<HyperlaneMessage>>)
@@ -321,7 +383,12 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let indexer =
+                    Box::new(h_fuel::FuelMailboxIndexer::new(conf, locator, wallet).await?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(h_sealevel::SealevelMailboxIndexer::new(conf, locator)?);
                 Ok(indexer as Box<dyn SequenceAwareIndexer<H256>>)
@@ -359,7 +426,12 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let paymaster =
+                    Box::new(h_fuel::FuelInterchainGasPaymaster::new(conf, locator, wallet).await?);
+                Ok(paymaster as Box<dyn InterchainGasPaymaster>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let paymaster = Box::new(
                     h_sealevel::SealevelInterchainGasPaymaster::new(conf, &locator).await?,
@@ -400,7 +472,13 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let indexer = Box::new(
+                    h_fuel::FuelInterchainGasPaymasterIndexer::new(conf, locator, wallet).await?,
+                );
+                Ok(indexer as Box<dyn SequenceAwareIndexer<InterchainGasPayment>>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let indexer = Box::new(
                     h_sealevel::SealevelInterchainGasPaymasterIndexer::new(conf, locator).await?,
@@ -439,7 +517,12 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let indexer =
+                    Box::new(h_fuel::FuelMerkleTreeHookIndexer::new(conf, locator, wallet).await?);
+                Ok(indexer as Box<dyn SequenceAwareIndexer<MerkleTreeInsertion>>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let mailbox_indexer =
                     Box::new(h_sealevel::SealevelMailboxIndexer::new(conf, locator)?);
@@ -475,7 +558,11 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::ValidatorAnnounceBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let va = Box::new(h_fuel::FuelValidatorAnnounce::new(conf, locator, wallet).await?);
+                Ok(va as Box<dyn ValidatorAnnounce>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let va = Box::new(h_sealevel::SealevelValidatorAnnounce::new(conf, locator));
                 Ok(va as Box<dyn ValidatorAnnounce>)
@@ -514,7 +601,13 @@ impl ChainConf {
                 )
                 .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let ism = Box::new(
+                    h_fuel::FuelInterchainSecurityModule::new(conf, locator, wallet).await?,
+                );
+                Ok(ism as Box<dyn InterchainSecurityModule>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let keypair = self.sealevel_signer().await.context(ctx)?;
                 let ism = Box::new(h_sealevel::SealevelInterchainSecurityModule::new(
@@ -547,8 +640,11 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::MultisigIsmBuilder {})
                     .await
             }
-
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let ism = Box::new(h_fuel::FuelMultisigIsm::new(conf, locator, wallet).await?);
+                Ok(ism as Box<dyn MultisigIsm>)
+            }
             ChainConnectionConf::Sealevel(conf) => {
                 let keypair = self.sealevel_signer().await.context(ctx)?;
                 let ism = Box::new(h_sealevel::SealevelMultisigIsm::new(conf, locator, keypair));
@@ -584,9 +680,15 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::RoutingIsmBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
-            ChainConnectionConf::Sealevel(_) => {
-                Err(eyre!("Sealevel does not support routing ISM yet")).context(ctx)
+            ChainConnectionConf::Fuel(conf) => {
+                let wallet = self.fuel_signer().await.context(ctx)?;
+                let ism = Box::new(h_fuel::FuelRoutingIsm::new(conf, locator, wallet).await?);
+                Ok(ism as Box<dyn RoutingIsm>)
+            }
+            ChainConnectionConf::Sealevel(conf) => {
+                let keypair = self.sealevel_signer().await.context(ctx)?;
+                let ism = Box::new(h_sealevel::SealevelRoutingIsm::new(conf, locator, keypair));
+                Ok(ism as Box<dyn RoutingIsm>)
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
@@ -618,9 +720,15 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::AggregationIsmBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
-            ChainConnectionConf::Sealevel(_) => {
-                Err(eyre!("Sealevel does not support aggregation ISM yet")).context(ctx)
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support aggregation ISM yet")).context(ctx)
+            }
+            ChainConnectionConf::Sealevel(conf) => {
+                let keypair = self.sealevel_signer().await.context(ctx)?;
+                let ism = Box::new(h_sealevel::SealevelAggregationIsm::new(
+                    conf, locator, keypair,
+                ));
+                Ok(ism as Box<dyn AggregationIsm>)
             }
             ChainConnectionConf::Cosmos(conf) => {
                 let signer = self.cosmos_signer().await.context(ctx)?;
@@ -653,7 +761,9 @@ impl ChainConf {
                 self.build_ethereum(conf, &locator, metrics, h_eth::CcipReadIsmBuilder {})
                     .await
             }
-            ChainConnectionConf::Fuel(_) => todo!(),
+            ChainConnectionConf::Fuel(_) => {
+                Err(eyre!("Fuel does not support CCIP read ISM yet")).context(ctx)
+            }
             ChainConnectionConf::Sealevel(_) => {
                 Err(eyre!("Sealevel does not support CCIP read ISM yet")).context(ctx)
             }
@@ -695,10 +805,8 @@ impl ChainConf {
         self.signer().await
     }
 
-    async fn fuel_signer(&self) -> Result<fuels::prelude::WalletUnlocked> {
-        self.signer().await.and_then(|opt| {
-            opt.ok_or_else(|| eyre!("Fuel requires a signer to construct contract instances"))
-        })
+    async fn fuel_signer(&self) -> Result<Option<fuels::prelude::WalletUnlocked>> {
+        self.signer().await
     }
 
     async fn sealevel_signer(&self) -> Result<Option<h_sealevel::Keypair>> {
@@ -787,6 +895,21 @@ impl ChainConf {
         if B::NEEDS_SIGNER {
             signer = self.ethereum_signer().await?;
         }
+        self.build_ethereum_with_signer(conf, locator, metrics, builder, signer)
+            .await
+    }
+
+    async fn build_ethereum_with_signer<B>(
+        &self,
+        conf: &h_eth::ConnectionConf,
+        locator: &ContractLocator<'_>,
+        metrics: &CoreMetrics,
+        builder: B,
+        signer: Option<h_eth::Signers>,
+    ) -> Result<B::Output>
+    where
+        B: BuildableWithProvider + Sync,
+    {
         let metrics_conf = self.metrics_conf();
         let rpc_metrics = Some(metrics.json_rpc_client_metrics());
         let middleware_metrics = Some((metrics.provider_metrics(), metrics_conf));
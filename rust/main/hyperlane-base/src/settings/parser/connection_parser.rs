@@ -1,10 +1,11 @@
 use eyre::eyre;
 use url::Url;
 
-use h_eth::TransactionOverrides;
+use h_eth::{FeeEscalation, TransactionOverrides};
 use hyperlane_core::config::{ConfigErrResultExt, OperationBatchConfig};
 use hyperlane_core::{config::ConfigParsingError, HyperlaneDomainProtocol};
 use hyperlane_cosmos::NativeToken;
+use hyperlane_sealevel::PriorityFeeConf;
 
 use crate::settings::envs::*;
 use crate::settings::ChainConnectionConf;
@@ -68,10 +69,99 @@ pub fn build_ethereum_connection_conf(
         })
         .unwrap_or_default();
 
+    let max_in_flight_transactions = chain
+        .chain(err)
+        .get_opt_key("maxInFlightTransactions")
+        .parse_u32()
+        .unwrap_or(1);
+
+    let fee_escalation = chain
+        .get_opt_key("feeEscalation")
+        .take_err(err, || &chain.cwp + "fee_escalation")
+        .flatten()
+        .and_then(|value_parser| {
+            let blocks_before_bump = value_parser
+                .chain(err)
+                .get_opt_key("blocksBeforeBump")
+                .parse_u32()
+                .unwrap_or(3);
+            if blocks_before_bump == 0 {
+                err.push(
+                    &value_parser.cwp + "blocks_before_bump",
+                    eyre!("Must wait at least one block before bumping fees"),
+                );
+            }
+            let bump_percent = value_parser
+                .chain(err)
+                .get_opt_key("bumpPercent")
+                .parse_u32()
+                .unwrap_or(15);
+            if bump_percent < 10 {
+                err.push(
+                    &value_parser.cwp + "bump_percent",
+                    eyre!("Fees must be bumped by at least 10% for replacements to be accepted"),
+                );
+            }
+            let max_fee_per_gas = value_parser
+                .chain(err)
+                .get_key("maxFeePerGas")
+                .parse_u256()
+                .end()?;
+            Some(FeeEscalation {
+                blocks_before_bump,
+                bump_percent,
+                max_fee_per_gas,
+            })
+        });
+
+    let log_fetcher = chain
+        .chain(err)
+        .get_opt_key("index")
+        .get_opt_key("unified")
+        .parse_bool()
+        .unwrap_or(false)
+        .then(h_eth::UnifiedLogFetcher::default);
+
+    let subscribe = chain
+        .chain(err)
+        .get_opt_key("index")
+        .get_opt_key("subscribe")
+        .parse_bool()
+        .unwrap_or(false);
+    let subscription_urls = if subscribe {
+        let urls = chain
+            .chain(err)
+            .get_key("rpcUrls")
+            .into_array_iter()
+            .map(|urls| {
+                urls.filter_map(|v| {
+                    v.chain(err)
+                        .get_opt_key("webSocket")
+                        .parse_from_str("Invalid url")
+                        .end()
+                })
+                .collect::<Vec<Url>>()
+            })
+            .unwrap_or_default();
+        if urls.is_empty() {
+            err.push(
+                &chain.cwp + "index",
+                eyre!("Subscribing to logs requires rpcUrls with a webSocket url"),
+            );
+        }
+        urls
+    } else {
+        vec![]
+    };
+
     Some(ChainConnectionConf::Ethereum(h_eth::ConnectionConf {
         rpc_connection: rpc_connection_conf?,
         transaction_overrides,
         operation_batch,
+        max_in_flight_transactions,
+        fee_escalation,
+        log_fetcher,
+        subscription_urls,
     }))
 }
 
@@ -156,13 +246,20 @@ pub fn build_cosmos_connection_conf(
         denom: native_token_denom.to_owned(),
     };
 
+    let subscribe_logs = chain
+        .chain(err)
+        .get_opt_key("index")
+        .get_opt_key("subscribe")
+        .parse_bool()
+        .unwrap_or(false);
+
     if !local_err.is_ok() {
         err.merge(local_err);
         None
     } else {
         Some(ChainConnectionConf::Cosmos(h_cosmos::ConnectionConf::new(
             grpcs,
-            rpcs.first().unwrap().to_string(),
+            rpcs.to_vec(),
             chain_id.unwrap().to_string(),
             prefix.unwrap().to_string(),
             canonical_asset.unwrap(),
@@ -170,10 +267,56 @@ pub fn build_cosmos_connection_conf(
             contract_address_bytes.unwrap().try_into().unwrap(),
             operation_batch,
             native_token,
+            subscribe_logs,
         )))
     }
 }
 
+pub fn build_sealevel_connection_conf(
+    rpcs: &[Url],
+    chain: &ValueParser,
+    err: &mut ConfigParsingError,
+    operation_batch: OperationBatchConfig,
+) -> Option<ChainConnectionConf> {
+    if rpcs.is_empty() {
+        return None;
+    }
+    let default_priority_fee = PriorityFeeConf::default();
+    let priority_fee = chain
+        .get_opt_key("priorityFee")
+        .take_err(err, || &chain.cwp + "priority_fee")
+        .flatten()
+        .map(|value_parser| {
+            let percentile = value_parser
+                .chain(err)
+                .get_opt_key("percentile")
+                .parse_u32()
+                .unwrap_or(default_priority_fee.percentile.into());
+            if percentile > 100 {
+                err.push(
+                    &value_parser.cwp + "percentile",
+                    eyre!("Percentile must be between 0 and 100"),
+                );
+            }
+            let max_compute_unit_price = value_parser
+                .chain(err)
+                .get_opt_key("maxComputeUnitPrice")
+                .parse_u64()
+                .unwrap_or(default_priority_fee.max_compute_unit_price);
+            PriorityFeeConf {
+                percentile: percentile.min(100) as u8,
+                max_compute_unit_price,
+            }
+        })
+        .unwrap_or(default_priority_fee);
+
+    Some(ChainConnectionConf::Sealevel(h_sealevel::ConnectionConf {
+        urls: rpcs.to_vec(),
+        operation_batch,
+        priority_fee,
+    }))
+}
+
 pub fn build_connection_conf(
     domain_protocol: HyperlaneDomainProtocol,
     rpcs: &[Url],
@@ -194,12 +337,9 @@ pub fn build_connection_conf(
             .iter()
             .next()
             .map(|url| ChainConnectionConf::Fuel(h_fuel::ConnectionConf { url: url.clone() })),
-        HyperlaneDomainProtocol::Sealevel => rpcs.iter().next().map(|url| {
-            ChainConnectionConf::Sealevel(h_sealevel::ConnectionConf {
-                url: url.clone(),
-                operation_batch,
-            })
-        }),
+        HyperlaneDomainProtocol::Sealevel => {
+            build_sealevel_connection_conf(rpcs, chain, err, operation_batch)
+        }
         HyperlaneDomainProtocol::Cosmos => {
             build_cosmos_connection_conf(rpcs, chain, err, operation_batch)
         }
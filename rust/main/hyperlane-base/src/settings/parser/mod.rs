@@ -131,6 +131,16 @@ fn parse_chain(
         .get_opt_key("signer")
         .and_then(parse_signer)
         .end();
+    let additional_signers = chain
+        .chain(&mut err)
+        .get_opt_key("additionalSigners")
+        .into_array_iter()
+        .map(|signers| {
+            signers
+                .filter_map(|signer| parse_signer(signer).take_config_err(&mut err))
+                .collect()
+        })
+        .unwrap_or_default();
 
     let reorg_period = chain
         .chain(&mut err)
@@ -153,6 +163,25 @@ fn parse_chain(
         .get_opt_key("chunk")
         .parse_u32()
         .unwrap_or(1999);
+    // Without bounds, the chunk size isn't adapted
+    let min_chunk_size = chain
+        .chain(&mut err)
+        .get_opt_key("index")
+        .get_opt_key("minChunk")
+        .parse_u32()
+        .unwrap_or(chunk_size);
+    let max_chunk_size = chain
+        .chain(&mut err)
+        .get_opt_key("index")
+        .get_opt_key("maxChunk")
+        .parse_u32()
+        .unwrap_or(chunk_size);
+    if !(min_chunk_size..=max_chunk_size).contains(&chunk_size) {
+        err.push(
+            &chain.cwp + "index",
+            eyre!("The chunk size must be between the min and max chunk sizes"),
+        );
+    }
     let mode = chain
         .chain(&mut err)
         .get_opt_key("index")
@@ -219,6 +248,7 @@ fn parse_chain(
     err.into_result(ChainConf {
         domain,
         signer,
+        additional_signers,
         reorg_period,
         addresses: CoreContractAddresses {
             mailbox,
@@ -231,6 +261,8 @@ fn parse_chain(
         index: IndexSettings {
             from,
             chunk_size,
+            min_chunk_size,
+            max_chunk_size,
             mode,
         },
     })
@@ -8,7 +8,7 @@ use async_trait::async_trait;
 use auto_impl::auto_impl;
 use eyre::Result;
 
-use crate::{Indexed, LogMeta};
+use crate::{ChainCommunicationError, Indexed, LogMeta};
 
 /// A cursor governs event indexing for a contract.
 #[async_trait]
@@ -23,6 +23,25 @@ pub trait ContractSyncCursor<T>: Debug + Send + Sync + 'static {
     /// TODO: consider a better way to assess health
     fn latest_queried_block(&self) -> u32;
 
+    /// The number of reorgs of indexed blocks that the cursor has detected and
+    /// rolled back since it was created.
+    fn reorgs_detected(&self) -> u64 {
+        0
+    }
+
+    /// The number of blocks, or sequences when indexing by sequence, that the
+    /// cursor currently queries at once.
+    fn chunk_size(&self) -> u32;
+
+    /// Handles a failure to query the range returned by the last `next_action`,
+    /// e.g. by querying smaller ranges if the range was too large.
+    fn handle_query_error(&mut self, range: &RangeInclusive<u32>, err: &ChainCommunicationError);
+
+    /// Records logs that were stored without being queried by the cursor, e.g. pushed
+    /// by a log subscription, so that they're rolled back if their blocks are reorged.
+    /// Does nothing by default.
+    fn record_stored_logs(&mut self, _logs: &[(Indexed<T>, LogMeta)]) {}
+
     /// Ingests the logs that were fetched from the chain and the range that was queried,
     /// and adjusts the cursor accordingly.
     /// This is called after the logs have been written to the store,
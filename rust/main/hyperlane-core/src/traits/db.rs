@@ -4,7 +4,7 @@ use async_trait::async_trait;
 use auto_impl::auto_impl;
 use eyre::Result;
 
-use crate::{Indexed, LogMeta};
+use crate::{Indexed, LogMeta, H256};
 
 /// Interface for a HyperlaneLogStore that ingests logs.
 #[async_trait]
@@ -13,6 +13,15 @@ pub trait HyperlaneLogStore<T>: Send + Sync + Debug {
     /// Store a list of logs and their associated metadata
     /// Returns the number of elements that were stored.
     async fn store_logs(&self, logs: &[(Indexed<T>, LogMeta)]) -> Result<u32>;
+
+    /// Delete a list of previously stored logs, e.g. because the blocks they
+    /// were emitted in were reorged.
+    /// Returns the number of elements that were deleted.
+    ///
+    /// Stores that can't delete logs keep them, which is the default.
+    async fn delete_logs(&self, _logs: &[(Indexed<T>, LogMeta)]) -> Result<u32> {
+        Ok(0)
+    }
 }
 
 /// A sequence is a monotonically increasing number that is incremented every time a message ID is indexed.
@@ -56,4 +65,20 @@ pub trait HyperlaneWatermarkedLogStore<T>: HyperlaneLogStore<T> {
 
     /// Stores the block number high watermark
     async fn store_high_watermark(&self, block_number: u32) -> Result<()>;
+
+    /// Gets the number and hash of the latest indexed block, stored along with
+    /// the high watermark.
+    ///
+    /// Stores that don't keep it return `None`, which is the default.
+    async fn retrieve_high_watermark_block_hash(&self) -> Result<Option<(u32, H256)>> {
+        Ok(None)
+    }
+
+    /// Stores the number and hash of the latest indexed block along with the
+    /// high watermark, so that a reorg of it is detected after a restart.
+    ///
+    /// Stores that can't keep it ignore it, which is the default.
+    async fn store_high_watermark_block_hash(&self, _block_number: u32, _hash: H256) -> Result<()> {
+        Ok(())
+    }
 }
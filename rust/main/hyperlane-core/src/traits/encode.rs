@@ -1,7 +1,10 @@
 use std::io::{Error, ErrorKind};
 
+use num_traits::FromPrimitive;
+
 use crate::{
-    GasPaymentKey, HyperlaneProtocolError, Indexed, InterchainGasPayment, H160, H256, H512, U256,
+    GasPaymentKey, HyperlaneProtocolError, Indexed, InterchainGasPayment, ModuleType, H160, H256,
+    H512, U256,
 };
 
 /// Simple trait for types with a canonical encoding
@@ -182,6 +185,33 @@ impl Decode for bool {
     }
 }
 
+impl Encode for ModuleType {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        writer.write_all(&[*self as u8])?;
+        Ok(1)
+    }
+}
+
+impl Decode for ModuleType {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        let mut buf = [0; 1];
+        reader.read_exact(&mut buf)?;
+        ModuleType::from_u8(buf[0]).ok_or_else(|| {
+            HyperlaneProtocolError::IoError(Error::new(
+                ErrorKind::InvalidData,
+                "decoded module type invalid",
+            ))
+        })
+    }
+}
+
 impl Encode for GasPaymentKey {
     fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
     where
@@ -277,10 +307,38 @@ impl<T: Decode> Decode for Indexed<T> {
     }
 }
 
+impl<A: Encode, B: Encode> Encode for (A, B) {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        Ok(self.0.write_to(writer)? + self.1.write_to(writer)?)
+    }
+}
+
+impl<A: Decode, B: Decode> Decode for (A, B) {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        Ok((A::read_from(reader)?, B::read_from(reader)?))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::{Decode, Encode, Indexed, H256};
 
+    #[test]
+    fn test_encoding_pair() {
+        let pair = (5u32, H256::random());
+        let encoded = pair.to_vec();
+        assert_eq!(encoded.len(), 36);
+        let decoded = <(u32, H256)>::read_from(&mut &encoded[..]).unwrap();
+        assert_eq!(pair, decoded);
+    }
+
     #[test]
     fn test_encoding_indexed() {
         let indexed: Indexed<H256> = Indexed::new(H256::random()).with_sequence(5);
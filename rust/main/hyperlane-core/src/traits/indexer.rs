@@ -6,12 +6,13 @@
 
 use std::fmt::Debug;
 use std::ops::RangeInclusive;
+use std::sync::Arc;
 
 use async_trait::async_trait;
 use auto_impl::auto_impl;
 use serde::Deserialize;
 
-use crate::{ChainResult, Indexed, LogMeta, H512};
+use crate::{ChainResult, HyperlaneLogStore, Indexed, LogMeta, H256, H512};
 
 /// Indexing mode.
 #[derive(Copy, Debug, Default, Deserialize, Clone)]
@@ -44,6 +45,42 @@ pub trait Indexer<T: Sized>: Send + Sync + Debug {
     ) -> ChainResult<Vec<(Indexed<T>, LogMeta)>> {
         Ok(vec![])
     }
+
+    /// Get the hash of the block with the given number, used to detect reorgs
+    /// of indexed blocks. Returns `None` if the block doesn't exist or if the
+    /// chain's indexed blocks can't be reorged, which is the default.
+    async fn get_block_hash(&self, _block_number: u32) -> ChainResult<Option<H256>> {
+        Ok(None)
+    }
+
+    /// Drop any state derived from the blocks from `first_reorged_block`
+    /// onwards, e.g. cached logs, once they're found to be reorged. Does
+    /// nothing by default.
+    async fn handle_reorg(&self, _first_reorged_block: u32) -> ChainResult<()> {
+        Ok(())
+    }
+
+    /// Store the logs this indexer would fetch in `store` whenever they're
+    /// fetched along with the logs of other indexers, e.g. because the logs
+    /// of several contracts are fetched at once. Not meant for stores that skip
+    /// the logs they already store, since a fanned out log of a reorged block
+    /// would block the canonical one. Does nothing by default.
+    fn fan_out_logs_to(&self, _store: Arc<dyn HyperlaneLogStore<T>>) {}
+
+    /// Subscribe to logs as they're emitted, to index them with lower latency
+    /// than by querying block ranges. Returns `None` if the indexer doesn't
+    /// support subscriptions or they aren't enabled, which is the default.
+    async fn subscribe_logs(&self) -> ChainResult<Option<Box<dyn LogSubscription<T>>>> {
+        Ok(None)
+    }
+}
+
+/// A subscription to logs as they're emitted.
+#[async_trait]
+pub trait LogSubscription<T>: Send {
+    /// Wait for the next emitted log. Returns `None` once the subscription
+    /// ended, e.g. because the connection dropped.
+    async fn next_log(&mut self) -> Option<(Indexed<T>, LogMeta)>;
 }
 
 /// Interface for indexing data in sequence.
@@ -54,6 +54,21 @@ pub trait PendingOperation: Send + Sync + Debug + TryBatchAs<HyperlaneMessage> {
     /// The domain this originates from.
     fn origin_domain_id(&self) -> u32;
 
+    /// The address of the sender on the origin domain.
+    fn sender_address(&self) -> &H256;
+
+    /// The address of the recipient on the destination domain.
+    fn recipient_address(&self) -> &H256;
+
+    /// The nonce of the message on the origin domain.
+    fn nonce(&self) -> u32;
+
+    /// The version of the message format.
+    fn version(&self) -> u8;
+
+    /// The body of the message.
+    fn body(&self) -> &[u8];
+
     /// Get the operation status from the local db, if there is one
     fn retrieve_status_from_db(&self) -> Option<PendingOperationStatus>;
 
@@ -116,6 +131,14 @@ pub trait PendingOperation: Send + Sync + Debug + TryBatchAs<HyperlaneMessage> {
     /// retried immediately.
     fn reset_attempts(&mut self);
 
+    /// Whether an operator has moved this operation ahead of all others in its
+    /// queue.
+    fn is_prioritized(&self) -> bool;
+
+    /// Move this operation ahead of all other operations in its queue,
+    /// regardless of their retry counts, and retry it immediately.
+    fn prioritize(&mut self);
+
     /// Set the number of times this operation has been retried.
     #[cfg(any(test, feature = "test-utils"))]
     fn set_retries(&mut self, retries: u32);
@@ -171,6 +194,46 @@ impl Decode for PendingOperationStatus {
     }
 }
 
+/// A status an operation moved to, and when it did so
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PendingOperationStatusTransition {
+    /// The status the operation moved to
+    pub status: PendingOperationStatus,
+    /// Unix timestamp, in seconds, at which the operation moved to `status`
+    pub timestamp: u64,
+}
+
+/// The statuses an operation has most recently moved through, oldest first.
+/// WARNING: This is serialized to JSON and stored in the database, see `PendingOperationStatus`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct PendingOperationStatusHistory(pub Vec<PendingOperationStatusTransition>);
+
+impl Encode for PendingOperationStatusHistory {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: Write,
+    {
+        let serialized = serde_json::to_vec(self)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "Failed to serialize"))?;
+        writer.write(&serialized)
+    }
+}
+
+impl Decode for PendingOperationStatusHistory {
+    fn read_from<R>(reader: &mut R) -> Result<Self, HyperlaneProtocolError>
+    where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        serde_json::from_reader(reader).map_err(|err| {
+            HyperlaneProtocolError::IoError(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("Failed to deserialize. Error: {}", err),
+            ))
+        })
+    }
+}
+
 #[derive(Display, Debug, Clone, Serialize, Deserialize, PartialEq)]
 /// Reasons for repreparing an operation
 /// WARNING: This enum is serialized to JSON and stored in the database, so to keep backwards compatibility, we shouldn't remove or rename any variants.
@@ -293,6 +356,12 @@ impl Eq for QueueOperation {}
 impl Ord for QueueOperation {
     fn cmp(&self, other: &Self) -> Ordering {
         use Ordering::*;
+        // Operations prioritized by an operator always come first
+        match (self.is_prioritized(), other.is_prioritized()) {
+            (true, false) => return Less,
+            (false, true) => return Greater,
+            _ => {}
+        }
         match (self.next_attempt_after(), other.next_attempt_after()) {
             (Some(a), Some(b)) => a.cmp(&b),
             // No time means it should come before
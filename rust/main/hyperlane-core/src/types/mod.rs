@@ -1,7 +1,7 @@
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::io::{Read, Write};
-use std::ops::Add;
+use std::ops::{Add, Sub};
 
 pub use self::primitive_types::*;
 #[cfg(feature = "ethers")]
@@ -184,6 +184,27 @@ impl Add for InterchainGasPayment {
     }
 }
 
+impl Sub for InterchainGasPayment {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        assert_eq!(
+            self.message_id, rhs.message_id,
+            "Cannot subtract interchain gas payments for different messages"
+        );
+        assert_eq!(
+            self.destination, rhs.destination,
+            "Cannot subtract interchain gas payments for different destinations"
+        );
+        Self {
+            message_id: self.message_id,
+            destination: self.destination,
+            payment: self.payment.saturating_sub(rhs.payment),
+            gas_amount: self.gas_amount.saturating_sub(rhs.gas_amount),
+        }
+    }
+}
+
 impl Add for InterchainGasExpenditure {
     type Output = Self;
 
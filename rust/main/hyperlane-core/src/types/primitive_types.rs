@@ -4,7 +4,7 @@
 #![allow(clippy::reversed_empty_ranges)]
 
 use std::{
-    ops::{Div, Mul},
+    ops::{Add, Div, Mul},
     str::FromStr,
 };
 
@@ -12,7 +12,7 @@ use bigdecimal::{BigDecimal, RoundingMode};
 use borsh::{BorshDeserialize, BorshSerialize};
 use fixed_hash::impl_fixed_hash_conversions;
 use num::CheckedDiv;
-use num_traits::Zero;
+use num_traits::{ToPrimitive, Zero};
 use uint::construct_uint;
 
 use crate::{types::serialize, ChainCommunicationError};
@@ -368,6 +368,11 @@ impl FixedPointNumber {
                 .with_scale_round(fractional_digit_count, bigdecimal::RoundingMode::Ceiling),
         )
     }
+
+    /// Lossy conversion to f64
+    pub fn to_f64_lossy(&self) -> f64 {
+        self.0.to_f64().unwrap_or(f64::NAN)
+    }
 }
 
 impl Default for FixedPointNumber {
@@ -413,6 +418,18 @@ where
     }
 }
 
+impl<T> Add<T> for FixedPointNumber
+where
+    T: Into<FixedPointNumber>,
+{
+    type Output = FixedPointNumber;
+
+    fn add(self, rhs: T) -> Self::Output {
+        let rhs = rhs.into();
+        Self(self.0 + rhs.0)
+    }
+}
+
 impl<T> Mul<T> for FixedPointNumber
 where
     T: Into<FixedPointNumber>,
@@ -25,6 +25,7 @@ use hyperlane_sealevel_connection_client::router::RemoteRouterConfig;
 use hyperlane_sealevel_igp::{
     accounts::{InterchainGasPaymasterType, OverheadIgpAccount},
     igp_gas_payment_pda_seeds, igp_program_data_pda_seeds,
+    instruction::post_dispatch_hook_account_metas,
 };
 use hyperlane_sealevel_mailbox::{
     accounts::{InboxAccount, OutboxAccount},
@@ -32,6 +33,7 @@ use hyperlane_sealevel_mailbox::{
     mailbox_dispatched_message_pda_seeds, mailbox_inbox_pda_seeds,
     mailbox_message_dispatch_authority_pda_seeds, mailbox_outbox_pda_seeds,
     mailbox_processed_message_pda_seeds,
+    processor::required_hook_account_count_key,
     protocol_fee::ProtocolFee,
     spl_noop,
 };
@@ -46,7 +48,10 @@ use hyperlane_sealevel_token_collateral::{
 use hyperlane_sealevel_token_lib::{
     accounts::HyperlaneTokenAccount,
     hyperlane_token_pda_seeds,
-    instruction::{Instruction as HtInstruction, TransferRemote as HtTransferRemote},
+    instruction::{
+        Instruction as HtInstruction, RateLimitConfig, RateLimitParams,
+        TransferRemote as HtTransferRemote,
+    },
 };
 use hyperlane_sealevel_token_native::hyperlane_token_native_collateral_pda_seeds;
 use hyperlane_sealevel_validator_announce::{
@@ -67,6 +72,7 @@ mod r#core;
 mod helloworld;
 mod igp;
 mod multisig_ism;
+mod multisig_ism_merkle_root;
 mod router;
 mod serde;
 mod warp_route;
@@ -74,6 +80,7 @@ mod warp_route;
 use crate::helloworld::process_helloworld_cmd;
 use crate::igp::process_igp_cmd;
 use crate::multisig_ism::process_multisig_ism_message_id_cmd;
+use crate::multisig_ism_merkle_root::process_multisig_ism_merkle_root_cmd;
 use crate::warp_route::process_warp_route_cmd;
 pub(crate) use crate::{context::*, core::*};
 
@@ -111,6 +118,7 @@ enum HyperlaneSealevelCmd {
     Igp(IgpCmd),
     ValidatorAnnounce(ValidatorAnnounceCmd),
     MultisigIsmMessageId(MultisigIsmMessageIdCmd),
+    MultisigIsmMerkleRoot(MultisigIsmMerkleRootCmd),
     WarpRoute(WarpRouteCmd),
     HelloWorld(HelloWorldCmd),
 }
@@ -206,6 +214,8 @@ enum MailboxSubCmd {
     Delivered(Delivered),
     TransferOwnership(TransferOwnership),
     SetDefaultIsm(SetDefaultIsm),
+    SetDefaultHook(SetHook),
+    SetRequiredHook(SetHook),
 }
 
 const MAILBOX_PROG_ID: Pubkey = pubkey!("692KZJaoe2KRcD6uhCQDLLXnLNA5ZLnfvdqjE4aX9iu1");
@@ -246,6 +256,15 @@ struct SetDefaultIsm {
     default_ism: Pubkey,
 }
 
+#[derive(Args)]
+struct SetHook {
+    #[arg(long, short)]
+    program_id: Pubkey,
+    /// The hook program. Unsets the hook if not provided.
+    #[arg(long)]
+    hook: Option<Pubkey>,
+}
+
 #[derive(Args)]
 struct Outbox {
     #[arg(long, short, default_value_t = ECLIPSE_DOMAIN)]
@@ -297,6 +316,7 @@ enum TokenSubCmd {
     EnrollRemoteRouter(TokenEnrollRemoteRouter),
     TransferOwnership(TransferOwnership),
     SetInterchainSecurityModule(SetInterchainSecurityModule),
+    SetRateLimit(TokenSetRateLimit),
     Igp(Igp),
 }
 
@@ -345,6 +365,20 @@ struct SetInterchainSecurityModule {
     ism: Option<Pubkey>,
 }
 
+#[derive(Args)]
+struct TokenSetRateLimit {
+    #[arg(long, short, default_value_t = HYPERLANE_TOKEN_PROG_ID)]
+    program_id: Pubkey,
+    domain: u32,
+    /// The max amount, in local decimals, transferable per window in each direction.
+    /// Removes the rate limit for the domain if not provided.
+    #[arg(long, requires = "window")]
+    capacity: Option<u64>,
+    /// The window length in seconds.
+    #[arg(long, requires = "capacity")]
+    window: Option<u64>,
+}
+
 #[derive(Args)]
 struct TransferOwnership {
     #[arg(long, short)]
@@ -657,6 +691,70 @@ struct MultisigIsmMessageIdSetValidatorsAndThreshold {
     threshold: u8,
 }
 
+#[derive(Args)]
+struct MultisigIsmMerkleRootCmd {
+    #[command(subcommand)]
+    cmd: MultisigIsmMerkleRootSubCmd,
+}
+
+#[derive(Subcommand)]
+enum MultisigIsmMerkleRootSubCmd {
+    Deploy(MultisigIsmMerkleRootDeploy),
+    Init(MultisigIsmMerkleRootInit),
+    SetValidatorsAndThreshold(MultisigIsmMerkleRootSetValidatorsAndThreshold),
+    Query(MultisigIsmMerkleRootQuery),
+    TransferOwnership(TransferOwnership),
+    Configure(MultisigIsmMerkleRootConfigure),
+}
+
+#[derive(Args)]
+struct MultisigIsmMerkleRootDeploy {
+    #[command(flatten)]
+    env_args: EnvironmentArgs,
+    #[arg(long)]
+    built_so_dir: PathBuf,
+    #[arg(long)]
+    chain: String,
+    #[arg(long)]
+    context: String,
+}
+
+#[derive(Args)]
+struct MultisigIsmMerkleRootConfigure {
+    #[arg(long)]
+    program_id: Pubkey,
+    #[arg(long)]
+    multisig_config_file: PathBuf,
+    #[arg(long)]
+    chain_config_file: PathBuf,
+}
+
+#[derive(Args)]
+struct MultisigIsmMerkleRootInit {
+    #[arg(long, short)]
+    program_id: Pubkey,
+}
+
+#[derive(Args)]
+struct MultisigIsmMerkleRootQuery {
+    #[arg(long, short)]
+    program_id: Pubkey,
+    #[arg(long, value_delimiter = ',')]
+    domains: Option<Vec<u32>>,
+}
+
+#[derive(Args)]
+struct MultisigIsmMerkleRootSetValidatorsAndThreshold {
+    #[arg(long, short)]
+    program_id: Pubkey,
+    #[arg(long)]
+    domain: u32,
+    #[arg(long, value_delimiter = ',')]
+    validators: Vec<H160>,
+    #[arg(long)]
+    threshold: u8,
+}
+
 #[derive(Args)]
 pub(crate) struct HelloWorldCmd {
     #[command(subcommand)]
@@ -761,6 +859,9 @@ fn main() {
         HyperlaneSealevelCmd::MultisigIsmMessageId(cmd) => {
             process_multisig_ism_message_id_cmd(ctx, cmd)
         }
+        HyperlaneSealevelCmd::MultisigIsmMerkleRoot(cmd) => {
+            process_multisig_ism_merkle_root_cmd(ctx, cmd)
+        }
         HyperlaneSealevelCmd::Core(cmd) => process_core_cmd(ctx, cmd),
         HyperlaneSealevelCmd::WarpRoute(cmd) => process_warp_route_cmd(ctx, cmd),
         HyperlaneSealevelCmd::HelloWorld(cmd) => process_helloworld_cmd(ctx, cmd),
@@ -834,6 +935,7 @@ fn process_mailbox_cmd(ctx: Context, cmd: MailboxCmd) {
                 destination_domain: outbox.destination,
                 recipient: H256(outbox.recipient.to_bytes()),
                 message_body: outbox.message.into(),
+                metadata: vec![],
             });
             let outbox_instruction = Instruction {
                 program_id: outbox.program_id,
@@ -892,6 +994,36 @@ fn process_mailbox_cmd(ctx: Context, cmd: MailboxCmd) {
                 )
                 .send_with_payer();
         }
+        MailboxSubCmd::SetDefaultHook(set_hook) => {
+            let instruction =
+                hyperlane_sealevel_mailbox::instruction::set_default_hook_instruction(
+                    set_hook.program_id,
+                    ctx.payer_pubkey,
+                    set_hook.hook,
+                )
+                .unwrap();
+            ctx.new_txn()
+                .add_with_description(
+                    instruction,
+                    format!("Setting default hook to {:?}", set_hook.hook),
+                )
+                .send_with_payer();
+        }
+        MailboxSubCmd::SetRequiredHook(set_hook) => {
+            let instruction =
+                hyperlane_sealevel_mailbox::instruction::set_required_hook_instruction(
+                    set_hook.program_id,
+                    ctx.payer_pubkey,
+                    set_hook.hook,
+                )
+                .unwrap();
+            ctx.new_txn()
+                .add_with_description(
+                    instruction,
+                    format!("Setting required hook to {:?}", set_hook.hook),
+                )
+                .send_with_payer();
+        }
     };
 }
 
@@ -1060,6 +1192,19 @@ fn process_token_cmd(ctx: Context, cmd: TokenCmd) {
 
             let (mailbox_outbox_account, _mailbox_outbox_bump) =
                 Pubkey::find_program_address(mailbox_outbox_pda_seeds!(), &token.mailbox);
+            let fetched_outbox_account = ctx
+                .client
+                .get_account_with_commitment(&mailbox_outbox_account, ctx.commitment)
+                .unwrap()
+                .value
+                .unwrap();
+            let outbox = OutboxAccount::fetch(&mut &fetched_outbox_account.data[..])
+                .unwrap()
+                .into_inner();
+            let mailbox_hooks: Vec<Pubkey> = [outbox.required_hook, outbox.default_hook]
+                .into_iter()
+                .flatten()
+                .collect();
 
             let ixn = HtInstruction::TransferRemote(HtTransferRemote {
                 destination_domain: xfer.destination_domain,
@@ -1073,14 +1218,16 @@ fn process_token_cmd(ctx: Context, cmd: TokenCmd) {
             //
             // 0.    [executable] The system program.
             // 1.    [executable] The spl_noop program.
-            // 2.    [] The token PDA account.
+            // 2.    [writeable] The token PDA account. Only required to be writeable
+            //       if the destination is rate limited, which isn't known here
+            //       without the plugin type.
             // 3.    [executable] The mailbox program.
             // 4.    [writeable] The mailbox outbox account.
             // 5.    [] Message dispatch authority.
             // 6.    [signer] The token sender and mailbox payer.
             // 7.    [signer] Unique message / gas payment account.
             // 8.    [writeable] Message storage PDA.
-            //       ---- If using an IGP ----
+            //       ---- If using an IGP and the Mailbox has no post-dispatch hooks ----
             // 9.    [executable] The IGP program.
             // 10.   [writeable] The IGP program data.
             // 11.   [writeable] Gas payment PDA.
@@ -1088,10 +1235,13 @@ fn process_token_cmd(ctx: Context, cmd: TokenCmd) {
             // 13.   [writeable] The IGP account.
             //       ---- End if ----
             // 14..N [??..??] Plugin-specific accounts.
+            //       ---- If the Mailbox has post-dispatch hooks ----
+            // N+1.. [??..??] The accounts of the Mailbox's post-dispatch hooks.
+            //       ---- End if ----
             let mut accounts = vec![
                 AccountMeta::new_readonly(system_program::id(), false),
                 AccountMeta::new_readonly(spl_noop::id(), false),
-                AccountMeta::new_readonly(token_account, false),
+                AccountMeta::new(token_account, false),
                 AccountMeta::new_readonly(token.mailbox, false),
                 AccountMeta::new(mailbox_outbox_account, false),
                 AccountMeta::new_readonly(dispatch_authority_account, false),
@@ -1100,7 +1250,11 @@ fn process_token_cmd(ctx: Context, cmd: TokenCmd) {
                 AccountMeta::new(dispatched_message_account, false),
             ];
 
-            if let Some((igp_program_id, igp_account_type)) = token.interchain_gas_paymaster {
+            if let Some((igp_program_id, igp_account_type)) = token
+                .interchain_gas_paymaster
+                .clone()
+                .filter(|_| mailbox_hooks.is_empty())
+            {
                 let (igp_program_data, _bump) =
                     Pubkey::find_program_address(igp_program_data_pda_seeds!(), &igp_program_id);
                 let (gas_payment_pda, _bump) = Pubkey::find_program_address(
@@ -1196,6 +1350,62 @@ fn process_token_cmd(ctx: Context, cmd: TokenCmd) {
                 }
             }
 
+            // The post-dispatch hook accounts expected by the Mailbox: the required
+            // hook's, then the default hook's. The token's configured IGP is paid by
+            // the sender when used as a hook, and any other hook is assumed to only
+            // require its program.
+            let mut hook_accounts: Vec<Vec<AccountMeta>> = vec![];
+            for hook in mailbox_hooks {
+                let hook_account_metas = match token.interchain_gas_paymaster.as_ref() {
+                    Some((igp_program_id, igp_account_type)) if *igp_program_id == hook => {
+                        let (igp_account, overhead_igp_account) = match igp_account_type {
+                            InterchainGasPaymasterType::Igp(igp_account_id) => {
+                                (*igp_account_id, None)
+                            }
+                            InterchainGasPaymasterType::OverheadIgp(overhead_igp_account_id) => {
+                                let overhead_igp_account = ctx
+                                    .client
+                                    .get_account_with_commitment(
+                                        overhead_igp_account_id,
+                                        ctx.commitment,
+                                    )
+                                    .unwrap()
+                                    .value
+                                    .unwrap();
+                                let overhead_igp_account =
+                                    OverheadIgpAccount::fetch(&mut &overhead_igp_account.data[..])
+                                        .unwrap()
+                                        .into_inner();
+                                (overhead_igp_account.inner, Some(*overhead_igp_account_id))
+                            }
+                        };
+                        let (igp_hook_accounts, _gas_payment_pda) =
+                            post_dispatch_hook_account_metas(
+                                hook,
+                                sender.pubkey(),
+                                igp_account,
+                                overhead_igp_account,
+                                unique_message_account_keypair.pubkey(),
+                            )
+                            .unwrap();
+                        igp_hook_accounts
+                    }
+                    _ => vec![AccountMeta::new_readonly(hook, false)],
+                };
+                hook_accounts.push(hook_account_metas);
+            }
+            // If both hooks are set, the Mailbox expects the number of accounts the
+            // required hook requires, excluding its program, to be specified first.
+            if let [required_hook_accounts, _] = &hook_accounts[..] {
+                let required_hook_account_count =
+                    (required_hook_accounts.len() - 1).try_into().unwrap();
+                accounts.push(AccountMeta::new_readonly(
+                    required_hook_account_count_key(required_hook_account_count),
+                    false,
+                ));
+            }
+            accounts.extend(hook_accounts.into_iter().flatten());
+
             eprintln!("accounts={:#?}", accounts); // FIXME remove
             let xfer_instruction = Instruction {
                 program_id: xfer.program_id,
@@ -1257,6 +1467,32 @@ fn process_token_cmd(ctx: Context, cmd: TokenCmd) {
                 .add_with_description(instruction, format!("Set ISM to {:?}", set_ism.ism))
                 .send_with_payer();
         }
+        TokenSubCmd::SetRateLimit(set_rate_limit) => {
+            let rate_limit = set_rate_limit
+                .capacity
+                .zip(set_rate_limit.window)
+                .map(|(capacity, window)| RateLimitParams { capacity, window });
+            let instruction =
+                hyperlane_sealevel_token_lib::instruction::set_rate_limit_configs_instruction(
+                    set_rate_limit.program_id,
+                    ctx.payer_pubkey,
+                    vec![RateLimitConfig {
+                        domain: set_rate_limit.domain,
+                        rate_limit: rate_limit.clone(),
+                    }],
+                )
+                .unwrap();
+
+            ctx.new_txn()
+                .add_with_description(
+                    instruction,
+                    format!(
+                        "Set rate limit for domain {} to {:?}",
+                        set_rate_limit.domain, rate_limit
+                    ),
+                )
+                .send_with_payer();
+        }
         TokenSubCmd::Igp(args) => match args.cmd {
             GetSetCmd::Set(set_args) => {
                 let igp_type: InterchainGasPaymasterType = match set_args.igp_type {
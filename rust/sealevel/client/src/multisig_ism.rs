@@ -24,9 +24,9 @@ use hyperlane_sealevel_multisig_ism_message_id::{
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct MultisigIsmConfig {
-    /// Note this type is ignored in this tooling. It'll always assume this
-    /// relates to a multisig-ism-message-id variant, which is the only type
-    /// implemented in Sealevel.
+    /// Note this type is ignored in this tooling. The multisig ISM variant
+    /// is instead determined by the command that's used, i.e. either
+    /// multisig-ism-message-id or multisig-ism-merkle-root.
     /// Commenting out for now until this is needed, and due to `infra`
     /// generating non-numeric types at the moment.
     // #[serde(rename = "type")]
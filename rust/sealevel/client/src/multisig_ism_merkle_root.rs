@@ -0,0 +1,321 @@
+use std::collections::{HashMap, HashSet};
+use std::{fs::File, path::Path};
+
+use solana_program::pubkey::Pubkey;
+use solana_sdk::signature::Signer;
+
+use crate::{
+    artifacts::{write_json, SingularProgramIdArtifact},
+    cmd_utils::{create_and_write_keypair, create_new_directory, deploy_program},
+    multisig_ism::MultisigIsmConfig,
+    router::ChainMetadata,
+    Context, MultisigIsmMerkleRootCmd, MultisigIsmMerkleRootSubCmd,
+};
+
+use hyperlane_core::{KnownHyperlaneDomain, H160};
+
+use hyperlane_sealevel_multisig_ism_merkle_root::{
+    access_control_pda_seeds,
+    accounts::{AccessControlAccount, DomainDataAccount},
+    domain_data_pda_seeds,
+    instruction::{set_validators_and_threshold_instruction, ValidatorsAndThreshold},
+};
+
+impl From<MultisigIsmConfig> for ValidatorsAndThreshold {
+    fn from(val: MultisigIsmConfig) -> Self {
+        ValidatorsAndThreshold {
+            validators: val.validators,
+            threshold: val.threshold,
+        }
+    }
+}
+
+pub(crate) fn process_multisig_ism_merkle_root_cmd(
+    mut ctx: Context,
+    cmd: MultisigIsmMerkleRootCmd,
+) {
+    match cmd.cmd {
+        MultisigIsmMerkleRootSubCmd::Deploy(deploy) => {
+            let environments_dir = create_new_directory(
+                &deploy.env_args.environments_dir,
+                &deploy.env_args.environment,
+            );
+            let ism_dir = create_new_directory(&environments_dir, "multisig-ism-merkle-root");
+            let chain_dir = create_new_directory(&ism_dir, &deploy.chain);
+            let context_dir = create_new_directory(&chain_dir, &deploy.context);
+            let key_dir = create_new_directory(&context_dir, "keys");
+            let local_domain = deploy
+                .chain
+                .parse::<KnownHyperlaneDomain>()
+                .map(|v| v as u32)
+                .expect("Invalid chain name");
+
+            let ism_program_id = deploy_multisig_ism_merkle_root(
+                &mut ctx,
+                &deploy.built_so_dir,
+                true,
+                &key_dir,
+                local_domain,
+            );
+
+            write_json::<SingularProgramIdArtifact>(
+                &context_dir.join("program-ids.json"),
+                ism_program_id.into(),
+            );
+        }
+        MultisigIsmMerkleRootSubCmd::Init(init) => {
+            let init_instruction =
+                hyperlane_sealevel_multisig_ism_merkle_root::instruction::init_instruction(
+                    init.program_id,
+                    ctx.payer_pubkey,
+                )
+                .unwrap();
+            ctx.new_txn().add(init_instruction).send_with_payer();
+        }
+        MultisigIsmMerkleRootSubCmd::SetValidatorsAndThreshold(set_config) => {
+            set_validators_and_threshold(
+                &mut ctx,
+                set_config.program_id,
+                set_config.domain,
+                ValidatorsAndThreshold {
+                    validators: set_config.validators,
+                    threshold: set_config.threshold,
+                },
+            );
+        }
+        MultisigIsmMerkleRootSubCmd::Query(query) => {
+            let (access_control_pda_key, _access_control_pda_bump) =
+                Pubkey::find_program_address(access_control_pda_seeds!(), &query.program_id);
+
+            let accounts = ctx
+                .client
+                .get_multiple_accounts_with_commitment(&[access_control_pda_key], ctx.commitment)
+                .unwrap()
+                .value;
+            let access_control =
+                AccessControlAccount::fetch(&mut &accounts[0].as_ref().unwrap().data[..])
+                    .unwrap()
+                    .into_inner();
+            println!("Access control: {:#?}", access_control);
+
+            if let Some(domains) = query.domains {
+                for domain in domains {
+                    println!("Querying domain data for origin domain: {}", domain);
+
+                    let (domain_data_pda_key, _domain_data_pda_bump) = Pubkey::find_program_address(
+                        domain_data_pda_seeds!(domain),
+                        &query.program_id,
+                    );
+
+                    let accounts = ctx
+                        .client
+                        .get_multiple_accounts_with_commitment(
+                            &[domain_data_pda_key],
+                            ctx.commitment,
+                        )
+                        .unwrap()
+                        .value;
+
+                    if let Some(account) = &accounts[0] {
+                        let domain_data = DomainDataAccount::fetch(&mut &account.data[..])
+                            .unwrap()
+                            .into_inner();
+                        println!("Domain data for {}:\n{:#?}", domain, domain_data);
+                    } else {
+                        println!("No domain data for domain {}", domain);
+                    }
+                }
+            }
+        }
+        MultisigIsmMerkleRootSubCmd::TransferOwnership(transfer_ownership) => {
+            let instruction =
+                hyperlane_sealevel_multisig_ism_merkle_root::instruction::transfer_ownership_instruction(
+                    transfer_ownership.program_id,
+                    ctx.payer_pubkey,
+                    Some(transfer_ownership.new_owner),
+                )
+                .unwrap();
+
+            ctx.new_txn()
+                .add_with_description(
+                    instruction,
+                    format!("Transfer ownership to {}", transfer_ownership.new_owner),
+                )
+                .send_with_payer();
+        }
+        MultisigIsmMerkleRootSubCmd::Configure(configure) => {
+            configure_multisig_ism_merkle_root(
+                &mut ctx,
+                configure.program_id,
+                &configure.multisig_config_file,
+                &configure.chain_config_file,
+            );
+        }
+    }
+}
+
+fn deploy_multisig_ism_merkle_root(
+    ctx: &mut Context,
+    built_so_dir: &Path,
+    use_existing_keys: bool,
+    key_dir: &Path,
+    local_domain: u32,
+) -> Pubkey {
+    let (keypair, keypair_path) = create_and_write_keypair(
+        key_dir,
+        "hyperlane_sealevel_multisig_ism_merkle_root-keypair.json",
+        use_existing_keys,
+    );
+    let program_id = keypair.pubkey();
+
+    deploy_program(
+        ctx.payer_keypair_path(),
+        keypair_path.to_str().unwrap(),
+        built_so_dir
+            .join("hyperlane_sealevel_multisig_ism_merkle_root.so")
+            .to_str()
+            .unwrap(),
+        &ctx.client.url(),
+        local_domain,
+    );
+
+    println!(
+        "Deployed Multisig ISM Merkle Root at program ID {}",
+        program_id
+    );
+
+    // Initialize
+    let instruction = hyperlane_sealevel_multisig_ism_merkle_root::instruction::init_instruction(
+        program_id,
+        ctx.payer_pubkey,
+    )
+    .unwrap();
+
+    ctx.new_txn()
+        .add_with_description(
+            instruction,
+            format!(
+                "Initializing Multisig ISM Merkle Root with payer & owner {}",
+                ctx.payer_pubkey
+            ),
+        )
+        .send_with_payer();
+    println!(
+        "initialized Multisig ISM Merkle Root at program ID {}",
+        program_id
+    );
+
+    program_id
+}
+
+/// Configures the multisig-ism-merkle-root program
+/// with the validators and thresholds for each of the domains
+/// specified in the multisig config file.
+fn configure_multisig_ism_merkle_root(
+    ctx: &mut Context,
+    program_id: Pubkey,
+    multisig_config_file_path: &Path,
+    chain_config_path: &Path,
+) {
+    let multisig_config_file =
+        File::open(multisig_config_file_path).expect("Failed to open config file");
+    let multisig_configs: HashMap<String, MultisigIsmConfig> =
+        serde_json::from_reader(multisig_config_file).expect("Failed to read config file");
+
+    let chain_config_file = File::open(chain_config_path).unwrap();
+    let chain_configs: HashMap<String, ChainMetadata> =
+        serde_json::from_reader(chain_config_file).unwrap();
+
+    for (chain_name, multisig_ism_config) in multisig_configs {
+        println!(
+            "Configuring Multisig ISM Merkle Root for chain {} and config {:?}",
+            chain_name, multisig_ism_config
+        );
+        let chain_config = chain_configs.get(&chain_name).unwrap();
+
+        let matches = multisig_ism_config_matches_chain(
+            ctx,
+            program_id,
+            chain_config.domain_id(),
+            &multisig_ism_config,
+        );
+
+        if matches {
+            println!(
+                "Multisig ISM Merkle Root already correctly configured for chain {}",
+                chain_name
+            );
+        } else {
+            println!(
+                "Multisig ISM Merkle Root incorrectly configured for chain {}, configuring now",
+                chain_name
+            );
+            set_validators_and_threshold(
+                ctx,
+                program_id,
+                chain_config.domain_id(),
+                multisig_ism_config.into(),
+            );
+        }
+    }
+}
+
+fn multisig_ism_config_matches_chain(
+    ctx: &mut Context,
+    program_id: Pubkey,
+    remote_domain: u32,
+    expected: &MultisigIsmConfig,
+) -> bool {
+    let (domain_data_key, _domain_data_bump) =
+        Pubkey::find_program_address(domain_data_pda_seeds!(remote_domain), &program_id);
+
+    let domain_data_account = ctx
+        .client
+        .get_account_with_commitment(&domain_data_key, ctx.commitment)
+        .expect("Failed to get domain data account")
+        .value;
+
+    if let Some(domain_data_account) = domain_data_account {
+        let domain_data = DomainDataAccount::fetch(&mut &domain_data_account.data[..])
+            .unwrap()
+            .into_inner();
+        let expected_validator_set =
+            HashSet::<H160>::from_iter(expected.validators.iter().cloned());
+        let actual_validator_set = HashSet::<H160>::from_iter(
+            domain_data
+                .validators_and_threshold
+                .validators
+                .iter()
+                .cloned(),
+        );
+
+        expected_validator_set == actual_validator_set
+            && expected.threshold == domain_data.validators_and_threshold.threshold
+    } else {
+        false
+    }
+}
+
+fn set_validators_and_threshold(
+    ctx: &mut Context,
+    program_id: Pubkey,
+    domain: u32,
+    validators_and_threshold: ValidatorsAndThreshold,
+) {
+    let description = format!(
+        "Set for remote domain {} validators and threshold: {:?}",
+        domain, validators_and_threshold
+    );
+    ctx.new_txn()
+        .add_with_description(
+            set_validators_and_threshold_instruction(
+                program_id,
+                ctx.payer_pubkey,
+                domain,
+                validators_and_threshold,
+            )
+            .unwrap(),
+            description,
+        )
+        .send_with_payer();
+}
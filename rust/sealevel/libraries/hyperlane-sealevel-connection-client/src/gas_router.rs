@@ -94,6 +94,32 @@ pub trait HyperlaneGasRouterDispatch: HyperlaneGasRouter + HyperlaneRouterDispat
             payment_account_infos,
         )
     }
+
+    /// Dispatches a message with the configured gas amount of the destination as
+    /// the gas limit in the hook metadata, paying for gas through the Mailbox's
+    /// post-dispatch hooks.
+    /// Errors if there is no gas amount configured for the destination.
+    fn dispatch_with_hook_gas(
+        &self,
+        program_id: &Pubkey,
+        dispatch_authority_seeds: &[&[u8]],
+        destination_domain: u32,
+        message_body: Vec<u8>,
+        account_metas: Vec<AccountMeta>,
+        account_infos: &[AccountInfo],
+    ) -> Result<H256, ProgramError> {
+        HyperlaneRouterDispatch::dispatch_with_hook_gas(
+            self,
+            program_id,
+            dispatch_authority_seeds,
+            destination_domain,
+            message_body,
+            self.destination_gas(destination_domain)
+                .ok_or(ProgramError::InvalidArgument)?,
+            account_metas,
+            account_infos,
+        )
+    }
 }
 
 // Auto-implement
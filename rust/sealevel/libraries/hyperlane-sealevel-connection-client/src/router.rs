@@ -4,9 +4,12 @@ use hyperlane_core::H256;
 use hyperlane_sealevel_igp::instruction::{
     Instruction as IgpInstruction, PayForGas as IgpPayForGas,
 };
-use hyperlane_sealevel_mailbox::instruction::{
-    Instruction as MailboxInstruction, OutboxDispatch as MailboxOutboxDispatch,
+use hyperlane_sealevel_mailbox::{
+    accounts::OutboxAccount,
+    instruction::{Instruction as MailboxInstruction, OutboxDispatch as MailboxOutboxDispatch},
+    mailbox_outbox_pda_seeds,
 };
+use hyperlane_sealevel_post_dispatch_hook_interface::StandardHookMetadata;
 use solana_program::{
     account_info::AccountInfo,
     instruction::{AccountMeta, Instruction},
@@ -90,7 +93,32 @@ impl<T> HyperlaneRouterAccessControl for T where T: HyperlaneRouter + AccessCont
 /// The Hyperlane router pattern with a helper function to dispatch messages
 /// to remote routers.
 pub trait HyperlaneRouterDispatch: HyperlaneRouter + HyperlaneConnectionClient {
+    /// Returns true if the Mailbox has a required or default post-dispatch hook.
+    /// If so, the hooks' accounts must be passed when dispatching, and gas is paid
+    /// for through the hooks rather than directly to the configured IGP.
+    fn mailbox_has_post_dispatch_hooks(
+        &self,
+        outbox_account: &AccountInfo,
+    ) -> Result<bool, ProgramError> {
+        let mailbox = self.mailbox();
+        if outbox_account.owner != mailbox {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+        let outbox = OutboxAccount::fetch(&mut &outbox_account.data.borrow()[..])?.into_inner();
+        let expected_outbox_key = Pubkey::create_program_address(
+            mailbox_outbox_pda_seeds!(outbox.outbox_bump_seed),
+            mailbox,
+        )?;
+        if outbox_account.key != &expected_outbox_key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(outbox.required_hook.is_some() || outbox.default_hook.is_some())
+    }
+
     /// Dispatches a message to the remote router for the provided destination domain.
+    /// The account metas and infos must include the accounts of the Mailbox's
+    /// post-dispatch hooks, if any are set.
     fn dispatch(
         &self,
         program_id: &Pubkey,
@@ -99,6 +127,32 @@ pub trait HyperlaneRouterDispatch: HyperlaneRouter + HyperlaneConnectionClient {
         message_body: Vec<u8>,
         account_metas: Vec<AccountMeta>,
         account_infos: &[AccountInfo],
+    ) -> Result<H256, ProgramError> {
+        self.dispatch_with_metadata(
+            program_id,
+            dispatch_authority_seeds,
+            destination_domain,
+            message_body,
+            vec![],
+            account_metas,
+            account_infos,
+        )
+    }
+
+    /// Dispatches a message to the remote router for the provided destination domain,
+    /// passing the provided metadata to the Mailbox's post-dispatch hooks.
+    /// The account metas and infos must include the accounts of the Mailbox's
+    /// post-dispatch hooks, if any are set.
+    #[allow(clippy::too_many_arguments)]
+    fn dispatch_with_metadata(
+        &self,
+        program_id: &Pubkey,
+        dispatch_authority_seeds: &[&[u8]],
+        destination_domain: u32,
+        message_body: Vec<u8>,
+        metadata: Vec<u8>,
+        account_metas: Vec<AccountMeta>,
+        account_infos: &[AccountInfo],
     ) -> Result<H256, ProgramError> {
         // The recipient is the remote router, which must be enrolled.
         let recipient = *self
@@ -110,6 +164,7 @@ pub trait HyperlaneRouterDispatch: HyperlaneRouter + HyperlaneConnectionClient {
             destination_domain,
             recipient,
             message_body,
+            metadata,
         });
         let mailbox = self.mailbox();
         let mailbox_ixn = Instruction {
@@ -123,8 +178,8 @@ pub trait HyperlaneRouterDispatch: HyperlaneRouter + HyperlaneConnectionClient {
         // Parse the message ID from the return data from the prior dispatch.
         let (returning_program_id, returned_data) =
             get_return_data().ok_or(ProgramError::InvalidArgument)?;
-        // The mailbox itself doesn't make any CPIs, but as a sanity check we confirm
-        // that the return data is from the mailbox.
+        // The mailbox sets the return data after any CPIs into post-dispatch hooks,
+        // but as a sanity check we confirm that the return data is from the mailbox.
         if returning_program_id != *mailbox {
             return Err(ProgramError::InvalidArgument);
         }
@@ -137,6 +192,8 @@ pub trait HyperlaneRouterDispatch: HyperlaneRouter + HyperlaneConnectionClient {
     /// Dispatches a message to the remote router for the provided destination domain,
     /// paying for gas with the IGP.
     /// Errors if there is no IGP configured.
+    /// Only to be used if the Mailbox has no post-dispatch hooks, which would
+    /// otherwise be paid for gas as well.
     #[allow(clippy::too_many_arguments)]
     fn dispatch_with_gas(
         &self,
@@ -178,6 +235,37 @@ pub trait HyperlaneRouterDispatch: HyperlaneRouter + HyperlaneConnectionClient {
 
         Ok(message_id)
     }
+
+    /// Dispatches a message to the remote router for the provided destination domain,
+    /// with hook metadata specifying the gas limit of the message on the destination.
+    /// Gas is paid for by the Mailbox's post-dispatch hooks, e.g. an IGP hook,
+    /// whose accounts must be included in the account metas and infos.
+    #[allow(clippy::too_many_arguments)]
+    fn dispatch_with_hook_gas(
+        &self,
+        program_id: &Pubkey,
+        dispatch_authority_seeds: &[&[u8]],
+        destination_domain: u32,
+        message_body: Vec<u8>,
+        gas_amount: u64,
+        account_metas: Vec<AccountMeta>,
+        account_infos: &[AccountInfo],
+    ) -> Result<H256, ProgramError> {
+        let metadata = StandardHookMetadata {
+            gas_limit: Some(gas_amount),
+        }
+        .to_bytes()?;
+
+        self.dispatch_with_metadata(
+            program_id,
+            dispatch_authority_seeds,
+            destination_domain,
+            message_body,
+            metadata,
+            account_metas,
+            account_infos,
+        )
+    }
 }
 
 // Auto-implement
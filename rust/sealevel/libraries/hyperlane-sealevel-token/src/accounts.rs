@@ -14,7 +14,7 @@ use hyperlane_sealevel_igp::accounts::InterchainGasPaymasterType;
 use solana_program::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
 use std::{cmp::Ordering, collections::HashMap, fmt::Debug};
 
-use crate::hyperlane_token_pda_seeds;
+use crate::{error::Error, hyperlane_token_pda_seeds, instruction::RateLimitConfig};
 
 /// HyperlaneToken account data.
 pub type HyperlaneTokenAccount<T> = AccountData<HyperlaneToken<T>>;
@@ -47,6 +47,9 @@ pub struct HyperlaneToken<T> {
     pub remote_routers: HashMap<u32, H256>,
     /// Plugin-specific data.
     pub plugin_data: T,
+    /// Per-remote-domain rate limits. Must remain the last field, as token
+    /// accounts created before rate limits existed don't include it.
+    pub rate_limits: RateLimits,
 }
 
 impl<T> HyperlaneToken<T>
@@ -86,6 +89,37 @@ where
             .as_u64();
         Ok(amount)
     }
+
+    /// Sets the rate limits of remote domains. Only callable by the owner.
+    /// A newly rate limited domain starts with its full capacity available,
+    /// and reconfiguring a domain keeps the amounts currently available.
+    pub fn set_rate_limit_configs_only_owner(
+        &mut self,
+        owner: &AccountInfo,
+        configs: Vec<RateLimitConfig>,
+        now: i64,
+    ) -> Result<(), ProgramError> {
+        self.ensure_owner_signer(owner)?;
+
+        for config in configs {
+            match config.rate_limit {
+                Some(params) if params.window == 0 => return Err(ProgramError::InvalidArgument),
+                Some(params) => {
+                    if let Some(rate_limit) = self.rate_limits.get_mut(config.domain) {
+                        rate_limit.reconfigure(params.capacity, params.window, now);
+                    } else {
+                        self.rate_limits.set(
+                            config.domain,
+                            Some(RateLimit::new(params.capacity, params.window, now)),
+                        );
+                    }
+                }
+                None => self.rate_limits.set(config.domain, None),
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl<T> SizedData for HyperlaneToken<T>
@@ -124,7 +158,9 @@ where
         // remote_routers keys & values
         (self.remote_routers.len() * (std::mem::size_of::<u32>() + 32)) +
         // plugin_data
-        self.plugin_data.size()
+        self.plugin_data.size() +
+        // rate_limits
+        self.rate_limits.size()
     }
 }
 
@@ -198,6 +234,174 @@ impl<T> HyperlaneGasRouter for HyperlaneToken<T> {
     }
 }
 
+/// Magic bytes prefixing the rate limits section of the token account.
+const RATE_LIMITS_MAGIC: [u8; 8] = *b"RATELMTS";
+
+/// Rate limits keyed by remote domain.
+///
+/// This is serialized as an optional trailing section of the token account,
+/// prefixed by `RATE_LIMITS_MAGIC`. The section is only written once rate limits
+/// have been configured, so token accounts created before rate limits existed
+/// keep their size and deserialize with `None`.
+#[derive(Debug, PartialEq, Eq, Default, Clone)]
+pub struct RateLimits(pub Option<HashMap<u32, RateLimit>>);
+
+impl RateLimits {
+    /// Gets the rate limit for the remote domain, if any.
+    pub fn get(&self, domain: u32) -> Option<&RateLimit> {
+        self.0.as_ref().and_then(|limits| limits.get(&domain))
+    }
+
+    /// Gets the rate limit for the remote domain as mutable, if any.
+    pub fn get_mut(&mut self, domain: u32) -> Option<&mut RateLimit> {
+        self.0.as_mut().and_then(|limits| limits.get_mut(&domain))
+    }
+
+    /// Sets the rate limit for the remote domain, removing it if `None`.
+    /// Once set, the rate limits section is always serialized, even if empty,
+    /// so that stale bytes from a previous, longer serialization are never
+    /// mistaken for it.
+    pub fn set(&mut self, domain: u32, rate_limit: Option<RateLimit>) {
+        let limits = self.0.get_or_insert_with(HashMap::new);
+        match rate_limit {
+            Some(rate_limit) => {
+                limits.insert(domain, rate_limit);
+            }
+            None => {
+                limits.remove(&domain);
+            }
+        }
+    }
+}
+
+impl BorshSerialize for RateLimits {
+    fn serialize<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        if let Some(limits) = &self.0 {
+            RATE_LIMITS_MAGIC.serialize(writer)?;
+            limits.serialize(writer)?;
+        }
+        Ok(())
+    }
+}
+
+impl BorshDeserialize for RateLimits {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        if !buf.starts_with(&RATE_LIMITS_MAGIC) {
+            return Ok(Self(None));
+        }
+        *buf = &buf[RATE_LIMITS_MAGIC.len()..];
+        Ok(Self(Some(HashMap::deserialize(buf)?)))
+    }
+}
+
+impl SizedData for RateLimits {
+    fn size(&self) -> usize {
+        match &self.0 {
+            // magic + limits length + limits keys & values
+            Some(limits) => {
+                RATE_LIMITS_MAGIC.len()
+                    + std::mem::size_of::<u32>()
+                    + (limits.len() * (std::mem::size_of::<u32>() + RateLimit::SIZE))
+            }
+            None => 0,
+        }
+    }
+}
+
+/// A rate limit on the amount of tokens transferred to and from a remote domain.
+/// The outbound and inbound directions are limited separately, each to at most
+/// `capacity` tokens per rolling `window`. The amount available refills linearly
+/// from empty to `capacity` over `window` seconds.
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, Eq, Default, Clone)]
+pub struct RateLimit {
+    /// The maximum amount, in local decimals, that can be transferred per window.
+    pub capacity: u64,
+    /// The window length in seconds.
+    pub window: u64,
+    /// The amount available for outbound transfers.
+    pub outbound: RateLimitLevel,
+    /// The amount available for inbound transfers.
+    pub inbound: RateLimitLevel,
+}
+
+/// The amount available in one direction of a `RateLimit`.
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, Eq, Default, Clone)]
+pub struct RateLimitLevel {
+    /// The amount available as of `last_updated`.
+    pub level: u64,
+    /// The unix timestamp `level` was last updated at.
+    pub last_updated: i64,
+}
+
+impl RateLimit {
+    /// The serialized size of a `RateLimit`.
+    pub const SIZE: usize = 8 + 8 + (8 + 8) * 2;
+
+    /// Creates a new rate limit, with the full capacity available.
+    pub fn new(capacity: u64, window: u64, now: i64) -> Self {
+        let level = RateLimitLevel {
+            level: capacity,
+            last_updated: now,
+        };
+        Self {
+            capacity,
+            window,
+            outbound: level.clone(),
+            inbound: level,
+        }
+    }
+
+    /// Updates the capacity and window, keeping the amounts currently
+    /// available but capping them at the new capacity.
+    pub fn reconfigure(&mut self, capacity: u64, window: u64, now: i64) {
+        for rate_limit_level in [&mut self.outbound, &mut self.inbound] {
+            rate_limit_level.level = rate_limit_level
+                .current_level(self.capacity, self.window, now)
+                .min(capacity);
+            rate_limit_level.last_updated = now;
+        }
+        self.capacity = capacity;
+        self.window = window;
+    }
+
+    /// Consumes `amount` from the outbound level, erroring if it exceeds the amount available.
+    pub fn consume_outbound(&mut self, amount: u64, now: i64) -> Result<(), Error> {
+        self.outbound
+            .consume(amount, self.capacity, self.window, now)
+    }
+
+    /// Consumes `amount` from the inbound level, erroring if it exceeds the amount available.
+    pub fn consume_inbound(&mut self, amount: u64, now: i64) -> Result<(), Error> {
+        self.inbound
+            .consume(amount, self.capacity, self.window, now)
+    }
+}
+
+impl RateLimitLevel {
+    /// The amount available at `now`, after refilling linearly since `last_updated`.
+    pub fn current_level(&self, capacity: u64, window: u64, now: i64) -> u64 {
+        if window == 0 {
+            return capacity;
+        }
+        // Clock drift can in theory make `now` earlier than `last_updated`.
+        let elapsed = u128::try_from(now.saturating_sub(self.last_updated)).unwrap_or_default();
+        let refill = elapsed.saturating_mul(capacity as u128) / window as u128;
+        let level = (self.level as u128).saturating_add(refill);
+        // Capped at `capacity`, so this cannot truncate.
+        level.min(capacity as u128) as u64
+    }
+
+    fn consume(&mut self, amount: u64, capacity: u64, window: u64, now: i64) -> Result<(), Error> {
+        let level = self.current_level(capacity, window, now);
+        if amount > level {
+            return Err(Error::RateLimitExceeded);
+        }
+        self.level = level - amount;
+        self.last_updated = now;
+        Ok(())
+    }
+}
+
 /// Converts an amount from one decimal representation to another.
 pub fn convert_decimals(amount: U256, from_decimals: u8, to_decimals: u8) -> Option<U256> {
     match from_decimals.cmp(&to_decimals) {
@@ -343,9 +547,115 @@ mod test {
             destination_gas: HashMap::from([(1000, 200000), (200, 400000)]),
             remote_routers: HashMap::from([(1000, H256::random()), (200, H256::random())]),
             plugin_data: Foo { bar: 69 },
+            rate_limits: RateLimits(Some(HashMap::from([
+                (1000, RateLimit::new(100, 3600, 1)),
+                (200, RateLimit::new(200, 60, 2)),
+            ]))),
+        };
+        let serialized = hyperlane_token_foo.try_to_vec().unwrap();
+
+        assert_eq!(serialized.len(), hyperlane_token_foo.size());
+
+        // Without the rate limits section.
+        let hyperlane_token_foo = HyperlaneToken::<Foo> {
+            rate_limits: RateLimits(None),
+            ..hyperlane_token_foo
         };
         let serialized = hyperlane_token_foo.try_to_vec().unwrap();
 
         assert_eq!(serialized.len(), hyperlane_token_foo.size());
     }
+
+    #[test]
+    fn test_rate_limits_serialization() {
+        let token = HyperlaneToken::<()> {
+            remote_routers: HashMap::from([(1000, H256::random())]),
+            rate_limits: RateLimits(Some(HashMap::from([(1000, RateLimit::new(100, 3600, 1))]))),
+            ..HyperlaneToken::<()>::default()
+        };
+        let serialized = token.try_to_vec().unwrap();
+        assert_eq!(
+            HyperlaneToken::<()>::deserialize(&mut &serialized[..]).unwrap(),
+            token
+        );
+
+        // Accounts serialized before rate limits existed have no rate limits,
+        // whether or not they have trailing zero bytes.
+        let legacy_token = HyperlaneToken::<()> {
+            rate_limits: RateLimits(None),
+            ..token
+        };
+        let mut serialized = legacy_token.try_to_vec().unwrap();
+        assert_eq!(
+            HyperlaneToken::<()>::deserialize(&mut &serialized[..]).unwrap(),
+            legacy_token
+        );
+        serialized.extend_from_slice(&[0u8; 64]);
+        assert_eq!(
+            HyperlaneToken::<()>::deserialize(&mut &serialized[..]).unwrap(),
+            legacy_token
+        );
+
+        // An empty rate limits section is still serialized once set.
+        let token = HyperlaneToken::<()> {
+            rate_limits: RateLimits(Some(HashMap::new())),
+            ..legacy_token
+        };
+        let serialized = token.try_to_vec().unwrap();
+        assert_eq!(
+            &serialized[serialized.len() - 12..serialized.len() - 4],
+            &RATE_LIMITS_MAGIC[..]
+        );
+        assert_eq!(
+            HyperlaneToken::<()>::deserialize(&mut &serialized[..]).unwrap(),
+            token
+        );
+    }
+
+    #[test]
+    fn test_rate_limit_consume() {
+        let mut rate_limit = RateLimit::new(1000, 100, 0);
+
+        // The full capacity is available immediately.
+        assert_eq!(rate_limit.consume_outbound(1000, 0), Ok(()));
+        assert_eq!(
+            rate_limit.consume_outbound(1, 0),
+            Err(Error::RateLimitExceeded)
+        );
+
+        // Inbound is limited separately.
+        assert_eq!(rate_limit.consume_inbound(600, 0), Ok(()));
+
+        // Refills linearly, 10 per second.
+        assert_eq!(
+            rate_limit.consume_outbound(101, 10),
+            Err(Error::RateLimitExceeded)
+        );
+        assert_eq!(rate_limit.consume_outbound(100, 10), Ok(()));
+        assert_eq!(rate_limit.outbound.current_level(1000, 100, 10), 0);
+
+        // Refills no higher than the capacity.
+        assert_eq!(rate_limit.inbound.current_level(1000, 100, 1_000_000), 1000);
+        assert_eq!(
+            rate_limit.consume_inbound(1001, 1_000_000),
+            Err(Error::RateLimitExceeded)
+        );
+
+        // A timestamp earlier than the last update doesn't refill.
+        assert_eq!(rate_limit.outbound.current_level(1000, 100, 5), 0);
+    }
+
+    #[test]
+    fn test_rate_limit_reconfigure() {
+        let mut rate_limit = RateLimit::new(1000, 100, 0);
+        rate_limit.consume_outbound(800, 0).unwrap();
+
+        // The amount available is kept...
+        rate_limit.reconfigure(2000, 100, 10);
+        assert_eq!(rate_limit.outbound.current_level(2000, 100, 10), 300);
+        // ...but capped at the new capacity.
+        rate_limit.reconfigure(100, 100, 10);
+        assert_eq!(rate_limit.outbound.current_level(100, 100, 10), 100);
+        assert_eq!(rate_limit.inbound.current_level(100, 100, 10), 100);
+    }
 }
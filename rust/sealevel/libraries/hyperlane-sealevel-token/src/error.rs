@@ -17,6 +17,10 @@ pub enum Error {
     /// A message decoding error occurred.
     #[error("Message decoding error")]
     MessageDecodeError = 3,
+
+    /// A transfer exceeded the rate limit for the remote domain.
+    #[error("Rate limit exceeded")]
+    RateLimitExceeded = 4,
 }
 
 impl From<Error> for ProgramError {
@@ -36,6 +36,8 @@ pub enum Instruction {
     SetInterchainGasPaymaster(Option<(Pubkey, InterchainGasPaymasterType)>),
     /// Transfer ownership of the program. Only owner.
     TransferOwnership(Option<Pubkey>),
+    /// Set the rate limits of remote domains. Only owner.
+    SetRateLimitConfigs(Vec<RateLimitConfig>),
 }
 
 impl DiscriminatorData for Instruction {
@@ -68,6 +70,25 @@ pub struct TransferRemote {
     pub amount_or_id: U256,
 }
 
+/// Rate limit configuration for a remote domain.
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, Clone)]
+pub struct RateLimitConfig {
+    /// The remote domain.
+    pub domain: u32,
+    /// The rate limit parameters. If `None`, the domain is no longer rate limited.
+    pub rate_limit: Option<RateLimitParams>,
+}
+
+/// Rate limit parameters.
+#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq, Clone)]
+pub struct RateLimitParams {
+    /// The maximum amount, in local decimals, that can be transferred per window
+    /// in each direction.
+    pub capacity: u64,
+    /// The window length in seconds. Must be non-zero.
+    pub window: u64,
+}
+
 /// Gets an instruction to initialize the program. This provides only the
 /// account metas required by the library, and consuming programs are expected
 /// to add the accounts for their own use.
@@ -258,3 +279,34 @@ pub fn set_igp_instruction(
 
     Ok(instruction)
 }
+
+/// Sets rate limit configs.
+pub fn set_rate_limit_configs_instruction(
+    program_id: Pubkey,
+    owner_payer: Pubkey,
+    configs: Vec<RateLimitConfig>,
+) -> Result<SolanaInstruction, ProgramError> {
+    let (token_key, _token_bump) =
+        Pubkey::try_find_program_address(hyperlane_token_pda_seeds!(), &program_id)
+            .ok_or(ProgramError::InvalidSeeds)?;
+
+    let ixn = Instruction::SetRateLimitConfigs(configs);
+
+    // Accounts:
+    // 0. `[executable]` The system program.
+    // 1. `[writeable]` The token PDA account.
+    // 2. `[signer]` The owner.
+    let accounts = vec![
+        AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        AccountMeta::new(token_key, false),
+        AccountMeta::new(owner_payer, true),
+    ];
+
+    let instruction = SolanaInstruction {
+        program_id,
+        data: ixn.encode()?,
+        accounts,
+    };
+
+    Ok(instruction)
+}
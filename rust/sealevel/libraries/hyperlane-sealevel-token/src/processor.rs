@@ -5,7 +5,10 @@ use account_utils::{create_pda_account, SizedData};
 use borsh::{BorshDeserialize, BorshSerialize};
 use hyperlane_core::{Decode, Encode};
 use hyperlane_sealevel_connection_client::{
-    gas_router::{GasRouterConfig, HyperlaneGasRouterAccessControl, HyperlaneGasRouterDispatch},
+    gas_router::{
+        GasRouterConfig, HyperlaneGasRouter, HyperlaneGasRouterAccessControl,
+        HyperlaneGasRouterDispatch,
+    },
     router::{
         HyperlaneRouterAccessControl, HyperlaneRouterDispatch, HyperlaneRouterMessageRecipient,
         RemoteRouterConfig,
@@ -27,14 +30,14 @@ use solana_program::{
     program_error::ProgramError,
     pubkey::Pubkey,
     rent::Rent,
-    sysvar::Sysvar,
+    sysvar::{clock::Clock, Sysvar},
 };
 use std::collections::HashMap;
 
 use crate::{
-    accounts::{HyperlaneToken, HyperlaneTokenAccount},
+    accounts::{HyperlaneToken, HyperlaneTokenAccount, RateLimits},
     error::Error,
-    instruction::{Init, TransferRemote},
+    instruction::{Init, RateLimitConfig, TransferRemote},
     message::TokenMessage,
 };
 
@@ -220,6 +223,7 @@ where
             remote_decimals: init.remote_decimals,
             remote_routers: HashMap::new(),
             plugin_data,
+            rate_limits: RateLimits::default(),
         };
         let token_account_data = HyperlaneTokenAccount::<T>::from(token);
 
@@ -257,14 +261,15 @@ where
     /// Accounts:
     /// 0.    `[executable]` The system program.
     /// 1.    `[executable]` The spl_noop program.
-    /// 2.    `[]` The token PDA account.
+    /// 2.    `[writeable]` The token PDA account. Only required to be writeable if
+    ///       the destination is rate limited.
     /// 3.    `[executable]` The mailbox program.
     /// 4.    `[writeable]` The mailbox outbox account.
     /// 5.    `[]` Message dispatch authority.
     /// 6.    `[signer]` The token sender and mailbox payer.
     /// 7.    `[signer]` Unique message / gas payment account.
     /// 8.    `[writeable]` Message storage PDA.
-    ///       ---- If using an IGP ----
+    ///       ---- If using an IGP and the Mailbox has no post-dispatch hooks ----
     /// 9.    `[executable]` The IGP program.
     /// 10.   `[writeable]` The IGP program data.
     /// 11.   `[writeable]` Gas payment PDA.
@@ -272,6 +277,15 @@ where
     /// 13.   `[writeable]` The IGP account.
     ///      ---- End if ----
     /// 14..N `[??..??]` Plugin-specific accounts.
+    ///       ---- If the Mailbox has post-dispatch hooks ----
+    /// N+1.. `[??..??]` The accounts of the Mailbox's post-dispatch hooks, as expected
+    ///       by the Mailbox's `OutboxDispatch` instruction: the required hook account
+    ///       count account if both hooks are set, the required hook program and its
+    ///       accounts, then the default hook program and its accounts.
+    ///       Gas is paid for through the hooks rather than to the configured IGP.
+    ///       An IGP hook is expected to be paid by the sender, using the unique
+    ///       message account as the unique gas payment account.
+    ///      ---- End if ----
     pub fn transfer_remote(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
@@ -293,7 +307,7 @@ where
 
         // Account 2: Token storage account
         let token_account = next_account_info(accounts_iter)?;
-        let token =
+        let mut token =
             HyperlaneTokenAccount::fetch(&mut &token_account.data.borrow()[..])?.into_inner();
         let token_seeds: &[&[u8]] = hyperlane_token_pda_seeds!(token.bump);
         let expected_token_key = Pubkey::create_program_address(token_seeds, program_id)?;
@@ -311,8 +325,10 @@ where
         }
 
         // Account 4: Mailbox Outbox data account.
-        // No verification is performed here, the Mailbox will do that.
+        // Read to find out whether the Mailbox has post-dispatch hooks, which
+        // verifies the account's owner and key.
         let mailbox_outbox_account = next_account_info(accounts_iter)?;
+        let mailbox_has_hooks = token.mailbox_has_post_dispatch_hooks(mailbox_outbox_account)?;
 
         // Account 5: Message dispatch authority
         let dispatch_authority_account = next_account_info(accounts_iter)?;
@@ -338,8 +354,9 @@ where
         // Similarly defer to the checks in the Mailbox to ensure account validity.
         let dispatched_message_pda = next_account_info(accounts_iter)?;
 
-        let igp_payment_accounts =
-            if let Some((igp_program_id, igp_account_type)) = token.interchain_gas_paymaster() {
+        let igp_payment_accounts = match token.interchain_gas_paymaster() {
+            // If the Mailbox has post-dispatch hooks, gas is paid for through them.
+            Some((igp_program_id, igp_account_type)) if !mailbox_has_hooks => {
                 // Account 9: The IGP program
                 let igp_program_account = next_account_info(accounts_iter)?;
                 if igp_program_account.key != igp_program_id {
@@ -406,9 +423,9 @@ where
                 };
 
                 Some((igp_payment_account_metas, igp_payment_account_infos))
-            } else {
-                None
-            };
+            }
+            _ => None,
+        };
 
         // The amount denominated in the local decimals.
         let local_amount: u64 = xfer
@@ -419,6 +436,15 @@ where
         // by the remote routers as the number of decimals used by the message amount.
         let remote_amount = token.local_amount_to_remote_amount(local_amount)?;
 
+        // Enforce the destination's outbound rate limit, if any.
+        let rate_limited =
+            if let Some(rate_limit) = token.rate_limits.get_mut(xfer.destination_domain) {
+                rate_limit.consume_outbound(local_amount, Clock::get()?.unix_timestamp)?;
+                true
+            } else {
+                false
+            };
+
         // Transfer `local_amount` of tokens in...
         T::transfer_in(
             program_id,
@@ -428,11 +454,7 @@ where
             local_amount,
         )?;
 
-        if accounts_iter.next().is_some() {
-            return Err(ProgramError::from(Error::ExtraneousAccount));
-        }
-
-        let dispatch_account_metas = vec![
+        let mut dispatch_account_metas = vec![
             AccountMeta::new(*mailbox_outbox_account.key, false),
             AccountMeta::new_readonly(*dispatch_authority_account.key, true),
             AccountMeta::new_readonly(solana_program::system_program::id(), false),
@@ -441,7 +463,7 @@ where
             AccountMeta::new_readonly(*unique_message_account.key, true),
             AccountMeta::new(*dispatched_message_pda.key, false),
         ];
-        let dispatch_account_infos = &[
+        let mut dispatch_account_infos = vec![
             mailbox_outbox_account.clone(),
             dispatch_authority_account.clone(),
             system_program_account.clone(),
@@ -451,6 +473,21 @@ where
             dispatched_message_pda.clone(),
         ];
 
+        if mailbox_has_hooks {
+            // The remaining accounts are the post-dispatch hook accounts, which are
+            // passed through to the Mailbox. The Mailbox and the hooks verify them.
+            for hook_account in accounts_iter {
+                dispatch_account_metas.push(AccountMeta {
+                    pubkey: *hook_account.key,
+                    is_signer: hook_account.is_signer,
+                    is_writable: hook_account.is_writable,
+                });
+                dispatch_account_infos.push(hook_account.clone());
+            }
+        } else if accounts_iter.next().is_some() {
+            return Err(ProgramError::from(Error::ExtraneousAccount));
+        }
+
         // The token message body, which specifies the remote_amount.
         let token_transfer_message =
             TokenMessage::new(xfer.recipient, remote_amount, vec![]).to_vec();
@@ -464,10 +501,22 @@ where
                 xfer.destination_domain,
                 token_transfer_message,
                 dispatch_account_metas,
-                dispatch_account_infos,
+                &dispatch_account_infos,
                 igp_payment_account_metas,
                 &igp_payment_account_infos,
             )?;
+        } else if mailbox_has_hooks && token.destination_gas(xfer.destination_domain).is_some() {
+            // Dispatch the message with the destination gas as the hook metadata's
+            // gas limit, paying for gas through the Mailbox's hooks.
+            HyperlaneGasRouterDispatch::dispatch_with_hook_gas(
+                &*token,
+                program_id,
+                dispatch_authority_seeds,
+                xfer.destination_domain,
+                token_transfer_message,
+                dispatch_account_metas,
+                &dispatch_account_infos,
+            )?;
         } else {
             // Dispatch the message.
             token.dispatch(
@@ -476,10 +525,15 @@ where
                 xfer.destination_domain,
                 token_transfer_message,
                 dispatch_account_metas,
-                dispatch_account_infos,
+                &dispatch_account_infos,
             )?;
         }
 
+        if rate_limited {
+            // Store the updated rate limit level. No need to realloc, the size is the same.
+            HyperlaneTokenAccount::<T>::from(token).store(token_account, false)?;
+        }
+
         msg!(
             "Warp route transfer completed to destination: {}, recipient: {}, remote_amount: {}",
             xfer.destination_domain,
@@ -493,7 +547,8 @@ where
     /// Accounts:
     /// 0.   `[signer]` Mailbox processor authority specific to this program.
     /// 1.   `[executable]` system_program
-    /// 2.   `[]` hyperlane_token storage
+    /// 2.   `[writeable]` hyperlane_token storage. Only required to be writeable if
+    ///      the origin is rate limited.
     /// 3.   [depends on plugin] recipient wallet address
     /// 4..N `[??..??]` Plugin-specific accounts.
     pub fn transfer_from_remote(
@@ -519,7 +574,7 @@ where
 
         // Account 2: Token account
         let token_account = next_account_info(accounts_iter)?;
-        let token =
+        let mut token =
             HyperlaneTokenAccount::fetch(&mut &token_account.data.borrow()[..])?.into_inner();
         let token_seeds: &[&[u8]] = hyperlane_token_pda_seeds!(token.bump);
         let expected_token_key = Pubkey::create_program_address(token_seeds, program_id)?;
@@ -547,6 +602,14 @@ where
         // Convert to the local number of decimals.
         let local_amount: u64 = token.remote_amount_to_local_amount(remote_amount)?;
 
+        // Enforce the origin's inbound rate limit, if any.
+        let rate_limited = if let Some(rate_limit) = token.rate_limits.get_mut(xfer.origin) {
+            rate_limit.consume_inbound(local_amount, Clock::get()?.unix_timestamp)?;
+            true
+        } else {
+            false
+        };
+
         // Transfer the `local_amount` of tokens out.
         T::transfer_out(
             program_id,
@@ -561,6 +624,11 @@ where
             return Err(ProgramError::from(Error::ExtraneousAccount));
         }
 
+        if rate_limited {
+            // Store the updated rate limit level. No need to realloc, the size is the same.
+            HyperlaneTokenAccount::<T>::from(token).store(token_account, false)?;
+        }
+
         msg!(
             "Warp route transfer completed from origin: {}, recipient: {}, remote_amount: {}",
             xfer.origin,
@@ -594,9 +662,17 @@ where
         let (transfer_out_account_metas, writeable_recipient) =
             T::transfer_out_account_metas(program_id, &token, &message)?;
 
+        // The token account is only written to if the origin is rate limited.
+        let writeable_token = token.rate_limits.get(transfer.origin).is_some();
+
         let mut accounts: Vec<SerializableAccountMeta> = vec![
             AccountMeta::new_readonly(solana_program::system_program::id(), false).into(),
-            AccountMeta::new_readonly(*token_account_info.key, false).into(),
+            AccountMeta {
+                pubkey: *token_account_info.key,
+                is_signer: false,
+                is_writable: writeable_token,
+            }
+            .into(),
             AccountMeta {
                 pubkey: Pubkey::new_from_array(message.recipient().into()),
                 is_signer: false,
@@ -857,6 +933,50 @@ where
         Ok(())
     }
 
+    /// Lets the owner set rate limit configs.
+    ///
+    /// Accounts:
+    /// 0. `[executable]` The system program.
+    /// 1. `[writeable]` The token PDA account.
+    /// 2. `[signer]` The access control owner.
+    pub fn set_rate_limit_configs(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        configs: Vec<RateLimitConfig>,
+    ) -> ProgramResult {
+        let accounts_iter = &mut accounts.iter();
+
+        // Account 0: System program. Only used if a realloc / rent exemption top up occurs.
+        let system_program = next_account_info(accounts_iter)?;
+        if system_program.key != &solana_program::system_program::id() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Account 1: Token account
+        let token_account = next_account_info(accounts_iter)?;
+        let mut token = HyperlaneToken::verify_account_and_fetch_inner(program_id, token_account)?;
+
+        // Account 2: Owner
+        let owner_account = next_account_info(accounts_iter)?;
+
+        // This errors if owner_account is not really the owner.
+        token.set_rate_limit_configs_only_owner(
+            owner_account,
+            configs,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        // Store the updated token account and realloc if necessary.
+        HyperlaneTokenAccount::<T>::from(token).store_with_rent_exempt_realloc(
+            token_account,
+            &Rent::get()?,
+            owner_account,
+            system_program,
+        )?;
+
+        Ok(())
+    }
+
     /// Lets the owner set the interchain gas paymaster.
     ///
     /// Accounts:
@@ -14,6 +14,11 @@ pub enum InterchainSecurityModuleInstruction {
     /// Gets the list of AccountMetas required for the `Verify` instruction.
     /// The only account expected to be passed into this instruction is the
     /// read-only PDA relating to the program ID and the seeds `VERIFY_ACCOUNT_METAS_PDA_SEEDS`
+    ///
+    /// ISMs that require the accounts of other ISMs, e.g. routing ISMs, may return
+    /// a list that starts with that same PDA. In this case the instruction is expected
+    /// to be called again, passing in the returned list as the accounts, until the
+    /// returned list is unchanged or `MAX_VERIFY_ACCOUNT_METAS_SIMULATIONS` is reached.
     VerifyAccountMetas(VerifyInstruction),
 }
 
@@ -47,6 +52,10 @@ const VERIFY_ACCOUNT_METAS_DISCRIMINATOR_SLICE: &[u8] = &VERIFY_ACCOUNT_METAS_DI
 pub const VERIFY_ACCOUNT_METAS_PDA_SEEDS: &[&[u8]] =
     &[b"hyperlane_ism", b"-", b"verify", b"-", b"account_metas"];
 
+/// The maximum number of times the `VerifyAccountMetas` instruction is expected
+/// to be called to get the list of AccountMetas required for the `Verify` instruction.
+pub const MAX_VERIFY_ACCOUNT_METAS_SIMULATIONS: usize = 8;
+
 impl InterchainSecurityModuleInstruction {
     pub fn encode(&self) -> Result<Vec<u8>, ProgramError> {
         let mut buf = vec![];
@@ -2,7 +2,10 @@
 //! Useful for use in unit & integration tests, which can't import from
 //! each other.
 
-use hyperlane_core::{Checkpoint, CheckpointWithMessageId, HyperlaneMessage, H160, H256};
+use hyperlane_core::{
+    accumulator::{TREE_DEPTH, ZERO_HASHES},
+    Checkpoint, CheckpointWithMessageId, HyperlaneMessage, H160, H256,
+};
 use std::str::FromStr;
 
 pub struct MultisigIsmTestData {
@@ -12,11 +15,22 @@ pub struct MultisigIsmTestData {
     pub signatures: Vec<Vec<u8>>,
 }
 
+pub struct MerkleRootMultisigIsmTestData {
+    pub message: HyperlaneMessage,
+    /// The index of the message's leaf in the origin merkle tree.
+    pub merkle_leaf_index: u32,
+    /// The proof of the message's leaf, from which the signed root is derived.
+    pub proof: [H256; TREE_DEPTH],
+    pub checkpoint: CheckpointWithMessageId,
+    pub validators: Vec<H160>,
+    pub signatures: Vec<Vec<u8>>,
+}
+
 const ORIGIN_DOMAIN: u32 = 1234u32;
 const DESTINATION_DOMAIN: u32 = 4321u32;
 
-pub fn get_multisig_ism_test_data() -> MultisigIsmTestData {
-    let message = HyperlaneMessage {
+fn test_message() -> HyperlaneMessage {
+    HyperlaneMessage {
         version: 3,
         nonce: 69,
         origin: ORIGIN_DOMAIN,
@@ -30,7 +44,11 @@ pub fn get_multisig_ism_test_data() -> MultisigIsmTestData {
         )
         .unwrap(),
         body: vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
-    };
+    }
+}
+
+pub fn get_multisig_ism_test_data() -> MultisigIsmTestData {
+    let message = test_message();
 
     let checkpoint = CheckpointWithMessageId {
         checkpoint: Checkpoint {
@@ -85,3 +103,58 @@ pub fn get_multisig_ism_test_data() -> MultisigIsmTestData {
         signatures: vec![signature_0, signature_1, signature_2],
     }
 }
+
+pub fn get_merkle_root_multisig_ism_test_data() -> MerkleRootMultisigIsmTestData {
+    let message = test_message();
+
+    // The proof of the message's leaf in a tree in which it's the only non-zero leaf.
+    let merkle_leaf_index = message.nonce;
+    let mut proof = [H256::zero(); TREE_DEPTH];
+    proof.copy_from_slice(&ZERO_HASHES[..TREE_DEPTH]);
+
+    let checkpoint = CheckpointWithMessageId {
+        checkpoint: Checkpoint {
+            merkle_tree_hook_address: H256::from_str(
+                "0xabababababababababababababababababababababababababababababababab",
+            )
+            .unwrap(),
+            mailbox_domain: ORIGIN_DOMAIN,
+            // The root derived from the message ID and the proof at `merkle_leaf_index`.
+            root: H256::from_str(
+                "0x27b178497999ab844d9a87c93f70f5977b287481ab4def16e6c5417991368fe9",
+            )
+            .unwrap(),
+            // Intentionally later than the message's leaf index, as a checkpoint
+            // signed after the message was inserted into the tree.
+            index: merkle_leaf_index + 1,
+        },
+        // The ID of the message at the signed checkpoint's index, which
+        // is not the message being verified.
+        message_id: H256::from_str(
+            "0xefefefefefefefefefefefefefefefefefefefefefefefefefefefefefefefef",
+        )
+        .unwrap(),
+    };
+
+    // checkpoint.signing_hash() is equal to:
+    // 0x9b32d51252ca126aa7f5dc982a06f0cd48100be040a36cd9ba4202076b699923
+    // The validators and private keys are the same as in `get_multisig_ism_test_data`.
+
+    let validator_0 = H160::from_str("0xE3DCDBbc248cE191bDc271f3FCcd0d95911BFC5D").unwrap();
+    let signature_0 = hex::decode("a3b4276975fdc0b8e5cdd0a6f292870ff6355aafd781238773920d1110e0e6472da18007421e7dbf56911131f4641ab6740dbcc18a0b9889d91832b7d226dfb71b").unwrap();
+
+    let validator_1 = H160::from_str("0xb25206874C24733F05CC0dD11924724A8E7175bd").unwrap();
+    let signature_1 = hex::decode("7982d56dcf07d869f9006cae5462b2e50f9149956e488fbc1f939a7560bb151d43b7b93b65bc1cd6e2109483f6e077fe0bb62ee2a893a1732e8d438048b97d2f1c").unwrap();
+
+    let validator_2 = H160::from_str("0x28b8d0E2bBfeDe9071F8Ff3DaC9CcE3d3176DBd3").unwrap();
+    let signature_2 = hex::decode("d9501c06e63283d99cfce627a813101a811fd75f943ab227e601a094312485b0269d1ffee30c893c18a4dc06dbfa1daac632e6889aee596cf2210890132bbf6e1c").unwrap();
+
+    MerkleRootMultisigIsmTestData {
+        message,
+        merkle_leaf_index,
+        proof,
+        checkpoint,
+        validators: vec![validator_0, validator_1, validator_2],
+        signatures: vec![signature_0, signature_1, signature_2],
+    }
+}
@@ -0,0 +1,186 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::program_error::ProgramError;
+use spl_type_length_value::discriminator::Discriminator;
+
+/// Instructions that a Hyperlane post-dispatch hook is expected to process.
+/// The first 8 bytes of the encoded instruction is a discriminator that
+/// allows programs to implement the required interface.
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum PostDispatchHookInstruction {
+    /// Called by the Mailbox after a message has been dispatched.
+    PostDispatch(PostDispatchInstruction),
+    /// Quotes the payment in lamports required by the `PostDispatch` instruction,
+    /// returned as a borsh-serialized `SimulationReturnData<u64>`.
+    QuoteDispatch(PostDispatchInstruction),
+}
+
+#[derive(Eq, PartialEq, BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct PostDispatchInstruction {
+    /// The hook metadata provided by the sender of the message.
+    pub metadata: Vec<u8>,
+    /// The encoded dispatched message.
+    pub message: Vec<u8>,
+}
+
+impl PostDispatchInstruction {
+    pub fn new(metadata: Vec<u8>, message: Vec<u8>) -> Self {
+        Self { metadata, message }
+    }
+}
+
+/// First 8 bytes of `hash::hashv(&[b"hyperlane-post-dispatch-hook:post-dispatch"])`
+const POST_DISPATCH_DISCRIMINATOR: [u8; Discriminator::LENGTH] =
+    [121, 67, 135, 153, 114, 129, 2, 213];
+const POST_DISPATCH_DISCRIMINATOR_SLICE: &[u8] = &POST_DISPATCH_DISCRIMINATOR;
+
+/// First 8 bytes of `hash::hashv(&[b"hyperlane-post-dispatch-hook:quote-dispatch"])`
+const QUOTE_DISPATCH_DISCRIMINATOR: [u8; Discriminator::LENGTH] =
+    [129, 95, 61, 116, 126, 140, 2, 194];
+const QUOTE_DISPATCH_DISCRIMINATOR_SLICE: &[u8] = &QUOTE_DISPATCH_DISCRIMINATOR;
+
+impl PostDispatchHookInstruction {
+    pub fn encode(&self) -> Result<Vec<u8>, ProgramError> {
+        let mut buf = vec![];
+        let (discriminator, instruction) = match self {
+            PostDispatchHookInstruction::PostDispatch(instruction) => {
+                (POST_DISPATCH_DISCRIMINATOR_SLICE, instruction)
+            }
+            PostDispatchHookInstruction::QuoteDispatch(instruction) => {
+                (QUOTE_DISPATCH_DISCRIMINATOR_SLICE, instruction)
+            }
+        };
+        buf.extend_from_slice(discriminator);
+        buf.extend_from_slice(
+            &instruction
+                .try_to_vec()
+                .map_err(|err| ProgramError::BorshIoError(err.to_string()))?[..],
+        );
+
+        Ok(buf)
+    }
+
+    pub fn decode(buf: &[u8]) -> Result<Self, ProgramError> {
+        if buf.len() < Discriminator::LENGTH {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+        let (discriminator, rest) = buf.split_at(Discriminator::LENGTH);
+        match discriminator {
+            POST_DISPATCH_DISCRIMINATOR_SLICE => {
+                let instruction = PostDispatchInstruction::try_from_slice(rest)
+                    .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+                Ok(Self::PostDispatch(instruction))
+            }
+            QUOTE_DISPATCH_DISCRIMINATOR_SLICE => {
+                let instruction = PostDispatchInstruction::try_from_slice(rest)
+                    .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+                Ok(Self::QuoteDispatch(instruction))
+            }
+            _ => Err(ProgramError::InvalidInstructionData),
+        }
+    }
+}
+
+/// Hook metadata understood by the hooks in this repository.
+/// The same metadata is passed to every hook invoked for a dispatch,
+/// so hooks are expected to ignore the fields they don't use.
+#[derive(Eq, PartialEq, BorshSerialize, BorshDeserialize, Debug, Default, Clone)]
+pub struct StandardHookMetadata {
+    /// The gas limit of the message on the destination chain.
+    /// Hooks that pay for gas use their own default if this is `None`.
+    pub gas_limit: Option<u64>,
+}
+
+impl StandardHookMetadata {
+    /// Decodes the metadata. Empty metadata decodes to the default,
+    /// and any trailing bytes are left for custom hook metadata.
+    pub fn from_bytes(mut bytes: &[u8]) -> Result<Self, ProgramError> {
+        if bytes.is_empty() {
+            return Ok(Self::default());
+        }
+        Self::deserialize(&mut bytes).map_err(|err| ProgramError::BorshIoError(err.to_string()))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ProgramError> {
+        self.try_to_vec()
+            .map_err(|err| ProgramError::BorshIoError(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use solana_program::hash::hashv;
+
+    #[test]
+    fn test_discriminator_slices() {
+        assert_eq!(
+            &hashv(&[b"hyperlane-post-dispatch-hook:post-dispatch"]).to_bytes()
+                [..Discriminator::LENGTH],
+            POST_DISPATCH_DISCRIMINATOR_SLICE,
+        );
+
+        assert_eq!(
+            &hashv(&[b"hyperlane-post-dispatch-hook:quote-dispatch"]).to_bytes()
+                [..Discriminator::LENGTH],
+            QUOTE_DISPATCH_DISCRIMINATOR_SLICE,
+        );
+    }
+
+    #[test]
+    fn test_encode_decode_post_dispatch_instruction() {
+        let instruction = PostDispatchHookInstruction::PostDispatch(PostDispatchInstruction::new(
+            vec![5, 4, 3, 2, 1],
+            vec![1, 2, 3, 4, 5],
+        ));
+
+        let encoded = instruction.encode().unwrap();
+        assert_eq!(
+            &encoded[..Discriminator::LENGTH],
+            POST_DISPATCH_DISCRIMINATOR_SLICE,
+        );
+
+        let decoded = PostDispatchHookInstruction::decode(&encoded).unwrap();
+        assert_eq!(instruction, decoded);
+    }
+
+    #[test]
+    fn test_encode_decode_quote_dispatch_instruction() {
+        let instruction = PostDispatchHookInstruction::QuoteDispatch(PostDispatchInstruction::new(
+            vec![5, 4, 3, 2, 1],
+            vec![1, 2, 3, 4, 5],
+        ));
+
+        let encoded = instruction.encode().unwrap();
+        assert_eq!(
+            &encoded[..Discriminator::LENGTH],
+            QUOTE_DISPATCH_DISCRIMINATOR_SLICE,
+        );
+
+        let decoded = PostDispatchHookInstruction::decode(&encoded).unwrap();
+        assert_eq!(instruction, decoded);
+    }
+
+    #[test]
+    fn test_standard_hook_metadata() {
+        assert_eq!(
+            StandardHookMetadata::from_bytes(&[]).unwrap(),
+            StandardHookMetadata::default(),
+        );
+
+        let metadata = StandardHookMetadata {
+            gas_limit: Some(69420),
+        };
+        let mut encoded = metadata.to_bytes().unwrap();
+        assert_eq!(
+            StandardHookMetadata::from_bytes(&encoded).unwrap(),
+            metadata
+        );
+
+        // Trailing custom metadata is ignored.
+        encoded.extend_from_slice(&[1, 2, 3]);
+        assert_eq!(
+            StandardHookMetadata::from_bytes(&encoded).unwrap(),
+            metadata
+        );
+    }
+}
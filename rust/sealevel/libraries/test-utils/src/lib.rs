@@ -18,7 +18,8 @@ use solana_sdk::{
 use spl_token_2022::{extension::StateWithExtensions, state::Account};
 
 use hyperlane_sealevel_interchain_security_module_interface::{
-    InterchainSecurityModuleInstruction, VerifyInstruction, VERIFY_ACCOUNT_METAS_PDA_SEEDS,
+    InterchainSecurityModuleInstruction, VerifyInstruction, MAX_VERIFY_ACCOUNT_METAS_SIMULATIONS,
+    VERIFY_ACCOUNT_METAS_PDA_SEEDS,
 };
 use hyperlane_sealevel_mailbox::{
     instruction::{InboxProcess, Init as InitMailbox, Instruction as MailboxInstruction},
@@ -230,6 +231,9 @@ pub async fn get_recipient_ism(
 }
 
 /// Gets the account metas required for the ISM's `Verify` instruction.
+/// `VerifyAccountMetas` is simulated again with the returned account metas for as
+/// long as they start with the ISM's `VERIFY_ACCOUNT_METAS_PDA_SEEDS` PDA and change,
+/// which is the case for ISMs that require the accounts of other ISMs.
 pub async fn get_ism_verify_account_metas(
     banks_client: &mut BanksClient,
     payer: &Keypair,
@@ -237,19 +241,36 @@ pub async fn get_ism_verify_account_metas(
     metadata: Vec<u8>,
     message: Vec<u8>,
 ) -> Result<Vec<AccountMeta>, BanksClientError> {
-    let instruction = InterchainSecurityModuleInstruction::VerifyAccountMetas(VerifyInstruction {
-        metadata,
-        message,
-    });
+    let instruction_data =
+        InterchainSecurityModuleInstruction::VerifyAccountMetas(VerifyInstruction {
+            metadata,
+            message,
+        })
+        .encode()
+        .unwrap();
 
-    get_account_metas_with_instruction_bytes(
-        banks_client,
-        payer,
-        ism,
-        &instruction.encode().unwrap(),
-        VERIFY_ACCOUNT_METAS_PDA_SEEDS,
-    )
-    .await
+    let (account_metas_pda_key, _) =
+        Pubkey::find_program_address(VERIFY_ACCOUNT_METAS_PDA_SEEDS, &ism);
+    let mut accounts = vec![AccountMeta::new(account_metas_pda_key, false)];
+    for _ in 0..MAX_VERIFY_ACCOUNT_METAS_SIMULATIONS {
+        let account_metas = get_account_metas(
+            banks_client,
+            payer,
+            Instruction::new_with_bytes(ism, &instruction_data, accounts.clone()),
+        )
+        .await?;
+        let complete = account_metas
+            .first()
+            .map(|account_meta| account_meta.pubkey)
+            != Some(account_metas_pda_key)
+            || account_metas == accounts;
+        accounts = account_metas;
+        if complete {
+            break;
+        }
+    }
+
+    Ok(accounts)
 }
 
 /// Gets the account metas required for the recipient's `MessageRecipientInstruction::Handle` instruction.
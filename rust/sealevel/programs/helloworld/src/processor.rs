@@ -165,13 +165,17 @@ fn init(program_id: &Pubkey, accounts: &[AccountInfo], init: Init) -> ProgramRes
 /// 7.  `[signer]` Unique message account.
 /// 8.  `[writeable]` Dispatched message PDA. An empty message PDA relating to the seeds
 ///     `mailbox_dispatched_message_pda_seeds` where the message contents will be stored.
-///     ---- if an IGP is configured ----
+///     ---- if an IGP is configured and the Mailbox has no post-dispatch hooks ----
 /// 9.  `[executable]` The IGP program.
 /// 10. `[writeable]` The IGP program data.
 /// 11. `[writeable]` The gas payment PDA.
 /// 12. `[]` OPTIONAL - The Overhead IGP program, if the configured IGP is an Overhead IGP.
 /// 13. `[writeable]` The IGP account.
-///     ---- end if an IGP is configured ----
+///     ---- end if an IGP is configured and the Mailbox has no post-dispatch hooks ----
+///     ---- if the Mailbox has post-dispatch hooks ----
+/// 9.. `[??..??]` The accounts of the Mailbox's post-dispatch hooks, as expected by the
+///     Mailbox's `OutboxDispatch` instruction, e.g. an IGP hook paid by the payer.
+///     ---- end if the Mailbox has post-dispatch hooks ----
 fn send_hello_world(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -194,6 +198,7 @@ fn send_hello_world(
 
     // Account 2: Outbox PDA.
     let mailbox_outbox_info = next_account_info(accounts_iter)?;
+    let mailbox_has_hooks = storage.mailbox_has_post_dispatch_hooks(mailbox_outbox_info)?;
 
     // Account 3: Dispatch authority.
     let dispatch_authority_info = next_account_info(accounts_iter)?;
@@ -218,7 +223,7 @@ fn send_hello_world(
     // Account 8: Dispatched message PDA.
     let dispatched_message_info = next_account_info(accounts_iter)?;
 
-    let dispatch_account_metas = vec![
+    let mut dispatch_account_metas = vec![
         AccountMeta::new(*mailbox_outbox_info.key, false),
         AccountMeta::new_readonly(*dispatch_authority_info.key, true),
         AccountMeta::new_readonly(*system_program_info.key, false),
@@ -227,7 +232,7 @@ fn send_hello_world(
         AccountMeta::new_readonly(*unique_message_account_info.key, true),
         AccountMeta::new(*dispatched_message_info.key, false),
     ];
-    let dispatch_account_infos = &[
+    let mut dispatch_account_infos = vec![
         mailbox_outbox_info.clone(),
         dispatch_authority_info.clone(),
         system_program_info.clone(),
@@ -237,8 +242,9 @@ fn send_hello_world(
         dispatched_message_info.clone(),
     ];
 
-    let igp_payment_accounts =
-        if let Some((igp_program_id, igp_account_type)) = storage.interchain_gas_paymaster() {
+    let igp_payment_accounts = match storage.interchain_gas_paymaster() {
+        // If the Mailbox has post-dispatch hooks, gas is paid for through them.
+        Some((igp_program_id, igp_account_type)) if !mailbox_has_hooks => {
             // Account 9: The IGP program
             let igp_program_account_info = next_account_info(accounts_iter)?;
             if igp_program_account_info.key != igp_program_id {
@@ -307,9 +313,22 @@ fn send_hello_world(
             };
 
             Some((igp_payment_account_metas, igp_payment_account_infos))
-        } else {
-            None
-        };
+        }
+        _ => None,
+    };
+
+    if mailbox_has_hooks {
+        // Accounts 9..N: The post-dispatch hook accounts, which are passed through
+        // to the Mailbox.
+        for hook_account_info in accounts_iter {
+            dispatch_account_metas.push(AccountMeta {
+                pubkey: *hook_account_info.key,
+                is_signer: hook_account_info.is_signer,
+                is_writable: hook_account_info.is_writable,
+            });
+            dispatch_account_infos.push(hook_account_info.clone());
+        }
+    }
 
     let dispatch_authority_seeds: &[&[u8]] =
         mailbox_message_dispatch_authority_pda_seeds!(expected_dispatch_authority_bump);
@@ -323,10 +342,21 @@ fn send_hello_world(
             hello_world.message.into(),
             HANDLE_GAS_AMOUNT,
             dispatch_account_metas,
-            dispatch_account_infos,
+            &dispatch_account_infos,
             igp_payment_account_metas,
             &igp_payment_account_infos,
         )?;
+    } else if mailbox_has_hooks {
+        // Dispatch the message, paying for gas through the Mailbox's hooks.
+        storage.dispatch_with_hook_gas(
+            program_id,
+            dispatch_authority_seeds,
+            hello_world.destination,
+            hello_world.message.into(),
+            HANDLE_GAS_AMOUNT,
+            dispatch_account_metas,
+            &dispatch_account_infos,
+        )?;
     } else {
         // Dispatch the message.
         storage.dispatch(
@@ -335,7 +365,7 @@ fn send_hello_world(
             hello_world.destination,
             hello_world.message.into(),
             dispatch_account_metas,
-            dispatch_account_infos,
+            &dispatch_account_infos,
         )?;
     }
 
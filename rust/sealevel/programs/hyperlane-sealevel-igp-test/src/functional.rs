@@ -1,4 +1,4 @@
-use hyperlane_core::H256;
+use hyperlane_core::{Encode, HyperlaneMessage, H256};
 
 use std::collections::HashMap;
 
@@ -31,11 +31,14 @@ use hyperlane_sealevel_igp::{
     error::Error as IgpError,
     igp_gas_payment_pda_seeds, igp_pda_seeds, igp_program_data_pda_seeds,
     instruction::{
-        GasOracleConfig, GasOverheadConfig, InitIgp, InitOverheadIgp,
-        Instruction as IgpInstruction, PayForGas, QuoteGasPayment,
+        post_dispatch_hook_account_metas, GasOracleConfig, GasOverheadConfig, InitIgp,
+        InitOverheadIgp, Instruction as IgpInstruction, PayForGas, QuoteGasPayment,
     },
     overhead_igp_pda_seeds,
-    processor::process_instruction as igp_process_instruction,
+    processor::{process_instruction as igp_process_instruction, DEFAULT_POST_DISPATCH_GAS_AMOUNT},
+};
+use hyperlane_sealevel_post_dispatch_hook_interface::{
+    PostDispatchHookInstruction, PostDispatchInstruction, StandardHookMetadata,
 };
 
 const TEST_DESTINATION_DOMAIN: u32 = 11111;
@@ -1271,6 +1274,237 @@ async fn test_pay_for_gas_errors_if_no_gas_oracle() {
     );
 }
 
+// ============ PostDispatch & QuoteDispatch ============
+
+fn test_message(destination_domain: u32) -> HyperlaneMessage {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 69,
+        origin: 1234,
+        sender: H256::random(),
+        destination: destination_domain,
+        recipient: H256::random(),
+        body: vec![1, 2, 3, 4, 5],
+    }
+}
+
+async fn quote_dispatch(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    igp_key: Pubkey,
+    metadata: Vec<u8>,
+    message: &HyperlaneMessage,
+) -> Result<u64, BanksClientError> {
+    let instruction = Instruction::new_with_bytes(
+        igp_program_id(),
+        &PostDispatchHookInstruction::QuoteDispatch(PostDispatchInstruction::new(
+            metadata,
+            message.to_vec(),
+        ))
+        .encode()
+        .unwrap(),
+        vec![
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(igp_key, false),
+        ],
+    );
+
+    simulate_instruction::<SimulationReturnData<u64>>(banks_client, payer, instruction)
+        .await
+        .map(|r| r.unwrap().return_data)
+}
+
+async fn post_dispatch(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    igp_key: Pubkey,
+    metadata: Vec<u8>,
+    message: &HyperlaneMessage,
+) -> Result<(Pubkey, Keypair, Signature), BanksClientError> {
+    let program_id = igp_program_id();
+    let unique_payment_account = Keypair::new();
+
+    let (mut accounts, gas_payment_pda_key) = post_dispatch_hook_account_metas(
+        program_id,
+        payer.pubkey(),
+        igp_key,
+        None,
+        unique_payment_account.pubkey(),
+    )
+    .unwrap();
+    // The first account is the hook program itself, which is only
+    // expected when dispatching through the Mailbox.
+    accounts.remove(0);
+
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &PostDispatchHookInstruction::PostDispatch(PostDispatchInstruction::new(
+            metadata,
+            message.to_vec(),
+        ))
+        .encode()
+        .unwrap(),
+        accounts,
+    );
+
+    let tx_signature = process_instruction(
+        banks_client,
+        instruction,
+        payer,
+        &[payer, &unique_payment_account],
+    )
+    .await?;
+
+    Ok((gas_payment_pda_key, unique_payment_account, tx_signature))
+}
+
+async fn run_post_dispatch_tests(metadata: StandardHookMetadata, expected_gas_amount: u64) {
+    let (mut banks_client, payer) = setup_client().await;
+    let message = test_message(TEST_DESTINATION_DOMAIN);
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let (igp_key, _overhead_igp_key) = setup_test_igps(
+        &mut banks_client,
+        &payer,
+        TEST_DESTINATION_DOMAIN,
+        GasOracle::RemoteGasData(RemoteGasData {
+            token_exchange_rate: TOKEN_EXCHANGE_RATE_SCALE,
+            gas_price: 1u128,
+            token_decimals: LOCAL_DECIMALS,
+        }),
+        None,
+    )
+    .await;
+
+    let quote = quote_dispatch(
+        &mut banks_client,
+        &payer,
+        igp_key,
+        metadata.to_bytes().unwrap(),
+        &message,
+    )
+    .await
+    .unwrap();
+    assert_eq!(
+        quote,
+        quote_gas_payment(
+            &mut banks_client,
+            &payer,
+            TEST_DESTINATION_DOMAIN,
+            expected_gas_amount,
+            igp_key,
+            None,
+        )
+        .await
+        .unwrap(),
+    );
+
+    let igp_balance_before = banks_client.get_balance(igp_key).await.unwrap();
+
+    let (gas_payment_pda_key, unique_payment_account, payment_tx_signature) = post_dispatch(
+        &mut banks_client,
+        &payer,
+        igp_key,
+        metadata.to_bytes().unwrap(),
+        &message,
+    )
+    .await
+    .unwrap();
+
+    let igp_balance_after = banks_client.get_balance(igp_key).await.unwrap();
+
+    assert_eq!(igp_balance_after - igp_balance_before, quote);
+    assert!(quote > 0);
+
+    assert_gas_payment(
+        &mut banks_client,
+        igp_key,
+        payment_tx_signature,
+        unique_payment_account.pubkey(),
+        gas_payment_pda_key,
+        TEST_DESTINATION_DOMAIN,
+        expected_gas_amount,
+        quote,
+        message.id(),
+        0,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_dispatch_with_gas_limit() {
+    run_post_dispatch_tests(
+        StandardHookMetadata {
+            gas_limit: Some(TEST_GAS_AMOUNT),
+        },
+        TEST_GAS_AMOUNT,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_dispatch_without_gas_limit() {
+    run_post_dispatch_tests(
+        StandardHookMetadata::default(),
+        DEFAULT_POST_DISPATCH_GAS_AMOUNT,
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_dispatch_errors_if_message_invalid() {
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let (igp_key, _overhead_igp_key) = setup_test_igps(
+        &mut banks_client,
+        &payer,
+        TEST_DESTINATION_DOMAIN,
+        GasOracle::RemoteGasData(RemoteGasData {
+            token_exchange_rate: TOKEN_EXCHANGE_RATE_SCALE,
+            gas_price: 1u128,
+            token_decimals: LOCAL_DECIMALS,
+        }),
+        None,
+    )
+    .await;
+
+    let unique_payment_account = Keypair::new();
+    let (mut accounts, _gas_payment_pda_key) = post_dispatch_hook_account_metas(
+        igp_program_id(),
+        payer.pubkey(),
+        igp_key,
+        None,
+        unique_payment_account.pubkey(),
+    )
+    .unwrap();
+    accounts.remove(0);
+
+    let instruction = Instruction::new_with_bytes(
+        igp_program_id(),
+        &PostDispatchHookInstruction::PostDispatch(PostDispatchInstruction::new(
+            vec![],
+            vec![1, 2, 3],
+        ))
+        .encode()
+        .unwrap(),
+        accounts,
+    );
+
+    assert_transaction_error(
+        process_instruction(
+            &mut banks_client,
+            instruction,
+            &payer,
+            &[&payer, &unique_payment_account],
+        )
+        .await,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+}
+
 // ============ Claim ============
 
 #[tokio::test]
@@ -273,6 +273,62 @@ pub fn pay_for_gas_instruction(
     destination_domain: u32,
     gas_amount: u64,
 ) -> Result<(SolanaInstruction, Pubkey), ProgramError> {
+    let ixn = Instruction::PayForGas(PayForGas {
+        message_id,
+        destination_domain,
+        gas_amount,
+    });
+
+    let (accounts, gas_payment_account) = pay_for_gas_account_metas(
+        program_id,
+        payer,
+        igp,
+        overhead_igp,
+        unique_gas_payment_account_pubkey,
+    )?;
+
+    let instruction = SolanaInstruction {
+        program_id,
+        data: ixn.try_to_vec()?,
+        accounts,
+    };
+
+    Ok((instruction, gas_payment_account))
+}
+
+/// Gets the accounts to pass to the Mailbox's `OutboxDispatch` instruction
+/// to use this program as a post-dispatch hook, starting with the program itself.
+/// Also returns the gas payment PDA.
+pub fn post_dispatch_hook_account_metas(
+    program_id: Pubkey,
+    payer: Pubkey,
+    igp: Pubkey,
+    overhead_igp: Option<Pubkey>,
+    unique_gas_payment_account_pubkey: Pubkey,
+) -> Result<(Vec<AccountMeta>, Pubkey), ProgramError> {
+    let (accounts, gas_payment_account) = pay_for_gas_account_metas(
+        program_id,
+        payer,
+        igp,
+        overhead_igp,
+        unique_gas_payment_account_pubkey,
+    )?;
+
+    Ok((
+        std::iter::once(AccountMeta::new_readonly(program_id, false))
+            .chain(accounts)
+            .collect(),
+        gas_payment_account,
+    ))
+}
+
+fn pay_for_gas_account_metas(
+    program_id: Pubkey,
+    payer: Pubkey,
+    igp: Pubkey,
+    overhead_igp: Option<Pubkey>,
+    unique_gas_payment_account_pubkey: Pubkey,
+) -> Result<(Vec<AccountMeta>, Pubkey), ProgramError> {
     let (program_data_account, _program_data_bump) =
         Pubkey::try_find_program_address(igp_program_data_pda_seeds!(), &program_id)
             .ok_or(ProgramError::InvalidSeeds)?;
@@ -282,12 +338,6 @@ pub fn pay_for_gas_instruction(
     )
     .ok_or(ProgramError::InvalidSeeds)?;
 
-    let ixn = Instruction::PayForGas(PayForGas {
-        message_id,
-        destination_domain,
-        gas_amount,
-    });
-
     // Accounts:
     // 0. `[executable]` The system program.
     // 1. `[signer]` The payer.
@@ -308,13 +358,7 @@ pub fn pay_for_gas_instruction(
         accounts.push(AccountMeta::new_readonly(overhead_igp, false));
     }
 
-    let instruction = SolanaInstruction {
-        program_id,
-        data: ixn.try_to_vec()?,
-        accounts,
-    };
-
-    Ok((instruction, gas_payment_account))
+    Ok((accounts, gas_payment_account))
 }
 
 /// Gets an instruction to change an IGP or Overhead IGP
@@ -1,6 +1,7 @@
 //! Program state processor.
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use hyperlane_core::{Decode, HyperlaneMessage};
 use std::collections::HashMap;
 
 #[cfg(not(feature = "no-entrypoint"))]
@@ -23,6 +24,9 @@ use account_utils::{
     create_pda_account, verify_account_uninitialized, verify_rent_exempt, AccountData,
     DiscriminatorPrefixed, SizedData,
 };
+use hyperlane_sealevel_post_dispatch_hook_interface::{
+    PostDispatchHookInstruction, PostDispatchInstruction, StandardHookMetadata,
+};
 use serializable_account_meta::SimulationReturnData;
 
 use crate::{
@@ -41,12 +45,37 @@ use crate::{
 #[cfg(not(feature = "no-entrypoint"))]
 entrypoint!(process_instruction);
 
+/// The gas amount paid for when used as a post-dispatch hook if the
+/// hook metadata doesn't specify a gas limit.
+pub const DEFAULT_POST_DISPATCH_GAS_AMOUNT: u64 = 50_000;
+
 /// Entrypoint for the IGP program.
 pub fn process_instruction(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     instruction_data: &[u8],
 ) -> ProgramResult {
+    // First, try to decode the instruction as a post-dispatch hook
+    // interface supported function based off the discriminator.
+    if let Ok(hook_instruction) = PostDispatchHookInstruction::decode(instruction_data) {
+        return match hook_instruction {
+            PostDispatchHookInstruction::PostDispatch(post_dispatch) => {
+                pay_for_gas(program_id, accounts, post_dispatch_payment(post_dispatch)?)
+            }
+            PostDispatchHookInstruction::QuoteDispatch(quote_dispatch) => {
+                let payment = post_dispatch_payment(quote_dispatch)?;
+                quote_gas_payment(
+                    program_id,
+                    accounts,
+                    QuoteGasPayment {
+                        destination_domain: payment.destination_domain,
+                        gas_amount: payment.gas_amount,
+                    },
+                )
+            }
+        };
+    }
+
     match IgpInstruction::try_from_slice(instruction_data)? {
         IgpInstruction::Init => {
             init(program_id, accounts)?;
@@ -404,6 +433,27 @@ fn pay_for_gas(program_id: &Pubkey, accounts: &[AccountInfo], payment: PayForGas
     Ok(())
 }
 
+/// Gets the gas payment for a dispatched message when used as a post-dispatch hook.
+/// The gas amount is the gas limit in the `StandardHookMetadata`, or
+/// `DEFAULT_POST_DISPATCH_GAS_AMOUNT` if none is specified.
+///
+/// The `PostDispatch` instruction expects the same accounts as the `PayForGas`
+/// instruction, and the `QuoteDispatch` instruction expects the same accounts
+/// as the `QuoteGasPayment` instruction.
+fn post_dispatch_payment(instruction: PostDispatchInstruction) -> Result<PayForGas, ProgramError> {
+    let message = HyperlaneMessage::read_from(&mut &instruction.message[..])
+        .map_err(|_| ProgramError::InvalidArgument)?;
+    let metadata = StandardHookMetadata::from_bytes(&instruction.metadata)?;
+
+    Ok(PayForGas {
+        message_id: message.id(),
+        destination_domain: message.destination,
+        gas_amount: metadata
+            .gas_limit
+            .unwrap_or(DEFAULT_POST_DISPATCH_GAS_AMOUNT),
+    })
+}
+
 /// Quotes the required payment for a given gas amount and destination domain.
 ///
 /// Accounts:
@@ -35,7 +35,7 @@ use hyperlane_sealevel_token_collateral::{
     plugin::CollateralPlugin, processor::process_instruction,
 };
 use hyperlane_sealevel_token_lib::{
-    accounts::{convert_decimals, HyperlaneToken, HyperlaneTokenAccount},
+    accounts::{convert_decimals, HyperlaneToken, HyperlaneTokenAccount, RateLimits},
     hyperlane_token_pda_seeds,
     instruction::{Init, Instruction as HyperlaneTokenInstruction, TransferRemote},
     message::TokenMessage,
@@ -495,6 +495,7 @@ async fn test_initialize() {
                 escrow_bump: hyperlane_token_accounts.escrow_bump,
                 ata_payer_bump: hyperlane_token_accounts.ata_payer_bump,
             },
+            rate_limits: RateLimits::default(),
         }),
     );
 
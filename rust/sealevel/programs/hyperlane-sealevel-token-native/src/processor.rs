@@ -9,7 +9,7 @@ use hyperlane_sealevel_message_recipient_interface::{
     HandleInstruction, MessageRecipientInstruction,
 };
 use hyperlane_sealevel_token_lib::{
-    instruction::{Init, Instruction as TokenIxn, TransferRemote},
+    instruction::{Init, Instruction as TokenIxn, RateLimitConfig, TransferRemote},
     processor::HyperlaneSealevelToken,
 };
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
@@ -79,6 +79,9 @@ pub fn process_instruction(
         TokenIxn::SetInterchainGasPaymaster(new_igp) => {
             set_interchain_gas_paymaster(program_id, accounts, new_igp)
         }
+        TokenIxn::SetRateLimitConfigs(configs) => {
+            set_rate_limit_configs(program_id, accounts, configs)
+        }
     }
     .map_err(|err| {
         msg!("{}", err);
@@ -105,14 +108,15 @@ fn initialize(program_id: &Pubkey, accounts: &[AccountInfo], init: Init) -> Prog
 /// Accounts:
 /// 0.   `[executable]` The system program.
 /// 1.   `[executable]` The spl_noop program.
-/// 2.   `[]` The token PDA account.
+/// 2.   `[writeable]` The token PDA account. Only required to be writeable
+///      if the destination is rate limited.
 /// 3.   `[executable]` The mailbox program.
 /// 4.   `[writeable]` The mailbox outbox account.
 /// 5.   `[]` Message dispatch authority.
 /// 6.   `[signer]` The token sender and mailbox payer.
 /// 7.   `[signer]` Unique message / gas payment account.
 /// 8.   `[writeable]` Message storage PDA.
-///      ---- If using an IGP ----
+///      ---- If using an IGP and the Mailbox has no post-dispatch hooks ----
 /// 9.   `[executable]` The IGP program.
 /// 10.  `[writeable]` The IGP program data.
 /// 11.  `[writeable]` Gas payment PDA.
@@ -121,6 +125,9 @@ fn initialize(program_id: &Pubkey, accounts: &[AccountInfo], init: Init) -> Prog
 ///      ---- End if ----
 /// 14.  `[executable]` The system program.
 /// 15.  `[writeable]` The native token collateral PDA account.
+///      ---- If the Mailbox has post-dispatch hooks ----
+/// 16.. `[??..??]` The accounts of the Mailbox's post-dispatch hooks, e.g. an IGP hook.
+///      ---- End if ----
 fn transfer_remote(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -132,7 +139,8 @@ fn transfer_remote(
 /// Accounts:
 /// 0.   `[signer]` Mailbox processor authority specific to this program.
 /// 1.   `[executable]` system_program
-/// 2.   `[]` hyperlane_token storage
+/// 2.   `[writeable]` hyperlane_token storage. Only required to be writeable
+///      if the origin is rate limited.
 /// 3.   `[writeable]` recipient wallet address
 /// 4.   `[executable]` The system program.
 /// 5.   `[writeable]` The native token collateral PDA account.
@@ -260,3 +268,17 @@ fn set_interchain_gas_paymaster(
         program_id, accounts, new_igp,
     )
 }
+
+/// Lets the owner set the rate limit configs.
+///
+/// Accounts:
+/// 0. `[executable]` The system program.
+/// 1. `[writeable]` The token PDA account.
+/// 2. `[signer]` The access control owner.
+fn set_rate_limit_configs(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    configs: Vec<RateLimitConfig>,
+) -> ProgramResult {
+    HyperlaneSealevelToken::<NativePlugin>::set_rate_limit_configs(program_id, accounts, configs)
+}
@@ -17,9 +17,11 @@ use hyperlane_sealevel_connection_client::{
 use hyperlane_sealevel_igp::{
     accounts::{GasPaymentAccount, GasPaymentData, InterchainGasPaymasterType},
     igp_gas_payment_pda_seeds,
+    instruction::post_dispatch_hook_account_metas,
 };
 use hyperlane_sealevel_mailbox::{
     accounts::{DispatchedMessage, DispatchedMessageAccount},
+    instruction::set_default_hook_instruction,
     mailbox_dispatched_message_pda_seeds, mailbox_message_dispatch_authority_pda_seeds,
     mailbox_process_authority_pda_seeds,
     protocol_fee::ProtocolFee,
@@ -28,7 +30,7 @@ use hyperlane_sealevel_message_recipient_interface::{
     HandleInstruction, MessageRecipientInstruction,
 };
 use hyperlane_sealevel_token_lib::{
-    accounts::{convert_decimals, HyperlaneToken, HyperlaneTokenAccount},
+    accounts::{convert_decimals, HyperlaneToken, HyperlaneTokenAccount, RateLimits},
     hyperlane_token_pda_seeds,
     instruction::{Init, Instruction as HyperlaneTokenInstruction, TransferRemote},
     message::TokenMessage,
@@ -257,6 +259,23 @@ async fn set_destination_gas_config(
     Ok(())
 }
 
+async fn set_mailbox_default_hook(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    hook: Pubkey,
+) -> Result<(), BanksClientError> {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[set_default_hook_instruction(mailbox_id(), payer.pubkey(), Some(hook)).unwrap()],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_initialize() {
     let program_id = hyperlane_sealevel_token_native_id();
@@ -315,6 +334,7 @@ async fn test_initialize() {
             plugin_data: NativePlugin {
                 native_collateral_bump: hyperlane_token_accounts.native_collateral_bump,
             },
+            rate_limits: RateLimits::default(),
         }),
     );
 
@@ -570,6 +590,228 @@ async fn test_transfer_remote() {
     );
 }
 
+#[tokio::test]
+async fn test_transfer_remote_with_mailbox_default_hook() {
+    let program_id = hyperlane_sealevel_token_native_id();
+    let mailbox_program_id = mailbox_id();
+
+    let (mut banks_client, payer) = setup_client().await;
+
+    let mailbox_accounts = initialize_mailbox(
+        &mut banks_client,
+        &mailbox_program_id,
+        &payer,
+        LOCAL_DOMAIN,
+        ONE_SOL_IN_LAMPORTS,
+        ProtocolFee::default(),
+    )
+    .await
+    .unwrap();
+
+    let igp_accounts =
+        initialize_igp_accounts(&mut banks_client, &igp_program_id(), &payer, REMOTE_DOMAIN)
+            .await
+            .unwrap();
+
+    // Pay for gas through the IGP as the Mailbox's default hook.
+    set_mailbox_default_hook(&mut banks_client, &payer, igp_accounts.program)
+        .await
+        .unwrap();
+
+    let hyperlane_token_accounts =
+        initialize_hyperlane_token(&program_id, &mut banks_client, &payer, Some(&igp_accounts))
+            .await
+            .unwrap();
+
+    // Enroll the remote router
+    let remote_router = H256::random();
+    enroll_remote_router(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &hyperlane_token_accounts.token,
+        REMOTE_DOMAIN,
+        remote_router,
+    )
+    .await
+    .unwrap();
+
+    // Send 100 SOL for the token sender to start with.
+    let token_sender =
+        new_funded_keypair(&mut banks_client, &payer, 100 * ONE_SOL_IN_LAMPORTS).await;
+    let token_sender_pubkey = token_sender.pubkey();
+
+    // Call transfer_remote
+    let unique_message_account_keypair = Keypair::new();
+    let (dispatched_message_key, _dispatched_message_bump) = Pubkey::find_program_address(
+        mailbox_dispatched_message_pda_seeds!(&unique_message_account_keypair.pubkey()),
+        &mailbox_program_id,
+    );
+    let (igp_hook_account_metas, gas_payment_pda_key) = post_dispatch_hook_account_metas(
+        igp_accounts.program,
+        token_sender_pubkey,
+        igp_accounts.igp,
+        Some(igp_accounts.overhead_igp),
+        unique_message_account_keypair.pubkey(),
+    )
+    .unwrap();
+
+    let remote_token_recipient = H256::random();
+    // Transfer 69 tokens.
+    let transfer_amount = 69 * ONE_SOL_IN_LAMPORTS;
+    let remote_transfer_amount =
+        convert_decimals(transfer_amount.into(), LOCAL_DECIMALS, REMOTE_DECIMALS).unwrap();
+
+    let sender_balance_before = banks_client.get_balance(token_sender_pubkey).await.unwrap();
+    let native_collateral_account_lamports_before = banks_client
+        .get_balance(hyperlane_token_accounts.native_collateral)
+        .await
+        .unwrap();
+
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(
+            program_id,
+            &HyperlaneTokenInstruction::TransferRemote(TransferRemote {
+                destination_domain: REMOTE_DOMAIN,
+                recipient: remote_token_recipient,
+                amount_or_id: transfer_amount.into(),
+            })
+            .encode()
+            .unwrap(),
+            // 0.   `[executable]` The system program.
+            // 1.   `[executable]` The spl_noop program.
+            // 2.   `[]` The token PDA account.
+            // 3.   `[executable]` The mailbox program.
+            // 4.   `[writeable]` The mailbox outbox account.
+            // 5.   `[]` Message dispatch authority.
+            // 6.   `[signer]` The token sender and mailbox payer.
+            // 7.   `[signer]` Unique message / gas payment account.
+            // 8.   `[writeable]` Message storage PDA.
+            // 9.   `[executable]` The system program.
+            // 10.  `[writeable]` The native token collateral PDA account.
+            // 11.. `[??..??]` The IGP default hook program and its accounts.
+            [
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(spl_noop::id(), false),
+                AccountMeta::new_readonly(hyperlane_token_accounts.token, false),
+                AccountMeta::new_readonly(mailbox_accounts.program, false),
+                AccountMeta::new(mailbox_accounts.outbox, false),
+                AccountMeta::new_readonly(hyperlane_token_accounts.dispatch_authority, false),
+                AccountMeta::new_readonly(token_sender_pubkey, true),
+                AccountMeta::new_readonly(unique_message_account_keypair.pubkey(), true),
+                AccountMeta::new(dispatched_message_key, false),
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new(hyperlane_token_accounts.native_collateral, false),
+            ]
+            .into_iter()
+            .chain(igp_hook_account_metas)
+            .collect(),
+        )],
+        Some(&token_sender_pubkey),
+        &[&token_sender, &unique_message_account_keypair],
+        recent_blockhash,
+    );
+
+    let transaction_fee = banks_client
+        .get_fee_for_message_with_commitment_and_context(
+            Context::current(),
+            CommitmentLevel::Processed,
+            transaction.message.clone(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    let tx_signature = transaction.signatures[0];
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // The transaction fee doesn't seem to be entirely accurate -
+    // this may be due to a mismatch between the SDK and the actual
+    // transaction fee calculation.
+    // For now, we'll just check that the sender's balance roughly correct.
+    let sender_balance_after = banks_client.get_balance(token_sender_pubkey).await.unwrap();
+    let expected_balance_after = sender_balance_before - transfer_amount - transaction_fee;
+    // Allow 0.005 SOL of extra transaction fees
+    assert!(
+        sender_balance_after >= expected_balance_after - 5000000
+            && sender_balance_after <= expected_balance_after
+    );
+
+    // And that the native collateral account's balance is 69 tokens.
+    assert_lamports(
+        &mut banks_client,
+        &hyperlane_token_accounts.native_collateral,
+        native_collateral_account_lamports_before + transfer_amount,
+    )
+    .await;
+
+    // And let's take a look at the dispatched message account data to verify the message looks right.
+    let dispatched_message_account_data = banks_client
+        .get_account(dispatched_message_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let dispatched_message =
+        DispatchedMessageAccount::fetch(&mut &dispatched_message_account_data[..])
+            .unwrap()
+            .into_inner();
+
+    let transfer_remote_tx_status = banks_client
+        .get_transaction_status(tx_signature)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let message = HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin: LOCAL_DOMAIN,
+        sender: program_id.to_bytes().into(),
+        destination: REMOTE_DOMAIN,
+        recipient: remote_router,
+        // Expect the remote_transfer_amount to be in the message.
+        body: TokenMessage::new(remote_token_recipient, remote_transfer_amount, vec![]).to_vec(),
+    };
+
+    assert_eq!(
+        dispatched_message,
+        Box::new(DispatchedMessage::new(
+            message.nonce,
+            transfer_remote_tx_status.slot,
+            unique_message_account_keypair.pubkey(),
+            message.to_vec(),
+        )),
+    );
+
+    // And let's also look at the gas payment account to verify the gas payment looks right.
+    let gas_payment_account_data = banks_client
+        .get_account(gas_payment_pda_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let gas_payment = GasPaymentAccount::fetch(&mut &gas_payment_account_data[..])
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(
+        *gas_payment,
+        GasPaymentData {
+            sequence_number: 0,
+            igp: igp_accounts.igp,
+            destination_domain: REMOTE_DOMAIN,
+            message_id: message.id(),
+            gas_amount: REMOTE_GAS_AMOUNT,
+            unique_gas_payment_pubkey: unique_message_account_keypair.pubkey(),
+            slot: transfer_remote_tx_status.slot,
+            payment: REMOTE_GAS_AMOUNT
+        }
+        .into(),
+    );
+}
+
 async fn transfer_from_remote(
     initial_native_collateral_balance: u64,
     remote_transfer_amount: U256,
@@ -658,6 +900,7 @@ async fn transfer_from_remote(
 }
 
 // Tests when the SPL token is the non-2022 version
+
 #[tokio::test]
 async fn test_transfer_from_success() {
     let initial_native_collateral_balance = 100 * 10u64.pow(LOCAL_DECIMALS_U32);
@@ -9,7 +9,7 @@ use hyperlane_sealevel_message_recipient_interface::{
     HandleInstruction, MessageRecipientInstruction,
 };
 use hyperlane_sealevel_token_lib::{
-    instruction::{Init, Instruction as TokenIxn, TransferRemote},
+    instruction::{Init, Instruction as TokenIxn, RateLimitConfig, TransferRemote},
     processor::HyperlaneSealevelToken,
 };
 use solana_program::{account_info::AccountInfo, entrypoint::ProgramResult, msg, pubkey::Pubkey};
@@ -76,6 +76,9 @@ pub fn process_instruction(
         TokenIxn::SetInterchainGasPaymaster(new_igp) => {
             set_interchain_gas_paymaster(program_id, accounts, new_igp)
         }
+        TokenIxn::SetRateLimitConfigs(configs) => {
+            set_rate_limit_configs(program_id, accounts, configs)
+        }
         TokenIxn::TransferOwnership(new_owner) => {
             transfer_ownership(program_id, accounts, new_owner)
         }
@@ -106,14 +109,15 @@ fn initialize(program_id: &Pubkey, accounts: &[AccountInfo], init: Init) -> Prog
 /// Accounts:
 /// 0.  `[executable]` The system program.
 /// 1.  `[executable]` The spl_noop program.
-/// 2.  `[]` The token PDA account.
+/// 2.  `[writeable]` The token PDA account. Only required to be writeable
+///     if the destination is rate limited.
 /// 3.  `[executable]` The mailbox program.
 /// 4.  `[writeable]` The mailbox outbox account.
 /// 5.  `[]` Message dispatch authority.
 /// 6.  `[signer]` The token sender and mailbox payer.
 /// 7.  `[signer]` Unique message / gas payment account.
 /// 8.  `[writeable]` Message storage PDA.
-///     ---- If using an IGP ----
+///     ---- If using an IGP and the Mailbox has no post-dispatch hooks ----
 /// 9.  `[executable]` The IGP program.
 /// 10. `[writeable]` The IGP program data.
 /// 11. `[writeable]` Gas payment PDA.
@@ -124,6 +128,9 @@ fn initialize(program_id: &Pubkey, accounts: &[AccountInfo], init: Init) -> Prog
 /// 15. `[executable]` The spl_token_2022 program.
 /// 16. `[writeable]` The mint / mint authority PDA account.
 /// 17. `[writeable]` The token sender's associated token account, from which tokens will be burned.
+///     ---- If the Mailbox has post-dispatch hooks ----
+/// 18.. `[??..??]` The accounts of the Mailbox's post-dispatch hooks, e.g. an IGP hook.
+///      ---- End if ----
 fn transfer_remote(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -135,7 +142,8 @@ fn transfer_remote(
 // Accounts:
 // 0. `[signer]` Mailbox process authority specific to this program.
 // 1. `[executable]` system_program
-// 2. `[]` hyperlane_token storage
+// 2. `[writeable]` hyperlane_token storage. Only required to be writeable
+//    if the origin is rate limited.
 // 3. `[]` recipient wallet address
 // 4. `[executable]` SPL token 2022 program
 // 5. `[executable]` SPL associated token account
@@ -263,3 +271,17 @@ fn set_interchain_gas_paymaster(
         program_id, accounts, new_igp,
     )
 }
+
+/// Lets the owner set the rate limit configs.
+///
+/// Accounts:
+/// 0. `[executable]` The system program.
+/// 1. `[writeable]` The token PDA account.
+/// 2. `[signer]` The access control owner.
+fn set_rate_limit_configs(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    configs: Vec<RateLimitConfig>,
+) -> ProgramResult {
+    HyperlaneSealevelToken::<SyntheticPlugin>::set_rate_limit_configs(program_id, accounts, configs)
+}
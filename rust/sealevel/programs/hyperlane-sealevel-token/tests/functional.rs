@@ -10,9 +10,11 @@ use hyperlane_sealevel_connection_client::{
 use hyperlane_sealevel_igp::{
     accounts::{GasPaymentAccount, GasPaymentData, InterchainGasPaymasterType},
     igp_gas_payment_pda_seeds,
+    instruction::post_dispatch_hook_account_metas,
 };
 use hyperlane_sealevel_mailbox::{
     accounts::{DispatchedMessage, DispatchedMessageAccount},
+    instruction::set_default_hook_instruction,
     mailbox_dispatched_message_pda_seeds, mailbox_message_dispatch_authority_pda_seeds,
     mailbox_process_authority_pda_seeds,
     protocol_fee::ProtocolFee,
@@ -25,9 +27,13 @@ use hyperlane_sealevel_token::{
     processor::process_instruction,
 };
 use hyperlane_sealevel_token_lib::{
-    accounts::{convert_decimals, HyperlaneToken, HyperlaneTokenAccount},
+    accounts::{convert_decimals, HyperlaneToken, HyperlaneTokenAccount, RateLimits},
+    error::Error as HyperlaneTokenError,
     hyperlane_token_pda_seeds,
-    instruction::{Init, Instruction as HyperlaneTokenInstruction, TransferRemote},
+    instruction::{
+        set_rate_limit_configs_instruction, Init, Instruction as HyperlaneTokenInstruction,
+        RateLimitConfig, RateLimitParams, TransferRemote,
+    },
     message::TokenMessage,
 };
 use hyperlane_test_utils::{
@@ -287,6 +293,47 @@ async fn set_destination_gas_config(
     Ok(())
 }
 
+async fn set_rate_limit_config(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    domain: u32,
+    rate_limit: Option<RateLimitParams>,
+) -> Result<(), BanksClientError> {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[set_rate_limit_configs_instruction(
+            *program_id,
+            payer.pubkey(),
+            vec![RateLimitConfig { domain, rate_limit }],
+        )
+        .unwrap()],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+
+    Ok(())
+}
+
+async fn set_mailbox_default_hook(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    hook: Pubkey,
+) -> Result<(), BanksClientError> {
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[set_default_hook_instruction(mailbox_id(), payer.pubkey(), Some(hook)).unwrap()],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_initialize() {
     let program_id = hyperlane_sealevel_token_id();
@@ -347,6 +394,7 @@ async fn test_initialize() {
                 mint_bump: hyperlane_token_accounts.mint_bump,
                 ata_payer_bump: hyperlane_token_accounts.ata_payer_bump,
             },
+            rate_limits: RateLimits::default(),
         }),
     );
 
@@ -849,6 +897,210 @@ async fn test_transfer_remote() {
     );
 }
 
+#[tokio::test]
+async fn test_transfer_remote_with_mailbox_default_hook() {
+    let program_id = hyperlane_sealevel_token_id();
+    let mailbox_program_id = mailbox_id();
+
+    let token_sender = Keypair::new();
+    let token_sender_pubkey = token_sender.pubkey();
+
+    // Mint 100 tokens to the token sender's ATA.
+    // We do this by just faking a transfer from remote.
+    let sender_initial_balance = 100 * 10u64.pow(LOCAL_DECIMALS_U32);
+    let (
+        mut banks_client,
+        payer,
+        mailbox_accounts,
+        igp_accounts,
+        hyperlane_token_accounts,
+        token_sender_ata,
+    ) = transfer_from_remote(
+        // The amount of remote tokens is expected
+        convert_decimals(
+            sender_initial_balance.into(),
+            LOCAL_DECIMALS,
+            REMOTE_DECIMALS,
+        )
+        .unwrap(),
+        None,
+        None,
+        Some(token_sender_pubkey),
+    )
+    .await
+    .unwrap();
+
+    // Give the token_sender a SOL balance to pay tx fees.
+    transfer_lamports(
+        &mut banks_client,
+        &payer,
+        &token_sender_pubkey,
+        ONE_SOL_IN_LAMPORTS,
+    )
+    .await;
+
+    // Pay for gas through the IGP as the Mailbox's default hook.
+    set_mailbox_default_hook(&mut banks_client, &payer, igp_accounts.program)
+        .await
+        .unwrap();
+
+    // Enroll the remote router
+    let remote_router = H256::random();
+    enroll_remote_router(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &hyperlane_token_accounts.token,
+        REMOTE_DOMAIN,
+        remote_router,
+    )
+    .await
+    .unwrap();
+
+    // Call transfer_remote
+    let unique_message_account_keypair = Keypair::new();
+    let (dispatched_message_key, _dispatched_message_bump) = Pubkey::find_program_address(
+        mailbox_dispatched_message_pda_seeds!(&unique_message_account_keypair.pubkey()),
+        &mailbox_program_id,
+    );
+    let (igp_hook_account_metas, gas_payment_pda_key) = post_dispatch_hook_account_metas(
+        igp_accounts.program,
+        token_sender_pubkey,
+        igp_accounts.igp,
+        Some(igp_accounts.overhead_igp),
+        unique_message_account_keypair.pubkey(),
+    )
+    .unwrap();
+
+    let remote_token_recipient = H256::random();
+    // Transfer 69 tokens.
+    let transfer_amount = 69 * 10u64.pow(LOCAL_DECIMALS_U32);
+    let remote_transfer_amount =
+        convert_decimals(transfer_amount.into(), LOCAL_DECIMALS, REMOTE_DECIMALS).unwrap();
+
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(
+            program_id,
+            &HyperlaneTokenInstruction::TransferRemote(TransferRemote {
+                destination_domain: REMOTE_DOMAIN,
+                recipient: remote_token_recipient,
+                amount_or_id: transfer_amount.into(),
+            })
+            .encode()
+            .unwrap(),
+            // 0.  `[executable]` The system program.
+            // 1.  `[executable]` The spl_noop program.
+            // 2.  `[]` The token PDA account.
+            // 3.  `[executable]` The mailbox program.
+            // 4.  `[writeable]` The mailbox outbox account.
+            // 5.  `[]` Message dispatch authority.
+            // 6.  `[signer]` The token sender and mailbox payer.
+            // 7.  `[signer]` Unique message account.
+            // 8.  `[writeable]` Message storage PDA.
+            // 9.  `[executable]` The spl_token_2022 program.
+            // 10. `[writeable]` The mint / mint authority PDA account.
+            // 11. `[writeable]` The token sender's associated token account, from which tokens will be burned.
+            // 12.. `[??..??]` The IGP default hook program and its accounts.
+            [
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(spl_noop::id(), false),
+                AccountMeta::new_readonly(hyperlane_token_accounts.token, false),
+                AccountMeta::new_readonly(mailbox_accounts.program, false),
+                AccountMeta::new(mailbox_accounts.outbox, false),
+                AccountMeta::new_readonly(hyperlane_token_accounts.dispatch_authority, false),
+                AccountMeta::new_readonly(token_sender_pubkey, true),
+                AccountMeta::new_readonly(unique_message_account_keypair.pubkey(), true),
+                AccountMeta::new(dispatched_message_key, false),
+                AccountMeta::new_readonly(spl_token_2022::id(), false),
+                AccountMeta::new(hyperlane_token_accounts.mint, false),
+                AccountMeta::new(token_sender_ata, false),
+            ]
+            .into_iter()
+            .chain(igp_hook_account_metas)
+            .collect(),
+        )],
+        Some(&token_sender_pubkey),
+        &[&token_sender, &unique_message_account_keypair],
+        recent_blockhash,
+    );
+    let tx_signature = transaction.signatures[0];
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // Verify the token sender's ATA balance went down
+    assert_token_balance(
+        &mut banks_client,
+        &token_sender_ata,
+        sender_initial_balance - transfer_amount,
+    )
+    .await;
+
+    // And let's take a look at the dispatched message account data to verify the message looks right.
+    let dispatched_message_account_data = banks_client
+        .get_account(dispatched_message_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let dispatched_message =
+        DispatchedMessageAccount::fetch(&mut &dispatched_message_account_data[..])
+            .unwrap()
+            .into_inner();
+
+    let transfer_remote_tx_status = banks_client
+        .get_transaction_status(tx_signature)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let message = HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin: LOCAL_DOMAIN,
+        sender: program_id.to_bytes().into(),
+        destination: REMOTE_DOMAIN,
+        recipient: remote_router,
+        // Expect the remote_transfer_amount to be in the message.
+        body: TokenMessage::new(remote_token_recipient, remote_transfer_amount, vec![]).to_vec(),
+    };
+
+    assert_eq!(
+        dispatched_message,
+        Box::new(DispatchedMessage::new(
+            message.nonce,
+            transfer_remote_tx_status.slot,
+            unique_message_account_keypair.pubkey(),
+            message.to_vec(),
+        )),
+    );
+
+    // And let's also look at the gas payment account to verify the gas payment looks right.
+    let gas_payment_account_data = banks_client
+        .get_account(gas_payment_pda_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let gas_payment = GasPaymentAccount::fetch(&mut &gas_payment_account_data[..])
+        .unwrap()
+        .into_inner();
+
+    assert_eq!(
+        *gas_payment,
+        GasPaymentData {
+            sequence_number: 0,
+            igp: igp_accounts.igp,
+            destination_domain: REMOTE_DOMAIN,
+            message_id: message.id(),
+            gas_amount: REMOTE_GAS_AMOUNT,
+            unique_gas_payment_pubkey: unique_message_account_keypair.pubkey(),
+            slot: transfer_remote_tx_status.slot,
+            payment: REMOTE_GAS_AMOUNT
+        }
+        .into(),
+    );
+}
+
 #[tokio::test]
 async fn test_enroll_remote_router() {
     let program_id = hyperlane_sealevel_token_id();
@@ -1355,3 +1607,421 @@ async fn test_set_interchain_gas_paymaster_errors_if_owner_not_signer() {
         TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature),
     );
 }
+
+#[allow(clippy::too_many_arguments)]
+async fn transfer_remote(
+    banks_client: &mut BanksClient,
+    program_id: &Pubkey,
+    mailbox_accounts: &MailboxAccounts,
+    igp_accounts: &IgpAccounts,
+    hyperlane_token_accounts: &HyperlaneTokenAccounts,
+    token_sender: &Keypair,
+    token_sender_ata: &Pubkey,
+    amount: u64,
+) -> Result<(), BanksClientError> {
+    let unique_message_account_keypair = Keypair::new();
+    let (dispatched_message_key, _dispatched_message_bump) = Pubkey::find_program_address(
+        mailbox_dispatched_message_pda_seeds!(&unique_message_account_keypair.pubkey()),
+        &mailbox_accounts.program,
+    );
+    let (gas_payment_pda_key, _gas_payment_pda_bump) = Pubkey::find_program_address(
+        igp_gas_payment_pda_seeds!(&unique_message_account_keypair.pubkey()),
+        &igp_accounts.program,
+    );
+
+    let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_bytes(
+            *program_id,
+            &HyperlaneTokenInstruction::TransferRemote(TransferRemote {
+                destination_domain: REMOTE_DOMAIN,
+                recipient: H256::random(),
+                amount_or_id: amount.into(),
+            })
+            .encode()
+            .unwrap(),
+            vec![
+                AccountMeta::new_readonly(solana_program::system_program::id(), false),
+                AccountMeta::new_readonly(spl_noop::id(), false),
+                AccountMeta::new(hyperlane_token_accounts.token, false),
+                AccountMeta::new_readonly(mailbox_accounts.program, false),
+                AccountMeta::new(mailbox_accounts.outbox, false),
+                AccountMeta::new_readonly(hyperlane_token_accounts.dispatch_authority, false),
+                AccountMeta::new_readonly(token_sender.pubkey(), true),
+                AccountMeta::new_readonly(unique_message_account_keypair.pubkey(), true),
+                AccountMeta::new(dispatched_message_key, false),
+                AccountMeta::new_readonly(igp_accounts.program, false),
+                AccountMeta::new(igp_accounts.program_data, false),
+                AccountMeta::new(gas_payment_pda_key, false),
+                AccountMeta::new_readonly(igp_accounts.overhead_igp, false),
+                AccountMeta::new(igp_accounts.igp, false),
+                AccountMeta::new_readonly(spl_token_2022::id(), false),
+                AccountMeta::new(hyperlane_token_accounts.mint, false),
+                AccountMeta::new(*token_sender_ata, false),
+            ],
+        )],
+        Some(&token_sender.pubkey()),
+        &[token_sender, &unique_message_account_keypair],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(transaction).await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_rate_limit_configs() {
+    let program_id = hyperlane_sealevel_token_id();
+
+    let (mut banks_client, payer) = setup_client().await;
+
+    let hyperlane_token_accounts =
+        initialize_hyperlane_token(&program_id, &mut banks_client, &payer, None)
+            .await
+            .unwrap();
+
+    // Set the rate limit
+    let capacity = 100 * 10u64.pow(LOCAL_DECIMALS_U32);
+    let window = 86400;
+    set_rate_limit_config(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        REMOTE_DOMAIN,
+        Some(RateLimitParams { capacity, window }),
+    )
+    .await
+    .unwrap();
+
+    // Verify the rate limit was set, with the full capacity available.
+    let token_account_data = banks_client
+        .get_account(hyperlane_token_accounts.token)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let token = HyperlaneTokenAccount::<SyntheticPlugin>::fetch(&mut &token_account_data[..])
+        .unwrap()
+        .into_inner();
+    let rate_limit = token.rate_limits.get(REMOTE_DOMAIN).unwrap();
+    assert_eq!(rate_limit.capacity, capacity);
+    assert_eq!(rate_limit.window, window);
+    assert_eq!(rate_limit.outbound.level, capacity);
+    assert_eq!(rate_limit.inbound.level, capacity);
+
+    // Remove the rate limit
+    set_rate_limit_config(&mut banks_client, &program_id, &payer, REMOTE_DOMAIN, None)
+        .await
+        .unwrap();
+
+    // Verify the rate limit was removed.
+    let token_account_data = banks_client
+        .get_account(hyperlane_token_accounts.token)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let token = HyperlaneTokenAccount::<SyntheticPlugin>::fetch(&mut &token_account_data[..])
+        .unwrap()
+        .into_inner();
+    assert_eq!(token.rate_limits, RateLimits(Some(HashMap::new())));
+}
+
+#[tokio::test]
+async fn test_set_rate_limit_configs_errors_if_not_signed_by_owner() {
+    let program_id = hyperlane_sealevel_token_id();
+
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize_hyperlane_token(&program_id, &mut banks_client, &payer, None)
+        .await
+        .unwrap();
+
+    let non_owner = new_funded_keypair(&mut banks_client, &payer, ONE_SOL_IN_LAMPORTS).await;
+
+    let result = set_rate_limit_config(
+        &mut banks_client,
+        &program_id,
+        &non_owner,
+        REMOTE_DOMAIN,
+        Some(RateLimitParams {
+            capacity: 100,
+            window: 86400,
+        }),
+    )
+    .await;
+
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+}
+
+#[tokio::test]
+async fn test_set_rate_limit_configs_errors_if_window_is_zero() {
+    let program_id = hyperlane_sealevel_token_id();
+
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize_hyperlane_token(&program_id, &mut banks_client, &payer, None)
+        .await
+        .unwrap();
+
+    let result = set_rate_limit_config(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        REMOTE_DOMAIN,
+        Some(RateLimitParams {
+            capacity: 100,
+            window: 0,
+        }),
+    )
+    .await;
+
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+}
+
+#[tokio::test]
+async fn test_transfer_from_remote_errors_if_rate_limit_exceeded() {
+    let program_id = hyperlane_sealevel_token_id();
+
+    // Start with a balance of 1 token.
+    let recipient_pubkey = Pubkey::new_unique();
+    let initial_balance = 10u64.pow(LOCAL_DECIMALS_U32);
+    let (
+        mut banks_client,
+        payer,
+        mailbox_accounts,
+        _igp_accounts,
+        hyperlane_token_accounts,
+        recipient_associated_token_account,
+    ) = transfer_from_remote(
+        convert_decimals(initial_balance.into(), LOCAL_DECIMALS, REMOTE_DECIMALS).unwrap(),
+        None,
+        None,
+        Some(recipient_pubkey),
+    )
+    .await
+    .unwrap();
+
+    // Enroll a new remote router, so that its address is known.
+    let remote_router = H256::random();
+    enroll_remote_router(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        &hyperlane_token_accounts.token,
+        REMOTE_DOMAIN,
+        remote_router,
+    )
+    .await
+    .unwrap();
+
+    // Rate limit transfers from the remote to 100 tokens per day.
+    let capacity = 100 * 10u64.pow(LOCAL_DECIMALS_U32);
+    set_rate_limit_config(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        REMOTE_DOMAIN,
+        Some(RateLimitParams {
+            capacity,
+            window: 86400,
+        }),
+    )
+    .await
+    .unwrap();
+
+    // Transfer 60 tokens at a time.
+    let local_transfer_amount = 60 * 10u64.pow(LOCAL_DECIMALS_U32);
+    let remote_transfer_amount = convert_decimals(
+        local_transfer_amount.into(),
+        LOCAL_DECIMALS,
+        REMOTE_DECIMALS,
+    )
+    .unwrap();
+    let recipient: H256 = recipient_pubkey.to_bytes().into();
+    let message = |nonce| HyperlaneMessage {
+        version: 3,
+        nonce,
+        origin: REMOTE_DOMAIN,
+        sender: remote_router,
+        destination: LOCAL_DOMAIN,
+        recipient: program_id.to_bytes().into(),
+        body: TokenMessage::new(recipient, remote_transfer_amount, vec![]).to_vec(),
+    };
+
+    // The first transfer is within the rate limit.
+    process(
+        &mut banks_client,
+        &payer,
+        &mailbox_accounts,
+        vec![],
+        &message(1),
+    )
+    .await
+    .unwrap();
+    assert_token_balance(
+        &mut banks_client,
+        &recipient_associated_token_account,
+        initial_balance + local_transfer_amount,
+    )
+    .await;
+
+    // The second exceeds it.
+    let result = process(
+        &mut banks_client,
+        &payer,
+        &mailbox_accounts,
+        vec![],
+        &message(2),
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(HyperlaneTokenError::RateLimitExceeded as u32),
+        ),
+    );
+    assert_token_balance(
+        &mut banks_client,
+        &recipient_associated_token_account,
+        initial_balance + local_transfer_amount,
+    )
+    .await;
+
+    // The inbound level was consumed, leaving the outbound level untouched.
+    let token_account_data = banks_client
+        .get_account(hyperlane_token_accounts.token)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let token = HyperlaneTokenAccount::<SyntheticPlugin>::fetch(&mut &token_account_data[..])
+        .unwrap()
+        .into_inner();
+    let rate_limit = token.rate_limits.get(REMOTE_DOMAIN).unwrap();
+    assert_eq!(rate_limit.inbound.level, capacity - local_transfer_amount);
+    assert_eq!(rate_limit.outbound.level, capacity);
+}
+
+#[tokio::test]
+async fn test_transfer_remote_errors_if_rate_limit_exceeded() {
+    let program_id = hyperlane_sealevel_token_id();
+
+    let token_sender = Keypair::new();
+    let token_sender_pubkey = token_sender.pubkey();
+
+    // Mint 200 tokens to the token sender's ATA.
+    let sender_initial_balance = 200 * 10u64.pow(LOCAL_DECIMALS_U32);
+    let (
+        mut banks_client,
+        payer,
+        mailbox_accounts,
+        igp_accounts,
+        hyperlane_token_accounts,
+        token_sender_ata,
+    ) = transfer_from_remote(
+        convert_decimals(
+            sender_initial_balance.into(),
+            LOCAL_DECIMALS,
+            REMOTE_DECIMALS,
+        )
+        .unwrap(),
+        None,
+        None,
+        Some(token_sender_pubkey),
+    )
+    .await
+    .unwrap();
+
+    // Give the token_sender a SOL balance to pay tx fees.
+    transfer_lamports(
+        &mut banks_client,
+        &payer,
+        &token_sender_pubkey,
+        ONE_SOL_IN_LAMPORTS,
+    )
+    .await;
+
+    // Rate limit transfers to the remote to 100 tokens per day.
+    let capacity = 100 * 10u64.pow(LOCAL_DECIMALS_U32);
+    set_rate_limit_config(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        REMOTE_DOMAIN,
+        Some(RateLimitParams {
+            capacity,
+            window: 86400,
+        }),
+    )
+    .await
+    .unwrap();
+
+    // The first transfer of 60 tokens is within the rate limit.
+    let transfer_amount = 60 * 10u64.pow(LOCAL_DECIMALS_U32);
+    transfer_remote(
+        &mut banks_client,
+        &program_id,
+        &mailbox_accounts,
+        &igp_accounts,
+        &hyperlane_token_accounts,
+        &token_sender,
+        &token_sender_ata,
+        transfer_amount,
+    )
+    .await
+    .unwrap();
+    assert_token_balance(
+        &mut banks_client,
+        &token_sender_ata,
+        sender_initial_balance - transfer_amount,
+    )
+    .await;
+
+    // The second exceeds it.
+    let result = transfer_remote(
+        &mut banks_client,
+        &program_id,
+        &mailbox_accounts,
+        &igp_accounts,
+        &hyperlane_token_accounts,
+        &token_sender,
+        &token_sender_ata,
+        transfer_amount,
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(HyperlaneTokenError::RateLimitExceeded as u32),
+        ),
+    );
+    assert_token_balance(
+        &mut banks_client,
+        &token_sender_ata,
+        sender_initial_balance - transfer_amount,
+    )
+    .await;
+
+    // The outbound level was consumed, leaving the inbound level untouched.
+    let token_account_data = banks_client
+        .get_account(hyperlane_token_accounts.token)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let token = HyperlaneTokenAccount::<SyntheticPlugin>::fetch(&mut &token_account_data[..])
+        .unwrap()
+        .into_inner();
+    let rate_limit = token.rate_limits.get(REMOTE_DOMAIN).unwrap();
+    assert_eq!(rate_limit.outbound.level, capacity - transfer_amount);
+    assert_eq!(rate_limit.inbound.level, capacity);
+}
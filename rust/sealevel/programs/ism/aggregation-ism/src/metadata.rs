@@ -0,0 +1,157 @@
+use hyperlane_core::Encode;
+
+use crate::error::Error;
+
+/// Bytes used to store one member of the (start, end) range tuple
+/// of a sub-ISM's metadata.
+const METADATA_RANGE_SIZE: usize = 4;
+
+/// The metadata of an aggregation ISM, which is made up of
+/// the metadata of each of its sub-ISMs.
+#[derive(Debug, PartialEq)]
+pub struct AggregationIsmMetadata {
+    /// The metadata of each sub-ISM, in the order of the sub-ISMs.
+    /// `None` if no metadata was provided for the sub-ISM.
+    pub sub_metadatas: Vec<Option<Vec<u8>>>,
+}
+
+/// Format of metadata:
+/// [????:????] Metadata start/end uint32 ranges of each sub-ISM, packed as uint64
+/// [????:????] Sub-ISM metadata, packed encoding
+/// A sub-ISM has no metadata if its range starts at 0.
+impl AggregationIsmMetadata {
+    /// Decodes the metadata of an aggregation ISM with `sub_ism_count` sub-ISMs.
+    pub fn from_bytes(bytes: &[u8], sub_ism_count: usize) -> Result<Self, Error> {
+        let ranges_len = sub_ism_count * METADATA_RANGE_SIZE * 2;
+        if bytes.len() < ranges_len {
+            return Err(Error::InvalidMetadata);
+        }
+
+        let sub_metadatas = (0..sub_ism_count)
+            .map(|index| {
+                let range_offset = index * METADATA_RANGE_SIZE * 2;
+                let start = read_range_member(bytes, range_offset)?;
+                if start == 0 {
+                    return Ok(None);
+                }
+                let end = read_range_member(bytes, range_offset + METADATA_RANGE_SIZE)?;
+                bytes
+                    .get(start..end)
+                    .map(|sub_metadata| Some(sub_metadata.to_vec()))
+                    .ok_or(Error::InvalidMetadata)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+
+        Ok(Self { sub_metadatas })
+    }
+}
+
+fn read_range_member(bytes: &[u8], offset: usize) -> Result<usize, Error> {
+    let range_member_bytes: [u8; METADATA_RANGE_SIZE] = bytes
+        .get(offset..offset + METADATA_RANGE_SIZE)
+        .and_then(|range_member_bytes| range_member_bytes.try_into().ok())
+        .ok_or(Error::InvalidMetadata)?;
+    Ok(u32::from_be_bytes(range_member_bytes) as usize)
+}
+
+impl Encode for AggregationIsmMetadata {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        let mut bytes_written = 0;
+        // The sub-ISM metadata starts after the ranges.
+        let mut range_start = self.sub_metadatas.len() * METADATA_RANGE_SIZE * 2;
+        for sub_metadata in &self.sub_metadatas {
+            let (start, end) = match sub_metadata {
+                Some(sub_metadata) => {
+                    let range = (range_start, range_start + sub_metadata.len());
+                    range_start = range.1;
+                    range
+                }
+                None => (0, 0),
+            };
+            bytes_written += writer.write(&(start as u32).to_be_bytes())?;
+            bytes_written += writer.write(&(end as u32).to_be_bytes())?;
+        }
+        for sub_metadata in self.sub_metadatas.iter().flatten() {
+            bytes_written += writer.write(sub_metadata)?;
+        }
+        Ok(bytes_written)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_decode_n_of_n_metadata() {
+        // Produced by the relayer's `AggregationIsmMetadataBuilder::format_metadata`
+        let bytes = hex::decode("000000180000003800000038000000580000005800000078290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563510e4e770828ddbf7f7b00ab00a9f6adaf81c0dc9cc85f1f8249c256942d61d9356e5a2cc1eba076e650ac7473fccc37952b46bc2e419a200cec0c451dce2336").unwrap();
+        let metadata = AggregationIsmMetadata::from_bytes(&bytes, 3).unwrap();
+        assert_eq!(
+            metadata,
+            AggregationIsmMetadata {
+                sub_metadatas: vec![
+                    Some(
+                        hex::decode(
+                            "290decd9548b62a8d60345a988386fc84ba6bc95484008f6362f93160ef3e563"
+                        )
+                        .unwrap()
+                    ),
+                    Some(
+                        hex::decode(
+                            "510e4e770828ddbf7f7b00ab00a9f6adaf81c0dc9cc85f1f8249c256942d61d9"
+                        )
+                        .unwrap()
+                    ),
+                    Some(
+                        hex::decode(
+                            "356e5a2cc1eba076e650ac7473fccc37952b46bc2e419a200cec0c451dce2336"
+                        )
+                        .unwrap()
+                    ),
+                ],
+            }
+        );
+        assert_eq!(metadata.to_vec(), bytes);
+    }
+
+    #[test]
+    fn test_encode_decode_n_of_m_metadata() {
+        let metadata = AggregationIsmMetadata {
+            sub_metadatas: vec![
+                Some(vec![1, 2, 3]),
+                None,
+                Some(vec![4, 5]),
+                None,
+                Some(vec![]),
+            ],
+        };
+        let bytes = metadata.to_vec();
+        assert_eq!(
+            AggregationIsmMetadata::from_bytes(&bytes, 5).unwrap(),
+            metadata
+        );
+    }
+
+    #[test]
+    fn test_decode_invalid_metadata_is_err() {
+        let metadata = AggregationIsmMetadata {
+            sub_metadatas: vec![Some(vec![1, 2, 3]), Some(vec![4, 5])],
+        };
+        let bytes = metadata.to_vec();
+
+        // Too short for the ranges of all the sub-ISMs
+        assert_eq!(
+            AggregationIsmMetadata::from_bytes(&bytes[..12], 2),
+            Err(Error::InvalidMetadata)
+        );
+        // A range that exceeds the metadata
+        assert_eq!(
+            AggregationIsmMetadata::from_bytes(&bytes[..bytes.len() - 1], 2),
+            Err(Error::InvalidMetadata)
+        );
+    }
+}
@@ -0,0 +1,630 @@
+use hyperlane_core::ModuleType;
+
+use access_control::AccessControl;
+use account_utils::{create_pda_account, DiscriminatorDecode, SizedData};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serializable_account_meta::{SerializableAccountMeta, SimulationReturnData};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction as SolanaInstruction},
+    program::{get_return_data, invoke, set_return_data},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    accounts::{
+        AccessControlAccount, AccessControlData, ModulesAndThresholdAccount,
+        ModulesAndThresholdData,
+    },
+    error::Error,
+    instruction::{Instruction, ModulesAndThreshold},
+    metadata::AggregationIsmMetadata,
+};
+
+use hyperlane_sealevel_interchain_security_module_interface::{
+    InterchainSecurityModuleInstruction, VerifyInstruction, VERIFY_ACCOUNT_METAS_PDA_SEEDS,
+};
+
+const ISM_TYPE: ModuleType = ModuleType::Aggregation;
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+/// PDA seeds relating to the access control PDA account.
+#[macro_export]
+macro_rules! access_control_pda_seeds {
+    () => {{
+        &[b"aggregation_ism", b"-", b"access_control"]
+    }};
+
+    ($bump_seed:expr) => {{
+        &[b"aggregation_ism", b"-", b"access_control", &[$bump_seed]]
+    }};
+}
+
+/// PDA seeds relating to the modules and threshold PDA account.
+/// These are the `VERIFY_ACCOUNT_METAS_PDA_SEEDS`, so that the modules
+/// and threshold are readable by the `VerifyAccountMetas` instruction.
+#[macro_export]
+macro_rules! modules_and_threshold_pda_seeds {
+    () => {{
+        &[b"hyperlane_ism", b"-", b"verify", b"-", b"account_metas"]
+    }};
+
+    ($bump_seed:expr) => {{
+        &[
+            b"hyperlane_ism",
+            b"-",
+            b"verify",
+            b"-",
+            b"account_metas",
+            &[$bump_seed],
+        ]
+    }};
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // First, try to decode the instruction as an interchain security module
+    // interface supported function based off the discriminator.
+    if let Ok(ism_instruction) = InterchainSecurityModuleInstruction::decode(instruction_data) {
+        return match ism_instruction {
+            InterchainSecurityModuleInstruction::Type => {
+                set_return_data(
+                    &SimulationReturnData::new(ISM_TYPE as u32)
+                        .try_to_vec()
+                        .map_err(|err| ProgramError::BorshIoError(err.to_string()))?[..],
+                );
+                return Ok(());
+            }
+            InterchainSecurityModuleInstruction::Verify(verify_data) => {
+                verify(program_id, accounts, verify_data)
+            }
+            InterchainSecurityModuleInstruction::VerifyAccountMetas(verify_data) => {
+                let account_metas = verify_account_metas(program_id, accounts, verify_data)?;
+                // Wrap it in the SimulationReturnData because serialized account_metas
+                // may end with zero byte(s), which are incorrectly truncated as
+                // simulated transaction return data.
+                // See `SimulationReturnData` for details.
+                let bytes = SimulationReturnData::new(account_metas)
+                    .try_to_vec()
+                    .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+                set_return_data(&bytes[..]);
+                Ok(())
+            }
+        };
+    }
+
+    match Instruction::decode(instruction_data)? {
+        // Initializes the program.
+        Instruction::Initialize => initialize(program_id, accounts),
+        // Sets the sub-ISMs and threshold.
+        Instruction::SetModulesAndThreshold(config) => {
+            set_modules_and_threshold(program_id, accounts, config)
+        }
+        // Gets the sub-ISMs and threshold.
+        Instruction::GetModulesAndThreshold => get_modules_and_threshold(program_id, accounts),
+        // Gets the owner of this program from the access control account.
+        Instruction::GetOwner => get_owner(program_id, accounts),
+        // Sets the owner of this program in the access control account.
+        Instruction::TransferOwnership(new_owner) => {
+            transfer_ownership(program_id, accounts, new_owner)
+        }
+    }
+}
+
+/// Initializes the program, creating the access control PDA account.
+///
+/// Accounts:
+/// 0. `[signer]` The new owner and payer of the access control PDA.
+/// 1. `[writable]` The access control PDA account.
+/// 2. `[executable]` The system program account.
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The new owner of this program and payer of the access control PDA.
+    let owner_account = next_account_info(accounts_iter)?;
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Account 1: The access control PDA account.
+    let access_control_pda_account = next_account_info(accounts_iter)?;
+    let (access_control_pda_key, access_control_pda_bump_seed) =
+        Pubkey::find_program_address(access_control_pda_seeds!(), program_id);
+    if *access_control_pda_account.key != access_control_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    // Ensure the access control PDA account isn't already initialized.
+    if let Ok(Some(_)) =
+        AccessControlAccount::fetch_data(&mut &access_control_pda_account.data.borrow()[..])
+    {
+        return Err(Error::AlreadyInitialized.into());
+    }
+
+    // Account 2: The system program account.
+    let system_program_account = next_account_info(accounts_iter)?;
+    if !solana_program::system_program::check_id(system_program_account.key) {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    // Create the access control PDA account.
+    let access_control_account = AccessControlAccount::from(AccessControlData {
+        bump_seed: access_control_pda_bump_seed,
+        owner: Some(*owner_account.key),
+    });
+    let access_control_account_data_size = access_control_account.size();
+    create_pda_account(
+        owner_account,
+        &Rent::get()?,
+        access_control_account_data_size,
+        program_id,
+        system_program_account,
+        access_control_pda_account,
+        access_control_pda_seeds!(access_control_pda_bump_seed),
+    )?;
+
+    // Store the access control data.
+    access_control_account.store(access_control_pda_account, false)?;
+
+    Ok(())
+}
+
+/// Verifies a message by invoking the `Verify` instruction of each sub-ISM
+/// that metadata was provided for. Requires metadata to be provided for at
+/// least the threshold of sub-ISMs.
+///
+/// Accounts:
+/// 0. `[]` The modules and threshold PDA account.
+/// 1. `[]` The sub-ISM account counts account. See `sub_ism_account_counts_key`.
+/// 2..N. `[??]` For each sub-ISM that metadata was provided for, in order:
+///       the sub-ISM program, followed by the accounts required by its `Verify` instruction.
+fn verify(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    verify_data: VerifyInstruction,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The modules and threshold PDA account.
+    let modules_and_threshold_pda_account = next_account_info(accounts_iter)?;
+    let modules_and_threshold =
+        modules_and_threshold(program_id, modules_and_threshold_pda_account)?;
+
+    let sub_isms = sub_isms_with_metadata(&modules_and_threshold, &verify_data.metadata)?;
+    if sub_isms.len() < modules_and_threshold.threshold as usize {
+        return Err(Error::ThresholdNotMet.into());
+    }
+
+    // Account 1: The sub-ISM account counts account.
+    let sub_ism_account_counts_account = next_account_info(accounts_iter)?;
+
+    // Accounts 2..N: The sub-ISM programs and their accounts.
+    let sub_ism_account_infos = sub_ism_account_infos(
+        &sub_isms,
+        Some(sub_ism_account_counts_account.key),
+        accounts_iter.as_slice(),
+    );
+
+    for ((ism, sub_metadata), account_infos) in sub_isms.into_iter().zip(sub_ism_account_infos) {
+        let account_infos = account_infos.ok_or(Error::AccountOutOfOrder)?;
+        let verify_instruction = SolanaInstruction::new_with_bytes(
+            ism,
+            &InterchainSecurityModuleInstruction::Verify(VerifyInstruction {
+                metadata: sub_metadata,
+                message: verify_data.message.clone(),
+            })
+            .encode()?,
+            to_account_metas(account_infos),
+        );
+        invoke(&verify_instruction, account_infos)?;
+    }
+
+    Ok(())
+}
+
+/// Gets the list of AccountMetas required by the `Verify` instruction.
+///
+/// The accounts required by a sub-ISM can only be known by invoking the
+/// sub-ISM's `VerifyAccountMetas` instruction, which requires the sub-ISM
+/// program to be passed in, so this may take multiple simulations.
+/// The returned list always starts with the modules and threshold PDA, which is this
+/// program's `VERIFY_ACCOUNT_METAS_PDA_SEEDS` PDA, followed by the sub-ISM account counts
+/// account. Callers are expected to simulate this instruction again with the returned list
+/// as the accounts until it's unchanged.
+///
+/// Accounts:
+/// 0. `[]` The modules and threshold PDA account.
+/// 1. `[]` OPTIONAL - The sub-ISM account counts account. See `sub_ism_account_counts_key`.
+/// 2..N. `[??]` OPTIONAL - For each sub-ISM that metadata was provided for, in order:
+///       the sub-ISM program, followed by the accounts to pass into its `VerifyAccountMetas`
+///       instruction.
+fn verify_account_metas(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    verify_data: VerifyInstruction,
+) -> Result<Vec<SerializableAccountMeta>, ProgramError> {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The modules and threshold PDA account.
+    let modules_and_threshold_pda_account = next_account_info(accounts_iter)?;
+    let modules_and_threshold =
+        modules_and_threshold(program_id, modules_and_threshold_pda_account)?;
+
+    let sub_isms = sub_isms_with_metadata(&modules_and_threshold, &verify_data.metadata)?;
+
+    // Account 1: The sub-ISM account counts account, if provided.
+    let sub_ism_account_counts_account = accounts_iter.next();
+
+    // Accounts 2..N: The sub-ISM programs and their accounts, if provided.
+    let sub_ism_account_infos = sub_ism_account_infos(
+        &sub_isms,
+        sub_ism_account_counts_account.map(|account| account.key),
+        accounts_iter.as_slice(),
+    );
+
+    let mut sub_ism_account_counts = Vec::with_capacity(sub_isms.len());
+    let mut sub_ism_account_metas: Vec<SerializableAccountMeta> = vec![];
+
+    for ((ism, sub_metadata), account_infos) in sub_isms.into_iter().zip(sub_ism_account_infos) {
+        // If the sub-ISM program wasn't provided, its `VerifyAccountMetas` instruction
+        // can't be invoked yet. Instead, return the accounts that must be passed in to do so.
+        let ism_account_metas = match account_infos {
+            Some(account_infos) => {
+                let verify_account_metas_instruction = SolanaInstruction::new_with_bytes(
+                    ism,
+                    &InterchainSecurityModuleInstruction::VerifyAccountMetas(VerifyInstruction {
+                        metadata: sub_metadata,
+                        message: verify_data.message.clone(),
+                    })
+                    .encode()?,
+                    to_account_metas(account_infos),
+                );
+                invoke(&verify_account_metas_instruction, account_infos)?;
+
+                let (return_data_program_id, return_data) =
+                    get_return_data().ok_or(Error::InvalidReturnData)?;
+                if return_data_program_id != ism {
+                    return Err(Error::InvalidReturnData.into());
+                }
+                SimulationReturnData::<Vec<SerializableAccountMeta>>::try_from_slice(
+                    &return_data[..],
+                )
+                .map_err(|_| Error::InvalidReturnData)?
+                .return_data
+            }
+            None => {
+                let (ism_verify_account_metas_pda_key, _) =
+                    Pubkey::find_program_address(VERIFY_ACCOUNT_METAS_PDA_SEEDS, &ism);
+                vec![AccountMeta::new_readonly(ism_verify_account_metas_pda_key, false).into()]
+            }
+        };
+
+        sub_ism_account_counts
+            .push(u8::try_from(ism_account_metas.len()).map_err(|_| Error::InvalidReturnData)?);
+        sub_ism_account_metas.push(AccountMeta::new_readonly(ism, false).into());
+        sub_ism_account_metas.extend(ism_account_metas);
+    }
+
+    let mut account_metas: Vec<SerializableAccountMeta> = vec![
+        AccountMeta::new_readonly(*modules_and_threshold_pda_account.key, false).into(),
+        AccountMeta::new_readonly(sub_ism_account_counts_key(&sub_ism_account_counts)?, false)
+            .into(),
+    ];
+    account_metas.extend(sub_ism_account_metas);
+
+    Ok(account_metas)
+}
+
+/// Gets the sub-ISMs that metadata was provided for, in order, alongside their metadata.
+fn sub_isms_with_metadata(
+    modules_and_threshold: &ModulesAndThreshold,
+    metadata_bytes: &[u8],
+) -> Result<Vec<(Pubkey, Vec<u8>)>, ProgramError> {
+    let metadata =
+        AggregationIsmMetadata::from_bytes(metadata_bytes, modules_and_threshold.modules.len())?;
+
+    Ok(modules_and_threshold
+        .modules
+        .iter()
+        .zip(metadata.sub_metadatas)
+        .filter_map(|(ism, sub_metadata)| sub_metadata.map(|sub_metadata| (*ism, sub_metadata)))
+        .collect())
+}
+
+/// Gets the key of the sub-ISM account counts account, which isn't a real account.
+/// Its key holds the number of accounts required by each sub-ISM that metadata
+/// was provided for, in order, as one byte each, zero-padded to 32 bytes.
+pub fn sub_ism_account_counts_key(sub_ism_account_counts: &[u8]) -> Result<Pubkey, ProgramError> {
+    let mut key = [0u8; 32];
+    key.get_mut(..sub_ism_account_counts.len())
+        .ok_or(Error::InvalidModulesAndThreshold)?
+        .copy_from_slice(sub_ism_account_counts);
+    Ok(Pubkey::new_from_array(key))
+}
+
+/// Splits the provided accounts into the accounts of each sub-ISM, using the
+/// account counts held by the sub-ISM account counts account's key.
+/// The accounts of a sub-ISM are expected to be its program account, followed by
+/// as many accounts as its account count.
+/// `None` is returned for a sub-ISM, and each sub-ISM after it, if the account counts
+/// weren't provided, or if its program account isn't where it's expected to be.
+fn sub_ism_account_infos<'a, 'b>(
+    sub_isms: &[(Pubkey, Vec<u8>)],
+    sub_ism_account_counts_key: Option<&Pubkey>,
+    accounts: &'a [AccountInfo<'b>],
+) -> Vec<Option<&'a [AccountInfo<'b>]>> {
+    let sub_ism_account_counts = match sub_ism_account_counts_key {
+        Some(sub_ism_account_counts_key) => sub_ism_account_counts_key.to_bytes(),
+        None => return vec![None; sub_isms.len()],
+    };
+    let mut start = Some(0);
+    sub_isms
+        .iter()
+        .enumerate()
+        .map(|(index, (ism, _))| {
+            let ism_index = start.take()?;
+            let end = ism_index + 1 + *sub_ism_account_counts.get(index)? as usize;
+            if accounts.get(ism_index)?.key != ism || end > accounts.len() {
+                return None;
+            }
+            start = Some(end);
+            Some(&accounts[ism_index + 1..end])
+        })
+        .collect()
+}
+
+/// Gets the sub-ISMs and threshold, and returns it as return data.
+/// Intended to be used by instructions querying the sub-ISMs and threshold.
+///
+/// Accounts:
+/// 0. `[]` The modules and threshold PDA account.
+fn get_modules_and_threshold(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The modules and threshold PDA account.
+    let modules_and_threshold_pda_account = next_account_info(accounts_iter)?;
+    let modules_and_threshold =
+        modules_and_threshold(program_id, modules_and_threshold_pda_account)?;
+
+    // Wrap it in the SimulationReturnData because serialized modules_and_threshold
+    // may end with zero byte(s), which are incorrectly truncated as
+    // simulated transaction return data.
+    // See `SimulationReturnData` for details.
+    let bytes = SimulationReturnData::new(modules_and_threshold)
+        .try_to_vec()
+        .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+    set_return_data(&bytes[..]);
+    Ok(())
+}
+
+/// Gets the sub-ISMs and threshold.
+/// Returns an Err if the provided account isn't the modules and threshold PDA.
+fn modules_and_threshold(
+    program_id: &Pubkey,
+    modules_and_threshold_pda_account: &AccountInfo,
+) -> Result<ModulesAndThreshold, ProgramError> {
+    // Sanity check that the owner of the PDA account is this program
+    if modules_and_threshold_pda_account.owner != program_id {
+        return Err(Error::ProgramIdNotOwner.into());
+    }
+    let modules_and_threshold_data = ModulesAndThresholdAccount::fetch_data(
+        &mut &modules_and_threshold_pda_account.data.borrow()[..],
+    )?
+    .ok_or(Error::AccountNotInitialized)?;
+    // Confirm the key of the modules_and_threshold_pda_account is the correct PDA
+    // using the stored bump seed.
+    let modules_and_threshold_pda_key = Pubkey::create_program_address(
+        modules_and_threshold_pda_seeds!(modules_and_threshold_data.bump_seed),
+        program_id,
+    )?;
+    // This check validates that the provided modules_and_threshold_pda_account is valid
+    if *modules_and_threshold_pda_account.key != modules_and_threshold_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    Ok(modules_and_threshold_data.modules_and_threshold)
+}
+
+/// Sets the sub-ISMs and threshold.
+///
+/// Accounts:
+/// 0. `[signer]` The access control owner and payer of the modules and threshold PDA.
+/// 1. `[]` The access control PDA account.
+/// 2. `[writable]` The modules and threshold PDA account.
+/// 3. `[executable]` The system program account.
+fn set_modules_and_threshold(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    config: ModulesAndThreshold,
+) -> ProgramResult {
+    // Validate the provided sub-ISMs and threshold.
+    config.validate()?;
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The owner of this program.
+    // This is verified as correct further below.
+    let owner_account = next_account_info(accounts_iter)?;
+
+    // Account 1: The access control PDA account.
+    let access_control_pda_account = next_account_info(accounts_iter)?;
+    let access_control_data = access_control_data(program_id, access_control_pda_account)?;
+    // Ensure the owner account is the owner of this program.
+    access_control_data.ensure_owner_signer(owner_account)?;
+
+    // Account 2: The modules and threshold PDA account.
+    let modules_and_threshold_pda_account = next_account_info(accounts_iter)?;
+
+    // Account 3: The system program account.
+    let system_program_account = next_account_info(accounts_iter)?;
+    if !solana_program::system_program::check_id(system_program_account.key) {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    let modules_and_threshold_data = ModulesAndThresholdAccount::fetch_data(
+        &mut &modules_and_threshold_pda_account.data.borrow()[..],
+    );
+
+    let rent = Rent::get()?;
+
+    match modules_and_threshold_data {
+        Ok(Some(modules_and_threshold_data)) => {
+            // The PDA account exists already, we need to confirm the key of the
+            // modules_and_threshold_pda_account is the PDA with the stored bump seed.
+            let modules_and_threshold_pda_key = Pubkey::create_program_address(
+                modules_and_threshold_pda_seeds!(modules_and_threshold_data.bump_seed),
+                program_id,
+            )?;
+            // This check validates that the provided modules_and_threshold_pda_account is valid
+            if *modules_and_threshold_pda_account.key != modules_and_threshold_pda_key {
+                return Err(Error::AccountOutOfOrder.into());
+            }
+            // Extra sanity check that the owner of the PDA account is this program
+            if modules_and_threshold_pda_account.owner != program_id {
+                return Err(Error::ProgramIdNotOwner.into());
+            }
+
+            ModulesAndThresholdAccount::from(ModulesAndThresholdData {
+                bump_seed: modules_and_threshold_data.bump_seed,
+                modules_and_threshold: config,
+            })
+            .store_with_rent_exempt_realloc(
+                modules_and_threshold_pda_account,
+                &rent,
+                owner_account,
+                system_program_account,
+            )?;
+        }
+        Ok(None) | Err(_) => {
+            // Create the modules and threshold PDA account if it doesn't exist.
+
+            // First find the key and bump seed for the PDA, and ensure
+            // it matches the provided account.
+            let (modules_and_threshold_pda_key, modules_and_threshold_pda_bump) =
+                Pubkey::find_program_address(modules_and_threshold_pda_seeds!(), program_id);
+            if *modules_and_threshold_pda_account.key != modules_and_threshold_pda_key {
+                return Err(Error::AccountOutOfOrder.into());
+            }
+
+            let modules_and_threshold_account =
+                ModulesAndThresholdAccount::from(ModulesAndThresholdData {
+                    bump_seed: modules_and_threshold_pda_bump,
+                    modules_and_threshold: config,
+                });
+            create_pda_account(
+                owner_account,
+                &rent,
+                modules_and_threshold_account.size(),
+                program_id,
+                system_program_account,
+                modules_and_threshold_pda_account,
+                modules_and_threshold_pda_seeds!(modules_and_threshold_pda_bump),
+            )?;
+            modules_and_threshold_account.store(modules_and_threshold_pda_account, false)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Gets the owner of this program from the access control account, and returns it as return data.
+/// Intended to be used by instructions querying the owner.
+///
+/// Accounts:
+/// 0. `[]` The access control PDA account.
+fn get_owner(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The access control PDA account.
+    let access_control_pda_account = next_account_info(accounts_iter)?;
+
+    let access_control_data = access_control_data(program_id, access_control_pda_account)?;
+
+    // Wrap it in the SimulationReturnData because serialized `access_control_data.owner`
+    // may end with zero byte(s), which are incorrectly truncated as
+    // simulated transaction return data.
+    // See `SimulationReturnData` for details.
+    let bytes = SimulationReturnData::new(access_control_data.owner)
+        .try_to_vec()
+        .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+    set_return_data(&bytes[..]);
+    Ok(())
+}
+
+/// Gets the access control data of this program.
+/// Returns an Err if the provided account isn't the access control PDA.
+fn access_control_data(
+    program_id: &Pubkey,
+    access_control_pda_account: &AccountInfo,
+) -> Result<AccessControlData, ProgramError> {
+    let access_control_data =
+        AccessControlAccount::fetch_data(&mut &access_control_pda_account.data.borrow()[..])?
+            .ok_or(Error::AccountNotInitialized)?;
+    // Confirm the key of the access_control_pda_account is the correct PDA
+    // using the stored bump seed.
+    let access_control_pda_key = Pubkey::create_program_address(
+        access_control_pda_seeds!(access_control_data.bump_seed),
+        program_id,
+    )?;
+    // This check validates that the provided access_control_pda_account is valid
+    if *access_control_pda_account.key != access_control_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+    // Extra sanity check that the owner of the PDA account is this program
+    if access_control_pda_account.owner != program_id {
+        return Err(Error::ProgramIdNotOwner.into());
+    }
+
+    Ok(*access_control_data)
+}
+
+/// Transfers ownership to a new access control owner.
+///
+/// Accounts:
+/// 0. `[signer]` The current access control owner.
+/// 1. `[writeable]` The access control PDA account.
+fn transfer_ownership(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_owner: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The current access control owner.
+    // This is verified as correct further below.
+    let owner_account = next_account_info(accounts_iter)?;
+
+    // Account 1: The access control PDA account.
+    let access_control_pda_account = next_account_info(accounts_iter)?;
+    let mut access_control_data = access_control_data(program_id, access_control_pda_account)?;
+
+    // Transfer ownership. This errors if `owner_account` is not a signer or the owner.
+    access_control_data.transfer_ownership(owner_account, new_owner)?;
+
+    // Store the new access control owner.
+    AccessControlAccount::from(access_control_data).store(access_control_pda_account, false)?;
+
+    Ok(())
+}
+
+/// Converts the provided account infos into the account metas of an instruction
+/// that's invoked with them.
+fn to_account_metas(account_infos: &[AccountInfo]) -> Vec<AccountMeta> {
+    account_infos
+        .iter()
+        .map(|account_info| AccountMeta {
+            pubkey: *account_info.key,
+            is_signer: account_info.is_signer,
+            is_writable: account_info.is_writable,
+        })
+        .collect()
+}
@@ -0,0 +1,696 @@
+//! Contains functional tests for things that cannot be done
+//! strictly in unit tests. This includes CPIs, like creating
+//! new PDA accounts.
+
+use account_utils::DiscriminatorEncode;
+use borsh::BorshSerialize;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+    system_program,
+};
+
+use hyperlane_core::{Encode, HyperlaneMessage, ModuleType, H256};
+use hyperlane_sealevel_aggregation_ism::{
+    access_control_pda_seeds,
+    accounts::{
+        AccessControlAccount, AccessControlData, ModulesAndThresholdAccount,
+        ModulesAndThresholdData,
+    },
+    error::Error as AggregationIsmError,
+    instruction::{
+        init_instruction, set_modules_and_threshold_instruction,
+        Instruction as AggregationIsmProgramInstruction, ModulesAndThreshold,
+    },
+    metadata::AggregationIsmMetadata,
+    modules_and_threshold_pda_seeds,
+    processor::{process_instruction, sub_ism_account_counts_key},
+};
+use hyperlane_sealevel_interchain_security_module_interface::{
+    InterchainSecurityModuleInstruction, VerifyInstruction,
+};
+use hyperlane_sealevel_routing_ism::instruction::{
+    init_instruction as routing_ism_init_instruction, set_routes_instruction, Domained,
+};
+use hyperlane_sealevel_test_ism::{
+    program::{TestIsmError, TestIsmInstruction},
+    test_ism_storage_pda_seeds,
+};
+use hyperlane_test_utils::{
+    assert_transaction_error, get_ism_verify_account_metas, new_funded_keypair,
+    process_instruction as process_test_instruction, simulate_instruction,
+};
+use serializable_account_meta::SimulationReturnData;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Signer, signer::keypair::Keypair,
+    transaction::TransactionError,
+};
+
+fn aggregation_ism_id() -> Pubkey {
+    pubkey!("DK7cWFQubb1vZUUn3Q6xDKCECYz1wTXJXxe7DLTbjBBh")
+}
+
+fn test_ism_a_id() -> Pubkey {
+    hyperlane_sealevel_test_ism::id()
+}
+
+fn test_ism_b_id() -> Pubkey {
+    pubkey!("B92cMLrqz3K4PBwYvr7GVidRi7S3mXmMhuphGpgfswsc")
+}
+
+fn routing_ism_id() -> Pubkey {
+    pubkey!("HiQW3qzw9NuNQgBudyjUL63KL4WizjG1L67NKrr2DhG3")
+}
+
+async fn setup_client() -> (BanksClient, Keypair) {
+    let mut program_test = ProgramTest::new(
+        "hyperlane_sealevel_aggregation_ism",
+        aggregation_ism_id(),
+        processor!(process_instruction),
+    );
+
+    // Two instances of the test ISM program, used as sub-ISMs.
+    for test_ism_id in [test_ism_a_id(), test_ism_b_id()] {
+        program_test.add_program(
+            "hyperlane_sealevel_test_ism",
+            test_ism_id,
+            processor!(hyperlane_sealevel_test_ism::program::process_instruction),
+        );
+    }
+
+    // A routing ISM program, used as a sub-ISM that itself invokes a routed ISM.
+    program_test.add_program(
+        "hyperlane_sealevel_routing_ism",
+        routing_ism_id(),
+        processor!(hyperlane_sealevel_routing_ism::processor::process_instruction),
+    );
+
+    let (banks_client, payer, _recent_blockhash) = program_test.start().await;
+
+    (banks_client, payer)
+}
+
+async fn initialize(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+) -> Result<(), BanksClientError> {
+    process_test_instruction(
+        banks_client,
+        init_instruction(aggregation_ism_id(), payer.pubkey()).unwrap(),
+        payer,
+        &[payer],
+    )
+    .await?;
+    Ok(())
+}
+
+async fn set_modules_and_threshold(
+    banks_client: &mut BanksClient,
+    owner: &Keypair,
+    modules_and_threshold: ModulesAndThreshold,
+) -> Result<(), BanksClientError> {
+    process_test_instruction(
+        banks_client,
+        set_modules_and_threshold_instruction(
+            aggregation_ism_id(),
+            owner.pubkey(),
+            modules_and_threshold,
+        )
+        .unwrap(),
+        owner,
+        &[owner],
+    )
+    .await?;
+    Ok(())
+}
+
+async fn initialize_test_ism(banks_client: &mut BanksClient, payer: &Keypair, program_id: Pubkey) {
+    let (storage_pda_key, _) =
+        Pubkey::find_program_address(test_ism_storage_pda_seeds!(), &program_id);
+    process_test_instruction(
+        banks_client,
+        Instruction::new_with_bytes(
+            program_id,
+            &TestIsmInstruction::Init.try_to_vec().unwrap(),
+            vec![
+                AccountMeta::new_readonly(system_program::id(), false),
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new(storage_pda_key, false),
+            ],
+        ),
+        payer,
+        &[payer],
+    )
+    .await
+    .unwrap();
+}
+
+async fn set_test_ism_accept(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    program_id: Pubkey,
+    accept: bool,
+) {
+    let (storage_pda_key, _) =
+        Pubkey::find_program_address(test_ism_storage_pda_seeds!(), &program_id);
+    process_test_instruction(
+        banks_client,
+        Instruction::new_with_bytes(
+            program_id,
+            &TestIsmInstruction::SetAccept(accept).try_to_vec().unwrap(),
+            vec![AccountMeta::new(storage_pda_key, false)],
+        ),
+        payer,
+        &[payer],
+    )
+    .await
+    .unwrap();
+}
+
+async fn get_modules_and_threshold_data(banks_client: &mut BanksClient) -> ModulesAndThresholdData {
+    let (modules_and_threshold_pda_key, _) =
+        Pubkey::find_program_address(modules_and_threshold_pda_seeds!(), &aggregation_ism_id());
+    let modules_and_threshold_account_data = banks_client
+        .get_account(modules_and_threshold_pda_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    *ModulesAndThresholdAccount::fetch_data(&mut &modules_and_threshold_account_data[..])
+        .unwrap()
+        .unwrap()
+}
+
+/// Initializes the routing ISM, routing messages from the test message's origin to `ism`.
+async fn initialize_routing_ism(banks_client: &mut BanksClient, payer: &Keypair, ism: Pubkey) {
+    process_test_instruction(
+        banks_client,
+        routing_ism_init_instruction(routing_ism_id(), payer.pubkey()).unwrap(),
+        payer,
+        &[payer],
+    )
+    .await
+    .unwrap();
+    process_test_instruction(
+        banks_client,
+        set_routes_instruction(
+            routing_ism_id(),
+            payer.pubkey(),
+            vec![Domained {
+                domain: test_message().origin,
+                data: Some(ism),
+            }],
+        )
+        .unwrap(),
+        payer,
+        &[payer],
+    )
+    .await
+    .unwrap();
+}
+
+fn test_message() -> HyperlaneMessage {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin: 1234,
+        sender: H256::random(),
+        destination: 4321,
+        recipient: H256::random(),
+        body: vec![1, 2, 3, 4, 5],
+    }
+}
+
+/// Gets the account metas for and processes a Verify instruction.
+async fn verify(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    metadata: AggregationIsmMetadata,
+) -> Result<(), BanksClientError> {
+    let verify_instruction = VerifyInstruction {
+        metadata: metadata.to_vec(),
+        message: test_message().to_vec(),
+    };
+
+    let account_metas = get_ism_verify_account_metas(
+        banks_client,
+        payer,
+        aggregation_ism_id(),
+        verify_instruction.metadata.clone(),
+        verify_instruction.message.clone(),
+    )
+    .await?;
+
+    process_test_instruction(
+        banks_client,
+        Instruction::new_with_bytes(
+            aggregation_ism_id(),
+            &InterchainSecurityModuleInstruction::Verify(verify_instruction)
+                .encode()
+                .unwrap(),
+            account_metas,
+        ),
+        payer,
+        &[payer],
+    )
+    .await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_initialize() {
+    let program_id = aggregation_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let (access_control_pda_key, access_control_pda_bump_seed) =
+        Pubkey::find_program_address(access_control_pda_seeds!(), &program_id);
+    let access_control_account_data = banks_client
+        .get_account(access_control_pda_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let access_control = AccessControlAccount::fetch_data(&mut &access_control_account_data[..])
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        access_control,
+        Box::new(AccessControlData {
+            bump_seed: access_control_pda_bump_seed,
+            owner: Some(payer.pubkey()),
+        }),
+    );
+}
+
+#[tokio::test]
+async fn test_initialize_errors_if_called_twice() {
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    // Use a new payer to get a new tx ID, because the instruction data is the same
+    let new_payer = new_funded_keypair(&mut banks_client, &payer, 1000000000).await;
+    let result = initialize(&mut banks_client, &new_payer).await;
+
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AggregationIsmError::AlreadyInitialized as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_set_modules_and_threshold() {
+    let program_id = aggregation_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    // Creates the modules and threshold PDA
+    let modules_and_threshold = ModulesAndThreshold {
+        modules: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+        threshold: 2,
+    };
+    set_modules_and_threshold(&mut banks_client, &payer, modules_and_threshold.clone())
+        .await
+        .unwrap();
+
+    let (modules_and_threshold_pda_key, modules_and_threshold_pda_bump_seed) =
+        Pubkey::find_program_address(modules_and_threshold_pda_seeds!(), &program_id);
+    assert_eq!(
+        get_modules_and_threshold_data(&mut banks_client).await,
+        ModulesAndThresholdData {
+            bump_seed: modules_and_threshold_pda_bump_seed,
+            modules_and_threshold,
+        },
+    );
+
+    // Updates the existing PDA, which requires it to be reallocated
+    let modules_and_threshold = ModulesAndThreshold {
+        modules: vec![
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ],
+        threshold: 1,
+    };
+    set_modules_and_threshold(&mut banks_client, &payer, modules_and_threshold.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        get_modules_and_threshold_data(&mut banks_client).await,
+        ModulesAndThresholdData {
+            bump_seed: modules_and_threshold_pda_bump_seed,
+            modules_and_threshold: modules_and_threshold.clone(),
+        },
+    );
+
+    // The modules and threshold can be queried with the GetModulesAndThreshold instruction
+    let queried_modules_and_threshold =
+        simulate_instruction::<SimulationReturnData<ModulesAndThreshold>>(
+            &mut banks_client,
+            &payer,
+            Instruction::new_with_bytes(
+                program_id,
+                &AggregationIsmProgramInstruction::GetModulesAndThreshold
+                    .encode()
+                    .unwrap(),
+                vec![AccountMeta::new_readonly(
+                    modules_and_threshold_pda_key,
+                    false,
+                )],
+            ),
+        )
+        .await
+        .unwrap()
+        .unwrap()
+        .return_data;
+    assert_eq!(queried_modules_and_threshold, modules_and_threshold);
+}
+
+#[tokio::test]
+async fn test_set_modules_and_threshold_errors_if_invalid() {
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let result = set_modules_and_threshold(
+        &mut banks_client,
+        &payer,
+        ModulesAndThreshold {
+            modules: vec![Pubkey::new_unique()],
+            threshold: 2,
+        },
+    )
+    .await;
+
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AggregationIsmError::InvalidModulesAndThreshold as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_set_modules_and_threshold_errors_if_not_owner() {
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let non_owner = new_funded_keypair(&mut banks_client, &payer, 1000000000).await;
+    let result = set_modules_and_threshold(
+        &mut banks_client,
+        &non_owner,
+        ModulesAndThreshold {
+            modules: vec![Pubkey::new_unique()],
+            threshold: 1,
+        },
+    )
+    .await;
+
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+}
+
+#[tokio::test]
+async fn test_ism_verify() {
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+    initialize_test_ism(&mut banks_client, &payer, test_ism_a_id()).await;
+    initialize_test_ism(&mut banks_client, &payer, test_ism_b_id()).await;
+
+    set_modules_and_threshold(
+        &mut banks_client,
+        &payer,
+        ModulesAndThreshold {
+            modules: vec![test_ism_a_id(), test_ism_b_id()],
+            threshold: 2,
+        },
+    )
+    .await
+    .unwrap();
+
+    let test_ism_a_metadata = vec![1, 2, 3];
+    let test_ism_b_metadata = vec![4, 5];
+
+    // The account metas are those of each sub-ISM, preceded by the sub-ISM program,
+    // after the account counts of each sub-ISM
+    let metadata = AggregationIsmMetadata {
+        sub_metadatas: vec![
+            Some(test_ism_a_metadata.clone()),
+            Some(test_ism_b_metadata.clone()),
+        ],
+    };
+    let message_bytes = test_message().to_vec();
+    let account_metas = get_ism_verify_account_metas(
+        &mut banks_client,
+        &payer,
+        aggregation_ism_id(),
+        metadata.to_vec(),
+        message_bytes.clone(),
+    )
+    .await
+    .unwrap();
+    let (modules_and_threshold_pda_key, _) =
+        Pubkey::find_program_address(modules_and_threshold_pda_seeds!(), &aggregation_ism_id());
+    let test_ism_a_account_metas = get_ism_verify_account_metas(
+        &mut banks_client,
+        &payer,
+        test_ism_a_id(),
+        test_ism_a_metadata,
+        message_bytes.clone(),
+    )
+    .await
+    .unwrap();
+    let test_ism_b_account_metas = get_ism_verify_account_metas(
+        &mut banks_client,
+        &payer,
+        test_ism_b_id(),
+        test_ism_b_metadata,
+        message_bytes,
+    )
+    .await
+    .unwrap();
+    let account_counts = [
+        test_ism_a_account_metas.len() as u8,
+        test_ism_b_account_metas.len() as u8,
+    ];
+    assert_eq!(
+        account_metas,
+        [
+            vec![
+                AccountMeta::new_readonly(modules_and_threshold_pda_key, false),
+                AccountMeta::new_readonly(
+                    sub_ism_account_counts_key(&account_counts).unwrap(),
+                    false,
+                ),
+                AccountMeta::new_readonly(test_ism_a_id(), false),
+            ],
+            test_ism_a_account_metas,
+            vec![AccountMeta::new_readonly(test_ism_b_id(), false)],
+            test_ism_b_account_metas,
+        ]
+        .concat(),
+    );
+
+    // Both sub-ISMs accept the message
+    verify(&mut banks_client, &payer, metadata).await.unwrap();
+
+    // Metadata for only one of the sub-ISMs doesn't meet the threshold
+    let result = verify(
+        &mut banks_client,
+        &payer,
+        AggregationIsmMetadata {
+            sub_metadatas: vec![None, Some(vec![4, 5])],
+        },
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(AggregationIsmError::ThresholdNotMet as u32),
+        ),
+    );
+
+    // Lower the threshold, and have the second sub-ISM reject messages
+    set_modules_and_threshold(
+        &mut banks_client,
+        &payer,
+        ModulesAndThreshold {
+            modules: vec![test_ism_a_id(), test_ism_b_id()],
+            threshold: 1,
+        },
+    )
+    .await
+    .unwrap();
+    set_test_ism_accept(&mut banks_client, &payer, test_ism_b_id(), false).await;
+
+    // Metadata for only the accepting sub-ISM meets the threshold
+    verify(
+        &mut banks_client,
+        &payer,
+        AggregationIsmMetadata {
+            sub_metadatas: vec![Some(vec![1, 2, 3, 4]), None],
+        },
+    )
+    .await
+    .unwrap();
+
+    // Metadata for a rejecting sub-ISM fails verification
+    let result = verify(
+        &mut banks_client,
+        &payer,
+        AggregationIsmMetadata {
+            sub_metadatas: vec![Some(vec![1, 2, 3, 4]), Some(vec![5])],
+        },
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TestIsmError::VerifyNotAccepted as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_ism_verify_with_routing_sub_ism() {
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+    initialize_test_ism(&mut banks_client, &payer, test_ism_a_id()).await;
+    initialize_test_ism(&mut banks_client, &payer, test_ism_b_id()).await;
+    // The routing ISM routes the test message to test ISM B
+    initialize_routing_ism(&mut banks_client, &payer, test_ism_b_id()).await;
+
+    set_modules_and_threshold(
+        &mut banks_client,
+        &payer,
+        ModulesAndThreshold {
+            modules: vec![routing_ism_id(), test_ism_a_id()],
+            threshold: 2,
+        },
+    )
+    .await
+    .unwrap();
+
+    let routing_ism_metadata = vec![1, 2, 3];
+    let test_ism_a_metadata = vec![4, 5];
+
+    // The routing ISM's account metas include those of the routed ISM, so they
+    // can't be told apart from test ISM A's by looking for its program
+    let metadata = AggregationIsmMetadata {
+        sub_metadatas: vec![
+            Some(routing_ism_metadata.clone()),
+            Some(test_ism_a_metadata.clone()),
+        ],
+    };
+    let message_bytes = test_message().to_vec();
+    let account_metas = get_ism_verify_account_metas(
+        &mut banks_client,
+        &payer,
+        aggregation_ism_id(),
+        metadata.to_vec(),
+        message_bytes.clone(),
+    )
+    .await
+    .unwrap();
+    let (modules_and_threshold_pda_key, _) =
+        Pubkey::find_program_address(modules_and_threshold_pda_seeds!(), &aggregation_ism_id());
+    let routing_ism_account_metas = get_ism_verify_account_metas(
+        &mut banks_client,
+        &payer,
+        routing_ism_id(),
+        routing_ism_metadata,
+        message_bytes.clone(),
+    )
+    .await
+    .unwrap();
+    let test_ism_a_account_metas = get_ism_verify_account_metas(
+        &mut banks_client,
+        &payer,
+        test_ism_a_id(),
+        test_ism_a_metadata,
+        message_bytes,
+    )
+    .await
+    .unwrap();
+    assert!(routing_ism_account_metas.contains(&AccountMeta::new_readonly(test_ism_b_id(), false)));
+    let account_counts = [
+        routing_ism_account_metas.len() as u8,
+        test_ism_a_account_metas.len() as u8,
+    ];
+    assert_eq!(
+        account_metas,
+        [
+            vec![
+                AccountMeta::new_readonly(modules_and_threshold_pda_key, false),
+                AccountMeta::new_readonly(
+                    sub_ism_account_counts_key(&account_counts).unwrap(),
+                    false,
+                ),
+                AccountMeta::new_readonly(routing_ism_id(), false),
+            ],
+            routing_ism_account_metas,
+            vec![AccountMeta::new_readonly(test_ism_a_id(), false)],
+            test_ism_a_account_metas,
+        ]
+        .concat(),
+    );
+
+    // Both sub-ISMs, and the ISM routed to, accept the message
+    verify(&mut banks_client, &payer, metadata).await.unwrap();
+
+    // The routed ISM rejecting the message fails verification.
+    // Use a new payer to get a new tx ID, because the instruction data is the same
+    set_test_ism_accept(&mut banks_client, &payer, test_ism_b_id(), false).await;
+    let new_payer = new_funded_keypair(&mut banks_client, &payer, 1000000000).await;
+    let result = verify(
+        &mut banks_client,
+        &new_payer,
+        AggregationIsmMetadata {
+            sub_metadatas: vec![Some(vec![1, 2, 3]), Some(vec![4, 5])],
+        },
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TestIsmError::VerifyNotAccepted as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_ism_type() {
+    let (mut banks_client, payer) = setup_client().await;
+
+    let type_u32 = simulate_instruction::<SimulationReturnData<u32>>(
+        &mut banks_client,
+        &payer,
+        Instruction::new_with_bytes(
+            aggregation_ism_id(),
+            &InterchainSecurityModuleInstruction::Type.encode().unwrap(),
+            vec![],
+        ),
+    )
+    .await
+    .unwrap()
+    .unwrap()
+    .return_data;
+    assert_eq!(type_u32, ModuleType::Aggregation as u32);
+}
@@ -0,0 +1,12 @@
+//! A multisig Interchain Security Module that accepts signatures over
+//! a checkpoint's merkle root, and a merkle proof of the ID of the message
+//! being verified against that root.
+
+#![deny(warnings)]
+#![deny(unsafe_code)]
+
+pub mod accounts;
+pub mod error;
+pub mod instruction;
+pub mod metadata;
+pub mod processor;
@@ -0,0 +1,193 @@
+use ecdsa_signature::EcdsaSignature;
+use hyperlane_core::{accumulator::TREE_DEPTH, Encode, H256};
+
+use crate::error::Error;
+
+#[derive(Debug)]
+pub struct MultisigIsmMerkleRootMetadata {
+    pub origin_merkle_tree_hook: H256,
+    pub message_index: u32,
+    pub signed_message_id: H256,
+    pub merkle_proof: [H256; TREE_DEPTH],
+    pub signed_index: u32,
+    pub validator_signatures: Vec<EcdsaSignature>,
+}
+
+const ORIGIN_MERKLE_TREE_HOOK_OFFSET: usize = 0;
+const MESSAGE_INDEX_OFFSET: usize = 32;
+const SIGNED_MESSAGE_ID_OFFSET: usize = 36;
+const MERKLE_PROOF_OFFSET: usize = 68;
+const MERKLE_PROOF_LENGTH: usize = TREE_DEPTH * 32;
+const SIGNED_INDEX_OFFSET: usize = MERKLE_PROOF_OFFSET + MERKLE_PROOF_LENGTH;
+const SIGNATURES_OFFSET: usize = SIGNED_INDEX_OFFSET + 4;
+const SIGNATURE_LENGTH: usize = 65;
+
+/// Format of metadata:
+/// [   0:  32] Origin merkle tree hook address
+/// [  32:  36] Index of message ID in merkle tree
+/// [  36:  68] Signed checkpoint message ID
+/// [  68:1092] Merkle proof
+/// [1092:1096] Signed checkpoint index (computed from proof and index)
+/// [1096:????] Validator signatures (length := threshold * 65)
+/// Note that the validator signatures being the length of the threshold is
+/// not enforced here and should be enforced by the caller.
+impl TryFrom<Vec<u8>> for MultisigIsmMerkleRootMetadata {
+    type Error = Error;
+
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Self::Error> {
+        let bytes_len = bytes.len();
+        // Require the bytes to be at least big enough to include a single signature.
+        if bytes_len < SIGNATURES_OFFSET + SIGNATURE_LENGTH {
+            return Err(Error::InvalidMetadata);
+        }
+
+        let origin_merkle_tree_hook =
+            H256::from_slice(&bytes[ORIGIN_MERKLE_TREE_HOOK_OFFSET..MESSAGE_INDEX_OFFSET]);
+        // This cannot panic since SIGNED_MESSAGE_ID_OFFSET - MESSAGE_INDEX_OFFSET is 4.
+        let message_index_bytes: [u8; 4] = bytes[MESSAGE_INDEX_OFFSET..SIGNED_MESSAGE_ID_OFFSET]
+            .try_into()
+            .map_err(|_| Error::InvalidMetadata)?;
+        let message_index = u32::from_be_bytes(message_index_bytes);
+        let signed_message_id =
+            H256::from_slice(&bytes[SIGNED_MESSAGE_ID_OFFSET..MERKLE_PROOF_OFFSET]);
+
+        let mut merkle_proof = [H256::zero(); TREE_DEPTH];
+        for (i, branch) in merkle_proof.iter_mut().enumerate() {
+            let branch_offset = MERKLE_PROOF_OFFSET + (i * 32);
+            *branch = H256::from_slice(&bytes[branch_offset..branch_offset + 32]);
+        }
+
+        // This cannot panic since SIGNATURES_OFFSET - SIGNED_INDEX_OFFSET is 4.
+        let signed_index_bytes: [u8; 4] = bytes[SIGNED_INDEX_OFFSET..SIGNATURES_OFFSET]
+            .try_into()
+            .map_err(|_| Error::InvalidMetadata)?;
+        let signed_index = u32::from_be_bytes(signed_index_bytes);
+
+        let signature_bytes_len = bytes_len - SIGNATURES_OFFSET;
+        // Require the signature bytes to be a multiple of the signature length.
+        // We don't need to check if signature_bytes_len is 0 because this is checked
+        // above.
+        if signature_bytes_len % SIGNATURE_LENGTH != 0 {
+            return Err(Error::InvalidMetadata);
+        }
+        let signature_count = signature_bytes_len / SIGNATURE_LENGTH;
+        let mut validator_signatures = Vec::with_capacity(signature_count);
+        for i in 0..signature_count {
+            let signature_offset = SIGNATURES_OFFSET + (i * SIGNATURE_LENGTH);
+            let signature = EcdsaSignature::from_bytes(
+                &bytes[signature_offset..signature_offset + SIGNATURE_LENGTH],
+            )
+            .map_err(|_| Error::InvalidMetadata)?;
+            validator_signatures.push(signature);
+        }
+
+        Ok(Self {
+            origin_merkle_tree_hook,
+            message_index,
+            signed_message_id,
+            merkle_proof,
+            signed_index,
+            validator_signatures,
+        })
+    }
+}
+
+impl Encode for MultisigIsmMerkleRootMetadata {
+    fn write_to<W>(&self, writer: &mut W) -> std::io::Result<usize>
+    where
+        W: std::io::Write,
+    {
+        let mut bytes_written = 0;
+        bytes_written += writer.write(self.origin_merkle_tree_hook.as_ref())?;
+        bytes_written += writer.write(&self.message_index.to_be_bytes())?;
+        bytes_written += writer.write(self.signed_message_id.as_ref())?;
+        for branch in &self.merkle_proof {
+            bytes_written += writer.write(branch.as_ref())?;
+        }
+        bytes_written += writer.write(&self.signed_index.to_be_bytes())?;
+        for signature in &self.validator_signatures {
+            bytes_written += writer.write(&signature.as_fixed_bytes()[..])?;
+        }
+        Ok(bytes_written)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::Rng;
+
+    // Provide a default test implementation
+    fn dummy_metadata_with_sigs(sigs: Vec<EcdsaSignature>) -> MultisigIsmMerkleRootMetadata {
+        let mut rng = rand::thread_rng();
+        let mut merkle_proof = [H256::zero(); TREE_DEPTH];
+        for branch in merkle_proof.iter_mut() {
+            *branch = H256::random();
+        }
+        MultisigIsmMerkleRootMetadata {
+            origin_merkle_tree_hook: H256::random(),
+            message_index: rng.gen(),
+            signed_message_id: H256::random(),
+            merkle_proof,
+            signed_index: rng.gen(),
+            validator_signatures: sigs,
+        }
+    }
+
+    #[test]
+    fn test_decode_correctly_formatted_metadata() {
+        let validator_signatures = vec![
+            EcdsaSignature {
+                serialized_rs: [11u8; 64],
+                recovery_id: 0,
+            },
+            EcdsaSignature {
+                serialized_rs: [12u8; 64],
+                recovery_id: 1,
+            },
+            EcdsaSignature {
+                serialized_rs: [13u8; 64],
+                recovery_id: 0,
+            },
+        ];
+        let test_meta = dummy_metadata_with_sigs(validator_signatures);
+        let encoded_meta = test_meta.to_vec();
+        assert_eq!(encoded_meta.len(), SIGNATURES_OFFSET + 3 * SIGNATURE_LENGTH);
+        let metadata = MultisigIsmMerkleRootMetadata::try_from(encoded_meta).unwrap();
+        assert_eq!(
+            metadata.origin_merkle_tree_hook,
+            test_meta.origin_merkle_tree_hook
+        );
+        assert_eq!(metadata.message_index, test_meta.message_index);
+        assert_eq!(metadata.signed_message_id, test_meta.signed_message_id);
+        assert_eq!(metadata.merkle_proof, test_meta.merkle_proof);
+        assert_eq!(metadata.signed_index, test_meta.signed_index);
+        assert_eq!(
+            metadata.validator_signatures,
+            test_meta.validator_signatures
+        );
+    }
+
+    #[test]
+    fn test_decode_no_signatures_is_err() {
+        let test_meta = dummy_metadata_with_sigs(vec![]);
+        let encoded_meta = test_meta.to_vec();
+        let result = MultisigIsmMerkleRootMetadata::try_from(encoded_meta);
+        assert!(result.unwrap_err() == Error::InvalidMetadata);
+    }
+
+    #[test]
+    fn test_decode_incorrect_signature_length_is_err() {
+        let sigs = vec![EcdsaSignature {
+            serialized_rs: [1u8; 64],
+            recovery_id: 0,
+        }];
+        let test_meta = dummy_metadata_with_sigs(sigs);
+        let encoded_meta = test_meta.to_vec();
+        // remove the last byte from the encoded signature
+        let faulty_encoded_meta = encoded_meta[..encoded_meta.len() - 1].to_vec();
+        let result = MultisigIsmMerkleRootMetadata::try_from(faulty_encoded_meta);
+        assert!(result.unwrap_err() == Error::InvalidMetadata);
+        MultisigIsmMerkleRootMetadata::try_from(encoded_meta).expect("Decoding should succeed");
+    }
+}
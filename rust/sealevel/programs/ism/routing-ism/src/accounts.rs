@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use access_control::AccessControl;
+use account_utils::{AccountData, SizedData};
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::error::Error;
+
+/// The data of the domain routes PDA account.
+/// The PDA is derived from the `VERIFY_ACCOUNT_METAS_PDA_SEEDS`, so that it's
+/// the first account passed into the `VerifyAccountMetas` instruction.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, PartialEq)]
+pub struct DomainRoutes {
+    pub bump_seed: u8,
+    /// The ISM program ID to route messages to, keyed by origin domain.
+    pub routes: HashMap<u32, Pubkey>,
+}
+
+impl DomainRoutes {
+    /// Gets the ISM program ID that messages from the provided origin domain
+    /// are routed to.
+    pub fn route(&self, origin: u32) -> Result<Pubkey, ProgramError> {
+        self.routes
+            .get(&origin)
+            .copied()
+            .ok_or_else(|| Error::NoRouteForDomain.into())
+    }
+
+    /// Sets the ISM program ID for a domain, or removes the route if `None`.
+    pub fn set_route(&mut self, domain: u32, ism: Option<Pubkey>) {
+        match ism {
+            Some(ism) => self.routes.insert(domain, ism),
+            None => self.routes.remove(&domain),
+        };
+    }
+}
+
+impl SizedData for DomainRoutes {
+    fn size(&self) -> usize {
+        // 1 byte bump seed
+        // 4 byte routes len + (4 byte domain + 32 byte ISM pubkey) per route
+        1 + 4 + (self.routes.len() * (4 + 32))
+    }
+}
+
+pub type DomainRoutesAccount = AccountData<DomainRoutes>;
+
+/// The data of the access control PDA account.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, PartialEq)]
+pub struct AccessControlData {
+    pub bump_seed: u8,
+    pub owner: Option<Pubkey>,
+}
+
+impl SizedData for AccessControlData {
+    fn size(&self) -> usize {
+        // 1 byte bump seed + 1 byte Option variant + 32 byte owner pubkey
+        1 + 1 + 32
+    }
+}
+
+impl AccessControl for AccessControlData {
+    fn owner(&self) -> Option<&Pubkey> {
+        self.owner.as_ref()
+    }
+
+    fn set_owner(&mut self, new_owner: Option<Pubkey>) -> Result<(), ProgramError> {
+        self.owner = new_owner;
+        Ok(())
+    }
+}
+
+pub type AccessControlAccount = AccountData<AccessControlData>;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_access_control_data_size() {
+        let data = AccessControlData {
+            bump_seed: 0,
+            owner: Some(Pubkey::new_unique()),
+        };
+        let serialized = data.try_to_vec().unwrap();
+        assert_eq!(data.size(), serialized.len());
+    }
+
+    #[test]
+    fn test_domain_routes_size() {
+        let data = DomainRoutes {
+            bump_seed: 0,
+            routes: HashMap::from([(1, Pubkey::new_unique()), (2, Pubkey::new_unique())]),
+        };
+        let serialized = data.try_to_vec().unwrap();
+        assert_eq!(data.size(), serialized.len());
+    }
+
+    #[test]
+    fn test_set_route() {
+        let mut data = DomainRoutes::default();
+        let ism = Pubkey::new_unique();
+
+        data.set_route(1, Some(ism));
+        assert_eq!(data.route(1), Ok(ism));
+        assert_eq!(data.route(2), Err(Error::NoRouteForDomain.into()));
+
+        data.set_route(1, None);
+        assert_eq!(data.route(1), Err(Error::NoRouteForDomain.into()));
+    }
+}
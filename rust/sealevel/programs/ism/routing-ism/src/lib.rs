@@ -0,0 +1,10 @@
+//! An Interchain Security Module that routes the verification of a message
+//! to an ISM configured for the message's origin domain.
+
+#![deny(warnings)]
+#![deny(unsafe_code)]
+
+pub mod accounts;
+pub mod error;
+pub mod instruction;
+pub mod processor;
@@ -0,0 +1,497 @@
+use hyperlane_core::{Decode, HyperlaneMessage, ModuleType};
+
+use access_control::AccessControl;
+use account_utils::{create_pda_account, DiscriminatorDecode, SizedData};
+use borsh::{BorshDeserialize, BorshSerialize};
+use serializable_account_meta::{SerializableAccountMeta, SimulationReturnData};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction as SolanaInstruction},
+    program::{get_return_data, invoke, set_return_data},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    accounts::{AccessControlAccount, AccessControlData, DomainRoutes, DomainRoutesAccount},
+    error::Error,
+    instruction::{Domained, Instruction},
+};
+
+use hyperlane_sealevel_interchain_security_module_interface::{
+    InterchainSecurityModuleInstruction, VerifyInstruction, VERIFY_ACCOUNT_METAS_PDA_SEEDS,
+};
+
+const ISM_TYPE: ModuleType = ModuleType::Routing;
+
+#[cfg(not(feature = "no-entrypoint"))]
+solana_program::entrypoint!(process_instruction);
+
+/// PDA seeds relating to the access control PDA account.
+#[macro_export]
+macro_rules! access_control_pda_seeds {
+    () => {{
+        &[b"routing_ism", b"-", b"access_control"]
+    }};
+
+    ($bump_seed:expr) => {{
+        &[b"routing_ism", b"-", b"access_control", &[$bump_seed]]
+    }};
+}
+
+/// PDA seeds relating to the domain routes PDA account.
+/// These are the `VERIFY_ACCOUNT_METAS_PDA_SEEDS`, so that the domain routes
+/// are readable by the `VerifyAccountMetas` instruction.
+#[macro_export]
+macro_rules! domain_routes_pda_seeds {
+    () => {{
+        &[b"hyperlane_ism", b"-", b"verify", b"-", b"account_metas"]
+    }};
+
+    ($bump_seed:expr) => {{
+        &[
+            b"hyperlane_ism",
+            b"-",
+            b"verify",
+            b"-",
+            b"account_metas",
+            &[$bump_seed],
+        ]
+    }};
+}
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    // First, try to decode the instruction as an interchain security module
+    // interface supported function based off the discriminator.
+    if let Ok(ism_instruction) = InterchainSecurityModuleInstruction::decode(instruction_data) {
+        return match ism_instruction {
+            InterchainSecurityModuleInstruction::Type => {
+                set_return_data(
+                    &SimulationReturnData::new(ISM_TYPE as u32)
+                        .try_to_vec()
+                        .map_err(|err| ProgramError::BorshIoError(err.to_string()))?[..],
+                );
+                return Ok(());
+            }
+            InterchainSecurityModuleInstruction::Verify(verify_data) => {
+                verify(program_id, accounts, verify_data)
+            }
+            InterchainSecurityModuleInstruction::VerifyAccountMetas(verify_data) => {
+                let account_metas = verify_account_metas(program_id, accounts, verify_data)?;
+                // Wrap it in the SimulationReturnData because serialized account_metas
+                // may end with zero byte(s), which are incorrectly truncated as
+                // simulated transaction return data.
+                // See `SimulationReturnData` for details.
+                let bytes = SimulationReturnData::new(account_metas)
+                    .try_to_vec()
+                    .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+                set_return_data(&bytes[..]);
+                Ok(())
+            }
+        };
+    }
+
+    match Instruction::decode(instruction_data)? {
+        // Initializes the program.
+        Instruction::Initialize => initialize(program_id, accounts),
+        // Sets the ISMs that messages from domains are routed to.
+        Instruction::SetRoutes(routes) => set_routes(program_id, accounts, routes),
+        // Gets the ISM that messages from a domain are routed to.
+        Instruction::Route(origin) => get_route(program_id, accounts, origin),
+        // Gets the owner of this program from the access control account.
+        Instruction::GetOwner => get_owner(program_id, accounts),
+        // Sets the owner of this program in the access control account.
+        Instruction::TransferOwnership(new_owner) => {
+            transfer_ownership(program_id, accounts, new_owner)
+        }
+    }
+}
+
+/// Initializes the program, creating the access control and domain routes PDA accounts.
+///
+/// Accounts:
+/// 0. `[signer]` The new owner and payer of the access control and domain routes PDAs.
+/// 1. `[writable]` The access control PDA account.
+/// 2. `[writable]` The domain routes PDA account.
+/// 3. `[executable]` The system program account.
+fn initialize(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The new owner of this program and payer of the PDAs.
+    let owner_account = next_account_info(accounts_iter)?;
+    if !owner_account.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    // Account 1: The access control PDA account.
+    let access_control_pda_account = next_account_info(accounts_iter)?;
+    let (access_control_pda_key, access_control_pda_bump_seed) =
+        Pubkey::find_program_address(access_control_pda_seeds!(), program_id);
+    if *access_control_pda_account.key != access_control_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    // Ensure the access control PDA account isn't already initialized.
+    if let Ok(Some(_)) =
+        AccessControlAccount::fetch_data(&mut &access_control_pda_account.data.borrow()[..])
+    {
+        return Err(Error::AlreadyInitialized.into());
+    }
+
+    // Account 2: The domain routes PDA account.
+    let domain_routes_pda_account = next_account_info(accounts_iter)?;
+    let (domain_routes_pda_key, domain_routes_pda_bump_seed) =
+        Pubkey::find_program_address(domain_routes_pda_seeds!(), program_id);
+    if *domain_routes_pda_account.key != domain_routes_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    // Account 3: The system program account.
+    let system_program_account = next_account_info(accounts_iter)?;
+    if !solana_program::system_program::check_id(system_program_account.key) {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    let rent = Rent::get()?;
+
+    // Create the access control PDA account.
+    let access_control_account = AccessControlAccount::from(AccessControlData {
+        bump_seed: access_control_pda_bump_seed,
+        owner: Some(*owner_account.key),
+    });
+    create_pda_account(
+        owner_account,
+        &rent,
+        access_control_account.size(),
+        program_id,
+        system_program_account,
+        access_control_pda_account,
+        access_control_pda_seeds!(access_control_pda_bump_seed),
+    )?;
+    access_control_account.store(access_control_pda_account, false)?;
+
+    // Create the domain routes PDA account, without any routes.
+    let domain_routes_account = DomainRoutesAccount::from(DomainRoutes {
+        bump_seed: domain_routes_pda_bump_seed,
+        routes: Default::default(),
+    });
+    create_pda_account(
+        owner_account,
+        &rent,
+        domain_routes_account.size(),
+        program_id,
+        system_program_account,
+        domain_routes_pda_account,
+        domain_routes_pda_seeds!(domain_routes_pda_bump_seed),
+    )?;
+    domain_routes_account.store(domain_routes_pda_account, false)?;
+
+    Ok(())
+}
+
+/// Verifies a message by invoking the `Verify` instruction of the ISM that
+/// messages from the message's origin domain are routed to.
+///
+/// Accounts:
+/// 0. `[]` The domain routes PDA account.
+/// 1. `[executable]` The ISM program that the message's origin domain is routed to.
+/// 2..N. `[??]` The accounts required by the routed ISM's `Verify` instruction.
+fn verify(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    verify_data: VerifyInstruction,
+) -> ProgramResult {
+    let message = HyperlaneMessage::read_from(&mut &verify_data.message[..])
+        .map_err(|_| ProgramError::InvalidArgument)?;
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The domain routes PDA account.
+    let domain_routes_pda_account = next_account_info(accounts_iter)?;
+    let ism = domain_routes(program_id, domain_routes_pda_account)?.route(message.origin)?;
+
+    // Account 1: The routed ISM program.
+    let ism_account = next_account_info(accounts_iter)?;
+    if *ism_account.key != ism {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    // Accounts 2..N: The accounts required by the routed ISM's `Verify` instruction.
+    let ism_verify_account_infos = accounts_iter.as_slice();
+
+    let verify_instruction = SolanaInstruction::new_with_bytes(
+        ism,
+        &InterchainSecurityModuleInstruction::Verify(verify_data).encode()?,
+        to_account_metas(ism_verify_account_infos),
+    );
+    invoke(&verify_instruction, ism_verify_account_infos)
+}
+
+/// Gets the list of AccountMetas required by the `Verify` instruction.
+///
+/// Because the accounts required by the routed ISM can only be known by
+/// invoking the routed ISM's `VerifyAccountMetas` instruction, which requires
+/// the routed ISM program to be passed in, this may take multiple simulations.
+/// The returned list always starts with the domain routes PDA, which is this program's
+/// `VERIFY_ACCOUNT_METAS_PDA_SEEDS` PDA. Callers are expected to simulate this
+/// instruction again with the returned list as the accounts until it's unchanged.
+///
+/// Accounts:
+/// 0. `[]` The domain routes PDA account.
+/// 1. `[executable]` OPTIONAL - The ISM program that the message's origin domain is routed to.
+/// 2..N. `[??]` OPTIONAL - The accounts to pass into the routed ISM's `VerifyAccountMetas` instruction.
+fn verify_account_metas(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    verify_data: VerifyInstruction,
+) -> Result<Vec<SerializableAccountMeta>, ProgramError> {
+    let message = HyperlaneMessage::read_from(&mut &verify_data.message[..])
+        .map_err(|_| ProgramError::InvalidArgument)?;
+
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The domain routes PDA account.
+    let domain_routes_pda_account = next_account_info(accounts_iter)?;
+    let ism = domain_routes(program_id, domain_routes_pda_account)?.route(message.origin)?;
+
+    let mut account_metas: Vec<SerializableAccountMeta> = vec![
+        AccountMeta::new_readonly(*domain_routes_pda_account.key, false).into(),
+        AccountMeta::new_readonly(ism, false).into(),
+    ];
+
+    // Account 1: The routed ISM program, if provided.
+    // If it wasn't provided, or the route has since changed, the routed ISM's
+    // `VerifyAccountMetas` instruction can't be invoked yet. Instead, return the
+    // accounts that must be passed in to do so.
+    match next_account_info(accounts_iter) {
+        Ok(ism_account) if *ism_account.key == ism => {}
+        _ => {
+            let (ism_verify_account_metas_pda_key, _) =
+                Pubkey::find_program_address(VERIFY_ACCOUNT_METAS_PDA_SEEDS, &ism);
+            account_metas
+                .push(AccountMeta::new_readonly(ism_verify_account_metas_pda_key, false).into());
+            return Ok(account_metas);
+        }
+    }
+
+    // Accounts 2..N: The accounts to pass into the routed ISM's `VerifyAccountMetas` instruction.
+    let ism_account_infos = accounts_iter.as_slice();
+
+    let verify_account_metas_instruction = SolanaInstruction::new_with_bytes(
+        ism,
+        &InterchainSecurityModuleInstruction::VerifyAccountMetas(verify_data).encode()?,
+        to_account_metas(ism_account_infos),
+    );
+    invoke(&verify_account_metas_instruction, ism_account_infos)?;
+
+    let (return_data_program_id, return_data) =
+        get_return_data().ok_or(Error::InvalidReturnData)?;
+    if return_data_program_id != ism {
+        return Err(Error::InvalidReturnData.into());
+    }
+    let ism_account_metas =
+        SimulationReturnData::<Vec<SerializableAccountMeta>>::try_from_slice(&return_data[..])
+            .map_err(|_| Error::InvalidReturnData)?
+            .return_data;
+    account_metas.extend(ism_account_metas);
+
+    Ok(account_metas)
+}
+
+/// Gets the ISM that messages from the provided origin domain are routed to,
+/// and returns it as return data.
+///
+/// Accounts:
+/// 0. `[]` The domain routes PDA account.
+fn get_route(program_id: &Pubkey, accounts: &[AccountInfo], origin: u32) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The domain routes PDA account.
+    let domain_routes_pda_account = next_account_info(accounts_iter)?;
+    let ism = domain_routes(program_id, domain_routes_pda_account)?.route(origin)?;
+
+    // Wrap it in the SimulationReturnData because serialized `ism`
+    // may end with zero byte(s), which are incorrectly truncated as
+    // simulated transaction return data.
+    // See `SimulationReturnData` for details.
+    let bytes = SimulationReturnData::new(ism)
+        .try_to_vec()
+        .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+    set_return_data(&bytes[..]);
+    Ok(())
+}
+
+/// Sets the ISMs that messages from domains are routed to.
+///
+/// Accounts:
+/// 0. `[signer]` The access control owner and payer of any domain routes PDA reallocation.
+/// 1. `[]` The access control PDA account.
+/// 2. `[writable]` The domain routes PDA account.
+/// 3. `[executable]` The system program account.
+fn set_routes(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    routes: Vec<Domained<Option<Pubkey>>>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The owner of this program.
+    // This is verified as correct further below.
+    let owner_account = next_account_info(accounts_iter)?;
+
+    // Account 1: The access control PDA account.
+    let access_control_pda_account = next_account_info(accounts_iter)?;
+    let access_control_data = access_control_data(program_id, access_control_pda_account)?;
+    // Ensure the owner account is the owner of this program.
+    access_control_data.ensure_owner_signer(owner_account)?;
+
+    // Account 2: The domain routes PDA account.
+    let domain_routes_pda_account = next_account_info(accounts_iter)?;
+    let mut domain_routes = domain_routes(program_id, domain_routes_pda_account)?;
+
+    // Account 3: The system program account.
+    let system_program_account = next_account_info(accounts_iter)?;
+    if !solana_program::system_program::check_id(system_program_account.key) {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    for route in routes {
+        domain_routes.set_route(route.domain, route.data);
+    }
+
+    DomainRoutesAccount::from(domain_routes).store_with_rent_exempt_realloc(
+        domain_routes_pda_account,
+        &Rent::get()?,
+        owner_account,
+        system_program_account,
+    )?;
+
+    Ok(())
+}
+
+/// Gets the owner of this program from the access control account, and returns it as return data.
+/// Intended to be used by instructions querying the owner.
+///
+/// Accounts:
+/// 0. `[]` The access control PDA account.
+fn get_owner(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The access control PDA account.
+    let access_control_pda_account = next_account_info(accounts_iter)?;
+
+    let access_control_data = access_control_data(program_id, access_control_pda_account)?;
+
+    // Wrap it in the SimulationReturnData because serialized `access_control_data.owner`
+    // may end with zero byte(s), which are incorrectly truncated as
+    // simulated transaction return data.
+    // See `SimulationReturnData` for details.
+    let bytes = SimulationReturnData::new(access_control_data.owner)
+        .try_to_vec()
+        .map_err(|err| ProgramError::BorshIoError(err.to_string()))?;
+    set_return_data(&bytes[..]);
+    Ok(())
+}
+
+/// Gets the domain routes of this program.
+/// Returns an Err if the provided account isn't the domain routes PDA.
+fn domain_routes(
+    program_id: &Pubkey,
+    domain_routes_pda_account: &AccountInfo,
+) -> Result<DomainRoutes, ProgramError> {
+    // Sanity check that the owner of the PDA account is this program
+    if domain_routes_pda_account.owner != program_id {
+        return Err(Error::ProgramIdNotOwner.into());
+    }
+    let domain_routes =
+        DomainRoutesAccount::fetch_data(&mut &domain_routes_pda_account.data.borrow()[..])?
+            .ok_or(Error::AccountNotInitialized)?;
+    // Confirm the key of the domain_routes_pda_account is the correct PDA
+    // using the stored bump seed.
+    let domain_routes_pda_key = Pubkey::create_program_address(
+        domain_routes_pda_seeds!(domain_routes.bump_seed),
+        program_id,
+    )?;
+    // This check validates that the provided domain_routes_pda_account is valid
+    if *domain_routes_pda_account.key != domain_routes_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+
+    Ok(*domain_routes)
+}
+
+/// Gets the access control data of this program.
+/// Returns an Err if the provided account isn't the access control PDA.
+fn access_control_data(
+    program_id: &Pubkey,
+    access_control_pda_account: &AccountInfo,
+) -> Result<AccessControlData, ProgramError> {
+    let access_control_data =
+        AccessControlAccount::fetch_data(&mut &access_control_pda_account.data.borrow()[..])?
+            .ok_or(Error::AccountNotInitialized)?;
+    // Confirm the key of the access_control_pda_account is the correct PDA
+    // using the stored bump seed.
+    let access_control_pda_key = Pubkey::create_program_address(
+        access_control_pda_seeds!(access_control_data.bump_seed),
+        program_id,
+    )?;
+    // This check validates that the provided access_control_pda_account is valid
+    if *access_control_pda_account.key != access_control_pda_key {
+        return Err(Error::AccountOutOfOrder.into());
+    }
+    // Extra sanity check that the owner of the PDA account is this program
+    if access_control_pda_account.owner != program_id {
+        return Err(Error::ProgramIdNotOwner.into());
+    }
+
+    Ok(*access_control_data)
+}
+
+/// Transfers ownership to a new access control owner.
+///
+/// Accounts:
+/// 0. `[signer]` The current access control owner.
+/// 1. `[writeable]` The access control PDA account.
+fn transfer_ownership(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_owner: Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: The current access control owner.
+    // This is verified as correct further below.
+    let owner_account = next_account_info(accounts_iter)?;
+
+    // Account 1: The access control PDA account.
+    let access_control_pda_account = next_account_info(accounts_iter)?;
+    let mut access_control_data = access_control_data(program_id, access_control_pda_account)?;
+
+    // Transfer ownership. This errors if `owner_account` is not a signer or the owner.
+    access_control_data.transfer_ownership(owner_account, new_owner)?;
+
+    // Store the new access control owner.
+    AccessControlAccount::from(access_control_data).store(access_control_pda_account, false)?;
+
+    Ok(())
+}
+
+/// Converts the provided account infos into the account metas of an instruction
+/// that's invoked with them.
+fn to_account_metas(account_infos: &[AccountInfo]) -> Vec<AccountMeta> {
+    account_infos
+        .iter()
+        .map(|account_info| AccountMeta {
+            pubkey: *account_info.key,
+            is_signer: account_info.is_signer,
+            is_writable: account_info.is_writable,
+        })
+        .collect()
+}
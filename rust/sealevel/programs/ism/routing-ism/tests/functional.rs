@@ -0,0 +1,402 @@
+//! Contains functional tests for things that cannot be done
+//! strictly in unit tests. This includes CPIs, like creating
+//! new PDA accounts.
+
+use account_utils::DiscriminatorEncode;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+
+use hyperlane_core::{Encode, HyperlaneMessage, ModuleType, H256};
+use hyperlane_sealevel_interchain_security_module_interface::{
+    InterchainSecurityModuleInstruction, VerifyInstruction, VERIFY_ACCOUNT_METAS_PDA_SEEDS,
+};
+use hyperlane_sealevel_routing_ism::{
+    access_control_pda_seeds,
+    accounts::{AccessControlAccount, AccessControlData, DomainRoutes, DomainRoutesAccount},
+    domain_routes_pda_seeds,
+    error::Error as RoutingIsmError,
+    instruction::{
+        init_instruction, set_routes_instruction, Domained,
+        Instruction as RoutingIsmProgramInstruction,
+    },
+    processor::process_instruction,
+};
+use hyperlane_sealevel_test_ism::{program::TestIsmError, test_client::TestIsmTestClient};
+use hyperlane_test_utils::{
+    assert_transaction_error, clone_keypair, get_ism_verify_account_metas, new_funded_keypair,
+    process_instruction as process_test_instruction, simulate_instruction,
+};
+use serializable_account_meta::SimulationReturnData;
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::InstructionError, signature::Signer, signer::keypair::Keypair,
+    transaction::TransactionError,
+};
+
+const ORIGIN_DOMAIN: u32 = 1234;
+
+fn routing_ism_id() -> Pubkey {
+    pubkey!("HiQW3qzw9NuNQgBudyjUL63KL4WizjG1L67NKrr2DhG3")
+}
+
+async fn setup_client() -> (BanksClient, Keypair) {
+    let program_id = routing_ism_id();
+    let mut program_test = ProgramTest::new(
+        "hyperlane_sealevel_routing_ism",
+        program_id,
+        processor!(process_instruction),
+    );
+
+    program_test.add_program(
+        "hyperlane_sealevel_test_ism",
+        hyperlane_sealevel_test_ism::id(),
+        processor!(hyperlane_sealevel_test_ism::program::process_instruction),
+    );
+
+    let (banks_client, payer, _recent_blockhash) = program_test.start().await;
+
+    (banks_client, payer)
+}
+
+async fn initialize(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+) -> Result<(), BanksClientError> {
+    process_test_instruction(
+        banks_client,
+        init_instruction(routing_ism_id(), payer.pubkey()).unwrap(),
+        payer,
+        &[payer],
+    )
+    .await?;
+    Ok(())
+}
+
+async fn set_routes(
+    banks_client: &mut BanksClient,
+    owner: &Keypair,
+    routes: Vec<Domained<Option<Pubkey>>>,
+) -> Result<(), BanksClientError> {
+    process_test_instruction(
+        banks_client,
+        set_routes_instruction(routing_ism_id(), owner.pubkey(), routes).unwrap(),
+        owner,
+        &[owner],
+    )
+    .await?;
+    Ok(())
+}
+
+async fn get_domain_routes(banks_client: &mut BanksClient) -> DomainRoutes {
+    let (domain_routes_pda_key, _) =
+        Pubkey::find_program_address(domain_routes_pda_seeds!(), &routing_ism_id());
+    let domain_routes_account_data = banks_client
+        .get_account(domain_routes_pda_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    *DomainRoutesAccount::fetch_data(&mut &domain_routes_account_data[..])
+        .unwrap()
+        .unwrap()
+}
+
+fn test_message(origin: u32) -> HyperlaneMessage {
+    HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin,
+        sender: H256::random(),
+        destination: origin + 1,
+        recipient: H256::random(),
+        body: vec![1, 2, 3, 4, 5],
+    }
+}
+
+#[tokio::test]
+async fn test_initialize() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let (access_control_pda_key, access_control_pda_bump_seed) =
+        Pubkey::find_program_address(access_control_pda_seeds!(), &program_id);
+    let access_control_account_data = banks_client
+        .get_account(access_control_pda_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let access_control = AccessControlAccount::fetch_data(&mut &access_control_account_data[..])
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        access_control,
+        Box::new(AccessControlData {
+            bump_seed: access_control_pda_bump_seed,
+            owner: Some(payer.pubkey()),
+        }),
+    );
+
+    let (_, domain_routes_pda_bump_seed) =
+        Pubkey::find_program_address(domain_routes_pda_seeds!(), &program_id);
+    assert_eq!(
+        get_domain_routes(&mut banks_client).await,
+        DomainRoutes {
+            bump_seed: domain_routes_pda_bump_seed,
+            routes: Default::default(),
+        },
+    );
+}
+
+#[tokio::test]
+async fn test_initialize_errors_if_called_twice() {
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    // Use a new payer to get a new tx ID, because the instruction data is the same
+    let new_payer = new_funded_keypair(&mut banks_client, &payer, 1000000000).await;
+    let result = initialize(&mut banks_client, &new_payer).await;
+
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(RoutingIsmError::AlreadyInitialized as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_set_routes() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let ism_a = Pubkey::new_unique();
+    let ism_b = Pubkey::new_unique();
+    set_routes(
+        &mut banks_client,
+        &payer,
+        vec![
+            Domained {
+                domain: ORIGIN_DOMAIN,
+                data: Some(ism_a),
+            },
+            Domained {
+                domain: ORIGIN_DOMAIN + 1,
+                data: Some(ism_b),
+            },
+        ],
+    )
+    .await
+    .unwrap();
+
+    let domain_routes = get_domain_routes(&mut banks_client).await;
+    assert_eq!(domain_routes.routes.len(), 2);
+    assert_eq!(domain_routes.route(ORIGIN_DOMAIN), Ok(ism_a));
+    assert_eq!(domain_routes.route(ORIGIN_DOMAIN + 1), Ok(ism_b));
+
+    // The route can be queried with the Route instruction
+    let (domain_routes_pda_key, _) =
+        Pubkey::find_program_address(domain_routes_pda_seeds!(), &program_id);
+    let route = simulate_instruction::<SimulationReturnData<Pubkey>>(
+        &mut banks_client,
+        &payer,
+        Instruction::new_with_bytes(
+            program_id,
+            &RoutingIsmProgramInstruction::Route(ORIGIN_DOMAIN + 1)
+                .encode()
+                .unwrap(),
+            vec![AccountMeta::new_readonly(domain_routes_pda_key, false)],
+        ),
+    )
+    .await
+    .unwrap()
+    .unwrap()
+    .return_data;
+    assert_eq!(route, ism_b);
+
+    // Now remove a route
+    set_routes(
+        &mut banks_client,
+        &payer,
+        vec![Domained {
+            domain: ORIGIN_DOMAIN,
+            data: None,
+        }],
+    )
+    .await
+    .unwrap();
+
+    let domain_routes = get_domain_routes(&mut banks_client).await;
+    assert_eq!(domain_routes.routes.len(), 1);
+    assert_eq!(
+        domain_routes.route(ORIGIN_DOMAIN),
+        Err(RoutingIsmError::NoRouteForDomain.into())
+    );
+    assert_eq!(domain_routes.route(ORIGIN_DOMAIN + 1), Ok(ism_b));
+}
+
+#[tokio::test]
+async fn test_set_routes_errors_if_not_owner() {
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let non_owner = new_funded_keypair(&mut banks_client, &payer, 1000000000).await;
+    let result = set_routes(
+        &mut banks_client,
+        &non_owner,
+        vec![Domained {
+            domain: ORIGIN_DOMAIN,
+            data: Some(Pubkey::new_unique()),
+        }],
+    )
+    .await;
+
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+}
+
+#[tokio::test]
+async fn test_ism_verify() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    initialize(&mut banks_client, &payer).await.unwrap();
+
+    let mut test_ism = TestIsmTestClient::new(banks_client.clone(), clone_keypair(&payer));
+    test_ism.init().await.unwrap();
+
+    set_routes(
+        &mut banks_client,
+        &payer,
+        vec![Domained {
+            domain: ORIGIN_DOMAIN,
+            data: Some(test_ism.id()),
+        }],
+    )
+    .await
+    .unwrap();
+
+    let verify_instruction = VerifyInstruction {
+        metadata: vec![],
+        message: test_message(ORIGIN_DOMAIN).to_vec(),
+    };
+
+    let account_metas = get_ism_verify_account_metas(
+        &mut banks_client,
+        &payer,
+        program_id,
+        verify_instruction.metadata.clone(),
+        verify_instruction.message.clone(),
+    )
+    .await
+    .unwrap();
+
+    let (domain_routes_pda_key, _) =
+        Pubkey::find_program_address(VERIFY_ACCOUNT_METAS_PDA_SEEDS, &program_id);
+    let test_ism_verify_account_metas = get_ism_verify_account_metas(
+        &mut banks_client,
+        &payer,
+        test_ism.id(),
+        verify_instruction.metadata.clone(),
+        verify_instruction.message.clone(),
+    )
+    .await
+    .unwrap();
+    // The domain routes PDA, the routed ISM, and then the routed ISM's accounts
+    assert_eq!(
+        account_metas,
+        [
+            vec![
+                AccountMeta::new_readonly(domain_routes_pda_key, false),
+                AccountMeta::new_readonly(test_ism.id(), false),
+            ],
+            test_ism_verify_account_metas,
+        ]
+        .concat(),
+    );
+
+    let verify_ixn = Instruction::new_with_bytes(
+        program_id,
+        &InterchainSecurityModuleInstruction::Verify(verify_instruction.clone())
+            .encode()
+            .unwrap(),
+        account_metas.clone(),
+    );
+
+    // The test ISM accepts the message
+    process_test_instruction(&mut banks_client, verify_ixn.clone(), &payer, &[&payer])
+        .await
+        .unwrap();
+
+    // The test ISM rejects the message, and so does the routing ISM.
+    // Use a new payer to get a new tx ID, because the instruction is the same.
+    test_ism.set_accept(false).await.unwrap();
+    let new_payer = new_funded_keypair(&mut banks_client, &payer, 1000000000).await;
+    let result = process_test_instruction(
+        &mut banks_client,
+        verify_ixn.clone(),
+        &new_payer,
+        &[&new_payer],
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(TestIsmError::VerifyNotAccepted as u32),
+        ),
+    );
+
+    // A message from a domain without a route is rejected
+    let verify_ixn = Instruction::new_with_bytes(
+        program_id,
+        &InterchainSecurityModuleInstruction::Verify(VerifyInstruction {
+            metadata: vec![],
+            message: test_message(ORIGIN_DOMAIN + 1).to_vec(),
+        })
+        .encode()
+        .unwrap(),
+        account_metas,
+    );
+    let result = process_test_instruction(&mut banks_client, verify_ixn, &payer, &[&payer]).await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(RoutingIsmError::NoRouteForDomain as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_ism_type() {
+    let program_id = routing_ism_id();
+    let (mut banks_client, payer) = setup_client().await;
+
+    let type_u32 = simulate_instruction::<SimulationReturnData<u32>>(
+        &mut banks_client,
+        &payer,
+        Instruction::new_with_bytes(
+            program_id,
+            &InterchainSecurityModuleInstruction::Type.encode().unwrap(),
+            vec![],
+        ),
+    )
+    .await
+    .unwrap()
+    .unwrap()
+    .return_data;
+    assert_eq!(type_u32, ModuleType::Routing as u32);
+}
@@ -1,12 +1,24 @@
+use account_utils::DiscriminatorEncode;
 use borsh::BorshDeserialize;
 use hyperlane_core::{
-    accumulator::incremental::IncrementalMerkle as MerkleTree, HyperlaneMessage, H256,
+    accumulator::incremental::IncrementalMerkle as MerkleTree, Encode, HyperlaneMessage, H256,
+};
+use hyperlane_sealevel_connection_client::{
+    gas_router::GasRouterConfig, router::RemoteRouterConfig,
+};
+use hyperlane_sealevel_igp::{
+    accounts::{GasPaymentAccount, InterchainGasPaymasterType},
+    instruction::post_dispatch_hook_account_metas,
 };
 use hyperlane_sealevel_mailbox::{
     accounts::{Inbox, InboxAccount, Outbox},
     error::Error as MailboxError,
-    instruction::{Instruction as MailboxInstruction, OutboxDispatch},
-    mailbox_dispatched_message_pda_seeds,
+    instruction::{
+        set_default_hook_instruction, set_required_hook_instruction,
+        Instruction as MailboxInstruction, OutboxDispatch,
+    },
+    mailbox_dispatched_message_pda_seeds, mailbox_message_dispatch_authority_pda_seeds,
+    processor::required_hook_account_count_key,
     protocol_fee::ProtocolFee,
 };
 use hyperlane_sealevel_test_ism::{program::TestIsmError, test_client::TestIsmTestClient};
@@ -14,13 +26,23 @@ use hyperlane_sealevel_test_send_receiver::{
     program::{HandleMode, IsmReturnDataMode, TestSendReceiverError},
     test_client::TestSendReceiverTestClient,
 };
+use hyperlane_sealevel_token_lib::{
+    hyperlane_token_pda_seeds,
+    instruction::{
+        enroll_remote_routers_instruction, init_instruction, set_destination_gas_configs, Init,
+        Instruction as HyperlaneTokenInstruction, TransferRemote,
+    },
+    message::TokenMessage,
+};
+use hyperlane_sealevel_token_native::hyperlane_token_native_collateral_pda_seeds;
 use hyperlane_test_utils::{
     assert_transaction_error, clone_keypair, get_process_account_metas, get_recipient_ism,
-    initialize_mailbox, mailbox_id, new_funded_keypair, process, process_instruction,
-    process_with_accounts,
+    igp_program_id, initialize_igp_accounts, initialize_mailbox, mailbox_id, new_funded_keypair,
+    process, process_instruction, process_with_accounts,
 };
 use solana_program::{
     instruction::{AccountMeta, Instruction},
+    pubkey,
     pubkey::Pubkey,
     rent::Rent,
     system_program,
@@ -36,13 +58,18 @@ use solana_sdk::{
 
 use crate::utils::{
     assert_dispatched_message, assert_inbox, assert_message_not_processed, assert_outbox,
-    assert_processed_message, dispatch_from_payer,
+    assert_processed_message, dispatch_from_payer, dispatch_from_payer_with_hook_accounts,
 };
 
 const LOCAL_DOMAIN: u32 = 13775;
 const REMOTE_DOMAIN: u32 = 69420;
 const PROTOCOL_FEE: u64 = 1_000_000_000;
 const MAX_PROTOCOL_FEE: u64 = 1_000_000_001;
+const REMOTE_GAS_AMOUNT: u64 = 200000;
+
+fn hyperlane_sealevel_token_native_id() -> Pubkey {
+    pubkey!("CGn8yNtSD3aTTqJfYhUb6s1aVTN75NzwtsFKo1e83aga")
+}
 
 async fn setup_client() -> (
     BanksClient,
@@ -78,6 +105,18 @@ async fn setup_client() -> (
         processor!(hyperlane_sealevel_test_send_receiver::program::process_instruction),
     );
 
+    program_test.add_program(
+        "hyperlane_sealevel_igp",
+        igp_program_id(),
+        processor!(hyperlane_sealevel_igp::processor::process_instruction),
+    );
+
+    program_test.add_program(
+        "hyperlane_sealevel_token_native",
+        hyperlane_sealevel_token_native_id(),
+        processor!(hyperlane_sealevel_token_native::processor::process_instruction),
+    );
+
     let (banks_client, payer, _recent_blockhash) = program_test.start().await;
 
     let test_ism = TestIsmTestClient::new(banks_client.clone(), clone_keypair(&payer));
@@ -130,6 +169,8 @@ async fn test_initialize() {
             tree: MerkleTree::default(),
             max_protocol_fee: MAX_PROTOCOL_FEE,
             protocol_fee: protocol_fee_config,
+            default_hook: None,
+            required_hook: None,
         },
     )
     .await;
@@ -213,6 +254,7 @@ async fn test_dispatch_from_eoa() {
         destination_domain: REMOTE_DOMAIN,
         recipient,
         message_body: message_body.clone(),
+        metadata: vec![],
     };
 
     let (dispatch_tx_signature, dispatch_unique_keypair, dispatched_message_account_key) =
@@ -258,6 +300,8 @@ async fn test_dispatch_from_eoa() {
             tree: expected_tree.clone(),
             max_protocol_fee: MAX_PROTOCOL_FEE,
             protocol_fee: protocol_fee_config.clone(),
+            default_hook: None,
+            required_hook: None,
         },
     )
     .await;
@@ -270,6 +314,7 @@ async fn test_dispatch_from_eoa() {
         destination_domain: REMOTE_DOMAIN,
         recipient,
         message_body: message_body.clone(),
+        metadata: vec![],
     };
 
     let (dispatch_tx_signature, dispatch_unique_keypair, dispatched_message_account_key) =
@@ -314,6 +359,8 @@ async fn test_dispatch_from_eoa() {
             tree: expected_tree.clone(),
             max_protocol_fee: MAX_PROTOCOL_FEE,
             protocol_fee: protocol_fee_config,
+            default_hook: None,
+            required_hook: None,
         },
     )
     .await;
@@ -343,6 +390,7 @@ async fn test_protocol_fee() {
         destination_domain: REMOTE_DOMAIN,
         recipient,
         message_body: message_body.clone(),
+        metadata: vec![],
     };
 
     // ---- Test protocol fee payment ----
@@ -466,6 +514,8 @@ async fn test_setting_valid_protocol_fee_config_works() {
             tree: MerkleTree::default(),
             max_protocol_fee: MAX_PROTOCOL_FEE,
             protocol_fee: new_protocol_fee,
+            default_hook: None,
+            required_hook: None,
         },
     )
     .await;
@@ -585,6 +635,7 @@ async fn test_dispatch_from_program() {
         destination_domain: REMOTE_DOMAIN,
         recipient,
         message_body: message_body.clone(),
+        metadata: vec![],
     };
 
     let (dispatch_tx_signature, dispatch_unique_keypair, dispatched_message_account_key) =
@@ -627,6 +678,8 @@ async fn test_dispatch_from_program() {
             tree: expected_tree.clone(),
             max_protocol_fee: MAX_PROTOCOL_FEE,
             protocol_fee: protocol_fee_config,
+            default_hook: None,
+            required_hook: None,
         },
     )
     .await;
@@ -655,6 +708,7 @@ async fn test_dispatch_returns_message_id() {
         destination_domain: REMOTE_DOMAIN,
         recipient,
         message_body: message_body.clone(),
+        metadata: vec![],
     };
     let expected_message = HyperlaneMessage {
         version: 3,
@@ -1414,3 +1468,460 @@ async fn test_inbox_set_default_ism_errors_if_owner_not_signer() {
         TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature),
     );
 }
+
+#[tokio::test]
+async fn test_outbox_set_hooks() {
+    let program_id = mailbox_id();
+    let (mut banks_client, payer, _, _) = setup_client().await;
+    let protocol_fee_config = test_protocol_fee_config();
+
+    let mailbox_accounts = initialize_mailbox(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        LOCAL_DOMAIN,
+        MAX_PROTOCOL_FEE,
+        protocol_fee_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let default_hook = Pubkey::new_unique();
+    let required_hook = Pubkey::new_unique();
+
+    process_instruction(
+        &mut banks_client,
+        set_default_hook_instruction(program_id, payer.pubkey(), Some(default_hook)).unwrap(),
+        &payer,
+        &[&payer],
+    )
+    .await
+    .unwrap();
+    process_instruction(
+        &mut banks_client,
+        set_required_hook_instruction(program_id, payer.pubkey(), Some(required_hook)).unwrap(),
+        &payer,
+        &[&payer],
+    )
+    .await
+    .unwrap();
+
+    assert_outbox(
+        &mut banks_client,
+        mailbox_accounts.outbox,
+        Outbox {
+            local_domain: LOCAL_DOMAIN,
+            outbox_bump_seed: mailbox_accounts.outbox_bump_seed,
+            owner: Some(payer.pubkey()),
+            tree: MerkleTree::default(),
+            max_protocol_fee: MAX_PROTOCOL_FEE,
+            protocol_fee: protocol_fee_config.clone(),
+            default_hook: Some(default_hook),
+            required_hook: Some(required_hook),
+        },
+    )
+    .await;
+
+    // Now unset the default hook
+    process_instruction(
+        &mut banks_client,
+        set_default_hook_instruction(program_id, payer.pubkey(), None).unwrap(),
+        &payer,
+        &[&payer],
+    )
+    .await
+    .unwrap();
+
+    assert_outbox(
+        &mut banks_client,
+        mailbox_accounts.outbox,
+        Outbox {
+            local_domain: LOCAL_DOMAIN,
+            outbox_bump_seed: mailbox_accounts.outbox_bump_seed,
+            owner: Some(payer.pubkey()),
+            tree: MerkleTree::default(),
+            max_protocol_fee: MAX_PROTOCOL_FEE,
+            protocol_fee: protocol_fee_config,
+            default_hook: None,
+            required_hook: Some(required_hook),
+        },
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_outbox_set_hook_errors_if_owner_not_signer() {
+    let program_id = mailbox_id();
+    let (mut banks_client, payer, _, _) = setup_client().await;
+
+    initialize_mailbox(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        LOCAL_DOMAIN,
+        MAX_PROTOCOL_FEE,
+        test_protocol_fee_config(),
+    )
+    .await
+    .unwrap();
+
+    let non_owner = new_funded_keypair(&mut banks_client, &payer, 1000000000).await;
+
+    // Where the signer is not the owner
+    let result = process_instruction(
+        &mut banks_client,
+        set_required_hook_instruction(program_id, non_owner.pubkey(), Some(Pubkey::new_unique()))
+            .unwrap(),
+        &non_owner,
+        &[&non_owner],
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+
+    // Where the owner is correct but not a signer
+    let mut instruction =
+        set_default_hook_instruction(program_id, payer.pubkey(), Some(Pubkey::new_unique()))
+            .unwrap();
+    instruction.accounts[1].is_signer = false;
+    let result =
+        process_instruction(&mut banks_client, instruction, &non_owner, &[&non_owner]).await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(0, InstructionError::MissingRequiredSignature),
+    );
+}
+
+#[tokio::test]
+async fn test_dispatch_invokes_hooks() {
+    let program_id = mailbox_id();
+    let (mut banks_client, payer, _, _) = setup_client().await;
+
+    let mailbox_accounts = initialize_mailbox(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        LOCAL_DOMAIN,
+        MAX_PROTOCOL_FEE,
+        test_protocol_fee_config(),
+    )
+    .await
+    .unwrap();
+
+    // The SPL Noop program accepts any instruction, so it's used as both hooks.
+    for instruction in [
+        set_required_hook_instruction(program_id, payer.pubkey(), Some(spl_noop::id())),
+        set_default_hook_instruction(program_id, payer.pubkey(), Some(spl_noop::id())),
+    ] {
+        process_instruction(&mut banks_client, instruction.unwrap(), &payer, &[&payer])
+            .await
+            .unwrap();
+    }
+
+    let outbox_dispatch = || OutboxDispatch {
+        sender: payer.pubkey(),
+        destination_domain: REMOTE_DOMAIN,
+        recipient: H256::random(),
+        message_body: vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+        metadata: vec![1, 2, 3],
+    };
+
+    // Errors if the hook programs aren't provided
+    let result = dispatch_from_payer(
+        &mut banks_client,
+        &payer,
+        &mailbox_accounts,
+        outbox_dispatch(),
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys),
+    );
+
+    // Errors if the required hook account count account isn't provided
+    let result = dispatch_from_payer_with_hook_accounts(
+        &mut banks_client,
+        &payer,
+        &mailbox_accounts,
+        outbox_dispatch(),
+        vec![
+            AccountMeta::new_readonly(spl_noop::id(), false),
+            AccountMeta::new_readonly(spl_noop::id(), false),
+        ],
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(0, InstructionError::InvalidArgument),
+    );
+
+    // Errors if only the required hook program is provided
+    let result = dispatch_from_payer_with_hook_accounts(
+        &mut banks_client,
+        &payer,
+        &mailbox_accounts,
+        outbox_dispatch(),
+        vec![
+            AccountMeta::new_readonly(required_hook_account_count_key(0), false),
+            AccountMeta::new_readonly(spl_noop::id(), false),
+        ],
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys),
+    );
+
+    // Errors if the required hook account count is more than the accounts provided
+    let result = dispatch_from_payer_with_hook_accounts(
+        &mut banks_client,
+        &payer,
+        &mailbox_accounts,
+        outbox_dispatch(),
+        vec![
+            AccountMeta::new_readonly(required_hook_account_count_key(2), false),
+            AccountMeta::new_readonly(spl_noop::id(), false),
+            AccountMeta::new_readonly(spl_noop::id(), false),
+        ],
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(0, InstructionError::NotEnoughAccountKeys),
+    );
+
+    // Succeeds with the required hook program and its accounts followed by the
+    // default hook program, even if the default hook program is among the
+    // required hook's accounts.
+    dispatch_from_payer_with_hook_accounts(
+        &mut banks_client,
+        &payer,
+        &mailbox_accounts,
+        outbox_dispatch(),
+        vec![
+            AccountMeta::new_readonly(required_hook_account_count_key(2), false),
+            AccountMeta::new_readonly(spl_noop::id(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_noop::id(), false),
+            AccountMeta::new_readonly(spl_noop::id(), false),
+        ],
+    )
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_dispatch_errors_if_hook_accounts_provided_without_hooks() {
+    let program_id = mailbox_id();
+    let (mut banks_client, payer, _, _) = setup_client().await;
+
+    let mailbox_accounts = initialize_mailbox(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        LOCAL_DOMAIN,
+        MAX_PROTOCOL_FEE,
+        test_protocol_fee_config(),
+    )
+    .await
+    .unwrap();
+
+    let result = dispatch_from_payer_with_hook_accounts(
+        &mut banks_client,
+        &payer,
+        &mailbox_accounts,
+        OutboxDispatch {
+            sender: payer.pubkey(),
+            destination_domain: REMOTE_DOMAIN,
+            recipient: H256::random(),
+            message_body: vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+            metadata: vec![],
+        },
+        vec![AccountMeta::new_readonly(spl_noop::id(), false)],
+    )
+    .await;
+    assert_transaction_error(
+        result,
+        TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(MailboxError::ExtraneousAccount as u32),
+        ),
+    );
+}
+
+#[tokio::test]
+async fn test_dispatch_from_token_program_with_igp_hook() {
+    let program_id = mailbox_id();
+    let token_program_id = hyperlane_sealevel_token_native_id();
+    let (mut banks_client, payer, _, _) = setup_client().await;
+
+    let mailbox_accounts = initialize_mailbox(
+        &mut banks_client,
+        &program_id,
+        &payer,
+        LOCAL_DOMAIN,
+        MAX_PROTOCOL_FEE,
+        ProtocolFee::default(),
+    )
+    .await
+    .unwrap();
+
+    let igp_accounts =
+        initialize_igp_accounts(&mut banks_client, &igp_program_id(), &payer, REMOTE_DOMAIN)
+            .await
+            .unwrap();
+
+    process_instruction(
+        &mut banks_client,
+        set_default_hook_instruction(program_id, payer.pubkey(), Some(igp_accounts.program))
+            .unwrap(),
+        &payer,
+        &[&payer],
+    )
+    .await
+    .unwrap();
+
+    // Initialize the native token program, which dispatches through the Mailbox.
+    let (native_collateral_key, _native_collateral_bump) = Pubkey::find_program_address(
+        hyperlane_token_native_collateral_pda_seeds!(),
+        &token_program_id,
+    );
+    let mut instruction = init_instruction(
+        token_program_id,
+        payer.pubkey(),
+        Init {
+            mailbox: program_id,
+            interchain_security_module: None,
+            interchain_gas_paymaster: Some((
+                igp_accounts.program,
+                InterchainGasPaymasterType::OverheadIgp(igp_accounts.overhead_igp),
+            )),
+            decimals: 9,
+            remote_decimals: 9,
+        },
+    )
+    .unwrap();
+    instruction
+        .accounts
+        .push(AccountMeta::new(native_collateral_key, false));
+
+    let remote_router = H256::random();
+    for instruction in [
+        instruction,
+        enroll_remote_routers_instruction(
+            token_program_id,
+            payer.pubkey(),
+            vec![RemoteRouterConfig {
+                domain: REMOTE_DOMAIN,
+                router: Some(remote_router),
+            }],
+        )
+        .unwrap(),
+        set_destination_gas_configs(
+            token_program_id,
+            payer.pubkey(),
+            vec![GasRouterConfig {
+                domain: REMOTE_DOMAIN,
+                gas: Some(REMOTE_GAS_AMOUNT),
+            }],
+        )
+        .unwrap(),
+    ] {
+        process_instruction(&mut banks_client, instruction, &payer, &[&payer])
+            .await
+            .unwrap();
+    }
+
+    let (token_key, _token_bump) =
+        Pubkey::find_program_address(hyperlane_token_pda_seeds!(), &token_program_id);
+    let (dispatch_authority_key, _dispatch_authority_bump) = Pubkey::find_program_address(
+        mailbox_message_dispatch_authority_pda_seeds!(),
+        &token_program_id,
+    );
+    let unique_message_account_keypair = Keypair::new();
+    let (dispatched_message_key, _dispatched_message_bump) = Pubkey::find_program_address(
+        mailbox_dispatched_message_pda_seeds!(&unique_message_account_keypair.pubkey()),
+        &program_id,
+    );
+    let (igp_hook_account_metas, gas_payment_pda_key) = post_dispatch_hook_account_metas(
+        igp_accounts.program,
+        payer.pubkey(),
+        igp_accounts.igp,
+        Some(igp_accounts.overhead_igp),
+        unique_message_account_keypair.pubkey(),
+    )
+    .unwrap();
+
+    let recipient = H256::random();
+    let amount = 1_000_000_000u64;
+    let transfer_remote_instruction = Instruction::new_with_bytes(
+        token_program_id,
+        &HyperlaneTokenInstruction::TransferRemote(TransferRemote {
+            destination_domain: REMOTE_DOMAIN,
+            recipient,
+            amount_or_id: amount.into(),
+        })
+        .encode()
+        .unwrap(),
+        [
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_noop::id(), false),
+            AccountMeta::new_readonly(token_key, false),
+            AccountMeta::new_readonly(program_id, false),
+            AccountMeta::new(mailbox_accounts.outbox, false),
+            AccountMeta::new_readonly(dispatch_authority_key, false),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(unique_message_account_keypair.pubkey(), true),
+            AccountMeta::new(dispatched_message_key, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(native_collateral_key, false),
+        ]
+        .into_iter()
+        // The IGP default hook program and the accounts it requires.
+        .chain(igp_hook_account_metas)
+        .collect(),
+    );
+    let tx_signature = process_instruction(
+        &mut banks_client,
+        transfer_remote_instruction,
+        &payer,
+        &[&payer, &unique_message_account_keypair],
+    )
+    .await
+    .unwrap();
+
+    let expected_message = HyperlaneMessage {
+        version: 3,
+        nonce: 0,
+        origin: LOCAL_DOMAIN,
+        sender: H256(token_program_id.to_bytes()),
+        destination: REMOTE_DOMAIN,
+        recipient: remote_router,
+        body: TokenMessage::new(recipient, amount.into(), vec![]).to_vec(),
+    };
+    assert_dispatched_message(
+        &mut banks_client,
+        tx_signature,
+        unique_message_account_keypair.pubkey(),
+        dispatched_message_key,
+        &expected_message,
+    )
+    .await;
+
+    // The IGP hook was paid once, for the token's destination gas.
+    let gas_payment_account_data = banks_client
+        .get_account(gas_payment_pda_key)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    let gas_payment = GasPaymentAccount::fetch(&mut &gas_payment_account_data[..])
+        .unwrap()
+        .into_inner();
+    assert_eq!(gas_payment.message_id, expected_message.id());
+    assert_eq!(gas_payment.gas_amount, REMOTE_GAS_AMOUNT);
+    assert_eq!(gas_payment.sequence_number, 0);
+}
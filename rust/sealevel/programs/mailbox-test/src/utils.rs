@@ -28,6 +28,23 @@ pub async fn dispatch_from_payer(
     payer: &Keypair,
     mailbox_accounts: &MailboxAccounts,
     outbox_dispatch: OutboxDispatch,
+) -> Result<(Signature, Keypair, Pubkey), BanksClientError> {
+    dispatch_from_payer_with_hook_accounts(
+        banks_client,
+        payer,
+        mailbox_accounts,
+        outbox_dispatch,
+        vec![],
+    )
+    .await
+}
+
+pub async fn dispatch_from_payer_with_hook_accounts(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    mailbox_accounts: &MailboxAccounts,
+    outbox_dispatch: OutboxDispatch,
+    hook_account_metas: Vec<AccountMeta>,
 ) -> Result<(Signature, Keypair, Pubkey), BanksClientError> {
     let unique_message_account_keypair = Keypair::new();
 
@@ -57,7 +74,12 @@ pub async fn dispatch_from_payer(
             AccountMeta::new(payer.pubkey(), true),
             AccountMeta::new(unique_message_account_keypair.pubkey(), true),
             AccountMeta::new(dispatched_message_account_key, false),
-        ],
+        ]
+        .into_iter()
+        // 7..M The required hook account count account if both hooks are set, then
+        //      the hook programs and the accounts they require.
+        .chain(hook_account_metas)
+        .collect(),
     };
 
     let recent_blockhash = banks_client.get_latest_blockhash().await.unwrap();
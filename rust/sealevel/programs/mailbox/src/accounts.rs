@@ -90,7 +90,7 @@ impl Inbox {
 pub type OutboxAccount = AccountData<Outbox>;
 
 /// The Outbox account data, which is used when dispatching messages.
-#[derive(BorshSerialize, BorshDeserialize, Debug, Default, PartialEq, Eq)]
+#[derive(BorshSerialize, Debug, Default, PartialEq, Eq)]
 pub struct Outbox {
     /// The local domain.
     pub local_domain: u32,
@@ -104,6 +104,10 @@ pub struct Outbox {
     pub max_protocol_fee: u64,
     /// The protocol fee configuration.
     pub protocol_fee: ProtocolFee,
+    /// The post-dispatch hook program invoked for messages that don't use another hook.
+    pub default_hook: Option<Pubkey>,
+    /// The post-dispatch hook program invoked for every dispatched message.
+    pub required_hook: Option<Pubkey>,
 }
 
 impl SizedData for Outbox {
@@ -114,7 +118,44 @@ impl SizedData for Outbox {
         // 1032 byte tree (32 * 32 = 1024 byte branch, 8 byte count)
         // 8 byte max_protocol_fee
         // 40 byte protocol_fee (8 byte fee, 32 byte beneficiary)
-        4 + 1 + 33 + 1032 + 8 + 40
+        // 33 byte default_hook (1 byte enum variant, 32 byte pubkey)
+        // 33 byte required_hook (1 byte enum variant, 32 byte pubkey)
+        4 + 1 + 33 + 1032 + 8 + 40 + 33 + 33
+    }
+}
+
+/// Outbox accounts created before post-dispatch hooks were introduced
+/// don't have the hook fields, so we implement our own deserialization that
+/// treats them as unset if absent.
+impl BorshDeserialize for Outbox {
+    fn deserialize(reader: &mut &[u8]) -> std::io::Result<Self> {
+        let local_domain = u32::deserialize(reader)?;
+        let outbox_bump_seed = u8::deserialize(reader)?;
+        let owner = Option::<Pubkey>::deserialize(reader)?;
+        let tree = MerkleTree::deserialize(reader)?;
+        let max_protocol_fee = u64::deserialize(reader)?;
+        let protocol_fee = ProtocolFee::deserialize(reader)?;
+        let default_hook = if reader.is_empty() {
+            None
+        } else {
+            Option::<Pubkey>::deserialize(reader)?
+        };
+        let required_hook = if reader.is_empty() {
+            None
+        } else {
+            Option::<Pubkey>::deserialize(reader)?
+        };
+
+        Ok(Self {
+            local_domain,
+            outbox_bump_seed,
+            owner,
+            tree,
+            max_protocol_fee,
+            protocol_fee,
+            default_hook,
+            required_hook,
+        })
     }
 }
 
@@ -339,6 +380,8 @@ mod test {
                 fee: 69696969,
                 beneficiary: Pubkey::new_unique(),
             },
+            default_hook: Some(Pubkey::new_unique()),
+            required_hook: Some(Pubkey::new_unique()),
         };
 
         let mut serialized = vec![];
@@ -350,6 +393,33 @@ mod test {
         assert_eq!(serialized.len(), outbox.size());
     }
 
+    #[test]
+    fn test_outbox_without_hooks_deser() {
+        let outbox = Outbox {
+            local_domain: 420,
+            outbox_bump_seed: 69,
+            owner: Some(Pubkey::new_unique()),
+            tree: MerkleTree::default(),
+            max_protocol_fee: 100000000,
+            protocol_fee: ProtocolFee {
+                fee: 69696969,
+                beneficiary: Pubkey::new_unique(),
+            },
+            default_hook: None,
+            required_hook: None,
+        };
+
+        let mut serialized = vec![];
+        outbox.serialize(&mut serialized).unwrap();
+        // Strip the two `None` hook bytes to get the layout of an account
+        // created before post-dispatch hooks were introduced.
+        let legacy_serialized = &serialized[..serialized.len() - 2];
+
+        let deserialized = Outbox::deserialize(&mut &legacy_serialized[..]).unwrap();
+
+        assert_eq!(outbox, deserialized);
+    }
+
     #[test]
     fn test_inbox_ser_deser() {
         let inbox = Inbox {
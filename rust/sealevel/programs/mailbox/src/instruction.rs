@@ -40,6 +40,10 @@ pub enum Instruction {
     ClaimProtocolFees,
     /// Sets the protocol fee configuration.
     SetProtocolFeeConfig(ProtocolFee),
+    /// Sets the default post-dispatch hook program.
+    OutboxSetDefaultHook(Option<Pubkey>),
+    /// Sets the required post-dispatch hook program.
+    OutboxSetRequiredHook(Option<Pubkey>),
 }
 
 impl Instruction {
@@ -69,7 +73,7 @@ pub struct Init {
 }
 
 /// Instruction data for the OutboxDispatch instruction.
-#[derive(BorshDeserialize, BorshSerialize, Debug, PartialEq)]
+#[derive(BorshSerialize, Debug, PartialEq)]
 pub struct OutboxDispatch {
     /// The sender of the message.
     /// This is required and not implied because a program uses a dispatch authority PDA
@@ -83,6 +87,32 @@ pub struct OutboxDispatch {
     pub recipient: H256,
     /// The message body.
     pub message_body: Vec<u8>,
+    /// The metadata passed to the required and default post-dispatch hooks.
+    pub metadata: Vec<u8>,
+}
+
+/// Callers that predate post-dispatch hooks don't provide `metadata`,
+/// so we implement our own deserialization that treats it as empty if absent.
+impl BorshDeserialize for OutboxDispatch {
+    fn deserialize(reader: &mut &[u8]) -> std::io::Result<Self> {
+        let sender = Pubkey::deserialize(reader)?;
+        let destination_domain = u32::deserialize(reader)?;
+        let recipient = H256::deserialize(reader)?;
+        let message_body = Vec::<u8>::deserialize(reader)?;
+        let metadata = if reader.is_empty() {
+            vec![]
+        } else {
+            Vec::<u8>::deserialize(reader)?
+        };
+
+        Ok(Self {
+            sender,
+            destination_domain,
+            recipient,
+            message_body,
+            metadata,
+        })
+    }
 }
 
 /// Instruction data for the InboxProcess instruction.
@@ -179,3 +209,92 @@ pub fn set_default_ism_instruction(
     };
     Ok(instruction)
 }
+
+/// Creates an OutboxSetDefaultHook instruction.
+pub fn set_default_hook_instruction(
+    program_id: Pubkey,
+    owner_payer: Pubkey,
+    default_hook: Option<Pubkey>,
+) -> Result<SolanaInstruction, ProgramError> {
+    set_hook_instruction(
+        program_id,
+        owner_payer,
+        Instruction::OutboxSetDefaultHook(default_hook),
+    )
+}
+
+/// Creates an OutboxSetRequiredHook instruction.
+pub fn set_required_hook_instruction(
+    program_id: Pubkey,
+    owner_payer: Pubkey,
+    required_hook: Option<Pubkey>,
+) -> Result<SolanaInstruction, ProgramError> {
+    set_hook_instruction(
+        program_id,
+        owner_payer,
+        Instruction::OutboxSetRequiredHook(required_hook),
+    )
+}
+
+fn set_hook_instruction(
+    program_id: Pubkey,
+    owner_payer: Pubkey,
+    ixn: Instruction,
+) -> Result<SolanaInstruction, ProgramError> {
+    let (outbox_account, _outbox_bump) =
+        Pubkey::try_find_program_address(mailbox_outbox_pda_seeds!(), &program_id)
+            .ok_or(ProgramError::InvalidSeeds)?;
+
+    // 0. `[writeable]` The Outbox PDA account.
+    // 1. `[signer, writeable]` The owner of the Mailbox, who pays for any realloc.
+    // 2. `[executable]` The system program.
+    let instruction = SolanaInstruction {
+        program_id,
+        data: ixn.into_instruction_data()?,
+        accounts: vec![
+            AccountMeta::new(outbox_account, false),
+            AccountMeta::new(owner_payer, true),
+            AccountMeta::new_readonly(solana_program::system_program::id(), false),
+        ],
+    };
+    Ok(instruction)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_outbox_dispatch_without_metadata_deser() {
+        let instruction = Instruction::OutboxDispatch(OutboxDispatch {
+            sender: Pubkey::new_unique(),
+            destination_domain: 420,
+            recipient: H256::random(),
+            message_body: vec![1, 2, 3, 4, 5],
+            metadata: vec![],
+        });
+
+        // Strip the 4 byte length prefix of the empty metadata to encode the
+        // instruction as callers that predate hooks do.
+        let serialized = instruction.try_to_vec().unwrap();
+        let legacy_serialized = &serialized[..serialized.len() - 4];
+
+        let deserialized = Instruction::from_instruction_data(legacy_serialized).unwrap();
+        assert_eq!(deserialized, instruction);
+    }
+
+    #[test]
+    fn test_outbox_dispatch_with_metadata_ser_deser() {
+        let instruction = Instruction::OutboxDispatch(OutboxDispatch {
+            sender: Pubkey::new_unique(),
+            destination_domain: 420,
+            recipient: H256::random(),
+            message_body: vec![1, 2, 3, 4, 5],
+            metadata: vec![6, 7, 8],
+        });
+
+        let serialized = instruction.try_to_vec().unwrap();
+        let deserialized = Instruction::from_instruction_data(&serialized).unwrap();
+        assert_eq!(deserialized, instruction);
+    }
+}
@@ -28,6 +28,9 @@ use hyperlane_sealevel_interchain_security_module_interface::{
 use hyperlane_sealevel_message_recipient_interface::{
     HandleInstruction, MessageRecipientInstruction,
 };
+use hyperlane_sealevel_post_dispatch_hook_interface::{
+    PostDispatchHookInstruction, PostDispatchInstruction,
+};
 use serializable_account_meta::SimulationReturnData;
 
 use crate::{
@@ -71,6 +74,16 @@ pub fn process_instruction(
         MailboxIxn::SetProtocolFeeConfig(new_protocol_fee_config) => {
             set_protocol_fee_config(program_id, accounts, new_protocol_fee_config)
         }
+        MailboxIxn::OutboxSetDefaultHook(default_hook) => {
+            outbox_set_hook(program_id, accounts, default_hook, |outbox| {
+                &mut outbox.default_hook
+            })
+        }
+        MailboxIxn::OutboxSetRequiredHook(required_hook) => {
+            outbox_set_hook(program_id, accounts, required_hook, |outbox| {
+                &mut outbox.required_hook
+            })
+        }
     }
     .map_err(|err| {
         msg!("{}", err);
@@ -151,6 +164,8 @@ fn initialize(program_id: &Pubkey, accounts: &[AccountInfo], init: Init) -> Prog
         tree: MerkleTree::default(),
         max_protocol_fee: init.max_protocol_fee,
         protocol_fee: init.protocol_fee,
+        default_hook: None,
+        required_hook: None,
     });
 
     // Create the outbox PDA account.
@@ -554,17 +569,26 @@ fn inbox_set_default_ism(
 /// in order for the sender field of the message to be set to the sending program
 /// ID. Otherwise, the sender field of the message is set to the message sender signer.
 ///
+/// After the message is inserted into the merkle tree, the required hook and then
+/// the default hook are invoked with the dispatch's metadata, if they are set.
+///
 /// Sets the ID of the message as return data.
 ///
 /// Accounts:
-/// 0. `[writeable]` Outbox PDA.
-/// 1. `[signer]` Message sender signer.
-/// 2. `[executable]` System program.
-/// 3. `[executable]` SPL Noop program.
-/// 4. `[signer]` Payer.
-/// 5. `[signer]` Unique message account.
-/// 6. `[writeable]` Dispatched message PDA. An empty message PDA relating to the seeds
-///    `mailbox_dispatched_message_pda_seeds` where the message contents will be stored.
+/// 0.     `[writeable]` Outbox PDA.
+/// 1.     `[signer]` Message sender signer.
+/// 2.     `[executable]` System program.
+/// 3.     `[executable]` SPL Noop program.
+/// 4.     `[signer]` Payer.
+/// 5.     `[signer]` Unique message account.
+/// 6.     `[writeable]` Dispatched message PDA. An empty message PDA relating to the seeds
+///        `mailbox_dispatched_message_pda_seeds` where the message contents will be stored.
+/// 7.     `[]` The required hook account count account, only if both the required hook
+///        and the default hook are set. See `required_hook_account_count_key`.
+/// 7..N   `[executable]` The required hook program, if one is set.
+///        Followed by the accounts required by its `PostDispatch` instruction.
+/// N+1..M `[executable]` The default hook program, if one is set.
+///        Followed by the accounts required by its `PostDispatch` instruction.
 fn outbox_dispatch(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
@@ -641,9 +665,13 @@ fn outbox_dispatch(
     // Make sure an account can't be written to that already exists.
     verify_account_uninitialized(dispatched_message_account_info)?;
 
-    if accounts_iter.next().is_some() {
-        return Err(ProgramError::from(Error::ExtraneousAccount));
-    }
+    // Accounts 7..M: The required hook account count account if both hooks are set,
+    // then the hook programs and the accounts they require.
+    let (required_hook_infos, default_hook_infos) = split_hook_accounts(
+        accounts_iter.as_slice(),
+        outbox.required_hook.as_ref(),
+        outbox.default_hook.as_ref(),
+    )?;
 
     let count = outbox
         .tree
@@ -701,6 +729,7 @@ fn outbox_dispatch(
         ),
     )?;
     dispatched_message_account.store(dispatched_message_account_info, false)?;
+    let encoded_message = dispatched_message_account.into_inner().encoded_message;
 
     // Log the message using the SPL Noop program.
     #[cfg(not(feature = "no-spl-noop"))]
@@ -719,13 +748,118 @@ fn outbox_dispatch(
         id
     );
 
-    // Store the Outbox with the new updates.
-    OutboxAccount::from(outbox).store(outbox_info, true)?;
+    let required_hook = outbox.required_hook;
+    let default_hook = outbox.default_hook;
+
+    // Store the Outbox with the new updates before invoking any hooks.
+    // Outbox accounts created before post-dispatch hooks were introduced
+    // are reallocated to fit the hook fields here.
+    OutboxAccount::from(outbox).store_with_rent_exempt_realloc(
+        outbox_info,
+        &Rent::get()?,
+        payer_info,
+        system_program_info,
+    )?;
+
+    if required_hook.is_some() || default_hook.is_some() {
+        let post_dispatch_data = PostDispatchHookInstruction::PostDispatch(
+            PostDispatchInstruction::new(dispatch.metadata, encoded_message),
+        )
+        .encode()?;
+
+        if let Some(required_hook) = required_hook {
+            invoke_hook(&required_hook, &post_dispatch_data, required_hook_infos)?;
+        }
+        if let Some(default_hook) = default_hook {
+            invoke_hook(&default_hook, &post_dispatch_data, default_hook_infos)?;
+        }
+    }
 
+    // Set the return data after invoking the hooks, which may set their own.
     set_return_data(id.as_ref());
     Ok(())
 }
 
+/// The key of the account passed into `OutboxDispatch` to specify the number of
+/// accounts required by the required hook's `PostDispatch` instruction, excluding
+/// the required hook program itself. Only expected if both the required hook and
+/// the default hook are set.
+/// The first byte of the key is the account count, and the remaining bytes are zero.
+pub fn required_hook_account_count_key(required_hook_account_count: u8) -> Pubkey {
+    let mut key = [0u8; 32];
+    key[0] = required_hook_account_count;
+    Pubkey::new_from_array(key)
+}
+
+/// Splits the accounts passed into `OutboxDispatch` after the dispatched message PDA
+/// into the accounts for the required hook and the accounts for the default hook.
+/// Each group of accounts starts with its hook program. If both hooks are set, the
+/// accounts start with the required hook account count account, which specifies
+/// where the required hook's group ends.
+/// Errors if accounts are provided for a hook that isn't set.
+fn split_hook_accounts<'a, 'b>(
+    accounts: &'a [AccountInfo<'b>],
+    required_hook: Option<&Pubkey>,
+    default_hook: Option<&Pubkey>,
+) -> Result<(&'a [AccountInfo<'b>], &'a [AccountInfo<'b>]), ProgramError> {
+    let (accounts, default_hook_start) = match (required_hook, default_hook) {
+        (Some(_), Some(_)) => {
+            let (account_count_info, accounts) = accounts
+                .split_first()
+                .ok_or(ProgramError::NotEnoughAccountKeys)?;
+            let required_hook_account_count = account_count_info.key.to_bytes()[0];
+            if *account_count_info.key
+                != required_hook_account_count_key(required_hook_account_count)
+            {
+                return Err(ProgramError::InvalidArgument);
+            }
+            // The required hook program, followed by its accounts.
+            let default_hook_start = 1 + required_hook_account_count as usize;
+            if default_hook_start > accounts.len() {
+                return Err(ProgramError::NotEnoughAccountKeys);
+            }
+            (accounts, default_hook_start)
+        }
+        (Some(_), None) => (accounts, accounts.len()),
+        (None, _) => (accounts, 0),
+    };
+    let (required_hook_infos, default_hook_infos) = accounts.split_at(default_hook_start);
+
+    if default_hook.is_none() && !default_hook_infos.is_empty() {
+        return Err(ProgramError::from(Error::ExtraneousAccount));
+    }
+
+    Ok((required_hook_infos, default_hook_infos))
+}
+
+/// Invokes the `PostDispatch` instruction of a hook.
+///
+/// Expects `hook_infos` to be the hook program followed by the accounts
+/// required by its `PostDispatch` instruction.
+fn invoke_hook(
+    hook: &Pubkey,
+    post_dispatch_data: &[u8],
+    hook_infos: &[AccountInfo],
+) -> ProgramResult {
+    let (hook_info, hook_account_infos) = hook_infos
+        .split_first()
+        .ok_or(ProgramError::NotEnoughAccountKeys)?;
+    if hook_info.key != hook || !hook_info.executable {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let hook_account_metas = hook_account_infos
+        .iter()
+        .map(|account_info| AccountMeta {
+            pubkey: *account_info.key,
+            is_signer: account_info.is_signer,
+            is_writable: account_info.is_writable,
+        })
+        .collect();
+    let post_dispatch = Instruction::new_with_bytes(*hook, post_dispatch_data, hook_account_metas);
+    invoke(&post_dispatch, hook_account_infos)
+}
+
 /// Gets the number of dispatched messages as little endian encoded return data.
 ///
 /// Accounts:
@@ -932,3 +1066,49 @@ fn set_protocol_fee_config(
 
     Ok(())
 }
+
+/// Sets the default or required post-dispatch hook.
+///
+/// Accounts:
+/// 0. `[writeable]` The Outbox PDA account.
+/// 1. `[signer, writeable]` The owner of the Mailbox, who pays for any realloc of the Outbox.
+/// 2. `[executable]` The system program.
+fn outbox_set_hook(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    hook: Option<Pubkey>,
+    hook_field: fn(&mut Outbox) -> &mut Option<Pubkey>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+
+    // Account 0: Outbox PDA.
+    let outbox_info = next_account_info(accounts_iter)?;
+    let mut outbox = Outbox::verify_account_and_fetch_inner(program_id, outbox_info)?;
+
+    // Account 1: Owner
+    let owner_info = next_account_info(accounts_iter)?;
+    outbox.ensure_owner_signer(owner_info)?;
+
+    // Account 2: System program.
+    let system_program_info = next_account_info(accounts_iter)?;
+    if system_program_info.key != &solana_program::system_program::id() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if accounts_iter.next().is_some() {
+        return Err(ProgramError::from(Error::ExtraneousAccount));
+    }
+
+    *hook_field(&mut outbox) = hook;
+
+    // Store the updated outbox, reallocating if it was created before
+    // post-dispatch hooks were introduced.
+    OutboxAccount::from(outbox).store_with_rent_exempt_realloc(
+        outbox_info,
+        &Rent::get()?,
+        owner_info,
+        system_program_info,
+    )?;
+
+    Ok(())
+}
@@ -144,13 +144,16 @@ export class SealevelHelloWorldAdapter
         isSigner: false,
         isWritable: true,
       },
-      /// ---- if an IGP is configured ----
+      /// ---- if an IGP is configured and the mailbox has no post-dispatch hooks ----
       /// 9. [executable] The IGP program.
       /// 10. [writeable] The IGP program data.
       /// 11. [writeable] The gas payment PDA.
       /// 12. [] OPTIONAL - The Overhead IGP program, if the configured IGP is an Overhead IGP.
       /// 13. [writeable] The IGP account.
-      /// ---- end if an IGP is configured ----
+      /// ---- end if an IGP is configured and the mailbox has no post-dispatch hooks ----
+      /// ---- if the mailbox has post-dispatch hooks ----
+      /// 9..N [??..??] The accounts of the mailbox's required and default post-dispatch hooks.
+      /// ---- end if the mailbox has post-dispatch hooks ----
     ];
   }
 
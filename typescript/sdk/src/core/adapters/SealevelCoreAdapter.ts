@@ -1,4 +1,5 @@
 import { PublicKey } from '@solana/web3.js';
+import { deserializeUnchecked } from 'borsh';
 
 import {
   Address,
@@ -15,7 +16,12 @@ import {
   TypedTransactionReceipt,
 } from '../../providers/ProviderType.js';
 import { ChainName } from '../../types.js';
+import { SealevelAccountDataWrapper } from '../../utils/sealevelSerialization.js';
 
+import {
+  SealevelMailboxOutboxData,
+  SealevelMailboxOutboxDataSchema,
+} from './serialization.js';
 import { ICoreAdapter } from './types.js';
 
 // https://github.com/hyperlane-xyz/hyperlane-monorepo/blob/main/rust/sealevel/programs/mailbox/src/processor.rs
@@ -78,6 +84,25 @@ export class SealevelCoreAdapter
     return true;
   }
 
+  async getOutboxAccountInfo(): Promise<SealevelMailboxOutboxData> {
+    const address = SealevelCoreAdapter.deriveMailboxOutboxPda(
+      this.addresses.mailbox,
+    );
+    const connection = this.getProvider();
+
+    const accountInfo = await connection.getAccountInfo(address);
+    if (!accountInfo)
+      throw new Error(`No account info found for ${address.toBase58()}`);
+
+    // Outboxes created before hooks were added lack the trailing hook options
+    const accountData = deserializeUnchecked(
+      SealevelMailboxOutboxDataSchema,
+      SealevelAccountDataWrapper,
+      Buffer.concat([accountInfo.data, Buffer.alloc(2)]),
+    );
+    return accountData.data as SealevelMailboxOutboxData;
+  }
+
   static parseMessageDispatchLogs(
     logs: string[],
   ): Array<{ destination: string; messageId: string }> {
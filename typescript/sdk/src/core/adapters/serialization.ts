@@ -0,0 +1,76 @@
+/* eslint-disable @typescript-eslint/explicit-module-boundary-types */
+import { PublicKey } from '@solana/web3.js';
+
+import { Domain } from '@hyperlane-xyz/utils';
+
+import {
+  SealevelAccountDataWrapper,
+  getSealevelAccountDataSchema,
+} from '../../utils/sealevelSerialization.js';
+
+/**
+ * Mailbox Outbox Borsh Schema
+ */
+
+// Should match https://github.com/hyperlane-xyz/hyperlane-monorepo/blob/main/rust/sealevel/programs/mailbox/src/accounts.rs#L94
+export class SealevelMailboxOutboxData {
+  /// The local domain.
+  local_domain!: Domain;
+  /// The bump seed of the outbox PDA.
+  outbox_bump_seed!: number;
+  /// The owner of the mailbox program.
+  owner?: Uint8Array;
+  owner_pub_key?: PublicKey;
+  /// The branch of the merkle tree of dispatched messages.
+  tree_branch!: Uint8Array;
+  /// The number of leaves in the merkle tree of dispatched messages.
+  tree_count!: bigint;
+  /// Max protocol fee that can be set.
+  max_protocol_fee!: bigint;
+  /// The current protocol fee.
+  protocol_fee!: bigint;
+  /// The beneficiary of protocol fees.
+  protocol_fee_beneficiary!: Uint8Array;
+  /// The post-dispatch hook program invoked for messages that don't use another hook.
+  default_hook?: Uint8Array;
+  default_hook_pub_key?: PublicKey;
+  /// The post-dispatch hook program invoked for every dispatched message.
+  required_hook?: Uint8Array;
+  required_hook_pub_key?: PublicKey;
+  constructor(public readonly fields: any) {
+    Object.assign(this, fields);
+    this.owner_pub_key = this.owner ? new PublicKey(this.owner) : undefined;
+    this.default_hook_pub_key = this.default_hook
+      ? new PublicKey(this.default_hook)
+      : undefined;
+    this.required_hook_pub_key = this.required_hook
+      ? new PublicKey(this.required_hook)
+      : undefined;
+  }
+}
+
+export const SealevelMailboxOutboxDataSchema = new Map<any, any>([
+  [
+    SealevelAccountDataWrapper,
+    getSealevelAccountDataSchema(SealevelMailboxOutboxData),
+  ],
+  [
+    SealevelMailboxOutboxData,
+    {
+      kind: 'struct',
+      fields: [
+        ['local_domain', 'u32'],
+        ['outbox_bump_seed', 'u8'],
+        ['owner', { kind: 'option', type: [32] }],
+        // 32 branch nodes of 32 bytes each
+        ['tree_branch', [1024]],
+        ['tree_count', 'u64'],
+        ['max_protocol_fee', 'u64'],
+        ['protocol_fee', 'u64'],
+        ['protocol_fee_beneficiary', [32]],
+        ['default_hook', { kind: 'option', type: [32] }],
+        ['required_hook', { kind: 'option', type: [32] }],
+      ],
+    },
+  ],
+]);
@@ -137,6 +137,55 @@ export const AgentChainMetadataSchema = ChainMetadataSchemaObject.merge(
     signer: AgentSignerSchema.optional().describe(
       'The signer to use for this chain',
     ),
+    additionalSigners: z
+      .array(AgentSignerSchema)
+      .optional()
+      .describe(
+        'Signers to submit transactions from in addition to the signer, so that more transactions can be in flight at once. Only supported on EVM chains.',
+      ),
+    maxInFlightTransactions: ZNzUint.optional().describe(
+      'How many transactions each signer may have in flight at the same time, using pipelined nonces. Only supported on EVM chains. Defaults to 1.',
+    ),
+    feeEscalation: z
+      .object({
+        blocksBeforeBump: ZNzUint.optional().describe(
+          'How many blocks to wait for a transaction to be included before replacing it with bumped fees. Defaults to 3.',
+        ),
+        bumpPercent: z
+          .number()
+          .int()
+          .min(10)
+          .optional()
+          .describe(
+            'How much to bump the fees by each time, in percent. Defaults to 15.',
+          ),
+        maxFeePerGas: ZUWei.describe(
+          'Fees are never bumped above this max fee per gas (or gas price), in wei.',
+        ),
+      })
+      .optional()
+      .describe(
+        'Replace transactions that are not included in time with bumped fees. Only supported on EVM chains.',
+      ),
+    priorityFee: z
+      .object({
+        percentile: z
+          .number()
+          .int()
+          .min(0)
+          .max(100)
+          .optional()
+          .describe(
+            'The percentile of recent prioritization fees to pay, among transactions that lock the same accounts. Defaults to 75.',
+          ),
+        maxComputeUnitPrice: ZUint.optional().describe(
+          'The max compute unit price to pay, in micro-lamports.',
+        ),
+      })
+      .optional()
+      .describe(
+        'How to set the compute unit price of transactions. Only supported on Sealevel chains.',
+      ),
     index: z
       .object({
         from: ZUint.optional().describe(
@@ -145,12 +194,30 @@ export const AgentChainMetadataSchema = ChainMetadataSchemaObject.merge(
         chunk: ZNzUint.optional().describe(
           'The number of blocks to index at a time.',
         ),
+        minChunk: ZNzUint.optional().describe(
+          'The min number of blocks to index at a time. The chunk size is shrunk down to it when queries fail because their range is too large. Defaults to the chunk size.',
+        ),
+        maxChunk: ZNzUint.optional().describe(
+          'The max number of blocks to index at a time. The chunk size is grown up to it after queries that find few logs. Defaults to the chunk size.',
+        ),
         mode: z
           .nativeEnum(AgentIndexMode)
           .optional()
           .describe(
             'The indexing method to use for this chain; will attempt to choose a suitable default if not specified.',
           ),
+        unified: z
+          .boolean()
+          .optional()
+          .describe(
+            'Fetch the logs of the mailbox, IGP and merkle tree hook with a single query per block range. Only supported on EVM chains. Defaults to false.',
+          ),
+        subscribe: z
+          .boolean()
+          .optional()
+          .describe(
+            'Subscribe to dispatched messages and merkle tree insertions to index them as they are emitted, over the webSocket rpcUrls on EVM chains and the Tendermint /websocket endpoint of the rpcUrls on Cosmos chains. Block ranges are still queried to fill in gaps after disconnects. Defaults to false.',
+          ),
       })
       .optional(),
   })
@@ -255,8 +322,52 @@ export enum GasPaymentEnforcementPolicyType {
   None = 'none',
   Minimum = 'minimum',
   OnChainFeeQuoting = 'onChainFeeQuoting',
+  UsdCost = 'usdCost',
+}
+
+export enum TokenPriceSourceType {
+  Static = 'static',
+  Http = 'http',
 }
 
+const ZDecimal = z.union([
+  z.number().nonnegative(),
+  z.string().regex(/^\d+(\.\d+)?$/),
+]);
+
+const TokenPriceSourceSchema = z.union([
+  z.object({
+    type: z.literal(TokenPriceSourceType.Static),
+    prices: z
+      .record(z.string().regex(/^\d+$/), ZDecimal)
+      .describe('The USD price of the native token of each domain id.'),
+  }),
+  z.object({
+    type: z.literal(TokenPriceSourceType.Http),
+    url: z
+      .string()
+      .url()
+      .describe(
+        'An endpoint serving a JSON object mapping domain ids to the USD price of their native token.',
+      ),
+    cacheDuration: ZNzUint.optional().describe(
+      'How long fetched prices are used for, in seconds. Defaults to 60.',
+    ),
+  }),
+]);
+
+const NativeTokenDecimalsSchema = z
+  .record(z.string().regex(/^\d+$/), ZUint)
+  .optional()
+  .describe(
+    'The decimals of the native token of each domain id. Defaults to 18.',
+  );
+
+const TokenPricesSchema = z.object({
+  nativeTokenDecimals: NativeTokenDecimalsSchema,
+  priceSource: TokenPriceSourceSchema,
+});
+
 const GasPaymentEnforcementBaseSchema = z.object({
   matchingList: MatchingListSchema.optional().describe(
     'An optional matching list, any message that matches will use this policy. By default all messages will match.',
@@ -277,6 +388,14 @@ const GasPaymentEnforcementSchema = z.union([
       .regex(/^\d+ ?\/ ?[1-9]\d*$/)
       .optional(),
   }),
+  GasPaymentEnforcementBaseSchema.extend({
+    type: z.literal(GasPaymentEnforcementPolicyType.UsdCost),
+    margin: ZDecimal.optional().describe(
+      'The USD value of the payment must be at least the USD value of the estimated delivery cost times this margin. Defaults to 1.',
+    ),
+    nativeTokenDecimals: NativeTokenDecimalsSchema,
+    priceSource: TokenPriceSourceSchema,
+  }),
 ]);
 export type GasPaymentEnforcement = z.infer<typeof GasPaymentEnforcementSchema>;
 
@@ -336,6 +455,19 @@ export const RelayerAgentConfigSchema = AgentConfigSchema.extend({
     .describe(
       'A list of app contexts and their matching lists to use for metrics. A message will be classified as the first matching app context.',
     ),
+  policiesReloadInterval: ZNzUint.optional().describe(
+    'If set, the relayer reloads its config files at this interval (in seconds) and applies any changes to the whitelist, blacklist, addressBlacklist and gasPaymentEnforcement without restarting.',
+  ),
+  adminToken: z
+    .string()
+    .min(1)
+    .optional()
+    .describe(
+      'Bearer token required by the admin API. The admin API is disabled if this is not set.',
+    ),
+  tokenPrices: TokenPricesSchema.optional().describe(
+    'Native token prices used to value gas payments and spending in USD when tracking the profitability of each route.',
+  ),
 });
 
 export type RelayerConfig = z.infer<typeof RelayerAgentConfigSchema>;
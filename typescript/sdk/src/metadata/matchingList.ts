@@ -4,7 +4,7 @@
  */
 import { z } from 'zod';
 
-import { ZHash, ZNzUint } from './customZodTypes.js';
+import { ZHash, ZNzUint, ZUint } from './customZodTypes.js';
 
 const DomainSchema = z.union([
   z.literal('*'),
@@ -14,12 +14,62 @@ const DomainSchema = z.union([
 
 const AddressSchema = z.union([z.literal('*'), ZHash, z.array(ZHash)]);
 
-const MatchingListElementSchema = z.object({
-  originDomain: DomainSchema.optional(),
-  senderAddress: AddressSchema.optional(),
-  destinationDomain: DomainSchema.optional(),
-  recipientAddress: AddressSchema.optional(),
-});
+const VersionSchema = z.union([
+  z.literal('*'),
+  ZUint.max(255),
+  z.array(ZUint.max(255)).nonempty(),
+]);
+
+// A nonce, or a range of nonces in Rust range syntax, e.g. "10..20",
+// "10..=20", "10.." or "..20"
+const NonceRangeSchema = z.union([
+  ZUint,
+  z.string().regex(/^(\d+|\d*\.\.=?\d*)$/),
+]);
+
+const NonceSchema = z.union([
+  z.literal('*'),
+  NonceRangeSchema,
+  z.array(NonceRangeSchema).nonempty(),
+]);
+
+const BodyPrefixSchema = z.string().regex(/^(0x)?([0-9a-fA-F]{2})*$/);
+
+const BodyPrefixesSchema = z.union([
+  z.literal('*'),
+  BodyPrefixSchema,
+  z.array(BodyPrefixSchema).nonempty(),
+]);
+
+type MatchingListElementInput = {
+  originDomain?: z.infer<typeof DomainSchema>;
+  senderAddress?: z.infer<typeof AddressSchema>;
+  destinationDomain?: z.infer<typeof DomainSchema>;
+  recipientAddress?: z.infer<typeof AddressSchema>;
+  version?: z.infer<typeof VersionSchema>;
+  nonce?: z.infer<typeof NonceSchema>;
+  bodyPrefix?: z.infer<typeof BodyPrefixesSchema>;
+  minBodyLength?: number;
+  maxBodyLength?: number;
+  not?: MatchingListElementInput[];
+};
+
+const MatchingListElementSchema: z.ZodType<MatchingListElementInput> = z.lazy(
+  () =>
+    z.object({
+      originDomain: DomainSchema.optional(),
+      senderAddress: AddressSchema.optional(),
+      destinationDomain: DomainSchema.optional(),
+      recipientAddress: AddressSchema.optional(),
+      version: VersionSchema.optional(),
+      nonce: NonceSchema.optional(),
+      bodyPrefix: BodyPrefixesSchema.optional(),
+      minBodyLength: ZUint.optional(),
+      maxBodyLength: ZUint.optional(),
+      // Messages matching any of these rules are excluded from this rule
+      not: z.array(MatchingListElementSchema).optional(),
+    }),
+);
 
 export const MatchingListSchema = z.array(MatchingListElementSchema);
 
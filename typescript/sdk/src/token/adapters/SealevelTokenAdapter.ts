@@ -25,6 +25,7 @@ import {
 
 import { BaseSealevelAdapter } from '../../app/MultiProtocolApp.js';
 import { SEALEVEL_SPL_NOOP_ADDRESS } from '../../consts/sealevel.js';
+import { SealevelCoreAdapter } from '../../core/adapters/SealevelCoreAdapter.js';
 import { SealevelOverheadIgpAdapter } from '../../gas/adapters/SealevelIgpAdapter.js';
 import { SealevelInterchainGasPaymasterType } from '../../gas/adapters/serialization.js';
 import { MultiProtocolProvider } from '../../providers/MultiProtocolProvider.js';
@@ -284,12 +285,21 @@ export abstract class SealevelHypTokenAdapter
     const fromWalletPubKey = new PublicKey(fromAccountOwner);
     const mailboxPubKey = new PublicKey(this.addresses.mailbox);
 
-    const keys = this.getTransferInstructionKeyList({
+    const keyListParams: KeyListParams = {
       sender: fromWalletPubKey,
       mailbox: mailboxPubKey,
       randomWallet: randomWallet.publicKey,
-      igp: await this.getIgpKeys(),
-    });
+    };
+    const igp = await this.getIgpKeys();
+    const hooks = await this.getMailboxHooks(mailboxPubKey);
+    // The IGP is paid through the mailbox's hooks if it has any,
+    // otherwise the token pays its configured IGP directly.
+    const keys = hooks.length
+      ? [
+          ...this.getTransferInstructionKeyList(keyListParams),
+          ...this.getHookKeys(keyListParams, hooks, igp),
+        ]
+      : this.getTransferInstructionKeyList({ ...keyListParams, igp });
 
     const value = new SealevelInstructionWrapper({
       instruction: SealevelHypTokenInstruction.TransferRemote,
@@ -341,13 +351,17 @@ export abstract class SealevelHypTokenAdapter
     return tx;
   }
 
-  async getIgpKeys(): Promise<KeyListParams['igp']> {
+  async getIgpKeys(): Promise<IgpKeys | undefined> {
     const tokenData = await this.getTokenAccountData();
     if (!tokenData.interchain_gas_paymaster) return undefined;
     const igpConfig = tokenData.interchain_gas_paymaster;
     if (igpConfig.type === SealevelInterchainGasPaymasterType.Igp) {
+      if (!igpConfig.igp_account_pub_key) {
+        throw new Error('igpAccount field expected for Sealevel IGP');
+      }
       return {
         programId: igpConfig.program_id_pubkey,
+        igpAccount: igpConfig.igp_account_pub_key,
       };
     } else if (
       igpConfig.type === SealevelInterchainGasPaymasterType.OverheadIgp
@@ -363,14 +377,109 @@ export abstract class SealevelHypTokenAdapter
       const overheadAccountInfo = await overheadAdapter.getAccountInfo();
       return {
         programId: igpConfig.program_id_pubkey,
-        igpAccount: igpConfig.igp_account_pub_key,
-        innerIgpAccount: overheadAccountInfo.inner_pub_key,
+        igpAccount: overheadAccountInfo.inner_pub_key,
+        overheadIgpAccount: igpConfig.igp_account_pub_key,
       };
     } else {
       throw new Error(`Unsupported IGP type ${igpConfig.type}`);
     }
   }
 
+  // The mailbox's required hook followed by its default hook, if set.
+  async getMailboxHooks(mailbox: PublicKey): Promise<Array<PublicKey>> {
+    const coreAdapter = new SealevelCoreAdapter(
+      this.chainName,
+      this.multiProvider,
+      { mailbox: mailbox.toBase58() },
+    );
+    const outbox = await coreAdapter.getOutboxAccountInfo();
+    return [outbox.required_hook_pub_key, outbox.default_hook_pub_key].filter(
+      (hook): hook is PublicKey => !!hook,
+    );
+  }
+
+  // Should match https://github.com/hyperlane-xyz/hyperlane-monorepo/blob/main/rust/sealevel/programs/mailbox/src/processor.rs
+  // The accounts of the mailbox's required hook followed by those of its default hook.
+  // If both hooks are set, they're preceded by the required hook account count account.
+  getHookKeys(
+    keyListParams: KeyListParams,
+    hooks: Array<PublicKey>,
+    igp?: IgpKeys,
+  ): Array<AccountMeta> {
+    const hookKeys = hooks.map((hook): Array<AccountMeta> => {
+      if (igp && hook.equals(igp.programId)) {
+        return this.getIgpHookKeys(keyListParams, igp);
+      }
+      // [executable] A hook program that doesn't require any accounts.
+      return [{ pubkey: hook, isSigner: false, isWritable: false }];
+    });
+    if (hookKeys.length === 2) {
+      // [] The required hook account count account, which specifies the number
+      // of accounts of the required hook, excluding its program.
+      hookKeys.unshift([
+        {
+          pubkey: SealevelHypTokenAdapter.deriveRequiredHookAccountCountKey(
+            hookKeys[0].length - 1,
+          ),
+          isSigner: false,
+          isWritable: false,
+        },
+      ]);
+    }
+    return hookKeys.flat();
+  }
+
+  // Should match https://github.com/hyperlane-xyz/hyperlane-monorepo/blob/main/rust/sealevel/programs/hyperlane-sealevel-igp/src/instruction.rs
+  getIgpHookKeys(
+    { sender, randomWallet }: KeyListParams,
+    igp: IgpKeys,
+  ): Array<AccountMeta> {
+    const keys: Array<AccountMeta> = [
+      // [executable] The IGP program.
+      { pubkey: igp.programId, isSigner: false, isWritable: false },
+      // [executable] The system program.
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      // [signer, writeable] The payer.
+      { pubkey: sender, isSigner: true, isWritable: true },
+      // [writeable] The IGP program data.
+      {
+        pubkey: SealevelOverheadIgpAdapter.deriveIgpProgramPda(igp.programId),
+        isSigner: false,
+        isWritable: true,
+      },
+      // [signer] Unique gas payment account.
+      { pubkey: randomWallet, isSigner: true, isWritable: false },
+      // [writeable] Gas payment PDA.
+      {
+        pubkey: SealevelOverheadIgpAdapter.deriveGasPaymentPda(
+          igp.programId,
+          randomWallet,
+        ),
+        isSigner: false,
+        isWritable: true,
+      },
+      // [writeable] The IGP account, or the Overhead IGP's inner IGP account.
+      { pubkey: igp.igpAccount, isSigner: false, isWritable: true },
+    ];
+    if (igp.overheadIgpAccount) {
+      // [] OPTIONAL - The Overhead IGP account, if the configured IGP is an Overhead IGP
+      keys.push({
+        pubkey: igp.overheadIgpAccount,
+        isSigner: false,
+        isWritable: false,
+      });
+    }
+    return keys;
+  }
+
+  // Should match https://github.com/hyperlane-xyz/hyperlane-monorepo/blob/main/rust/sealevel/programs/mailbox/src/processor.rs
+  // The first byte of the key is the account count, and the remaining bytes are zero.
+  static deriveRequiredHookAccountCountKey(accountCount: number): PublicKey {
+    const key = new Uint8Array(32);
+    key[0] = accountCount;
+    return new PublicKey(key);
+  }
+
   // Should match https://github.com/hyperlane-xyz/hyperlane-monorepo/blob/main/rust/sealevel/libraries/hyperlane-sealevel-token/src/processor.rs#L257-L274
   getTransferInstructionKeyList({
     sender,
@@ -387,11 +496,11 @@ export abstract class SealevelHypTokenAdapter
         isSigner: false,
         isWritable: false,
       },
-      // 2.   [] The token PDA account.
+      // 2.   [writeable] The token PDA account.
       {
         pubkey: this.deriveHypTokenAccount(),
         isSigner: false,
-        isWritable: false,
+        isWritable: true,
       },
       // 3.   [executable] The mailbox program.
       { pubkey: mailbox, isSigner: false, isWritable: false },
@@ -418,6 +527,7 @@ export abstract class SealevelHypTokenAdapter
         isWritable: true,
       },
     ];
+    // Only used if the mailbox has no post-dispatch hooks.
     if (igp) {
       keys = [
         ...keys,
@@ -439,33 +549,22 @@ export abstract class SealevelHypTokenAdapter
           isWritable: true,
         },
       ];
-      if (igp.igpAccount && igp.innerIgpAccount) {
+      if (igp.overheadIgpAccount) {
         keys = [
           ...keys,
           // 12.   [] OPTIONAL - The Overhead IGP account, if the configured IGP is an Overhead IGP
           {
-            pubkey: igp.igpAccount,
+            pubkey: igp.overheadIgpAccount,
             isSigner: false,
             isWritable: false,
           },
-          // 13.   [writeable] The Overhead's inner IGP account
-          {
-            pubkey: igp.innerIgpAccount,
-            isSigner: false,
-            isWritable: true,
-          },
-        ];
-      } else {
-        keys = [
-          ...keys,
-          // 12.   [writeable] The IGP account.
-          {
-            pubkey: igp.programId,
-            isSigner: false,
-            isWritable: true,
-          },
         ];
       }
+      keys = [
+        ...keys,
+        // 13.   [writeable] The IGP account, or the Overhead IGP's inner IGP account.
+        { pubkey: igp.igpAccount, isSigner: false, isWritable: true },
+      ];
     }
     return keys;
   }
@@ -724,9 +823,12 @@ interface KeyListParams {
   sender: PublicKey;
   mailbox: PublicKey;
   randomWallet: PublicKey;
-  igp?: {
-    programId: PublicKey;
-    igpAccount?: PublicKey;
-    innerIgpAccount?: PublicKey;
-  };
+  igp?: IgpKeys;
+}
+
+interface IgpKeys {
+  programId: PublicKey;
+  // The IGP account, or the inner IGP account of an Overhead IGP
+  igpAccount: PublicKey;
+  overheadIgpAccount?: PublicKey;
 }